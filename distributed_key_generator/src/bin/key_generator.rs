@@ -3,6 +3,7 @@ use std::str::FromStr;
 use clap::{Parser, Subcommand};
 use distributed_key_generation::{
     error::{self, Error},
+    metrics::{run_metrics_server, Metrics},
     rpc::{
         cluster::{self, GetKeyGeneratorList, GetKeyGeneratorRpcUrlListResponse},
         external, internal,
@@ -122,7 +123,7 @@ async fn main() -> Result<(), Error> {
             }
 
             // Initialize an application-wide state instance
-            let app_state = AppState::new(config, skde_params);
+            let app_state = AppState::new(config, skde_params, Metrics::new()?);
 
             if app_state.config().seed_cluster_rpc_url().is_none() {
                 // Leader
@@ -130,6 +131,9 @@ async fn main() -> Result<(), Error> {
                 run_single_key_generator(app_state.clone());
             }
 
+            // Serve the metrics if the metrics url is set
+            run_metrics_server(app_state.clone());
+
             // Initialize the internal RPC server
             initialize_internal_rpc_server(&app_state).await?;
 
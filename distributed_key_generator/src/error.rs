@@ -6,6 +6,7 @@ pub enum Error {
     Database(radius_sdk::kvstore::KvStoreError),
     RpcServerError(radius_sdk::json_rpc::server::RpcServerError),
     RpcClientError(radius_sdk::json_rpc::client::RpcClientError),
+    Metrics(crate::metrics::MetricsError),
 
     LoadConfigOption(std::io::Error),
     ParseTomlString(toml::de::Error),
@@ -51,3 +52,9 @@ impl From<radius_sdk::json_rpc::client::RpcClientError> for Error {
         Self::RpcClientError(value)
     }
 }
+
+impl From<crate::metrics::MetricsError> for Error {
+    fn from(value: crate::metrics::MetricsError) -> Self {
+        Self::Metrics(value)
+    }
+}
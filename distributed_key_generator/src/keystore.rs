@@ -0,0 +1,65 @@
+//! Encrypted JSON keystores (web3 secret storage) for the signing key. A
+//! keystore created with `tx_orderer key` can be used as is.
+
+use std::{
+    env, fs,
+    io::{self, IsTerminal},
+    path::Path,
+};
+
+pub const KEYSTORE_FILE_NAME: &str = "keystore.json";
+pub const KEYSTORE_PASSWORD_ENV: &str = "KEY_GENERATOR_KEYSTORE_PASSWORD";
+
+#[derive(Debug)]
+pub enum KeystoreError {
+    Io(io::Error),
+    Keystore(eth_keystore::KeystoreError),
+    PasswordNotProvided,
+}
+
+impl std::fmt::Display for KeystoreError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+impl std::error::Error for KeystoreError {}
+
+impl From<io::Error> for KeystoreError {
+    fn from(value: io::Error) -> Self {
+        Self::Io(value)
+    }
+}
+
+impl From<eth_keystore::KeystoreError> for KeystoreError {
+    fn from(value: eth_keystore::KeystoreError) -> Self {
+        Self::Keystore(value)
+    }
+}
+
+/// Reads the keystore password from [`KEYSTORE_PASSWORD_ENV`], then from
+/// `password_file`, and prompts for it if neither is set and stdin is a
+/// terminal.
+pub fn read_password(password_file: Option<&Path>) -> Result<String, KeystoreError> {
+    if let Ok(password) = env::var(KEYSTORE_PASSWORD_ENV) {
+        return Ok(password);
+    }
+
+    if let Some(password_file) = password_file {
+        let password = fs::read_to_string(password_file)?;
+        return Ok(password.trim_end_matches(['\r', '\n']).to_owned());
+    }
+
+    if !io::stdin().is_terminal() {
+        return Err(KeystoreError::PasswordNotProvided);
+    }
+
+    Ok(rpassword::prompt_password("Keystore password: ")?)
+}
+
+/// Decrypts the keystore and returns the key as a hex string.
+pub fn decrypt_keystore(keystore_path: &Path, password: &str) -> Result<String, KeystoreError> {
+    let private_key = eth_keystore::decrypt_key(keystore_path, password)?;
+
+    Ok(const_hex::encode_prefixed(private_key))
+}
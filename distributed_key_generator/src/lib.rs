@@ -1,4 +1,6 @@
 pub mod error;
+pub mod keystore;
+pub mod metrics;
 pub mod rpc;
 pub mod state;
 pub mod task;
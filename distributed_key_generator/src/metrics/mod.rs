@@ -0,0 +1,125 @@
+//! Prometheus metrics of the key generator, served at `/metrics` on
+//! `metrics_url`.
+
+mod server;
+
+use std::{sync::Arc, time::Duration};
+
+use prometheus::{Histogram, HistogramOpts, Registry, TextEncoder};
+pub use server::*;
+
+#[derive(Debug)]
+pub enum MetricsError {
+    Register(prometheus::Error),
+    Encode(prometheus::Error),
+    Serve(std::io::Error),
+}
+
+impl std::fmt::Display for MetricsError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+impl std::error::Error for MetricsError {}
+
+pub struct Metrics {
+    inner: Arc<MetricsInner>,
+}
+
+struct MetricsInner {
+    registry: Registry,
+
+    partial_key_generation_duration: Histogram,
+    key_aggregation_duration: Histogram,
+    puzzle_solve_duration: Histogram,
+}
+
+impl Clone for Metrics {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl Metrics {
+    pub fn new() -> Result<Self, MetricsError> {
+        let registry = Registry::new_custom(Some("key_generator".into()), None)
+            .map_err(MetricsError::Register)?;
+
+        let partial_key_generation_duration = register(
+            &registry,
+            Histogram::with_opts(HistogramOpts::new(
+                "partial_key_generation_duration_seconds",
+                "Time to generate a partial key and its validity proof",
+            )),
+        )?;
+        let key_aggregation_duration = register(
+            &registry,
+            Histogram::with_opts(HistogramOpts::new(
+                "key_aggregation_duration_seconds",
+                "Time to aggregate the partial keys into an encryption key",
+            )),
+        )?;
+        let puzzle_solve_duration = register(
+            &registry,
+            Histogram::with_opts(
+                HistogramOpts::new(
+                    "puzzle_solve_duration_seconds",
+                    "Time to solve the time-lock puzzle for a decryption key",
+                )
+                .buckets(vec![
+                    0.1, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0, 60.0, 120.0, 300.0,
+                ]),
+            ),
+        )?;
+
+        let inner = MetricsInner {
+            registry,
+            partial_key_generation_duration,
+            key_aggregation_duration,
+            puzzle_solve_duration,
+        };
+
+        Ok(Self {
+            inner: Arc::new(inner),
+        })
+    }
+
+    pub fn record_partial_key_generation(&self, duration: Duration) {
+        self.inner
+            .partial_key_generation_duration
+            .observe(duration.as_secs_f64());
+    }
+
+    pub fn record_key_aggregation(&self, duration: Duration) {
+        self.inner
+            .key_aggregation_duration
+            .observe(duration.as_secs_f64());
+    }
+
+    pub fn record_puzzle_solve(&self, duration: Duration) {
+        self.inner
+            .puzzle_solve_duration
+            .observe(duration.as_secs_f64());
+    }
+
+    pub fn encode(&self) -> Result<String, MetricsError> {
+        TextEncoder::new()
+            .encode_to_string(&self.inner.registry.gather())
+            .map_err(MetricsError::Encode)
+    }
+}
+
+fn register<T>(registry: &Registry, metric: prometheus::Result<T>) -> Result<T, MetricsError>
+where
+    T: prometheus::core::Collector + Clone + 'static,
+{
+    let metric = metric.map_err(MetricsError::Register)?;
+    registry
+        .register(Box::new(metric.clone()))
+        .map_err(MetricsError::Register)?;
+
+    Ok(metric)
+}
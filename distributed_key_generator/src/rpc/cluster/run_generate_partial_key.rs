@@ -1,3 +1,5 @@
+use std::time::Instant;
+
 use radius_sdk::{
     json_rpc::{
         client::{Id, RpcClient},
@@ -26,10 +28,14 @@ impl RpcParameter<AppState> for RunGeneratePartialKey {
 
     async fn handler(self, context: AppState) -> Result<Self::Response, RpcError> {
         let skde_params = context.skde_params();
+        let started_at = Instant::now();
 
         let (secret_value, partial_key) = generate_partial_key(skde_params);
 
         let partial_key_proof = prove_partial_key_validity(skde_params, &secret_value);
+        context
+            .metrics()
+            .record_partial_key_generation(started_at.elapsed());
 
         sync_partial_key(
             context.config().signer().address().clone(),
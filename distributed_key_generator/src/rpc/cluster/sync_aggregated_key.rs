@@ -1,3 +1,5 @@
+use std::time::Instant;
+
 use radius_sdk::{
     json_rpc::server::{RpcError, RpcParameter},
     signature::Address,
@@ -32,7 +34,12 @@ impl RpcParameter<AppState> for SyncAggregatedKey {
 
         let partial_key_list = partial_key_address_list.get_partial_key_list(self.key_id)?;
 
+        let started_at = Instant::now();
         let skde_aggregated_key = aggregate_key(&skde_params, &partial_key_list);
+        context
+            .metrics()
+            .record_key_aggregation(started_at.elapsed());
+
         let aggregated_key = AggregatedKey::new(skde_aggregated_key.clone());
         aggregated_key.put(self.key_id)?;
 
@@ -43,8 +50,10 @@ impl RpcParameter<AppState> for SyncAggregatedKey {
         );
 
         tokio::spawn(async move {
+            let started_at = Instant::now();
             let decryption_key =
                 solve_time_lock_puzzle(&skde_params, &skde_aggregated_key).unwrap();
+            context.metrics().record_puzzle_solve(started_at.elapsed());
             let decryption_key = DecryptionKey::new(decryption_key.sk.clone());
 
             decryption_key.put(self.key_id).unwrap();
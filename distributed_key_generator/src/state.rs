@@ -1,6 +1,6 @@
 use std::sync::Arc;
 
-use crate::types::Config;
+use crate::{metrics::Metrics, types::Config};
 
 pub struct AppState {
     inner: Arc<AppStateInner>,
@@ -9,6 +9,7 @@ pub struct AppState {
 struct AppStateInner {
     config: Config,
     skde_params: skde::delay_encryption::SkdeParams,
+    metrics: Metrics,
 }
 
 unsafe impl Send for AppState {}
@@ -23,11 +24,16 @@ impl Clone for AppState {
 }
 
 impl AppState {
-    pub fn new(config: Config, skde_params: skde::delay_encryption::SkdeParams) -> Self {
+    pub fn new(
+        config: Config,
+        skde_params: skde::delay_encryption::SkdeParams,
+        metrics: Metrics,
+    ) -> Self {
         let inner = AppStateInner {
             config,
 
             skde_params,
+            metrics,
         };
 
         Self {
@@ -42,4 +48,8 @@ impl AppState {
     pub fn skde_params(&self) -> &skde::delay_encryption::SkdeParams {
         &self.inner.skde_params
     }
+
+    pub fn metrics(&self) -> &Metrics {
+        &self.inner.metrics
+    }
 }
@@ -1,4 +1,4 @@
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use radius_sdk::{
     json_rpc::{
@@ -48,7 +48,11 @@ pub fn run_single_key_generator(context: AppState) {
                     .get_partial_key_list(current_key_id)
                     .unwrap();
 
+                let started_at = Instant::now();
                 let skde_aggregated_key = aggregate_key(&skde_params, &partial_key_list);
+                context
+                    .metrics()
+                    .record_key_aggregation(started_at.elapsed());
 
                 let aggregated_key = AggregatedKey::new(skde_aggregated_key.clone());
                 aggregated_key.put(current_key_id).unwrap();
@@ -66,8 +70,10 @@ pub fn run_single_key_generator(context: AppState) {
                     context.config().signer().address(),
                 );
 
+                let started_at = Instant::now();
                 let secure_key =
                     solve_time_lock_puzzle(&skde_params, &skde_aggregated_key).unwrap();
+                context.metrics().record_puzzle_solve(started_at.elapsed());
                 let decryption_key = DecryptionKey::new(secure_key.sk.clone());
                 decryption_key.put(current_key_id).unwrap();
 
@@ -46,6 +46,18 @@ pub struct ConfigOption {
     #[doc = "Set partial key aggregation cycle"]
     #[clap(long = "partial-key-aggregation-cycle")]
     pub partial_key_aggregation_cycle: Option<u64>,
+
+    #[doc = "Set the encrypted keystore of the signing key (defaults to `keystore.json` if it exists, or the plaintext `signing_key` file)"]
+    #[clap(long = "keystore-path")]
+    pub keystore_path: Option<String>,
+
+    #[doc = "Set the file the keystore password is read from (falls back to a prompt)"]
+    #[clap(long = "keystore-password-file")]
+    pub keystore_password_file: Option<String>,
+
+    #[doc = "Set the metrics url (metrics are not served if unset)"]
+    #[clap(long = "metrics-url")]
+    pub metrics_url: Option<String>,
 }
 
 impl Default for ConfigOption {
@@ -61,6 +73,9 @@ impl Default for ConfigOption {
             chain_type: Some(DEFAULT_CHAIN_TYPE.into()),
             partial_key_generation_cycle: Some(DEFAULT_PARTIAL_KEY_GENERATION_CYCLE),
             partial_key_aggregation_cycle: Some(DEFAULT_PARTIAL_KEY_AGGREGATION_CYCLE),
+            keystore_path: None,
+            keystore_password_file: None,
+            metrics_url: None,
         }
     }
 }
@@ -112,6 +127,22 @@ impl ConfigOption {
             &self.partial_key_aggregation_cycle,
         );
 
+        set_toml_comment(
+            &mut toml_string,
+            "Set the encrypted keystore of the signing key",
+        );
+        set_toml_name_value(&mut toml_string, "keystore_path", &self.keystore_path);
+
+        set_toml_comment(&mut toml_string, "Set the keystore password file");
+        set_toml_name_value(
+            &mut toml_string,
+            "keystore_password_file",
+            &self.keystore_password_file,
+        );
+
+        set_toml_comment(&mut toml_string, "Set metrics url");
+        set_toml_name_value(&mut toml_string, "metrics_url", &self.metrics_url);
+
         toml_string
     }
 
@@ -156,6 +187,19 @@ impl ConfigOption {
                 .clone_from(&other.partial_key_aggregation_cycle);
         }
 
+        if other.keystore_path.is_some() {
+            self.keystore_path.clone_from(&other.keystore_path);
+        }
+
+        if other.keystore_password_file.is_some() {
+            self.keystore_password_file
+                .clone_from(&other.keystore_password_file);
+        }
+
+        if other.metrics_url.is_some() {
+            self.metrics_url.clone_from(&other.metrics_url);
+        }
+
         self
     }
 }
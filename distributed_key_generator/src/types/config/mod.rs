@@ -1,12 +1,17 @@
 mod config_option;
 mod config_path;
 
-use std::{fs, path::PathBuf};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
 
 pub use config_option::*;
 pub use config_path::*;
 use radius_sdk::signature::{Address, ChainType, PrivateKeySigner};
 
+use crate::keystore::{decrypt_keystore, read_password, KeystoreError, KEYSTORE_FILE_NAME};
+
 pub const DEFAULT_HOME_PATH: &str = ".radius";
 pub const DATABASE_DIR_NAME: &str = "database";
 pub const CONFIG_FILE_NAME: &str = "Config.toml";
@@ -38,6 +43,8 @@ pub struct Config {
 
     partial_key_generation_cycle: u64,
     partial_key_aggregation_cycle: u64,
+
+    metrics_url: Option<String>,
 }
 
 impl Config {
@@ -64,11 +71,27 @@ impl Config {
 
         let chain_type = merged_config_option.chain_type.unwrap().try_into().unwrap();
 
-        // Read signing key
-        let signing_key_path = config_path.join(SIGNING_KEY);
-        let signer =
-            PrivateKeySigner::from_str(chain_type, &fs::read_to_string(signing_key_path).unwrap())
-                .unwrap();
+        // Read the signing key from the keystore if there is one, and from the
+        // plaintext file otherwise.
+        let keystore_path = match &merged_config_option.keystore_path {
+            Some(keystore_path) => Some(config_path.join(keystore_path)),
+            None => Some(config_path.join(KEYSTORE_FILE_NAME)).filter(|path| path.exists()),
+        };
+        let signing_key = match &keystore_path {
+            Some(keystore_path) => {
+                let password_file = merged_config_option
+                    .keystore_password_file
+                    .as_ref()
+                    .map(|password_file| config_path.join(password_file));
+                let password =
+                    read_password(password_file.as_deref()).map_err(ConfigError::Keystore)?;
+
+                decrypt_keystore(keystore_path, &password).map_err(ConfigError::Keystore)?
+            }
+            None => load_plaintext_signing_key(&config_path)?,
+        };
+        let signer = PrivateKeySigner::from_str(chain_type, &signing_key)
+            .map_err(|_| ConfigError::InvalidSigningKey)?;
 
         Ok(Config {
             path: config_path,
@@ -90,6 +113,8 @@ impl Config {
             partial_key_aggregation_cycle: merged_config_option
                 .partial_key_aggregation_cycle
                 .unwrap(),
+
+            metrics_url: merged_config_option.metrics_url.clone(),
         })
     }
 
@@ -141,6 +166,10 @@ impl Config {
         &self.seed_cluster_rpc_url
     }
 
+    pub fn metrics_url(&self) -> &Option<String> {
+        &self.metrics_url
+    }
+
     pub fn external_port(&self) -> Result<String, ConfigError> {
         Ok(self
             .external_rpc_url()
@@ -160,6 +189,13 @@ impl Config {
     }
 }
 
+fn load_plaintext_signing_key(config_path: &Path) -> Result<String, ConfigError> {
+    let signing_key_path = config_path.join(SIGNING_KEY);
+    let signing_key = fs::read_to_string(signing_key_path).map_err(ConfigError::LoadSigningKey)?;
+
+    Ok(signing_key.trim().to_owned())
+}
+
 #[derive(Debug)]
 pub enum ConfigError {
     Load(std::io::Error),
@@ -168,6 +204,9 @@ pub enum ConfigError {
     CreateConfigDirectory(std::io::Error),
     CreateConfigFile(std::io::Error),
     CreatePrivateKeyFile(std::io::Error),
+    LoadSigningKey(std::io::Error),
+    Keystore(KeystoreError),
+    InvalidSigningKey,
 
     InvalidExternalPort,
     InvalidClusterPort,
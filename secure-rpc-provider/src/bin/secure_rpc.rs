@@ -31,12 +31,15 @@ use radius_sdk::{
 use secure_rpc::{
     client::distributed_key_generation::DistributedKeyGenerationClient,
     error::Error,
+    metrics::{run_metrics_server, Metrics},
     rpc::{eth, *},
     state::{AppState, PvdeParams},
+    telemetry::init_tracer,
     types::config::{Config, ConfigOption, ConfigPath},
 };
 use serde::{Deserialize, Serialize};
 use tokio::task::JoinHandle;
+use tracing_subscriber::{filter::LevelFilter, layer::SubscriberExt, util::SubscriberInitExt};
 
 #[derive(Debug, Deserialize, Parser, Serialize)]
 #[command(author, version, about, long_about = None)]
@@ -69,7 +72,6 @@ pub enum Commands {
 
 #[tokio::main]
 async fn main() -> Result<(), Error> {
-    tracing_subscriber::fmt().init();
     std::panic::set_hook(Box::new(|panic_info| {
         let payload = panic_info.payload();
         let location = panic_info.location();
@@ -86,7 +88,10 @@ async fn main() -> Result<(), Error> {
     let mut cli = Cli::init();
 
     match cli.command {
-        Commands::Init { ref config_path } => ConfigPath::init(config_path)?,
+        Commands::Init { ref config_path } => {
+            tracing_subscriber::fmt().init();
+            ConfigPath::init(config_path)?
+        }
         Commands::Start {
             ref mut config_option,
         } => {
@@ -95,6 +100,7 @@ async fn main() -> Result<(), Error> {
 
             let config = Config::load(config_option)?;
             let config_path = config_option.path.clone();
+            initialize_logger(&config)?;
 
             tracing::info!("Successfully loaded the configuration file.",);
 
@@ -119,11 +125,15 @@ async fn main() -> Result<(), Error> {
                 config,
                 skde_params,
                 Some(distributed_key_generation_client),
+                Metrics::new()?,
             ));
 
             // Initialize the secure RPC server.
             let server_handle = initialize_external_rpc_server(&app_state).await?;
 
+            // Serve the metrics if the metrics url is set
+            run_metrics_server(app_state.as_ref().clone());
+
             if let Some(path) = config_path {
                 // Initialize the time lock puzzle parameters.
                 store_time_lock_puzzle_param(app_state, path, is_using_zkp).await?;
@@ -136,6 +146,23 @@ async fn main() -> Result<(), Error> {
     Ok(())
 }
 
+/// Installs the logger with the spans exported as configured.
+fn initialize_logger(config: &Config) -> Result<(), Error> {
+    let tracer = init_tracer(
+        "secure-rpc",
+        config.otlp_endpoint().as_deref(),
+        config.trace_file_path().as_deref(),
+    )?;
+
+    tracing_subscriber::registry()
+        .with(tracing_subscriber::fmt::layer())
+        .with(tracing_opentelemetry::layer().with_tracer(tracer))
+        .with(LevelFilter::INFO)
+        .init();
+
+    Ok(())
+}
+
 async fn initialize_external_rpc_server(
     context: &AppState, // rpc_client: &RpcClient,
 ) -> Result<JoinHandle<()>, Error> {
@@ -3,6 +3,8 @@ pub enum Error {
     Syscall(std::io::Error),
     Config(crate::types::config::ConfigError),
     RpcServer(radius_sdk::json_rpc::server::RpcServerError),
+    Metrics(crate::metrics::MetricsError),
+    Telemetry(crate::telemetry::TelemetryError),
     Uninitialized,
     FetchResponse,
     InvalidSequencerPort,
@@ -63,3 +65,15 @@ impl From<radius_sdk::json_rpc::server::RpcServerError> for Error {
         Self::RpcServer(value)
     }
 }
+
+impl From<crate::metrics::MetricsError> for Error {
+    fn from(value: crate::metrics::MetricsError) -> Self {
+        Self::Metrics(value)
+    }
+}
+
+impl From<crate::telemetry::TelemetryError> for Error {
+    fn from(value: crate::telemetry::TelemetryError) -> Self {
+        Self::Telemetry(value)
+    }
+}
@@ -1,5 +1,7 @@
 pub mod client;
 pub mod error;
+pub mod metrics;
 pub mod rpc;
 pub mod state;
+pub mod telemetry;
 pub mod types;
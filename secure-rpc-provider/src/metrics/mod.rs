@@ -0,0 +1,138 @@
+//! Prometheus metrics of the secure RPC, served at `/metrics` on
+//! `metrics_url`.
+
+mod server;
+
+use std::{sync::Arc, time::Duration};
+
+use prometheus::{
+    Histogram, HistogramOpts, HistogramVec, IntCounterVec, Opts, Registry, TextEncoder,
+};
+pub use server::*;
+
+pub const SEQUENCER_UPSTREAM: &str = "sequencer";
+pub const ROLLUP_UPSTREAM: &str = "rollup";
+
+#[derive(Debug)]
+pub enum MetricsError {
+    Register(prometheus::Error),
+    Encode(prometheus::Error),
+    Serve(std::io::Error),
+}
+
+impl std::fmt::Display for MetricsError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+impl std::error::Error for MetricsError {}
+
+pub struct Metrics {
+    inner: Arc<MetricsInner>,
+}
+
+struct MetricsInner {
+    registry: Registry,
+
+    forwarded_request_count: IntCounterVec,
+    forward_duration: HistogramVec,
+    encryption_duration: Histogram,
+}
+
+impl Clone for Metrics {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl Metrics {
+    pub fn new() -> Result<Self, MetricsError> {
+        let registry = Registry::new_custom(Some("secure_rpc".into()), None)
+            .map_err(MetricsError::Register)?;
+
+        let forwarded_request_count = register(
+            &registry,
+            IntCounterVec::new(
+                Opts::new(
+                    "forwarded_requests_total",
+                    "Requests forwarded to the sequencer or the rollup",
+                ),
+                &["upstream", "method", "result"],
+            ),
+        )?;
+        let forward_duration = register(
+            &registry,
+            HistogramVec::new(
+                HistogramOpts::new(
+                    "forward_duration_seconds",
+                    "Time until the sequencer or the rollup responds to a forwarded request",
+                ),
+                &["upstream", "method"],
+            ),
+        )?;
+        let encryption_duration = register(
+            &registry,
+            Histogram::with_opts(HistogramOpts::new(
+                "encryption_duration_seconds",
+                "Time to encrypt a raw transaction",
+            )),
+        )?;
+
+        let inner = MetricsInner {
+            registry,
+            forwarded_request_count,
+            forward_duration,
+            encryption_duration,
+        };
+
+        Ok(Self {
+            inner: Arc::new(inner),
+        })
+    }
+
+    pub fn record_forward(
+        &self,
+        upstream: &str,
+        method: &str,
+        is_success: bool,
+        duration: Duration,
+    ) {
+        let result = if is_success { "success" } else { "failure" };
+
+        self.inner
+            .forwarded_request_count
+            .with_label_values(&[upstream, method, result])
+            .inc();
+        self.inner
+            .forward_duration
+            .with_label_values(&[upstream, method])
+            .observe(duration.as_secs_f64());
+    }
+
+    pub fn record_encryption(&self, duration: Duration) {
+        self.inner
+            .encryption_duration
+            .observe(duration.as_secs_f64());
+    }
+
+    pub fn encode(&self) -> Result<String, MetricsError> {
+        TextEncoder::new()
+            .encode_to_string(&self.inner.registry.gather())
+            .map_err(MetricsError::Encode)
+    }
+}
+
+fn register<T>(registry: &Registry, metric: prometheus::Result<T>) -> Result<T, MetricsError>
+where
+    T: prometheus::core::Collector + Clone + 'static,
+{
+    let metric = metric.map_err(MetricsError::Register)?;
+    registry
+        .register(Box::new(metric.clone()))
+        .map_err(MetricsError::Register)?;
+
+    Ok(metric)
+}
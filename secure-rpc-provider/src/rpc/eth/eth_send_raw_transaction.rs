@@ -1,7 +1,8 @@
 use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
 use serde_json::Value;
+use tracing::Instrument;
 
-use crate::rpc::prelude::*;
+use crate::{rpc::prelude::*, telemetry::TraceContext};
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct EthSendRawTransaction(Vec<String>);
@@ -10,6 +11,8 @@ pub struct EthSendRawTransaction(Vec<String>);
 struct RawTransactionRequest<'a> {
     pub rollup_id: &'a str,
     pub raw_transaction: RawTransactionRequestData<'a>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub trace_context: Option<TraceContext>,
 }
 
 #[derive(Debug, Serialize)]
@@ -27,6 +30,22 @@ impl RpcParameter<AppState> for EthSendRawTransaction {
     }
 
     async fn handler(self, context: AppState) -> Result<Self::Response, RpcError> {
+        let transaction_hash = self.0.first().map(|raw_transaction| {
+            EthRawTransaction(raw_transaction.clone())
+                .raw_transaction_hash()
+                .as_string()
+        });
+        let span = tracing::info_span!(
+            "eth_send_raw_transaction",
+            transaction_hash = transaction_hash.as_deref().unwrap_or_default(),
+        );
+
+        self.handle(context).instrument(span).await
+    }
+}
+
+impl EthSendRawTransaction {
+    async fn handle(self, context: AppState) -> Result<Value, RpcError> {
         if self.0.is_empty() {
             return Err(Error::EmptyRawTransaction.into());
         }
@@ -41,6 +60,7 @@ impl RpcParameter<AppState> for EthSendRawTransaction {
                 transaction_type: "eth",
                 data: raw_transaction_string,
             },
+            trace_context: TraceContext::current(),
         };
 
         let seed: u64 = std::time::SystemTime::now()
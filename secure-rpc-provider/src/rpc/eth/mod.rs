@@ -41,14 +41,19 @@ pub mod prelude {
     pub use crate::state::AppState;
 }
 
+use std::time::Instant;
+
 use prelude::*;
 
+use crate::metrics::ROLLUP_UPSTREAM;
+
 pub async fn forward<P, R>(method: &str, parameter: P, context: AppState) -> Result<R, RpcError>
 where
     P: Serialize,
     R: DeserializeOwned,
 {
-    context
+    let started_at = Instant::now();
+    let result = context
         .rpc_client()
         .request(
             context.config().rollup_rpc_url(),
@@ -56,6 +61,13 @@ where
             parameter,
             Id::Null,
         )
-        .await
-        .map_err(RpcError::from)
+        .await;
+    context.metrics().record_forward(
+        ROLLUP_UPSTREAM,
+        method,
+        result.is_ok(),
+        started_at.elapsed(),
+    );
+
+    result.map_err(RpcError::from)
 }
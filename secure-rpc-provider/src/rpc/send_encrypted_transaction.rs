@@ -1,17 +1,29 @@
+use std::time::Instant;
+
 use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
+use tracing::Instrument;
 
-use crate::rpc::{prelude::*, EncryptTransaction};
+use crate::{
+    metrics::SEQUENCER_UPSTREAM,
+    rpc::{prelude::*, EncryptTransaction},
+    telemetry::TraceContext,
+};
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct SendEncryptedTransaction {
     pub rollup_id: String,
     pub raw_transaction: RawTransaction,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trace_context: Option<TraceContext>,
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
 struct SendEncryptedTransactionRequest {
     rollup_id: String,
     encrypted_transaction: EncryptedTransaction,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    trace_context: Option<TraceContext>,
 }
 
 impl RpcParameter<AppState> for SendEncryptedTransaction {
@@ -22,6 +34,20 @@ impl RpcParameter<AppState> for SendEncryptedTransaction {
     }
 
     async fn handler(self, context: AppState) -> Result<Self::Response, RpcError> {
+        let span = tracing::info_span!(
+            "send_encrypted_transaction",
+            transaction_hash = %self.raw_transaction.raw_transaction_hash().as_string(),
+        );
+        if let Some(trace_context) = &self.trace_context {
+            trace_context.attach(&span);
+        }
+
+        self.handle(context).instrument(span).await
+    }
+}
+
+impl SendEncryptedTransaction {
+    async fn handle(self, context: AppState) -> Result<OrderCommitment, RpcError> {
         if !context.config().is_using_encryption() {
             return Err(Error::EncryptionNotEnabled.into());
         }
@@ -30,12 +56,15 @@ impl RpcParameter<AppState> for SendEncryptedTransaction {
         let encrypt_transaction_request = EncryptTransaction {
             raw_transaction: self.raw_transaction,
         };
+        let started_at = Instant::now();
         let encrypt_transaction_response =
             encrypt_transaction_request.handler(context.clone()).await?;
+        context.metrics().record_encryption(started_at.elapsed());
 
         let parameter = SendEncryptedTransactionRequest {
             rollup_id: self.rollup_id,
             encrypted_transaction: encrypt_transaction_response.encrypted_transaction,
+            trace_context: TraceContext::current(),
         };
 
         let seed: u64 = std::time::SystemTime::now()
@@ -45,7 +74,8 @@ impl RpcParameter<AppState> for SendEncryptedTransaction {
             .try_into()
             .unwrap();
 
-        match context
+        let started_at = Instant::now();
+        let result = context
             .rpc_client()
             .request(
                 context
@@ -57,8 +87,15 @@ impl RpcParameter<AppState> for SendEncryptedTransaction {
                 parameter,
                 Id::Null,
             )
-            .await
-        {
+            .await;
+        context.metrics().record_forward(
+            SEQUENCER_UPSTREAM,
+            Self::method(),
+            result.is_ok(),
+            started_at.elapsed(),
+        );
+
+        match result {
             Ok(order_commitment) => {
                 tracing::info!("Order commitment: {:?}", order_commitment);
                 Ok(order_commitment)
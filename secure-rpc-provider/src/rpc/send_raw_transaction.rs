@@ -1,11 +1,17 @@
+use std::time::Instant;
+
 use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
+use tracing::Instrument;
 
-use crate::rpc::prelude::*;
+use crate::{metrics::SEQUENCER_UPSTREAM, rpc::prelude::*, telemetry::TraceContext};
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct SendRawTransaction {
     pub rollup_id: String,
     pub raw_transaction: RawTransaction,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trace_context: Option<TraceContext>,
 }
 
 impl RpcParameter<AppState> for SendRawTransaction {
@@ -15,7 +21,23 @@ impl RpcParameter<AppState> for SendRawTransaction {
         "send_raw_transaction"
     }
 
-    async fn handler(self, context: AppState) -> Result<Self::Response, RpcError> {
+    async fn handler(mut self, context: AppState) -> Result<Self::Response, RpcError> {
+        let span = tracing::info_span!(
+            "send_raw_transaction",
+            transaction_hash = %self.raw_transaction.raw_transaction_hash().as_string(),
+        );
+        if let Some(trace_context) = &self.trace_context {
+            trace_context.attach(&span);
+        }
+        // The parameter is forwarded to the tx_orderer as is.
+        self.trace_context = TraceContext::from_span(&span);
+
+        self.handle(context).instrument(span).await
+    }
+}
+
+impl SendRawTransaction {
+    async fn handle(self, context: AppState) -> Result<OrderCommitment, RpcError> {
         let seed: u64 = std::time::SystemTime::now()
             .duration_since(std::time::UNIX_EPOCH)
             .unwrap()
@@ -23,7 +45,8 @@ impl RpcParameter<AppState> for SendRawTransaction {
             .try_into()
             .unwrap();
 
-        match context
+        let started_at = Instant::now();
+        let result = context
             .rpc_client()
             .request(
                 context
@@ -35,8 +58,15 @@ impl RpcParameter<AppState> for SendRawTransaction {
                 self,
                 Id::Null,
             )
-            .await
-        {
+            .await;
+        context.metrics().record_forward(
+            SEQUENCER_UPSTREAM,
+            Self::method(),
+            result.is_ok(),
+            started_at.elapsed(),
+        );
+
+        match result {
             Ok(order_commitment) => {
                 tracing::info!("Order commitment: {:?}", order_commitment);
                 Ok(order_commitment)
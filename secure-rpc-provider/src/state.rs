@@ -11,7 +11,8 @@ use pvde::{
 use radius_sdk::{context::SharedContext, json_rpc::client::RpcClient};
 
 use crate::{
-    client::distributed_key_generation::DistributedKeyGenerationClient, types::config::Config,
+    client::distributed_key_generation::DistributedKeyGenerationClient, metrics::Metrics,
+    types::config::Config,
 };
 
 pub struct AppState {
@@ -24,6 +25,7 @@ struct AppStateInner {
     pvde_params: SharedContext<Option<PvdeParams>>,
     skde_params: skde::delay_encryption::SkdeParams,
     distributed_key_generation_client: Option<DistributedKeyGenerationClient>,
+    metrics: Metrics,
 }
 
 impl Clone for AppState {
@@ -39,6 +41,7 @@ impl AppState {
         config: Config,
         skde_params: skde::delay_encryption::SkdeParams,
         distributed_key_generation_client: Option<DistributedKeyGenerationClient>,
+        metrics: Metrics,
     ) -> Self {
         let inner = AppStateInner {
             config,
@@ -46,6 +49,7 @@ impl AppState {
             pvde_params: SharedContext::from(None),
             skde_params,
             distributed_key_generation_client,
+            metrics,
         };
 
         Self {
@@ -72,6 +76,10 @@ impl AppState {
     pub fn distributed_key_generation_client(&self) -> &Option<DistributedKeyGenerationClient> {
         &self.inner.distributed_key_generation_client
     }
+
+    pub fn metrics(&self) -> &Metrics {
+        &self.inner.metrics
+    }
 }
 
 // TODO: Import from sequencer
@@ -0,0 +1,128 @@
+//! OpenTelemetry tracing of the transactions secure-rpc sends to the
+//! tx_orderers.
+//!
+//! A transaction enters the trace here. The trace context is sent to the
+//! tx_orderer inside the JSON-RPC parameter as a [`TraceContext`], in the same
+//! shape the tx_orderer reads it, and a client may send one to secure-rpc the
+//! same way.
+
+use std::{collections::HashMap, fs::OpenOptions, io};
+
+use opentelemetry::{
+    global,
+    trace::{TraceError, TracerProvider as _},
+    KeyValue,
+};
+use opentelemetry_otlp::WithExportConfig;
+use opentelemetry_sdk::{
+    propagation::TraceContextPropagator,
+    runtime,
+    trace::{self, Tracer, TracerProvider},
+    Resource,
+};
+use serde::{Deserialize, Serialize};
+use tracing_opentelemetry::OpenTelemetrySpanExt;
+
+/// The `trace_file_path` that writes the spans to stdout.
+pub const STDOUT_TRACE_FILE_PATH: &str = "-";
+
+#[derive(Debug)]
+pub enum TelemetryError {
+    Otlp(TraceError),
+    OpenFile(io::Error),
+}
+
+impl std::fmt::Display for TelemetryError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+impl std::error::Error for TelemetryError {}
+
+/// The W3C `traceparent` and `tracestate` of the span that sent a request.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct TraceContext(HashMap<String, String>);
+
+impl TraceContext {
+    /// Returns the trace context of the current span, if it is traced.
+    pub fn current() -> Option<Self> {
+        Self::from_span(&tracing::Span::current())
+    }
+
+    pub fn from_span(span: &tracing::Span) -> Option<Self> {
+        let context = span.context();
+
+        let mut trace_context = HashMap::new();
+        global::get_text_map_propagator(|propagator| {
+            propagator.inject_context(&context, &mut trace_context)
+        });
+
+        (!trace_context.is_empty()).then_some(Self(trace_context))
+    }
+
+    /// Makes the span a child of the span that sent the trace context.
+    pub fn attach(&self, span: &tracing::Span) {
+        let context = global::get_text_map_propagator(|propagator| propagator.extract(&self.0));
+        span.set_parent(context);
+    }
+}
+
+/// Installs the tracer. The spans are exported to `otlp_endpoint` if set,
+/// written to `trace_file_path` otherwise, and only propagated to the
+/// tx_orderer if neither is set.
+pub fn init_tracer(
+    service_name: &'static str,
+    otlp_endpoint: Option<&str>,
+    trace_file_path: Option<&str>,
+) -> Result<Tracer, TelemetryError> {
+    global::set_text_map_propagator(TraceContextPropagator::new());
+
+    let trace_config = trace::config().with_resource(Resource::new(vec![KeyValue::new(
+        "service.name",
+        service_name,
+    )]));
+
+    if let Some(otlp_endpoint) = otlp_endpoint {
+        return opentelemetry_otlp::new_pipeline()
+            .tracing()
+            .with_exporter(
+                opentelemetry_otlp::new_exporter()
+                    .tonic()
+                    .with_endpoint(otlp_endpoint),
+            )
+            .with_trace_config(trace_config)
+            .install_batch(runtime::Tokio)
+            .map_err(TelemetryError::Otlp);
+    }
+
+    let mut tracer_provider_builder = TracerProvider::builder().with_config(trace_config);
+    match trace_file_path {
+        Some(STDOUT_TRACE_FILE_PATH) => {
+            let exporter = opentelemetry_stdout::SpanExporter::builder()
+                .with_writer(io::stdout())
+                .build();
+            tracer_provider_builder =
+                tracer_provider_builder.with_batch_exporter(exporter, runtime::Tokio);
+        }
+        Some(trace_file_path) => {
+            let trace_file = OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(trace_file_path)
+                .map_err(TelemetryError::OpenFile)?;
+            let exporter = opentelemetry_stdout::SpanExporter::builder()
+                .with_writer(trace_file)
+                .build();
+            tracer_provider_builder =
+                tracer_provider_builder.with_batch_exporter(exporter, runtime::Tokio);
+        }
+        None => {}
+    }
+
+    let tracer_provider = tracer_provider_builder.build();
+    let tracer = tracer_provider.tracer(service_name);
+    global::set_tracer_provider(tracer_provider);
+
+    Ok(tracer)
+}
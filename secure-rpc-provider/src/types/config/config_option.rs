@@ -49,6 +49,18 @@ pub struct ConfigOption {
     #[doc = "Set the distributed key generation rpc url"]
     #[clap(long = "distributed-key-generation-rpc-url")]
     pub distributed_key_generation_rpc_url: Option<String>,
+
+    #[doc = "Set the metrics url (metrics are not served if unset)"]
+    #[clap(long = "metrics-url")]
+    pub metrics_url: Option<String>,
+
+    #[doc = "Set the OTLP endpoint spans are exported to"]
+    #[clap(long = "otlp-endpoint")]
+    pub otlp_endpoint: Option<String>,
+
+    #[doc = "Set the file spans are written to when no OTLP endpoint is set, `-` for stdout (not exported if unset)"]
+    #[clap(long = "trace-file-path")]
+    pub trace_file_path: Option<String>,
 }
 
 impl Default for ConfigOption {
@@ -65,6 +77,9 @@ impl Default for ConfigOption {
             distributed_key_generation_rpc_url: Some(
                 DEFAULT_DISTRIBUTED_KEY_GENERATION_RPC_URL.into(),
             ),
+            metrics_url: None,
+            otlp_endpoint: None,
+            trace_file_path: None,
         }
     }
 }
@@ -136,6 +151,15 @@ impl ConfigOption {
             &self.distributed_key_generation_rpc_url,
         );
 
+        set_toml_comment(&mut toml_string, "Set metrics url");
+        set_toml_name_value(&mut toml_string, "metrics_url", &self.metrics_url);
+
+        set_toml_comment(&mut toml_string, "Set OTLP endpoint");
+        set_toml_name_value(&mut toml_string, "otlp_endpoint", &self.otlp_endpoint);
+
+        set_toml_comment(&mut toml_string, "Set trace file path");
+        set_toml_name_value(&mut toml_string, "trace_file_path", &self.trace_file_path);
+
         toml_string
     }
 
@@ -180,6 +204,18 @@ impl ConfigOption {
                 .clone_from(&other.distributed_key_generation_rpc_url);
         }
 
+        if other.metrics_url.is_some() {
+            self.metrics_url.clone_from(&other.metrics_url);
+        }
+
+        if other.otlp_endpoint.is_some() {
+            self.otlp_endpoint.clone_from(&other.otlp_endpoint);
+        }
+
+        if other.trace_file_path.is_some() {
+            self.trace_file_path.clone_from(&other.trace_file_path);
+        }
+
         self
     }
 }
@@ -37,6 +37,13 @@ pub struct Config {
 
     // (when using SKDE)
     distributed_key_generation_rpc_url: String,
+
+    // Metrics
+    metrics_url: Option<String>,
+
+    // Tracing
+    otlp_endpoint: Option<String>,
+    trace_file_path: Option<String>,
 }
 
 impl Config {
@@ -81,6 +88,9 @@ impl Config {
             distributed_key_generation_rpc_url: merged_config_option
                 .distributed_key_generation_rpc_url
                 .unwrap(),
+            metrics_url: merged_config_option.metrics_url,
+            otlp_endpoint: merged_config_option.otlp_endpoint,
+            trace_file_path: merged_config_option.trace_file_path,
         })
     }
 
@@ -124,6 +134,18 @@ impl Config {
     pub fn distributed_key_generation_rpc_url(&self) -> &String {
         &self.distributed_key_generation_rpc_url
     }
+
+    pub fn metrics_url(&self) -> &Option<String> {
+        &self.metrics_url
+    }
+
+    pub fn otlp_endpoint(&self) -> &Option<String> {
+        &self.otlp_endpoint
+    }
+
+    pub fn trace_file_path(&self) -> &Option<String> {
+        &self.trace_file_path
+    }
 }
 
 #[derive(Debug)]
@@ -1,11 +1,13 @@
 #!/bin/bash
 SCRIPT_PATH="$( cd -- "$(dirname "$0")" >/dev/null 2>&1 ; pwd -P )"
 source $SCRIPT_PATH/env.sh
+source $SCRIPT_PATH/jwt.sh
 
 echo "add_sequencing_info (related to liveness)"
 
 curl --location $TX_ORDERER_INTERNAL_RPC_URL \
 --header 'Content-Type: application/json' \
+--header "Authorization: Bearer $(jwt_token)" \
 --data '{
   "jsonrpc": "2.0",
   "method": "add_sequencing_info",
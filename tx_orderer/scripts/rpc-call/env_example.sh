@@ -1,5 +1,6 @@
 #!/bin/bash
 TX_ORDERER_INTERNAL_RPC_URL="http://127.0.0.1:4000"
+JWT_SECRET_PATH="$SCRIPT_PATH/../../data/jwt.hex" # The `jwt.hex` file in the data directory of the tx_orderer
 
 ################################# Sequencing (liveness) Contract ####################
 LIVENESS_PLATFORM="ethereum" # Option: [ethereum]
@@ -0,0 +1,18 @@
+#!/bin/bash
+# Issues the bearer token the internal RPC server of the tx_orderer expects:
+# an HS256 JWT with an `iat` claim, signed with the secret in $JWT_SECRET_PATH.
+
+base64url() {
+  openssl base64 -A | tr '+/' '-_' | tr -d '='
+}
+
+jwt_token() {
+  local secret=$(tr -d '[:space:]' < "$JWT_SECRET_PATH" | sed 's/^0x//')
+  local header=$(printf '{"alg":"HS256","typ":"JWT"}' | base64url)
+  local claims=$(printf '{"iat":%d}' "$(date +%s)" | base64url)
+  local signature=$(printf '%s.%s' "$header" "$claims" \
+    | openssl dgst -sha256 -mac HMAC -macopt hexkey:"$secret" -binary \
+    | base64url)
+
+  echo "$header.$claims.$signature"
+}
@@ -0,0 +1,406 @@
+use std::{
+    net::{IpAddr, SocketAddr},
+    sync::Arc,
+    time::Duration,
+};
+
+use axum::{
+    body::Bytes,
+    extract::{ConnectInfo, State},
+    http::{header, HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
+    routing::post,
+    Router,
+};
+use radius_sdk::{json_rpc::server::RpcParameter, kvstore::KvStoreError};
+use rand::RngCore;
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use tokio::net::TcpListener;
+
+use super::{AccessControlError, JwtSecret};
+use crate::{
+    rpc::internal::*,
+    types::{InternalAuditOutcome, InternalAuditRecord},
+};
+
+/// `deregister` waits for the in-flight batches to be sealed before it
+/// responds.
+pub const FORWARD_TIMEOUT: Duration = Duration::from_secs(60);
+
+const GATEWAY_TOKEN_LENGTH: usize = 32;
+
+/// A secret the gateway adds to every request it forwards. It only lives in
+/// the memory of the node, so the internal RPC server rejects the requests
+/// that reach its loopback port without going through the gateway.
+#[derive(Clone, Deserialize, Serialize)]
+#[serde(transparent)]
+pub struct GatewayToken(String);
+
+impl std::fmt::Debug for GatewayToken {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "GatewayToken(..)")
+    }
+}
+
+impl Default for GatewayToken {
+    fn default() -> Self {
+        Self::random()
+    }
+}
+
+impl GatewayToken {
+    pub fn random() -> Self {
+        let mut token = [0; GATEWAY_TOKEN_LENGTH];
+        rand::thread_rng().fill_bytes(&mut token);
+
+        Self(const_hex::encode(token))
+    }
+
+    /// Compares the tokens in constant time.
+    pub fn verify(&self, token: &GatewayToken) -> Result<(), AccessControlError> {
+        let is_equal = self.0.len() == token.0.len()
+            && self
+                .0
+                .bytes()
+                .zip(token.0.bytes())
+                .fold(0, |difference, (a, b)| difference | (a ^ b))
+                == 0;
+
+        match is_equal {
+            true => Ok(()),
+            false => Err(AccessControlError::InvalidGatewayToken),
+        }
+    }
+}
+
+struct GatewayState {
+    upstream_rpc_url: String,
+    gateway_token: GatewayToken,
+    jwt_secret: JwtSecret,
+    allowlist: Vec<IpAddr>,
+    http_client: Client,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum OneOrMany<T> {
+    One(T),
+    Many(Vec<T>),
+}
+
+impl<T> OneOrMany<T> {
+    fn into_vec(self) -> Vec<T> {
+        match self {
+            Self::One(value) => vec![value],
+            Self::Many(value_list) => value_list,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct JsonRpcRequest {
+    method: String,
+    #[serde(default)]
+    params: Value,
+    #[serde(default)]
+    id: Value,
+}
+
+#[derive(Deserialize)]
+struct JsonRpcResponse {
+    #[serde(default)]
+    id: Value,
+    error: Option<Value>,
+}
+
+/// The internal RPC methods that change the state of the node. Every call to
+/// them is recorded in the audit log.
+fn is_mutation(method: &str) -> bool {
+    [
+        AddSequencingInfo::method(),
+        AddValidationInfo::method(),
+        AddCluster::method(),
+        Deregister::method(),
+        SetRetentionPolicy::method(),
+    ]
+    .contains(&method)
+}
+
+/// Serves the internal RPC server at `upstream_rpc_url` on `listener` to the
+/// callers that hold a token signed with `jwt_secret`. Any address may call if
+/// `allowlist` is empty. The requests are forwarded with `gateway_token`.
+pub async fn run_internal_rpc_gateway(
+    listener: TcpListener,
+    upstream_rpc_url: String,
+    gateway_token: GatewayToken,
+    jwt_secret: JwtSecret,
+    allowlist: Vec<IpAddr>,
+) -> Result<(), AccessControlError> {
+    let http_client = Client::builder()
+        .timeout(FORWARD_TIMEOUT)
+        .build()
+        .map_err(AccessControlError::HttpClient)?;
+
+    let state = Arc::new(GatewayState {
+        upstream_rpc_url,
+        gateway_token,
+        jwt_secret,
+        allowlist,
+        http_client,
+    });
+    let router = Router::new().route("/", post(forward)).with_state(state);
+
+    axum::serve(
+        listener,
+        router.into_make_service_with_connect_info::<SocketAddr>(),
+    )
+    .await
+    .map_err(AccessControlError::Serve)
+}
+
+async fn forward(
+    State(state): State<Arc<GatewayState>>,
+    ConnectInfo(peer_address): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Response {
+    let caller = peer_address.ip().to_canonical();
+    if !state.allowlist.is_empty() && !state.allowlist.contains(&caller) {
+        tracing::warn!(
+            "Rejected an internal RPC request from {} outside the allowlist",
+            caller
+        );
+        return (StatusCode::FORBIDDEN, "The address is not allowed").into_response();
+    }
+
+    let token = headers
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .ok_or(AccessControlError::MissingToken);
+    if let Err(error) = token.and_then(|token| state.jwt_secret.verify_token(token)) {
+        tracing::warn!(
+            "Rejected an unauthenticated internal RPC request from {}: {}",
+            caller,
+            error
+        );
+        return (StatusCode::UNAUTHORIZED, error.to_string()).into_response();
+    }
+
+    let upstream_body = match add_gateway_token(&body, &state.gateway_token) {
+        Ok(upstream_body) => upstream_body,
+        Err(error) => return (StatusCode::BAD_REQUEST, error.to_string()).into_response(),
+    };
+
+    // The mutations are recorded before they are forwarded, so that none of
+    // them runs without a record, even if the node stops during the call.
+    let recorded_mutation_list = match record_mutations(caller, &body) {
+        Ok(recorded_mutation_list) => recorded_mutation_list,
+        Err(error) => {
+            tracing::error!("Failed to append to the internal audit log: {:?}", error);
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "Failed to record the call in the audit log",
+            )
+                .into_response();
+        }
+    };
+
+    let response = state
+        .http_client
+        .post(&state.upstream_rpc_url)
+        .header(header::CONTENT_TYPE.as_str(), "application/json")
+        .body(upstream_body)
+        .send()
+        .await;
+    let (status, response_body) = match response {
+        Ok(response) => {
+            let status =
+                StatusCode::from_u16(response.status().as_u16()).unwrap_or(StatusCode::BAD_GATEWAY);
+            match response.bytes().await {
+                Ok(response_body) => (status, response_body),
+                Err(error) => {
+                    complete_mutations(&recorded_mutation_list, Err(&error));
+                    return (StatusCode::BAD_GATEWAY, error.to_string()).into_response();
+                }
+            }
+        }
+        Err(error) => {
+            complete_mutations(&recorded_mutation_list, Err(&error));
+            return (StatusCode::BAD_GATEWAY, error.to_string()).into_response();
+        }
+    };
+
+    complete_mutations(&recorded_mutation_list, Ok(&response_body));
+
+    (
+        status,
+        [(header::CONTENT_TYPE, "application/json")],
+        response_body,
+    )
+        .into_response()
+}
+
+/// Moves the parameters of each request under `params` along with the gateway
+/// token, the shape [`Forwarded`] deserializes.
+fn add_gateway_token(
+    request_body: &[u8],
+    gateway_token: &GatewayToken,
+) -> Result<Vec<u8>, serde_json::Error> {
+    let add_to_request = |request: &mut Value| {
+        if let Value::Object(request) = request {
+            let params = request.remove("params").unwrap_or(Value::Null);
+            request.insert(
+                "params".to_owned(),
+                json!({
+                    "gateway_token": gateway_token,
+                    "params": params,
+                }),
+            );
+        }
+    };
+
+    let mut request_body = serde_json::from_slice::<Value>(request_body)?;
+    match &mut request_body {
+        Value::Array(request_list) => request_list.iter_mut().for_each(add_to_request),
+        request => add_to_request(request),
+    }
+
+    serde_json::to_vec(&request_body)
+}
+
+/// A mutation appended to the audit log, waiting for its response.
+struct RecordedMutation {
+    index: u64,
+    method: String,
+    id: Value,
+}
+
+/// Appends the mutations of the request to the audit log as pending.
+fn record_mutations(
+    caller: IpAddr,
+    request_body: &[u8],
+) -> Result<Vec<RecordedMutation>, KvStoreError> {
+    let request_list = match serde_json::from_slice::<OneOrMany<JsonRpcRequest>>(request_body) {
+        Ok(request_list) => request_list.into_vec(),
+        Err(_) => return Ok(Vec::new()),
+    };
+
+    request_list
+        .into_iter()
+        .filter(|request| is_mutation(&request.method))
+        .map(|request| {
+            let index =
+                InternalAuditRecord::append(caller, &request.method, request.params.to_string())?;
+            tracing::info!(
+                "Internal RPC mutation - caller: {} / method: {:?} / index: {}",
+                caller,
+                request.method,
+                index
+            );
+
+            Ok(RecordedMutation {
+                index,
+                method: request.method,
+                id: request.id,
+            })
+        })
+        .collect()
+}
+
+/// Completes the records of the mutations with the error each of them
+/// responded with.
+fn complete_mutations(
+    recorded_mutation_list: &[RecordedMutation],
+    response: Result<&[u8], &reqwest::Error>,
+) {
+    let response_list = match response {
+        Ok(response_body) => serde_json::from_slice::<OneOrMany<JsonRpcResponse>>(response_body)
+            .map(OneOrMany::into_vec)
+            .unwrap_or_default(),
+        Err(_) => Vec::new(),
+    };
+
+    for recorded_mutation in recorded_mutation_list {
+        let outcome = match response {
+            Ok(_) => get_outcome(&response_list, &recorded_mutation.id),
+            Err(error) => InternalAuditOutcome::Failed(error.to_string()),
+        };
+
+        tracing::info!(
+            "Internal RPC mutation completed - method: {:?} / index: {} / outcome: {:?}",
+            recorded_mutation.method,
+            recorded_mutation.index,
+            outcome
+        );
+        if let Err(error) = InternalAuditRecord::complete(recorded_mutation.index, outcome) {
+            tracing::error!("Failed to complete the internal audit record: {:?}", error);
+        }
+    }
+}
+
+fn get_outcome(response_list: &[JsonRpcResponse], id: &Value) -> InternalAuditOutcome {
+    match response_list.iter().find(|response| &response.id == id) {
+        Some(response) => match &response.error {
+            Some(error) => InternalAuditOutcome::Failed(error.to_string()),
+            None => InternalAuditOutcome::Succeeded,
+        },
+        None => InternalAuditOutcome::Failed("Missing response".to_owned()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn forwarded_requests_carry_the_gateway_token() {
+        let gateway_token = GatewayToken::random();
+        let request_body = json!([
+            { "jsonrpc": "2.0", "method": "get_disk_usage", "params": {}, "id": 1 },
+            { "jsonrpc": "2.0", "method": "get_disk_usage", "id": 2 },
+        ]);
+
+        let upstream_body =
+            add_gateway_token(&serde_json::to_vec(&request_body).unwrap(), &gateway_token).unwrap();
+        let upstream_body: Value = serde_json::from_slice(&upstream_body).unwrap();
+        let forwarded_token = |index: usize| {
+            serde_json::from_value::<GatewayToken>(
+                upstream_body[index]["params"]["gateway_token"].clone(),
+            )
+            .unwrap()
+        };
+
+        assert!(gateway_token.verify(&forwarded_token(0)).is_ok());
+        assert!(gateway_token.verify(&forwarded_token(1)).is_ok());
+        assert!(GatewayToken::random().verify(&forwarded_token(0)).is_err());
+        assert_eq!(upstream_body[0]["params"]["params"], json!({}));
+        assert_eq!(upstream_body[1]["params"]["params"], Value::Null);
+    }
+
+    #[test]
+    fn mutations_are_completed_with_their_own_response() {
+        let response_body = json!([
+            { "jsonrpc": "2.0", "result": null, "id": 1 },
+            { "jsonrpc": "2.0", "error": { "code": -32000, "message": "failed" }, "id": 2 },
+        ]);
+        let response_list = serde_json::from_value::<OneOrMany<JsonRpcResponse>>(response_body)
+            .unwrap()
+            .into_vec();
+
+        assert_eq!(
+            get_outcome(&response_list, &json!(1)),
+            InternalAuditOutcome::Succeeded
+        );
+        assert!(matches!(
+            get_outcome(&response_list, &json!(2)),
+            InternalAuditOutcome::Failed(error) if error.contains("failed")
+        ));
+        assert!(matches!(
+            get_outcome(&response_list, &json!(3)),
+            InternalAuditOutcome::Failed(_)
+        ));
+    }
+}
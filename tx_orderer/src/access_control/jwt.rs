@@ -0,0 +1,171 @@
+use std::{
+    fs,
+    io::Write,
+    os::unix::fs::{OpenOptionsExt, PermissionsExt},
+    path::Path,
+};
+
+use jsonwebtoken::{Algorithm, DecodingKey, EncodingKey, Header, Validation};
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+
+use super::AccessControlError;
+use crate::types::current_timestamp;
+
+pub const JWT_SECRET_FILE_NAME: &str = "jwt.hex";
+
+const JWT_SECRET_LENGTH: usize = 32;
+
+/// How far the `iat` claim of a token may be from the clock of the node, in
+/// seconds.
+const MAX_ISSUED_AT_DRIFT: u64 = 60;
+
+#[derive(Debug, Deserialize, Serialize)]
+struct JwtClaims {
+    /// Unix timestamp in seconds.
+    iat: u64,
+}
+
+/// The 256-bit secret shared between the node and the callers of its internal
+/// RPC server, stored as hex in a file like the JWT secret of the Engine API.
+/// Callers send an HS256 token signed with it, issued less than a minute ago.
+#[derive(Clone)]
+pub struct JwtSecret([u8; JWT_SECRET_LENGTH]);
+
+impl JwtSecret {
+    pub fn random() -> Self {
+        let mut secret = [0; JWT_SECRET_LENGTH];
+        rand::thread_rng().fill_bytes(&mut secret);
+
+        Self(secret)
+    }
+
+    /// Reads the hex encoded secret, with or without the `0x` prefix.
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, AccessControlError> {
+        let secret = fs::read_to_string(path).map_err(AccessControlError::LoadJwtSecret)?;
+        let secret_bytes =
+            const_hex::decode(secret.trim()).map_err(|_| AccessControlError::InvalidJwtSecret)?;
+
+        secret_bytes
+            .try_into()
+            .map(Self)
+            .map_err(|_| AccessControlError::InvalidJwtSecret)
+    }
+
+    /// Reads the secret, or generates one if the file does not exist so that
+    /// the nodes initialized before the secret was introduced keep starting.
+    pub fn load_or_create(path: impl AsRef<Path>) -> Result<Self, AccessControlError> {
+        let path = path.as_ref();
+        if path.exists() {
+            return Self::from_file(path);
+        }
+
+        let jwt_secret = Self::random();
+        jwt_secret
+            .write(path)
+            .map_err(AccessControlError::CreateJwtSecret)?;
+        tracing::info!(
+            "Created a JWT secret for the internal RPC server at {:?}",
+            path
+        );
+
+        Ok(jwt_secret)
+    }
+
+    /// Writes the secret to a file only the owner can read, like the keystore
+    /// of the signing key.
+    pub fn write(&self, path: impl AsRef<Path>) -> std::io::Result<()> {
+        let mut file = fs::OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .mode(0o600)
+            .open(path)?;
+        // The mode only applies to a new file.
+        file.set_permissions(fs::Permissions::from_mode(0o600))?;
+
+        file.write_all(const_hex::encode_prefixed(self.0).as_bytes())
+    }
+
+    pub fn issue_token(&self) -> Result<String, AccessControlError> {
+        let claims = JwtClaims {
+            iat: current_timestamp() / 1000,
+        };
+
+        jsonwebtoken::encode(
+            &Header::new(Algorithm::HS256),
+            &claims,
+            &EncodingKey::from_secret(&self.0),
+        )
+        .map_err(AccessControlError::IssueToken)
+    }
+
+    pub fn verify_token(&self, token: &str) -> Result<(), AccessControlError> {
+        // The Engine API tokens carry `iat` instead of `exp`.
+        let mut validation = Validation::new(Algorithm::HS256);
+        validation.validate_exp = false;
+        validation.required_spec_claims.clear();
+
+        let token_data = jsonwebtoken::decode::<JwtClaims>(
+            token,
+            &DecodingKey::from_secret(&self.0),
+            &validation,
+        )
+        .map_err(AccessControlError::InvalidToken)?;
+
+        if (current_timestamp() / 1000).abs_diff(token_data.claims.iat) > MAX_ISSUED_AT_DRIFT {
+            return Err(AccessControlError::ExpiredToken);
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn token_verifies_only_with_its_secret() {
+        let jwt_secret = JwtSecret::random();
+        let token = jwt_secret.issue_token().unwrap();
+
+        assert!(jwt_secret.verify_token(&token).is_ok());
+        assert!(matches!(
+            JwtSecret::random().verify_token(&token),
+            Err(AccessControlError::InvalidToken(_))
+        ));
+    }
+
+    #[test]
+    fn token_issued_too_long_ago_is_rejected() {
+        let jwt_secret = JwtSecret::random();
+        let claims = JwtClaims {
+            iat: current_timestamp() / 1000 - MAX_ISSUED_AT_DRIFT - 1,
+        };
+        let token = jsonwebtoken::encode(
+            &Header::new(Algorithm::HS256),
+            &claims,
+            &EncodingKey::from_secret(&jwt_secret.0),
+        )
+        .unwrap();
+
+        assert!(matches!(
+            jwt_secret.verify_token(&token),
+            Err(AccessControlError::ExpiredToken)
+        ));
+    }
+
+    #[test]
+    fn secret_file_is_only_readable_by_its_owner() {
+        let path = std::env::temp_dir().join(format!("jwt-{}.hex", std::process::id()));
+        let jwt_secret = JwtSecret::load_or_create(&path).unwrap();
+
+        let mode = fs::metadata(&path).unwrap().permissions().mode();
+        let stored_secret = JwtSecret::from_file(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(mode & 0o777, 0o600);
+        assert_eq!(stored_secret.0, jwt_secret.0);
+    }
+}
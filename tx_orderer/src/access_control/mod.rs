@@ -0,0 +1,37 @@
+//! Access control for the internal RPC server.
+//!
+//! The internal RPC server only listens on the loopback interface. A gateway
+//! listens on `internal_rpc_url` in its place: it checks the address of the
+//! caller against the allowlist and its bearer token against the JWT secret
+//! the same way the Engine API does, records the calls that change the state
+//! of the node in the audit log and forwards the request. The internal RPC
+//! methods are wrapped in [`Forwarded`](crate::rpc::internal::Forwarded), so
+//! the requests sent to the loopback port directly are rejected.
+
+mod gateway;
+mod jwt;
+
+pub use gateway::*;
+pub use jwt::*;
+
+#[derive(Debug)]
+pub enum AccessControlError {
+    LoadJwtSecret(std::io::Error),
+    CreateJwtSecret(std::io::Error),
+    InvalidJwtSecret,
+    IssueToken(jsonwebtoken::errors::Error),
+    MissingToken,
+    InvalidToken(jsonwebtoken::errors::Error),
+    ExpiredToken,
+    InvalidGatewayToken,
+    HttpClient(reqwest::Error),
+    Serve(std::io::Error),
+}
+
+impl std::fmt::Display for AccessControlError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+impl std::error::Error for AccessControlError {}
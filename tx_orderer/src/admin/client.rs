@@ -0,0 +1,281 @@
+use std::time::Duration;
+
+use radius_sdk::json_rpc::server::RpcParameter;
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::{
+    access_control::{AccessControlError, JwtSecret, FORWARD_TIMEOUT},
+    state::AppState,
+};
+
+/// Longer than the gateway waits for the internal RPC server, so that a slow
+/// call such as `deregister` is reported with the response of the gateway.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(FORWARD_TIMEOUT.as_secs() + 30);
+
+#[derive(Debug)]
+pub enum AdminError {
+    Config(crate::types::ConfigError),
+    JwtSecret(AccessControlError),
+    InvalidArgument(String),
+    Connect {
+        rpc_url: String,
+    },
+    Timeout {
+        rpc_url: String,
+        method: String,
+    },
+    Http {
+        rpc_url: String,
+        status: u16,
+    },
+    Rpc {
+        method: String,
+        code: i64,
+        message: String,
+    },
+    InvalidResponse {
+        method: String,
+        reason: String,
+    },
+    Output(std::io::Error),
+}
+
+impl std::fmt::Display for AdminError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Config(error) => write!(f, "Failed to read the configuration: {}", error),
+            Self::JwtSecret(error) => write!(f, "Failed to use the JWT secret: {}", error),
+            Self::InvalidArgument(reason) => write!(f, "Invalid argument: {}", reason),
+            Self::Connect { rpc_url } => write!(
+                f,
+                "Could not connect to the internal RPC server at {} (is the node running?)",
+                rpc_url
+            ),
+            Self::Timeout { rpc_url, method } => write!(
+                f,
+                "`{}` timed out after {}s on {}",
+                method,
+                REQUEST_TIMEOUT.as_secs(),
+                rpc_url
+            ),
+            Self::Http { rpc_url, status } => {
+                write!(f, "{} responded with HTTP status {}", rpc_url, status)
+            }
+            Self::Rpc {
+                method,
+                code,
+                message,
+            } => write!(f, "`{}` failed ({}): {}", method, code, message),
+            Self::InvalidResponse { method, reason } => {
+                write!(f, "Unexpected response to `{}`: {}", method, reason)
+            }
+            Self::Output(error) => write!(f, "Failed to write the output: {}", error),
+        }
+    }
+}
+
+impl std::error::Error for AdminError {}
+
+#[derive(Serialize)]
+struct RpcRequest<'a, P> {
+    jsonrpc: &'static str,
+    method: &'static str,
+    params: &'a P,
+    id: u64,
+}
+
+#[derive(Deserialize)]
+struct RpcResponse {
+    result: Option<Value>,
+    error: Option<RpcResponseError>,
+}
+
+#[derive(Deserialize)]
+struct RpcResponseError {
+    code: i64,
+    message: String,
+}
+
+/// Calls the internal RPC methods of a running node.
+pub struct AdminClient {
+    rpc_url: String,
+    jwt_secret: JwtSecret,
+    http_client: Client,
+}
+
+impl AdminClient {
+    pub fn new(rpc_url: impl AsRef<str>, jwt_secret: JwtSecret) -> Result<Self, AdminError> {
+        let http_client = Client::builder()
+            .timeout(REQUEST_TIMEOUT)
+            .build()
+            .map_err(|error| AdminError::InvalidArgument(error.to_string()))?;
+
+        Ok(Self {
+            rpc_url: rpc_url.as_ref().to_owned(),
+            jwt_secret,
+            http_client,
+        })
+    }
+
+    /// Sends the parameter to its method and returns the untyped result, so
+    /// that it can be rendered as JSON or as a table.
+    pub async fn request<P>(&self, parameter: &P) -> Result<Value, AdminError>
+    where
+        P: RpcParameter<AppState> + Serialize,
+    {
+        let method = P::method();
+        let request = RpcRequest {
+            jsonrpc: "2.0",
+            method,
+            params: parameter,
+            id: 1,
+        };
+        let token = self
+            .jwt_secret
+            .issue_token()
+            .map_err(AdminError::JwtSecret)?;
+
+        let response = self
+            .http_client
+            .post(&self.rpc_url)
+            .bearer_auth(token)
+            .json(&request)
+            .send()
+            .await
+            .map_err(|error| {
+                if error.is_timeout() {
+                    AdminError::Timeout {
+                        rpc_url: self.rpc_url.clone(),
+                        method: method.to_owned(),
+                    }
+                } else {
+                    AdminError::Connect {
+                        rpc_url: self.rpc_url.clone(),
+                    }
+                }
+            })?;
+
+        let status = response.status();
+        if !status.is_success() {
+            return Err(AdminError::Http {
+                rpc_url: self.rpc_url.clone(),
+                status: status.as_u16(),
+            });
+        }
+
+        let response: RpcResponse =
+            response
+                .json()
+                .await
+                .map_err(|error| AdminError::InvalidResponse {
+                    method: method.to_owned(),
+                    reason: error.to_string(),
+                })?;
+
+        match (response.result, response.error) {
+            (_, Some(error)) => Err(AdminError::Rpc {
+                method: method.to_owned(),
+                code: error.code,
+                message: error.message,
+            }),
+            (Some(result), None) => Ok(result),
+            (None, None) => Ok(Value::Null),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::{
+        http::{HeaderMap, StatusCode},
+        routing::post,
+        Json, Router,
+    };
+    use serde_json::json;
+    use tokio::net::TcpListener;
+
+    use super::*;
+    use crate::rpc::internal::{GetDiskUsage, GetSequencingInfos};
+
+    /// Answers `get_sequencing_infos` and fails every other method, for the
+    /// callers holding `jwt_secret`.
+    async fn run_internal_rpc_server(jwt_secret: JwtSecret) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let rpc_url = format!("http://{}", listener.local_addr().unwrap());
+        let router = Router::new().route(
+            "/",
+            post(
+                move |headers: HeaderMap, Json(request): Json<Value>| async move {
+                    let token = headers
+                        .get("authorization")
+                        .and_then(|value| value.to_str().ok())
+                        .and_then(|value| value.strip_prefix("Bearer "))
+                        .unwrap_or_default();
+                    if jwt_secret.verify_token(token).is_err() {
+                        return Err(StatusCode::UNAUTHORIZED);
+                    }
+
+                    Ok(Json(match request["method"].as_str() {
+                        Some("get_sequencing_infos") => json!({
+                            "jsonrpc": "2.0",
+                            "id": request["id"],
+                            "result": { "sequencing_infos": [] },
+                        }),
+                        _ => json!({
+                            "jsonrpc": "2.0",
+                            "id": request["id"],
+                            "error": { "code": -32601, "message": "Method not found" },
+                        }),
+                    }))
+                },
+            ),
+        );
+        tokio::spawn(async move { axum::serve(listener, router).await });
+
+        rpc_url
+    }
+
+    #[tokio::test]
+    async fn result_and_error_of_the_method_are_returned() {
+        let jwt_secret = JwtSecret::random();
+        let rpc_url = run_internal_rpc_server(jwt_secret.clone()).await;
+        let admin_client = AdminClient::new(&rpc_url, jwt_secret).unwrap();
+
+        let result = admin_client.request(&GetSequencingInfos).await.unwrap();
+        assert_eq!(result, json!({ "sequencing_infos": [] }));
+
+        match admin_client.request(&GetDiskUsage {}).await {
+            Err(AdminError::Rpc { method, code, .. }) => {
+                assert_eq!(method, "get_disk_usage");
+                assert_eq!(code, -32601);
+            }
+            result => panic!("unexpected result: {:?}", result),
+        }
+    }
+
+    #[tokio::test]
+    async fn caller_without_the_secret_is_rejected() {
+        let rpc_url = run_internal_rpc_server(JwtSecret::random()).await;
+        let admin_client = AdminClient::new(&rpc_url, JwtSecret::random()).unwrap();
+
+        assert!(matches!(
+            admin_client.request(&GetSequencingInfos).await,
+            Err(AdminError::Http { status: 401, .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn stopped_node_is_reported_as_a_connection_error() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let rpc_url = format!("http://{}", listener.local_addr().unwrap());
+        drop(listener);
+        let admin_client = AdminClient::new(&rpc_url, JwtSecret::random()).unwrap();
+
+        assert!(matches!(
+            admin_client.request(&GetSequencingInfos).await,
+            Err(AdminError::Connect { .. })
+        ));
+    }
+}
@@ -0,0 +1,477 @@
+//! Typed client for the internal RPC methods, used by the `admin`
+//! subcommands.
+
+mod client;
+mod table;
+
+use std::{fs, io::Write, path::PathBuf, str::FromStr};
+
+use clap::{Parser, Subcommand, ValueEnum};
+pub use client::*;
+use serde::{Deserialize, Serialize};
+pub use table::*;
+
+use crate::{
+    access_control::{JwtSecret, JWT_SECRET_FILE_NAME},
+    rpc::internal::*,
+    types::{
+        ClusterId, ConfigError, ConfigOption, ConfigPath, EigenLayerValidationInfo, LivenessRadius,
+        LivenessServiceProvider, Platform, RetentionPolicy, RollupId, SequencingInfoPayload,
+        SymbioticValidationInfo, ValidationInfo, ValidationServiceProvider, CONFIG_FILE_NAME,
+    },
+};
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize, ValueEnum)]
+#[serde(rename_all = "snake_case")]
+pub enum OutputFormat {
+    #[default]
+    Json,
+    Table,
+}
+
+#[derive(Debug, Deserialize, Parser, Serialize)]
+pub struct AdminOption {
+    #[clap(flatten)]
+    pub config_path: ConfigPath,
+
+    #[doc = "Set the internal rpc url (defaults to the one in the configuration file)"]
+    #[clap(long = "internal-rpc-url")]
+    pub internal_rpc_url: Option<String>,
+
+    #[doc = "Set the JWT secret file of the internal rpc server (defaults to the one in the configuration file)"]
+    #[clap(long = "jwt-secret-path")]
+    pub jwt_secret_path: Option<PathBuf>,
+
+    #[doc = "Set the output format"]
+    #[clap(long = "output", value_enum, default_value_t = OutputFormat::Json)]
+    pub output: OutputFormat,
+}
+
+/// Identifies a liveness service on a platform.
+#[derive(Debug, Deserialize, Parser, Serialize)]
+pub struct LivenessOption {
+    #[clap(long = "platform", default_value = "ethereum", value_parser = parse_argument::<Platform>)]
+    pub platform: Platform,
+
+    #[clap(long = "liveness-service-provider", default_value = "radius", value_parser = parse_argument::<LivenessServiceProvider>)]
+    pub liveness_service_provider: LivenessServiceProvider,
+}
+
+#[derive(Debug, Deserialize, Serialize, Subcommand)]
+pub enum AdminCommand {
+    /// Registers the liveness contract of a platform
+    AddSequencingInfo {
+        #[clap(flatten)]
+        liveness_option: LivenessOption,
+
+        #[clap(long = "liveness-rpc-url")]
+        liveness_rpc_url: String,
+
+        #[clap(long = "liveness-websocket-url")]
+        liveness_websocket_url: String,
+
+        #[clap(long = "contract-address")]
+        contract_address: String,
+    },
+    /// Registers the validation contracts of a platform
+    AddValidationInfo {
+        #[clap(long = "platform", default_value = "ethereum", value_parser = parse_argument::<Platform>)]
+        platform: Platform,
+
+        #[clap(long = "validation-service-provider", value_parser = parse_argument::<ValidationServiceProvider>)]
+        validation_service_provider: ValidationServiceProvider,
+
+        #[clap(long = "validation-rpc-url")]
+        validation_rpc_url: String,
+
+        #[clap(long = "validation-websocket-url")]
+        validation_websocket_url: String,
+
+        /// Required for symbiotic
+        #[clap(long = "validation-contract-address")]
+        validation_contract_address: Option<String>,
+
+        /// Required for eigen_layer
+        #[clap(long = "delegation-manager-contract-address")]
+        delegation_manager_contract_address: Option<String>,
+
+        /// Required for eigen_layer
+        #[clap(long = "stake-registry-contract-address")]
+        stake_registry_contract_address: Option<String>,
+
+        /// Required for eigen_layer
+        #[clap(long = "avs-directory-contract-address")]
+        avs_directory_contract_address: Option<String>,
+
+        /// Required for eigen_layer
+        #[clap(long = "avs-contract-address")]
+        avs_contract_address: Option<String>,
+    },
+    /// Joins a cluster
+    AddCluster {
+        #[clap(flatten)]
+        liveness_option: LivenessOption,
+
+        #[clap(long = "cluster-id")]
+        cluster_id: ClusterId,
+    },
+    /// Prints the cluster snapshots within the block margin
+    GetCluster {
+        #[clap(flatten)]
+        liveness_option: LivenessOption,
+
+        #[clap(long = "cluster-id")]
+        cluster_id: ClusterId,
+    },
+    /// Prints the clusters this node joined
+    GetClusterIdList {
+        #[clap(flatten)]
+        liveness_option: LivenessOption,
+    },
+    /// Prints the liveness contract of a platform
+    GetSequencingInfo {
+        #[clap(flatten)]
+        liveness_option: LivenessOption,
+    },
+    /// Prints the liveness contracts of every platform
+    GetSequencingInfos,
+    /// Leaves a cluster
+    Deregister {
+        #[clap(flatten)]
+        liveness_option: LivenessOption,
+
+        #[clap(long = "cluster-id")]
+        cluster_id: ClusterId,
+
+        /// What to do with the rollup data of the cluster (keep, archive or
+        /// purge)
+        #[clap(long = "rollup-data", default_value = "keep", value_parser = parse_argument::<RollupDataAction>)]
+        rollup_data: RollupDataAction,
+
+        /// Leaves even if this node still leads unsealed transactions or the
+        /// in-flight batches are not sealed in time
+        #[clap(long = "force")]
+        force: bool,
+    },
+    /// Exports the equivocation evidence of every rollup
+    ExportEquivocationEvidence {
+        /// Only exports the evidence detected after this timestamp in
+        /// milliseconds
+        #[clap(long = "detected-after")]
+        detected_after: Option<u64>,
+    },
+    /// Sets how long the batches of a rollup are kept
+    SetRetentionPolicy {
+        #[clap(long = "rollup-id")]
+        rollup_id: RollupId,
+
+        /// Keeps the latest batches, or every batch if neither this nor
+        /// `--keep-until-finalized` is set
+        #[clap(long = "keep-batch-count", conflicts_with = "keep_until_finalized")]
+        keep_batch_count: Option<u64>,
+
+        /// Keeps a batch until its commitment task has been responded
+        #[clap(long = "keep-until-finalized")]
+        keep_until_finalized: bool,
+    },
+    /// Prints the number and size of the retained entries per model
+    GetDiskUsage,
+    /// Prints the calls to the internal RPC methods that changed the state of
+    /// the node
+    GetInternalAuditLog {
+        /// Prints the records from this index instead of the latest ones
+        #[clap(long = "from-index")]
+        from_index: Option<u64>,
+
+        #[clap(long = "limit")]
+        limit: Option<u64>,
+    },
+}
+
+fn parse_argument<T>(value: &str) -> Result<T, String>
+where
+    T: FromStr,
+    T::Err: std::fmt::Debug,
+{
+    value.parse().map_err(|error| format!("{:?}", error))
+}
+
+/// Calls the internal RPC method of the command and writes the result to
+/// `writer` in the requested format.
+pub async fn run_admin_command(
+    admin_option: &AdminOption,
+    admin_command: AdminCommand,
+    mut writer: impl Write,
+) -> Result<(), AdminError> {
+    let internal_rpc_url = match &admin_option.internal_rpc_url {
+        Some(internal_rpc_url) => internal_rpc_url.clone(),
+        None => load_internal_rpc_url(&admin_option.config_path)?,
+    };
+    let jwt_secret_path = match &admin_option.jwt_secret_path {
+        Some(jwt_secret_path) => jwt_secret_path.clone(),
+        None => load_jwt_secret_path(&admin_option.config_path)?,
+    };
+    let jwt_secret = JwtSecret::from_file(jwt_secret_path).map_err(AdminError::JwtSecret)?;
+    let admin_client = AdminClient::new(&internal_rpc_url, jwt_secret)?;
+
+    let result = match admin_command {
+        AdminCommand::AddSequencingInfo {
+            liveness_option,
+            liveness_rpc_url,
+            liveness_websocket_url,
+            contract_address,
+        } => {
+            admin_client
+                .request(&AddSequencingInfo {
+                    platform: liveness_option.platform,
+                    liveness_service_provider: liveness_option.liveness_service_provider,
+                    payload: SequencingInfoPayload::Ethereum(LivenessRadius {
+                        liveness_rpc_url,
+                        liveness_websocket_url,
+                        contract_address,
+                    }),
+                })
+                .await?
+        }
+        AdminCommand::AddValidationInfo {
+            platform,
+            validation_service_provider,
+            validation_rpc_url,
+            validation_websocket_url,
+            validation_contract_address,
+            delegation_manager_contract_address,
+            stake_registry_contract_address,
+            avs_directory_contract_address,
+            avs_contract_address,
+        } => {
+            let validation_info = match validation_service_provider {
+                ValidationServiceProvider::EigenLayer => {
+                    ValidationInfo::EigenLayer(EigenLayerValidationInfo::new(
+                        platform,
+                        validation_rpc_url,
+                        validation_websocket_url,
+                        required(
+                            delegation_manager_contract_address,
+                            "--delegation-manager-contract-address",
+                        )?,
+                        required(
+                            stake_registry_contract_address,
+                            "--stake-registry-contract-address",
+                        )?,
+                        required(
+                            avs_directory_contract_address,
+                            "--avs-directory-contract-address",
+                        )?,
+                        required(avs_contract_address, "--avs-contract-address")?,
+                    ))
+                }
+                ValidationServiceProvider::Symbiotic => {
+                    ValidationInfo::Symbiotic(SymbioticValidationInfo::new(
+                        platform,
+                        validation_rpc_url,
+                        validation_websocket_url,
+                        required(validation_contract_address, "--validation-contract-address")?,
+                    ))
+                }
+            };
+
+            admin_client
+                .request(&AddValidationInfo {
+                    platform,
+                    validation_service_provider,
+                    validation_info,
+                })
+                .await?
+        }
+        AdminCommand::AddCluster {
+            liveness_option,
+            cluster_id,
+        } => {
+            admin_client
+                .request(&AddCluster {
+                    platform: liveness_option.platform,
+                    liveness_service_provider: liveness_option.liveness_service_provider,
+                    cluster_id,
+                })
+                .await?
+        }
+        AdminCommand::GetCluster {
+            liveness_option,
+            cluster_id,
+        } => {
+            admin_client
+                .request(&GetCluster {
+                    platform: liveness_option.platform,
+                    liveness_service_provider: liveness_option.liveness_service_provider,
+                    cluster_id,
+                })
+                .await?
+        }
+        AdminCommand::GetClusterIdList { liveness_option } => {
+            admin_client
+                .request(&GetClusterIdList {
+                    platform: liveness_option.platform,
+                    liveness_service_provider: liveness_option.liveness_service_provider,
+                })
+                .await?
+        }
+        AdminCommand::GetSequencingInfo { liveness_option } => {
+            admin_client
+                .request(&GetSequencingInfo {
+                    platform: liveness_option.platform,
+                    liveness_service_provider: liveness_option.liveness_service_provider,
+                })
+                .await?
+        }
+        AdminCommand::GetSequencingInfos => admin_client.request(&GetSequencingInfos).await?,
+        AdminCommand::Deregister {
+            liveness_option,
+            cluster_id,
+            rollup_data,
+            force,
+        } => {
+            admin_client
+                .request(&Deregister {
+                    platform: liveness_option.platform,
+                    liveness_service_provider: liveness_option.liveness_service_provider,
+                    cluster_id,
+                    rollup_data,
+                    force,
+                })
+                .await?
+        }
+        AdminCommand::ExportEquivocationEvidence { detected_after } => {
+            admin_client
+                .request(&ExportEquivocationEvidence { detected_after })
+                .await?
+        }
+        AdminCommand::SetRetentionPolicy {
+            rollup_id,
+            keep_batch_count,
+            keep_until_finalized,
+        } => {
+            let retention_policy = match (keep_batch_count, keep_until_finalized) {
+                (Some(batch_count), _) => RetentionPolicy::KeepBatchCount { batch_count },
+                (None, true) => RetentionPolicy::KeepUntilFinalized,
+                (None, false) => RetentionPolicy::KeepAll,
+            };
+
+            admin_client
+                .request(&SetRetentionPolicy {
+                    rollup_id,
+                    retention_policy,
+                })
+                .await?
+        }
+        AdminCommand::GetDiskUsage => admin_client.request(&GetDiskUsage {}).await?,
+        AdminCommand::GetInternalAuditLog { from_index, limit } => {
+            admin_client
+                .request(&GetInternalAuditLog { from_index, limit })
+                .await?
+        }
+    };
+
+    let output = match admin_option.output {
+        OutputFormat::Json => format!(
+            "{}\n",
+            serde_json::to_string_pretty(&result).unwrap_or_default()
+        ),
+        OutputFormat::Table => render_table(&result),
+    };
+    writer
+        .write_all(output.as_bytes())
+        .map_err(AdminError::Output)?;
+
+    Ok(())
+}
+
+fn required(value: Option<String>, argument: &'static str) -> Result<String, AdminError> {
+    value.ok_or_else(|| AdminError::InvalidArgument(format!("{} is required", argument)))
+}
+
+fn load_config_option(config_path: &ConfigPath) -> Result<ConfigOption, AdminError> {
+    let config_file_path = config_path.as_ref().join(CONFIG_FILE_NAME);
+    let config_string = fs::read_to_string(config_file_path)
+        .map_err(|error| AdminError::Config(ConfigError::Load(error)))?;
+
+    toml::from_str(&config_string).map_err(|error| AdminError::Config(ConfigError::Parse(error)))
+}
+
+/// Reads the internal rpc url from the configuration file, falling back to
+/// the default one.
+fn load_internal_rpc_url(config_path: &ConfigPath) -> Result<String, AdminError> {
+    let config_option = load_config_option(config_path)?;
+
+    Ok(config_option
+        .internal_rpc_url
+        .unwrap_or_else(|| ConfigOption::default().internal_rpc_url.unwrap()))
+}
+
+/// Resolves the JWT secret file of the configuration file the same way the
+/// node does.
+fn load_jwt_secret_path(config_path: &ConfigPath) -> Result<PathBuf, AdminError> {
+    let config_option = load_config_option(config_path)?;
+
+    Ok(config_path.as_ref().join(
+        config_option
+            .jwt_secret_path
+            .as_deref()
+            .unwrap_or(JWT_SECRET_FILE_NAME),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Parser)]
+    struct AdminCli {
+        #[clap(subcommand)]
+        admin_command: AdminCommand,
+    }
+
+    fn parse(argument_list: &[&str]) -> Result<AdminCommand, clap::Error> {
+        AdminCli::try_parse_from(std::iter::once("admin").chain(argument_list.iter().copied()))
+            .map(|admin_cli| admin_cli.admin_command)
+    }
+
+    #[test]
+    fn deregister_keeps_the_rollup_data_by_default() {
+        match parse(&["deregister", "--cluster-id", "cluster"]).unwrap() {
+            AdminCommand::Deregister {
+                liveness_option,
+                cluster_id,
+                rollup_data,
+                force,
+            } => {
+                assert_eq!(liveness_option.platform, Platform::Ethereum);
+                assert_eq!(cluster_id, "cluster");
+                assert_eq!(rollup_data, RollupDataAction::Keep);
+                assert!(!force);
+            }
+            admin_command => panic!("unexpected command: {:?}", admin_command),
+        }
+
+        assert!(parse(&[
+            "deregister",
+            "--cluster-id",
+            "cluster",
+            "--rollup-data",
+            "shred"
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn retention_policy_takes_a_single_rule() {
+        assert!(parse(&[
+            "set-retention-policy",
+            "--rollup-id",
+            "rollup",
+            "--keep-batch-count",
+            "3",
+            "--keep-until-finalized",
+        ])
+        .is_err());
+    }
+}
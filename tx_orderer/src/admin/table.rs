@@ -0,0 +1,143 @@
+use serde_json::{Map, Value};
+
+/// Renders an RPC result as a plain text table. A response that wraps a
+/// single field is unwrapped first, a list of objects becomes one row per
+/// object and any other object becomes one row per field. Nested values are
+/// printed as compact JSON.
+pub fn render_table(value: &Value) -> String {
+    let value = match value {
+        Value::Object(object) if object.len() == 1 => object.values().next().unwrap(),
+        value => value,
+    };
+
+    match value {
+        Value::Null => "OK\n".to_owned(),
+        Value::Array(list) if list.iter().all(Value::is_object) && !list.is_empty() => {
+            let mut header: Vec<String> = Vec::new();
+            for object in list.iter().filter_map(Value::as_object) {
+                for key in object.keys() {
+                    if !header.contains(key) {
+                        header.push(key.clone());
+                    }
+                }
+            }
+
+            let row_list = list
+                .iter()
+                .filter_map(Value::as_object)
+                .map(|object| {
+                    header
+                        .iter()
+                        .map(|key| object.get(key).map(render_cell).unwrap_or_default())
+                        .collect()
+                })
+                .collect();
+
+            render_rows(header, row_list)
+        }
+        Value::Array(list) => render_rows(
+            vec!["value".to_owned()],
+            list.iter().map(|value| vec![render_cell(value)]).collect(),
+        ),
+        Value::Object(object) => render_object(object),
+        value => format!("{}\n", render_cell(value)),
+    }
+}
+
+fn render_object(object: &Map<String, Value>) -> String {
+    render_rows(
+        vec!["field".to_owned(), "value".to_owned()],
+        object
+            .iter()
+            .map(|(key, value)| vec![key.clone(), render_cell(value)])
+            .collect(),
+    )
+}
+
+fn render_cell(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(string) => string.clone(),
+        value => value.to_string(),
+    }
+}
+
+fn render_rows(header: Vec<String>, row_list: Vec<Vec<String>>) -> String {
+    let mut width_list: Vec<usize> = header.iter().map(|column| column.len()).collect();
+    for row in row_list.iter() {
+        for (width, cell) in width_list.iter_mut().zip(row.iter()) {
+            *width = (*width).max(cell.len());
+        }
+    }
+
+    let render_row = |row: &[String]| {
+        let line = row
+            .iter()
+            .zip(width_list.iter())
+            .map(|(cell, width)| format!("{:<width$}", cell, width = width))
+            .collect::<Vec<_>>()
+            .join("  ");
+
+        format!("{}\n", line.trim_end())
+    };
+
+    let mut table = render_row(&header);
+    table.push_str(&render_row(
+        &width_list
+            .iter()
+            .map(|width| "-".repeat(*width))
+            .collect::<Vec<_>>(),
+    ));
+    for row in row_list.iter() {
+        table.push_str(&render_row(row));
+    }
+
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn list_of_objects_is_rendered_one_row_per_object() {
+        let value = json!({
+            "record_list": [
+                { "index": 0, "method": "add_cluster" },
+                { "index": 12, "method": "deregister", "outcome": { "failed": "timeout" } },
+            ]
+        });
+
+        assert_eq!(
+            render_table(&value),
+            "index  method       outcome\n\
+             -----  -----------  --------------------\n\
+             0      add_cluster\n\
+             12     deregister   {\"failed\":\"timeout\"}\n"
+        );
+    }
+
+    #[test]
+    fn object_is_rendered_one_row_per_field() {
+        let value = json!({ "database_size": 42, "measured_at": null });
+
+        assert_eq!(
+            render_table(&value),
+            "field          value\n\
+             -------------  -----\n\
+             database_size  42\n\
+             measured_at\n"
+        );
+    }
+
+    #[test]
+    fn empty_result_is_rendered_as_ok() {
+        assert_eq!(render_table(&Value::Null), "OK\n");
+        assert_eq!(
+            render_table(&json!({ "cluster_id_list": [] })),
+            "value\n-----\n"
+        );
+    }
+}
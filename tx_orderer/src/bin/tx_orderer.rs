@@ -1,5 +1,8 @@
 use std::{
     collections::HashMap,
+    fs::File,
+    io::{self, BufReader, Write},
+    path::PathBuf,
     sync::{Arc, Mutex},
 };
 
@@ -12,16 +15,30 @@ use radius_sdk::{
 };
 use serde::{Deserialize, Serialize};
 use tx_orderer::{
+    access_control::{run_internal_rpc_gateway, GatewayToken, JwtSecret},
+    admin::{run_admin_command, AdminCommand, AdminOption},
     client::{
         distributed_key_generation::DistributedKeyGenerationClient, liveness_service_manager,
         reward_manager::RewardManagerClient, seeder::SeederClient, validation_service_manager,
     },
+    database::{self, open_database},
     error::{self, Error},
+    health::{run_health_monitor, HealthMonitor},
+    keystore::{run_key_command, KeyCommand},
     logger::PanicLog,
     merkle_tree_manager::MerkleTreeManager,
+    metrics::{run_metrics_server, Metrics},
+    migration::{get_migration_plan, migrate},
     rpc::{cluster, external, internal},
+    shutdown::{shutdown, wait_for_shutdown_signal, ShutdownManager},
+    signer::run_mock_signer,
     state::AppState,
-    task::{run_backrunning_server, Decryptor},
+    task::{
+        run_backrunning_server, run_censorship_monitor, run_external_rpc_front,
+        run_leader_health_monitor, run_pruner, run_range_sync, BuilderSinkManager,
+        CensorshipMonitor, Decryptor, DiskUsageCache, LeaderHealthTracker, SubscriptionManager,
+    },
+    telemetry::shutdown_tracer,
     types::*,
     util::initialize_logger,
 };
@@ -45,6 +62,95 @@ enum Commands {
         #[clap(flatten)]
         config_option: ConfigOption,
     },
+    /// Migrates the database to the version required by this binary
+    Migrate {
+        #[clap(flatten)]
+        config_path: ConfigPath,
+
+        /// Reports the changes without writing them
+        #[clap(long = "dry-run")]
+        dry_run: bool,
+    },
+    /// Manages a running node through its internal RPC
+    Admin {
+        #[clap(flatten)]
+        admin_option: AdminOption,
+
+        #[command(subcommand)]
+        command: AdminCommand,
+    },
+    /// Inspects the database of a stopped node
+    Db {
+        #[command(subcommand)]
+        command: DbCommands,
+    },
+    /// Manages the encrypted keystore of the signing key
+    Key {
+        #[command(subcommand)]
+        command: KeyCommand,
+    },
+    /// Serves a web3signer-style remote signer with a local key, for tests
+    MockSigner {
+        #[clap(long = "listen-address", default_value = "127.0.0.1:9000")]
+        listen_address: String,
+
+        /// Defaults to the development key
+        #[clap(long = "private-key-file")]
+        private_key_file: Option<PathBuf>,
+    },
+}
+
+#[derive(Subcommand, Debug, Deserialize, Serialize)]
+enum DbCommands {
+    /// Exports a rollup and a range of its batches to JSON lines
+    Export {
+        #[clap(flatten)]
+        config_path: ConfigPath,
+
+        #[clap(long = "rollup-id")]
+        rollup_id: RollupId,
+
+        /// Defaults to the first batch that has not been pruned
+        #[clap(long = "from-batch-number")]
+        from_batch_number: Option<u64>,
+
+        /// Defaults to the current batch
+        #[clap(long = "to-batch-number")]
+        to_batch_number: Option<u64>,
+
+        /// Writes to stdout if not set
+        #[clap(long = "output")]
+        output: Option<PathBuf>,
+    },
+    /// Imports the records of an export file
+    Import {
+        #[clap(flatten)]
+        config_path: ConfigPath,
+
+        #[clap(long = "input")]
+        input: PathBuf,
+    },
+    /// Prints the metadata of a rollup and its cluster
+    Inspect {
+        #[clap(flatten)]
+        config_path: ConfigPath,
+
+        #[clap(long = "rollup-id")]
+        rollup_id: RollupId,
+
+        /// Defaults to the block height in the cluster metadata
+        #[clap(long = "platform-block-height")]
+        platform_block_height: Option<u64>,
+    },
+    /// Recomputes the stored batch commitments and reports mismatches
+    Verify {
+        #[clap(flatten)]
+        config_path: ConfigPath,
+
+        /// Verifies every rollup if not set
+        #[clap(long = "rollup-id")]
+        rollup_id: Option<RollupId>,
+    },
 }
 
 #[tokio::main]
@@ -74,7 +180,112 @@ async fn main() -> Result<(), Error> {
             version.put().map_err(error::Error::Database)?;
         }
         Commands::Start { mut config_option } => start_tx_orderer(&mut config_option).await?,
+        Commands::Migrate {
+            config_path,
+            dry_run,
+        } => {
+            tracing_subscriber::fmt().init();
+
+            let migration_report = migrate(config_path.as_ref(), dry_run)?;
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&migration_report).map_err(|_| Error::Convert)?
+            );
+        }
+        Commands::Admin {
+            admin_option,
+            command,
+        } => {
+            if let Err(error) = run_admin_command(&admin_option, command, io::stdout()).await {
+                eprintln!("Error: {}", error);
+                std::process::exit(1);
+            }
+        }
+        Commands::Db { command } => run_db_command(command).await?,
+        Commands::Key { command } => {
+            if let Err(error) = run_key_command(command, io::stdout()) {
+                eprintln!("Error: {}", error);
+                std::process::exit(1);
+            }
+        }
+        Commands::MockSigner {
+            listen_address,
+            private_key_file,
+        } => {
+            tracing_subscriber::fmt().init();
+
+            let private_key = match private_key_file {
+                Some(private_key_file) => std::fs::read_to_string(private_key_file)?,
+                None => DEFAULT_SIGNING_KEY.to_owned(),
+            };
+            let listener = tokio::net::TcpListener::bind(&listen_address).await?;
+            run_mock_signer(listener, private_key).await?;
+        }
+    }
+
+    Ok(())
+}
+
+async fn run_db_command(command: DbCommands) -> Result<(), Error> {
+    // Logs go to stderr so that an export to stdout stays parseable.
+    tracing_subscriber::fmt().with_writer(io::stderr).init();
+
+    let report = match command {
+        DbCommands::Export {
+            config_path,
+            rollup_id,
+            from_batch_number,
+            to_batch_number,
+            output,
+        } => {
+            open_database(config_path.as_ref())?;
+
+            let export_report = match output {
+                Some(output) => database::export(
+                    &rollup_id,
+                    from_batch_number,
+                    to_batch_number,
+                    File::create(output)?,
+                )?,
+                None => database::export(
+                    &rollup_id,
+                    from_batch_number,
+                    to_batch_number,
+                    io::stdout().lock(),
+                )?,
+            };
+            tracing::info!("Export done - {:?}", export_report);
+
+            return Ok(());
+        }
+        DbCommands::Import { config_path, input } => {
+            open_database(config_path.as_ref())?;
+
+            serde_json::to_value(database::import(BufReader::new(File::open(input)?))?)
+        }
+        DbCommands::Inspect {
+            config_path,
+            rollup_id,
+            platform_block_height,
+        } => {
+            open_database(config_path.as_ref())?;
+
+            serde_json::to_value(database::inspect(&rollup_id, platform_block_height)?)
+        }
+        DbCommands::Verify {
+            config_path,
+            rollup_id,
+        } => {
+            open_database(config_path.as_ref())?;
+
+            serde_json::to_value(database::verify(rollup_id.as_ref()).await?)
+        }
     }
+    .map_err(|_| Error::Convert)?;
+
+    let mut stdout = io::stdout().lock();
+    serde_json::to_writer_pretty(&mut stdout, &report).map_err(|_| Error::Convert)?;
+    writeln!(stdout)?;
 
     Ok(())
 }
@@ -84,6 +295,19 @@ async fn start_tx_orderer(config_option: &mut ConfigOption) -> Result<(), Error>
     let config = Config::load(config_option)?;
     initialize_logger(&config)?;
 
+    match (&config.remote_signer_url, &config.keystore_path) {
+        (Some(remote_signer_url), _) => {
+            tracing::info!("Signing with the remote signer at {:?}", remote_signer_url)
+        }
+        (None, Some(keystore_path)) => {
+            tracing::info!("Signing key loaded from {:?}", keystore_path)
+        }
+        (None, None) => tracing::warn!(
+            "Signing key loaded from the plaintext `{}` file. Run `tx_orderer key import` to encrypt it",
+            SIGNING_KEY_PATH
+        ),
+    }
+
     // Initialize the profiler.
     // let profiler = Profiler::init("http://127.0.0.1:4040", "tx_orderer", 100)?;
     let profiler = None;
@@ -97,6 +321,7 @@ async fn start_tx_orderer(config_option: &mut ConfigOption) -> Result<(), Error>
     tracing::info!("Database initialized at {:?}", config.database_path());
 
     check_and_update_version()?;
+    check_signing_key(&config)?;
 
     let (seeder_client, dkg_client, reward_manager_client) = tokio::try_join!(
         async { initialize_seeder_client(&config) },
@@ -106,11 +331,17 @@ async fn start_tx_orderer(config_option: &mut ConfigOption) -> Result<(), Error>
     let skde_params = dkg_client.get_skde_params().await?.skde_params;
     let latest_key_id = dkg_client.get_latest_key_id().await?.latest_key_id;
 
+    let metrics = Metrics::new()?;
+    let subscription_manager = SubscriptionManager::default();
+    let builder_sink_manager = BuilderSinkManager::new(&config)?;
+
     let decryptor = Decryptor::new(
         dkg_client.clone(),
         skde_params.clone(),
         latest_key_id,
-        config.builder_rpc_url.clone(),
+        subscription_manager.clone(),
+        builder_sink_manager.clone(),
+        metrics.clone(),
     )?;
     Decryptor::start(decryptor.clone()).await;
 
@@ -129,9 +360,26 @@ async fn start_tx_orderer(config_option: &mut ConfigOption) -> Result<(), Error>
         rpc_client,
         merkle_tree_manager,
         Arc::new(Mutex::new(HashMap::new())),
+        subscription_manager,
+        LeaderHealthTracker::default(),
+        CensorshipMonitor::default(),
+        builder_sink_manager,
+        metrics,
+        HealthMonitor::default(),
+        ShutdownManager::default(),
+        DiskUsageCache::default(),
+        cluster::ReplayCache::default(),
+        GatewayToken::random(),
     );
 
     run_backrunning_server(app_state.shared_channel_infos().clone()).await;
+    run_range_sync(app_state.clone());
+    run_leader_health_monitor(app_state.clone());
+    run_censorship_monitor(app_state.clone());
+    run_pruner(app_state.clone());
+    run_metrics_server(app_state.clone());
+    run_health_monitor(app_state.clone());
+    app_state.builder_sink_manager().start();
 
     initialize_clients(app_state.clone()).await?;
 
@@ -140,10 +388,45 @@ async fn start_tx_orderer(config_option: &mut ConfigOption) -> Result<(), Error>
     let external_handle = tokio::spawn(initialize_external_rpc_server(app_state.clone()));
 
     let handles = vec![internal_handle, cluster_handle, external_handle];
-    let results = try_join_all(handles).await;
-    if let Err(e) = results {
-        tracing::error!("One of the RPC servers terminated unexpectedly: {:?}", e);
-        return Err(error::Error::RpcServerTerminated);
+    tokio::select! {
+        results = try_join_all(handles) => {
+            if let Err(e) = results {
+                tracing::error!("One of the RPC servers terminated unexpectedly: {:?}", e);
+                return Err(error::Error::RpcServerTerminated);
+            }
+        }
+        result = wait_for_shutdown_signal() => {
+            result?;
+
+            // The RPC servers keep running while the node drains, so that the
+            // cluster can still sync from it and take over its rollups.
+            let shutdown_report = shutdown(&app_state).await;
+            if shutdown_report.is_drained {
+                tracing::info!("Shutdown complete - {:?}", shutdown_report);
+            } else {
+                tracing::warn!("Shutdown timed out - {:?}", shutdown_report);
+            }
+            shutdown_tracer();
+        }
+    }
+
+    Ok(())
+}
+
+/// Refuses to start with the development key if a mainnet platform is
+/// configured.
+fn check_signing_key(config: &Config) -> Result<(), Error> {
+    let sequencing_info_list =
+        SequencingInfoList::get_or(SequencingInfoList::default).map_err(Error::Database)?;
+    for (platform, _) in sequencing_info_list.iter() {
+        config.check_signing_key(*platform)?;
+    }
+
+    let validation_service_providers =
+        ValidationServiceProviders::get_or(ValidationServiceProviders::default)
+            .map_err(Error::Database)?;
+    for (platform, _) in validation_service_providers.iter() {
+        config.check_signing_key(*platform)?;
     }
 
     Ok(())
@@ -241,43 +524,88 @@ async fn initialize_clients(app_state: AppState) -> Result<(), Error> {
 
 async fn initialize_internal_rpc_server(context: AppState) -> Result<(), Error> {
     let internal_rpc_url = context.config().internal_rpc_url.to_string();
+    let gateway_listener = tokio::net::TcpListener::bind(context.config().internal_rpc_address())
+        .await
+        .map_err(Error::Syscall)?;
+    let jwt_secret = JwtSecret::load_or_create(&context.config().jwt_secret_path)?;
+
+    // The RPC server only listens on the loopback interface, behind the gateway
+    // that authenticates the callers. It rejects the requests that do not carry
+    // the gateway token, so the port is not reserved until the server binds it.
+    let upstream_rpc_url = {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").map_err(Error::Syscall)?;
+        format!("http://{}", listener.local_addr().map_err(Error::Syscall)?)
+    };
 
     let internal_rpc_server = Arc::new(RpcServer::new(context.clone()));
 
     // register each RPC method
     internal_rpc_server
-        .register_rpc_method::<internal::AddSequencingInfo>()
+        .register_rpc_method::<internal::Forwarded<internal::AddSequencingInfo>>()
+        .await?;
+    internal_rpc_server
+        .register_rpc_method::<internal::Forwarded<internal::AddValidationInfo>>()
+        .await?;
+    internal_rpc_server
+        .register_rpc_method::<internal::Forwarded<internal::AddCluster>>()
+        .await?;
+    internal_rpc_server
+        .register_rpc_method::<internal::Forwarded<internal::GetCluster>>()
+        .await?;
+    internal_rpc_server
+        .register_rpc_method::<internal::Forwarded<internal::GetClusterIdList>>()
         .await?;
     internal_rpc_server
-        .register_rpc_method::<internal::AddValidationInfo>()
+        .register_rpc_method::<internal::Forwarded<internal::GetSequencingInfos>>()
         .await?;
     internal_rpc_server
-        .register_rpc_method::<internal::AddCluster>()
+        .register_rpc_method::<internal::Forwarded<internal::GetSequencingInfo>>()
         .await?;
     internal_rpc_server
-        .register_rpc_method::<internal::GetCluster>()
+        .register_rpc_method::<internal::Forwarded<internal::Deregister>>()
         .await?;
     internal_rpc_server
-        .register_rpc_method::<internal::GetClusterIdList>()
+        .register_rpc_method::<internal::Forwarded<internal::ExportEquivocationEvidence>>()
         .await?;
     internal_rpc_server
-        .register_rpc_method::<internal::GetSequencingInfos>()
+        .register_rpc_method::<internal::Forwarded<internal::SetRetentionPolicy>>()
         .await?;
     internal_rpc_server
-        .register_rpc_method::<internal::GetSequencingInfo>()
+        .register_rpc_method::<internal::Forwarded<internal::GetDiskUsage>>()
+        .await?;
+    internal_rpc_server
+        .register_rpc_method::<internal::Forwarded<internal::GetInternalAuditLog>>()
         .await?;
 
     // start the server
 
-    let internal_handle = internal_rpc_server.init(internal_rpc_url.clone()).await?;
+    let internal_handle = internal_rpc_server.init(upstream_rpc_url.clone()).await?;
 
+    let is_loopback = gateway_listener
+        .local_addr()
+        .map(|address| address.ip().is_loopback())
+        .unwrap_or_default();
+    if !is_loopback && context.config().internal_rpc_allowlist.is_empty() {
+        tracing::warn!(
+            "The internal RPC server listens on {} without an allowlist",
+            internal_rpc_url
+        );
+    }
     tracing::info!(
         "Successfully started the internal RPC server: {}",
         internal_rpc_url
     );
 
-    internal_handle.stopped().await;
-    Ok(())
+    tokio::select! {
+        _ = internal_handle.stopped() => Ok(()),
+        result = run_internal_rpc_gateway(
+            gateway_listener,
+            upstream_rpc_url,
+            context.gateway_token().clone(),
+            jwt_secret,
+            context.config().internal_rpc_allowlist.clone(),
+        ) => Ok(result?),
+    }
 }
 
 async fn initialize_cluster_rpc_server(context: AppState) -> Result<(), Error> {
@@ -286,29 +614,57 @@ async fn initialize_cluster_rpc_server(context: AppState) -> Result<(), Error> {
     let cluster_rpc_server = Arc::new(RpcServer::new(context.clone()));
 
     // register each RPC method
+    cluster_rpc_server
+        .register_rpc_method::<cluster::ChangeLeaderTxOrderer>()
+        .await?;
+    cluster_rpc_server
+        .register_rpc_method::<cluster::GetTransactionsSince>()
+        .await?;
+    // TODO: remove in the next release.
     cluster_rpc_server
         .register_rpc_method::<cluster::GetRawTransactionList>()
         .await?;
+    cluster_rpc_server
+        .register_rpc_method::<cluster::Authenticated<cluster::GetBatchRange>>()
+        .await?;
+    cluster_rpc_server
+        .register_rpc_method::<cluster::Authenticated<cluster::GetTransactionRange>>()
+        .await?;
+    cluster_rpc_server
+        .register_rpc_method::<cluster::Authenticated<cluster::Heartbeat>>()
+        .await?;
+    cluster_rpc_server
+        .register_rpc_method::<cluster::Authenticated<cluster::AttestLeaderFailure>>()
+        .await?;
+    cluster_rpc_server
+        .register_rpc_method::<cluster::Authenticated<cluster::SyncLeaderFailover>>()
+        .await?;
+    cluster_rpc_server
+        .register_rpc_method::<cluster::AcknowledgeTransactions>()
+        .await?;
+    cluster_rpc_server
+        .register_rpc_method::<cluster::Authenticated<cluster::AddOrderCommitmentSignature>>()
+        .await?;
     cluster_rpc_server
         .register_rpc_method::<cluster::SetMaxGasLimit>()
         .await?;
     cluster_rpc_server
-        .register_rpc_method::<cluster::SyncEncryptedTransaction>()
+        .register_rpc_method::<cluster::Authenticated<cluster::SyncEncryptedTransaction>>()
         .await?;
     cluster_rpc_server
-        .register_rpc_method::<cluster::GetOrderCommitmentInfo>()
+        .register_rpc_method::<cluster::Authenticated<cluster::GetOrderCommitmentInfo>>()
         .await?;
     cluster_rpc_server
-        .register_rpc_method::<cluster::SyncLeaderTxOrderer>()
+        .register_rpc_method::<cluster::Authenticated<cluster::SyncLeaderTxOrderer>>()
         .await?;
     cluster_rpc_server
-        .register_rpc_method::<cluster::SyncRawTransaction>()
+        .register_rpc_method::<cluster::Authenticated<cluster::SyncRawTransaction>>()
         .await?;
     cluster_rpc_server
-        .register_rpc_method::<cluster::SyncMaxGasLimit>()
+        .register_rpc_method::<cluster::Authenticated<cluster::SyncMaxGasLimit>>()
         .await?;
     cluster_rpc_server
-        .register_rpc_method::<cluster::SyncBatchCreation>()
+        .register_rpc_method::<cluster::Authenticated<cluster::SyncBatchCreation>>()
         .await?;
 
     cluster_rpc_server
@@ -336,11 +692,17 @@ async fn initialize_cluster_rpc_server(context: AppState) -> Result<(), Error> {
 
 async fn initialize_external_rpc_server(context: AppState) -> Result<(), Error> {
     let external_rpc_url = anywhere(&context.config().external_port()?);
-
-    tracing::info!(
-        "Successfully started the tx_orderer external RPC server: {}",
-        external_rpc_url
-    );
+    let external_listener = tokio::net::TcpListener::bind(&external_rpc_url)
+        .await
+        .map_err(Error::Syscall)?;
+
+    // The RPC server listens on the loopback interface and the external port is
+    // shared with the subscriptions, which are told apart by their WebSocket
+    // upgrade request.
+    let upstream_rpc_address = {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").map_err(Error::Syscall)?;
+        listener.local_addr().map_err(Error::Syscall)?.to_string()
+    };
 
     let external_rpc_server = Arc::new(RpcServer::new(context.clone()));
 
@@ -392,11 +754,38 @@ async fn initialize_external_rpc_server(context: AppState) -> Result<(), Error>
     external_rpc_server
         .register_rpc_method::<external::GetCanProvideTransactionInfo>()
         .await?;
+    external_rpc_server
+        .register_rpc_method::<external::GetTransactionStatus>()
+        .await?;
+    external_rpc_server
+        .register_rpc_method::<external::ReportOrderCommitment>()
+        .await?;
+    external_rpc_server
+        .register_rpc_method::<external::GetEquivocationEvidenceList>()
+        .await?;
+    external_rpc_server
+        .register_rpc_method::<external::VerifyOrderCommitment>()
+        .await?;
+    external_rpc_server
+        .register_rpc_method::<external::GetCensorshipViolationList>()
+        .await?;
+    external_rpc_server
+        .register_rpc_method::<external::GetQuorumOrderCommitment>()
+        .await?;
+
+    let external_handle = external_rpc_server
+        .init(upstream_rpc_address.clone())
+        .await?;
 
-    let external_handle = external_rpc_server.init(external_rpc_url.clone()).await?;
+    tracing::info!(
+        "Successfully started the tx_orderer external RPC server: {}",
+        external_rpc_url
+    );
 
-    external_handle.stopped().await;
-    Ok(())
+    tokio::select! {
+        _ = external_handle.stopped() => Ok(()),
+        result = run_external_rpc_front(context, external_listener, upstream_rpc_address) => result,
+    }
 }
 
 pub fn anywhere(port: &str) -> String {
@@ -411,6 +800,9 @@ fn check_and_update_version() -> Result<(), Error> {
             REQURIED_DATABASE_VERSION,
             version.database_version
         );
+        if get_migration_plan(&version.database_version).is_ok() {
+            tracing::error!("Run `tx_orderer migrate` to migrate the database");
+        }
         return Err(error::Error::DatabaseVersionMismatch);
     }
     version.code_version = CURRENT_CODE_VERSION.to_string();
@@ -1,4 +1,11 @@
-use std::{collections::BTreeMap, str::FromStr, sync::Arc};
+use std::{
+    collections::BTreeMap,
+    str::FromStr,
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        Arc,
+    },
+};
 
 use radius_sdk::{
     liveness::radius::{
@@ -6,13 +13,14 @@ use radius_sdk::{
         subscriber::Subscriber,
         types::{Events, ILivenessServiceManager::Rollup as RollupInfo},
     },
-    signature::{Address, PrivateKeySigner},
+    signature::Address,
 };
 use tokio::time::{sleep, Duration};
 
 use crate::{
     client::seeder::{SeederClient, TxOrdererRpcInfo},
     error::Error,
+    signer::{PublisherEndpoint, TxOrdererSigner},
     state::AppState,
     types::*,
 };
@@ -27,6 +35,10 @@ struct LivenessServiceManagerClientInner {
     publisher: Publisher,
     subscriber: Subscriber,
     seeder: SeederClient,
+
+    is_listening: AtomicBool,
+    /// Unix timestamp in milliseconds of the last block event, 0 if none.
+    last_block_received_at: AtomicU64,
 }
 
 impl Clone for LivenessServiceManagerClient {
@@ -42,12 +54,12 @@ impl LivenessServiceManagerClient {
         platform: Platform,
         liveness_service_provider: LivenessServiceProvider,
         liveness_info: LivenessRadius,
-        signing_key: impl AsRef<str>,
+        publisher_endpoint: PublisherEndpoint,
         seeder: SeederClient,
     ) -> Result<Self, Error> {
         let publisher = Publisher::new(
-            liveness_info.liveness_rpc_url,
-            signing_key,
+            publisher_endpoint.rpc_url,
+            publisher_endpoint.signing_key,
             &liveness_info.contract_address,
         )
         .map_err(|error| Error::LivenessServiceManagerClient(error.into()))?;
@@ -65,6 +77,8 @@ impl LivenessServiceManagerClient {
                 publisher,
                 subscriber,
                 seeder,
+                is_listening: AtomicBool::new(false),
+                last_block_received_at: AtomicU64::new(0),
             }),
         })
     }
@@ -75,9 +89,18 @@ impl LivenessServiceManagerClient {
         liveness_service_provider: LivenessServiceProvider,
         liveness_info: LivenessRadius,
     ) -> Result<(), Error> {
-        let signing_key = &context.config().signing_key;
-        let signer =
-            PrivateKeySigner::from_str(platform.into(), signing_key).expect("Invalid signing key");
+        let signer = TxOrdererSigner::from_config(context.config(), platform).await?;
+        if let TxOrdererSigner::Remote(remote_signer) = &signer {
+            tracing::info!(
+                "Signing with the remote signer - url: {:?}, address: {:?}",
+                remote_signer.url(),
+                remote_signer.address().as_hex_string()
+            );
+        }
+
+        let publisher_endpoint = signer
+            .publisher_endpoint(context.config(), &liveness_info.liveness_rpc_url)
+            .await?;
 
         context.add_signer(platform, signer).await.map_err(|e| {
             tracing::error!(
@@ -92,7 +115,7 @@ impl LivenessServiceManagerClient {
             platform,
             liveness_service_provider,
             liveness_info.clone(),
-            signing_key,
+            publisher_endpoint,
             context.seeder_client().clone(),
         )?;
 
@@ -157,7 +180,11 @@ impl LivenessServiceManagerClient {
                     liveness_service_provider
                 );
 
-                if let Err(error) = event_listener_client
+                event_listener_client
+                    .inner
+                    .is_listening
+                    .store(true, Ordering::Relaxed);
+                let result = event_listener_client
                     .subscriber()
                     .initialize_event_handler(
                         callback,
@@ -166,8 +193,13 @@ impl LivenessServiceManagerClient {
                             event_listener_client.clone(),
                         ),
                     )
-                    .await
-                {
+                    .await;
+                event_listener_client
+                    .inner
+                    .is_listening
+                    .store(false, Ordering::Relaxed);
+
+                if let Err(error) = result {
                     tracing::warn!(
                         "Liveness event listener encountered an error for {:?}, {:?} - {:?}",
                         platform,
@@ -208,6 +240,10 @@ async fn callback(
                 liveness_service_manager_client.service_provider(),
                 block.number
             );
+            liveness_service_manager_client
+                .inner
+                .last_block_received_at
+                .store(current_timestamp(), Ordering::Relaxed);
 
             let cluster_id_list = ClusterIdList::get_or(
                 liveness_service_manager_client.platform(),
@@ -225,6 +261,20 @@ async fn callback(
                 .expect("Failed to convert block margin");
 
             for cluster_id in cluster_id_list.iter() {
+                // Skip the clusters that were deregistered while the previous
+                // ones were being synced, so that their rollups are not
+                // created again.
+                let is_registered = ClusterIdList::get_or(
+                    liveness_service_manager_client.platform(),
+                    liveness_service_manager_client.service_provider(),
+                    ClusterIdList::default,
+                )
+                .map(|cluster_id_list| cluster_id_list.contains(cluster_id))
+                .unwrap_or(false);
+                if !is_registered {
+                    continue;
+                }
+
                 initialize_new_cluster(
                     app_state.clone(),
                     &liveness_service_manager_client,
@@ -304,7 +354,7 @@ pub async fn initialize_new_cluster(
                         tx_orderer_address,
                         block_margin,
                     );
-                    cluster.put(
+                    cluster.put_and_update_with_margin(
                         liveness_service_manager_client.platform(),
                         liveness_service_manager_client.service_provider(),
                         cluster_id,
@@ -409,7 +459,17 @@ async fn get_rollup_id_list(
         .get_rollup_info_list(cluster_id, platform_block_height)
         .await?;
 
+    let mut supported_rollup_id_list = Vec::new();
     for rollup in rollup_list.iter() {
+        if RollupType::from_str(&rollup.rollupType).is_err() {
+            tracing::warn!(
+                "Skipping rollup with unsupported rollup type - rollup_id: {:?} / rollup_type: {:?}",
+                rollup.id,
+                rollup.rollupType
+            );
+            continue;
+        }
+
         let validation_service_provider = ValidationServiceProvider::from_str(
             &rollup.validationInfo.serviceProvider,
         )
@@ -427,10 +487,12 @@ async fn get_rollup_id_list(
             rollup,
         )
         .await?;
+
+        supported_rollup_id_list.push(rollup.id.clone());
     }
 
     let mut rollup_id_list = RollupIdList::new();
-    rollup_id_list.set(rollup_list.iter().map(|rollup| rollup.id.clone()).collect());
+    rollup_id_list.set(supported_rollup_id_list);
 
     Ok(rollup_id_list)
 }
@@ -465,10 +527,7 @@ async fn update_or_create_rollup(
                     .map(|addr| address_from_str(platform, addr.to_string()))
                     .collect();
 
-                let rollup_type = RollupType::from_str(&rollup_info.rollupType).expect(&format!(
-                    "Unsupported rollup type: {:?}",
-                    &rollup_info.rollupType
-                ));
+                let rollup_type = RollupType::from_str(&rollup_info.rollupType)?;
 
                 let order_commitment_type = OrderCommitmentType::from_str(
                     &rollup_info.orderCommitmentType,
@@ -538,6 +597,17 @@ impl LivenessServiceManagerClient {
         &self.inner.subscriber
     }
 
+    pub fn is_listening(&self) -> bool {
+        self.inner.is_listening.load(Ordering::Relaxed)
+    }
+
+    pub fn last_block_received_at(&self) -> Option<u64> {
+        match self.inner.last_block_received_at.load(Ordering::Relaxed) {
+            0 => None,
+            last_block_received_at => Some(last_block_received_at),
+        }
+    }
+
     pub fn seeder(&self) -> &SeederClient {
         &self.inner.seeder
     }
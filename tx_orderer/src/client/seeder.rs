@@ -2,11 +2,14 @@ use std::sync::Arc;
 
 use radius_sdk::{
     json_rpc::client::{Id, RpcClient},
-    signature::{Address, ChainType, PrivateKeySigner, Signature},
+    signature::{Address, ChainType, Signature},
 };
 use serde::{Deserialize, Serialize};
 
-use crate::types::*;
+use crate::{
+    signer::{SignerError, TxOrdererSigner},
+    types::*,
+};
 
 pub struct SeederClient {
     inner: Arc<SeederClientInner>,
@@ -44,7 +47,7 @@ impl SeederClient {
         cluster_id: &ClusterId,
         external_rpc_url: &str,
         cluster_rpc_url: &str,
-        signer: &PrivateKeySigner,
+        signer: &TxOrdererSigner,
     ) -> Result<(), SeederError> {
         let message = RegisterTxOrdererMessage {
             platform,
@@ -56,6 +59,7 @@ impl SeederClient {
         };
         let signature = signer
             .sign_message(&message)
+            .await
             .map_err(SeederError::SignMessage)?;
         let parameter = RegisterTxOrderer { message, signature };
 
@@ -82,7 +86,7 @@ impl SeederClient {
         platform: Platform,
         liveness_service_provider: LivenessServiceProvider,
         cluster_id: &ClusterId,
-        signer: &PrivateKeySigner,
+        signer: &TxOrdererSigner,
     ) -> Result<(), SeederError> {
         let message = DeregisterTxOrdererMessage {
             platform,
@@ -92,6 +96,7 @@ impl SeederClient {
         };
         let signature = signer
             .sign_message(&message)
+            .await
             .map_err(SeederError::SignMessage)?;
         let parameter = DeregisterTxOrderer { message, signature };
 
@@ -240,7 +245,7 @@ pub enum SeederError {
     Deregister(radius_sdk::json_rpc::client::RpcClientError),
     GetTxOrdererInfoList(radius_sdk::json_rpc::client::RpcClientError),
     GetTxOrdererInfo(radius_sdk::json_rpc::client::RpcClientError),
-    SignMessage(radius_sdk::signature::SignatureError),
+    SignMessage(SignerError),
 }
 
 impl std::fmt::Display for SeederError {
@@ -1,13 +1,21 @@
 use std::sync::Arc;
 
-use radius_sdk::validation::eigenlayer::{
-    publisher::Publisher,
-    subscriber::Subscriber,
-    types::{Avs, Bytes, IValidationServiceManager},
+use radius_sdk::{
+    signature::Address,
+    validation::eigenlayer::{
+        publisher::Publisher,
+        subscriber::Subscriber,
+        types::{Avs, Bytes, IValidationServiceManager},
+    },
 };
 use tokio::time::{sleep, Duration};
 
-use crate::{error::Error, state::AppState, types::*};
+use crate::{
+    error::Error,
+    signer::{PublisherEndpoint, TxOrdererSigner},
+    state::AppState,
+    types::*,
+};
 
 const LOG_TARGET: &str = "client::validation_service_manager::eigenlayer";
 
@@ -20,6 +28,9 @@ struct ValidationServiceManagerClientInner {
     validation_service_provider: ValidationServiceProvider,
     publisher: Publisher,
     subscriber: Subscriber,
+    /// Address of the signer of the node, which the publisher sends its
+    /// transactions for.
+    tx_orderer_address: Address,
 }
 
 impl Clone for ValidationServiceManagerClient {
@@ -47,15 +58,20 @@ impl ValidationServiceManagerClient {
         &self.inner.subscriber
     }
 
+    pub fn tx_orderer_address(&self) -> &Address {
+        &self.inner.tx_orderer_address
+    }
+
     pub fn new(
         platform: Platform,
         validation_service_provider: ValidationServiceProvider,
         eigen_layer_validation_info: EigenLayerValidationInfo,
-        signing_key: impl AsRef<str>,
+        publisher_endpoint: PublisherEndpoint,
+        tx_orderer_address: Address,
     ) -> Result<Self, Error> {
         let publisher = Publisher::new(
-            eigen_layer_validation_info.validation_rpc_url,
-            signing_key,
+            publisher_endpoint.rpc_url,
+            publisher_endpoint.signing_key,
             eigen_layer_validation_info.delegation_manager_contract_address,
             eigen_layer_validation_info.avs_directory_contract_address,
             eigen_layer_validation_info.stake_registry_contract_address,
@@ -74,6 +90,7 @@ impl ValidationServiceManagerClient {
             validation_service_provider,
             publisher,
             subscriber,
+            tx_orderer_address,
         };
 
         Ok(Self {
@@ -92,12 +109,19 @@ impl ValidationServiceManagerClient {
             let validation_info = eigen_layer_validation_info.clone();
 
             async move {
-                let signing_key = &context.config().signing_key;
+                let signer = TxOrdererSigner::from_config(context.config(), platform)
+                    .await
+                    .expect("Failed to initialize the signer");
+                let publisher_endpoint = signer
+                    .publisher_endpoint(context.config(), &validation_info.validation_rpc_url)
+                    .await
+                    .expect("Failed to initialize the publisher endpoint");
                 let validation_service_manager_client = Self::new(
                     platform,
                     validation_service_provider,
                     validation_info,
-                    signing_key,
+                    publisher_endpoint,
+                    signer.address().clone(),
                 )
                 .expect("Failed to initialize `EigenLayer` validation service manager client");
 
@@ -155,7 +179,7 @@ async fn callback(event: Avs::NewTaskCreated, context: ValidationServiceManagerC
             }
         };
 
-        if batch.batch_creator_address != context.publisher().address() {
+        if batch.batch_creator_address != *context.tx_orderer_address() {
             let task = IValidationServiceManager::Task {
                 commitment: Bytes::from_iter(&[0u8; 32]),
                 blockNumber: 0, // TODO: change
@@ -181,6 +205,16 @@ async fn callback(event: Avs::NewTaskCreated, context: ValidationServiceManagerC
                 "callback response: {:?}",
                 transaction_hash
             );
+
+            let _ =
+                BatchCommitmentSubmission::set_task_responded(&event.rollupId, batch.batch_number)
+                    .map_err(|error| {
+                        tracing::error!(
+                            target: LOG_TARGET,
+                            "Failed to put batch commitment submission: {:?}",
+                            error
+                        );
+                    });
         }
     }
 }
@@ -1,11 +1,20 @@
 use std::sync::Arc;
 
-use radius_sdk::validation::symbiotic::{
-    publisher::Publisher, subscriber::Subscriber, types::ValidationServiceManager,
+use radius_sdk::{
+    signature::Address,
+    validation::symbiotic::{
+        publisher::Publisher, subscriber::Subscriber, types::ValidationServiceManager,
+    },
 };
 use tokio::time::{sleep, Duration};
 
-use crate::{client::reward_manager, error::Error, state::AppState, types::*};
+use crate::{
+    client::reward_manager,
+    error::Error,
+    signer::{PublisherEndpoint, TxOrdererSigner},
+    state::AppState,
+    types::*,
+};
 const LOG_TARGET: &str = "client::validation_service_manager::symbiotic";
 
 pub struct ValidationServiceManagerClient {
@@ -17,6 +26,9 @@ struct ValidationServiceManagerClientInner {
     validation_service_provider: ValidationServiceProvider,
     publisher: Publisher,
     subscriber: Subscriber,
+    /// Address of the signer of the node, which the publisher sends its
+    /// transactions for.
+    tx_orderer_address: Address,
 }
 
 impl Clone for ValidationServiceManagerClient {
@@ -44,15 +56,20 @@ impl ValidationServiceManagerClient {
         &self.inner.subscriber
     }
 
+    pub fn tx_orderer_address(&self) -> &Address {
+        &self.inner.tx_orderer_address
+    }
+
     pub fn new(
         platform: Platform,
         validation_service_provider: ValidationServiceProvider,
         symbiotic_validation_info: SymbioticValidationInfo,
-        signing_key: impl AsRef<str>,
+        publisher_endpoint: PublisherEndpoint,
+        tx_orderer_address: Address,
     ) -> Result<Self, Error> {
         let publisher = Publisher::new(
-            symbiotic_validation_info.validation_rpc_url,
-            signing_key,
+            publisher_endpoint.rpc_url,
+            publisher_endpoint.signing_key,
             symbiotic_validation_info
                 .validation_contract_address
                 .clone(),
@@ -67,6 +84,7 @@ impl ValidationServiceManagerClient {
             validation_service_provider,
             publisher,
             subscriber,
+            tx_orderer_address,
         };
 
         Ok(Self {
@@ -85,11 +103,19 @@ impl ValidationServiceManagerClient {
             let validation_info = symbiotic_validation_info.clone();
 
             async move {
+                let signer = TxOrdererSigner::from_config(context.config(), platform)
+                    .await
+                    .expect("Failed to initialize the signer");
+                let publisher_endpoint = signer
+                    .publisher_endpoint(context.config(), &validation_info.validation_rpc_url)
+                    .await
+                    .expect("Failed to initialize the publisher endpoint");
                 let validation_service_manager_client = Self::new(
                     platform,
                     validation_service_provider,
                     validation_info,
-                    &context.config().signing_key,
+                    publisher_endpoint,
+                    signer.address().clone(),
                 )
                 .expect("Failed to initialize Symbiotic validation service manager client");
 
@@ -185,7 +211,7 @@ async fn callback(
             event.batchCommitment
         );
 
-        if batch.batch_creator_address != context.publisher().address() {
+        if batch.batch_creator_address != *context.tx_orderer_address() {
             let (
                 reward_task_id,
                 vault_address_list,
@@ -262,6 +288,18 @@ async fn callback(
                             "respond_to_task: {:?}",
                             transaction_hash
                         );
+
+                        let _ = BatchCommitmentSubmission::set_task_responded(
+                            &rollup.rollup_id,
+                            batch.batch_number,
+                        )
+                        .map_err(|error| {
+                            tracing::error!(
+                                target: LOG_TARGET,
+                                "Failed to put batch commitment submission: {:?}",
+                                error
+                            );
+                        });
                         break;
                     }
                     Err(error) => {
@@ -0,0 +1,264 @@
+use std::io::Write;
+
+use serde::{Deserialize, Serialize};
+
+use super::get_batch_with_transaction_count;
+use crate::{error::Error, types::*};
+
+/// A line of an export file. Transactions carry the hash they are indexed by,
+/// so that both indexes can be restored.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(tag = "type", content = "data")]
+#[serde(rename_all = "snake_case")]
+pub enum ExportRecord {
+    Rollup(Rollup),
+    RollupMetadata {
+        rollup_id: RollupId,
+        rollup_metadata: RollupMetadata,
+    },
+    Batch {
+        rollup_id: RollupId,
+        batch: Batch,
+    },
+    RawTransaction {
+        rollup_id: RollupId,
+        batch_number: u64,
+        transaction_order: u64,
+        transaction_hash: String,
+        raw_transaction: RawTransaction,
+        is_direct_sent: bool,
+    },
+    EncryptedTransaction {
+        rollup_id: RollupId,
+        batch_number: u64,
+        transaction_order: u64,
+        transaction_hash: String,
+        encrypted_transaction: EncryptedTransaction,
+    },
+    OrderCommitment {
+        rollup_id: RollupId,
+        batch_number: u64,
+        transaction_order: u64,
+        order_commitment: OrderCommitment,
+    },
+    QuorumOrderCommitment {
+        rollup_id: RollupId,
+        batch_number: u64,
+        transaction_order: u64,
+        quorum_order_commitment: QuorumOrderCommitment,
+    },
+    TransactionStatusInfo {
+        rollup_id: RollupId,
+        transaction_hash: String,
+        transaction_status_info: TransactionStatusInfo,
+    },
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct ExportReport {
+    pub rollup_id: RollupId,
+    pub from_batch_number: u64,
+    pub to_batch_number: u64,
+    pub record_count: u64,
+}
+
+struct ExportWriter<W: Write> {
+    writer: W,
+    record_count: u64,
+}
+
+impl<W: Write> ExportWriter<W> {
+    fn write(&mut self, export_record: &ExportRecord) -> Result<(), Error> {
+        serde_json::to_writer(&mut self.writer, export_record).map_err(|_| Error::Convert)?;
+        self.writer.write_all(b"\n")?;
+        self.record_count += 1;
+
+        Ok(())
+    }
+}
+
+/// Writes the rollup, its metadata and every batch, transaction and order
+/// commitment in `from_batch_number..=to_batch_number` as JSON lines. The range
+/// defaults to the batches that have not been pruned.
+pub fn export(
+    rollup_id: &RollupId,
+    from_batch_number: Option<u64>,
+    to_batch_number: Option<u64>,
+    writer: impl Write,
+) -> Result<ExportReport, Error> {
+    let rollup = Rollup::get(rollup_id)?;
+    let rollup_metadata = RollupMetadata::get(rollup_id)?;
+
+    let from_batch_number = match from_batch_number {
+        Some(from_batch_number) => from_batch_number,
+        None => RetentionCursor::get_or(rollup_id, RetentionCursor::default)?.batch_number,
+    };
+    let to_batch_number = to_batch_number
+        .unwrap_or(rollup_metadata.batch_number)
+        .min(rollup_metadata.batch_number);
+
+    let mut export_writer = ExportWriter {
+        writer,
+        record_count: 0,
+    };
+    export_writer.write(&ExportRecord::Rollup(rollup))?;
+    export_writer.write(&ExportRecord::RollupMetadata {
+        rollup_id: rollup_id.clone(),
+        rollup_metadata: rollup_metadata.clone(),
+    })?;
+
+    for batch_number in from_batch_number..=to_batch_number {
+        let (batch, transaction_count) =
+            get_batch_with_transaction_count(rollup_id, batch_number, &rollup_metadata)?;
+
+        for transaction_order in 0..transaction_count {
+            export_transaction(
+                &mut export_writer,
+                rollup_id,
+                batch_number,
+                transaction_order,
+            )?;
+        }
+
+        if let Some(batch) = batch {
+            export_writer.write(&ExportRecord::Batch {
+                rollup_id: rollup_id.clone(),
+                batch,
+            })?;
+        }
+    }
+
+    export_writer.writer.flush()?;
+
+    Ok(ExportReport {
+        rollup_id: rollup_id.clone(),
+        from_batch_number,
+        to_batch_number,
+        record_count: export_writer.record_count,
+    })
+}
+
+fn export_transaction<W: Write>(
+    export_writer: &mut ExportWriter<W>,
+    rollup_id: &RollupId,
+    batch_number: u64,
+    transaction_order: u64,
+) -> Result<(), Error> {
+    let encrypted_transaction =
+        match EncryptedTransactionModel::get(rollup_id, batch_number, transaction_order) {
+            Ok(encrypted_transaction) => Some(encrypted_transaction),
+            Err(error) if error.is_none_type() => None,
+            Err(error) => return Err(error.into()),
+        };
+    let raw_transaction = match RawTransactionModel::get(rollup_id, batch_number, transaction_order)
+    {
+        Ok(raw_transaction) => Some(raw_transaction),
+        Err(error) if error.is_none_type() => None,
+        Err(error) => return Err(error.into()),
+    };
+
+    // Encrypted transactions are indexed by the hash in their open data.
+    let transaction_hash = match (&encrypted_transaction, &raw_transaction) {
+        (Some(encrypted_transaction), _) => encrypted_transaction.raw_transaction_hash(),
+        (None, Some((raw_transaction, _))) => raw_transaction.raw_transaction_hash(),
+        (None, None) => return Ok(()),
+    };
+    let transaction_hash: &str = transaction_hash.as_ref();
+
+    if let Some(encrypted_transaction) = encrypted_transaction {
+        export_writer.write(&ExportRecord::EncryptedTransaction {
+            rollup_id: rollup_id.clone(),
+            batch_number,
+            transaction_order,
+            transaction_hash: transaction_hash.to_owned(),
+            encrypted_transaction,
+        })?;
+    }
+
+    if let Some((raw_transaction, is_direct_sent)) = raw_transaction {
+        export_writer.write(&ExportRecord::RawTransaction {
+            rollup_id: rollup_id.clone(),
+            batch_number,
+            transaction_order,
+            transaction_hash: transaction_hash.to_owned(),
+            raw_transaction,
+            is_direct_sent,
+        })?;
+    }
+
+    match OrderCommitment::get(rollup_id, batch_number, transaction_order) {
+        Ok(order_commitment) => export_writer.write(&ExportRecord::OrderCommitment {
+            rollup_id: rollup_id.clone(),
+            batch_number,
+            transaction_order,
+            order_commitment,
+        })?,
+        Err(error) if error.is_none_type() => {}
+        Err(error) => return Err(error.into()),
+    }
+
+    match QuorumOrderCommitment::get(rollup_id, batch_number, transaction_order) {
+        Ok(quorum_order_commitment) => {
+            export_writer.write(&ExportRecord::QuorumOrderCommitment {
+                rollup_id: rollup_id.clone(),
+                batch_number,
+                transaction_order,
+                quorum_order_commitment,
+            })?
+        }
+        Err(error) if error.is_none_type() => {}
+        Err(error) => return Err(error.into()),
+    }
+
+    match TransactionStatusInfo::get(rollup_id, transaction_hash) {
+        Ok(transaction_status_info) => {
+            export_writer.write(&ExportRecord::TransactionStatusInfo {
+                rollup_id: rollup_id.clone(),
+                transaction_hash: transaction_hash.to_owned(),
+                transaction_status_info,
+            })?
+        }
+        Err(error) if error.is_none_type() => {}
+        Err(error) => return Err(error.into()),
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn records_are_written_as_json_lines() {
+        let mut export_writer = ExportWriter {
+            writer: Vec::new(),
+            record_count: 0,
+        };
+        for transaction_order in 0..2 {
+            export_writer
+                .write(&ExportRecord::RawTransaction {
+                    rollup_id: "rollup".to_owned(),
+                    batch_number: 1,
+                    transaction_order,
+                    transaction_hash: format!("0x{:064x}", transaction_order),
+                    raw_transaction: RawTransaction::default(),
+                    is_direct_sent: false,
+                })
+                .unwrap();
+        }
+        assert_eq!(export_writer.record_count, 2);
+
+        let export_file = String::from_utf8(export_writer.writer).unwrap();
+        let transaction_order_list: Vec<u64> = export_file
+            .lines()
+            .map(|line| match serde_json::from_str(line).unwrap() {
+                ExportRecord::RawTransaction {
+                    transaction_order, ..
+                } => transaction_order,
+                export_record => panic!("unexpected record: {:?}", export_record),
+            })
+            .collect();
+        assert_eq!(transaction_order_list, vec![0, 1]);
+    }
+}
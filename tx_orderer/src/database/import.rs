@@ -0,0 +1,161 @@
+use std::io::BufRead;
+
+use serde::{Deserialize, Serialize};
+
+use super::ExportRecord;
+use crate::{error::Error, types::*};
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct ImportReport {
+    pub record_count: u64,
+}
+
+/// Restores the records of an export file, overwriting the entries that
+/// already exist. Every line is parsed before anything is written, so a
+/// malformed file leaves the database untouched.
+pub fn import(reader: impl BufRead) -> Result<ImportReport, Error> {
+    let export_record_list = parse_export_record_list(reader)?;
+
+    let record_count = export_record_list.len() as u64;
+    for export_record in export_record_list {
+        import_record(export_record)?;
+    }
+
+    Ok(ImportReport { record_count })
+}
+
+/// Parses every line of an export file, skipping the blank ones.
+fn parse_export_record_list(reader: impl BufRead) -> Result<Vec<ExportRecord>, Error> {
+    let mut export_record_list = Vec::new();
+    for (line_number, line) in reader.lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let export_record: ExportRecord = serde_json::from_str(&line).map_err(|error| {
+            Error::GeneralError(format!(
+                "Invalid record at line {}: {}",
+                line_number + 1,
+                error
+            ))
+        })?;
+        export_record_list.push(export_record);
+    }
+
+    Ok(export_record_list)
+}
+
+fn import_record(export_record: ExportRecord) -> Result<(), Error> {
+    match export_record {
+        ExportRecord::Rollup(rollup) => {
+            rollup.put(&rollup.rollup_id)?;
+
+            let mut rollup_id_list = RollupIdList::get_mut_or(RollupIdList::default)?;
+            rollup_id_list.insert(&rollup.rollup_id);
+            rollup_id_list.update()?;
+        }
+        ExportRecord::RollupMetadata {
+            rollup_id,
+            rollup_metadata,
+        } => rollup_metadata.put(&rollup_id)?,
+        ExportRecord::Batch { rollup_id, batch } => {
+            Batch::put(&batch, &rollup_id, batch.batch_number)?
+        }
+        ExportRecord::RawTransaction {
+            rollup_id,
+            batch_number,
+            transaction_order,
+            transaction_hash,
+            raw_transaction,
+            is_direct_sent,
+        } => {
+            RawTransactionModel::put_with_transaction_hash(
+                &rollup_id,
+                &RawTransactionHash::from(transaction_hash),
+                raw_transaction.clone(),
+                is_direct_sent,
+            )?;
+            RawTransactionModel::put(
+                &rollup_id,
+                batch_number,
+                transaction_order,
+                raw_transaction,
+                is_direct_sent,
+            )?;
+        }
+        ExportRecord::EncryptedTransaction {
+            rollup_id,
+            batch_number,
+            transaction_order,
+            transaction_hash,
+            encrypted_transaction,
+        } => {
+            EncryptedTransactionModel::put_with_transaction_hash(
+                &rollup_id,
+                &RawTransactionHash::from(transaction_hash),
+                &encrypted_transaction,
+            )?;
+            EncryptedTransactionModel::put(
+                &rollup_id,
+                batch_number,
+                transaction_order,
+                &encrypted_transaction,
+            )?;
+        }
+        ExportRecord::OrderCommitment {
+            rollup_id,
+            batch_number,
+            transaction_order,
+            order_commitment,
+        } => order_commitment.put(&rollup_id, batch_number, transaction_order)?,
+        ExportRecord::QuorumOrderCommitment {
+            rollup_id,
+            batch_number,
+            transaction_order,
+            quorum_order_commitment,
+        } => quorum_order_commitment.put(&rollup_id, batch_number, transaction_order)?,
+        ExportRecord::TransactionStatusInfo {
+            rollup_id,
+            transaction_hash,
+            transaction_status_info,
+        } => transaction_status_info.put(&rollup_id, &transaction_hash)?,
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn blank_lines_are_skipped() {
+        let rollup_metadata = serde_json::to_string(&ExportRecord::RollupMetadata {
+            rollup_id: "rollup".to_owned(),
+            rollup_metadata: RollupMetadata::default(),
+        })
+        .unwrap();
+        let export_file = format!("{}\n\n  \n{}\n", rollup_metadata, rollup_metadata);
+
+        let export_record_list = parse_export_record_list(export_file.as_bytes()).unwrap();
+        assert_eq!(export_record_list.len(), 2);
+    }
+
+    #[test]
+    fn malformed_line_is_reported_with_its_number() {
+        let rollup_metadata = serde_json::to_string(&ExportRecord::RollupMetadata {
+            rollup_id: "rollup".to_owned(),
+            rollup_metadata: RollupMetadata::default(),
+        })
+        .unwrap();
+        let export_file = format!("{}\n\n{{\"type\":\"batch\"}}\n", rollup_metadata);
+
+        match parse_export_record_list(export_file.as_bytes()) {
+            Err(Error::GeneralError(message)) => {
+                assert!(message.starts_with("Invalid record at line 3"))
+            }
+            result => panic!("unexpected result: {:?}", result),
+        }
+    }
+}
@@ -0,0 +1,62 @@
+use radius_sdk::kvstore::KvStoreError;
+use serde::{Deserialize, Serialize};
+
+use crate::{error::Error, types::*};
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct InspectReport {
+    pub rollup: Rollup,
+    pub rollup_metadata: Option<RollupMetadata>,
+    pub can_provide_transaction_info: Option<CanProvideTransactionInfo>,
+    pub retention_cursor: Option<RetentionCursor>,
+
+    pub cluster_metadata: Option<ClusterMetadata>,
+    pub platform_block_height: u64,
+    pub cluster: Option<Cluster>,
+}
+
+/// Collects the state of the rollup and of its cluster at
+/// `platform_block_height`, which defaults to the height in the cluster
+/// metadata.
+pub fn inspect(
+    rollup_id: &RollupId,
+    platform_block_height: Option<u64>,
+) -> Result<InspectReport, Error> {
+    let rollup = Rollup::get(rollup_id)?;
+
+    let cluster_metadata = get_optional(ClusterMetadata::get(
+        rollup.platform,
+        rollup.liveness_service_provider,
+        &rollup.cluster_id,
+    ))?;
+    let platform_block_height = platform_block_height.unwrap_or_else(|| {
+        cluster_metadata
+            .as_ref()
+            .map(|cluster_metadata| cluster_metadata.platform_block_height)
+            .unwrap_or_default()
+    });
+    let cluster = get_optional(Cluster::get(
+        rollup.platform,
+        rollup.liveness_service_provider,
+        &rollup.cluster_id,
+        platform_block_height,
+    ))?;
+
+    Ok(InspectReport {
+        rollup_metadata: get_optional(RollupMetadata::get(rollup_id))?,
+        can_provide_transaction_info: get_optional(CanProvideTransactionInfo::get(rollup_id))?,
+        retention_cursor: get_optional(RetentionCursor::get(rollup_id))?,
+        rollup,
+        cluster_metadata,
+        platform_block_height,
+        cluster,
+    })
+}
+
+fn get_optional<T>(result: Result<T, KvStoreError>) -> Result<Option<T>, Error> {
+    match result {
+        Ok(value) => Ok(Some(value)),
+        Err(error) if error.is_none_type() => Ok(None),
+        Err(error) => Err(error.into()),
+    }
+}
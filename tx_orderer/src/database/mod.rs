@@ -0,0 +1,74 @@
+//! Offline access to the database of a stopped node, used by the `db`
+//! subcommands to debug incidents and to move data between nodes.
+
+mod export;
+mod import;
+mod inspect;
+mod verify;
+
+use std::path::Path;
+
+pub use export::*;
+pub use import::*;
+pub use inspect::*;
+use radius_sdk::kvstore::KvStoreBuilder;
+pub use verify::*;
+
+use crate::{error::Error, types::*};
+
+/// Opens the database under `config_path`. The database must already exist,
+/// so that a mistyped path does not create an empty one, and be at
+/// [`REQURIED_DATABASE_VERSION`], so that models are not read in a layout they
+/// were not written in.
+pub fn open_database(config_path: &Path) -> Result<(), Error> {
+    let database_path = config_path.join(DATABASE_DIR_NAME);
+    if !database_path.exists() {
+        return Err(Error::GeneralError(format!(
+            "Database does not exist at {:?}",
+            database_path
+        )));
+    }
+
+    let kv_store = KvStoreBuilder::default()
+        .set_default_lock_timeout(10000)
+        .set_txn_lock_timeout(10000)
+        .build(database_path)
+        .map_err(Error::Database)?;
+    kv_store.init();
+
+    let version = Version::get_or(Version::default).map_err(Error::Database)?;
+    if version.database_version != REQURIED_DATABASE_VERSION {
+        tracing::error!(
+            "Database version mismatch: expected {}, found {}",
+            REQURIED_DATABASE_VERSION,
+            version.database_version
+        );
+        return Err(Error::DatabaseVersionMismatch);
+    }
+
+    Ok(())
+}
+
+/// Returns the batch and the number of transactions stored for it. The
+/// transactions of a sealed batch are counted from the batch itself, and those
+/// of the batch that is still being filled from the rollup metadata.
+pub(crate) fn get_batch_with_transaction_count(
+    rollup_id: &RollupId,
+    batch_number: u64,
+    rollup_metadata: &RollupMetadata,
+) -> Result<(Option<Batch>, u64), Error> {
+    match Batch::get(rollup_id, batch_number) {
+        Ok(batch) => {
+            let transaction_count = batch.raw_transaction_list.len() as u64;
+            Ok((Some(batch), transaction_count))
+        }
+        Err(error) if error.is_none_type() => {
+            if batch_number == rollup_metadata.batch_number {
+                Ok((None, rollup_metadata.transaction_order))
+            } else {
+                Ok((None, 0))
+            }
+        }
+        Err(error) => Err(error.into()),
+    }
+}
@@ -0,0 +1,126 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{error::Error, types::*};
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct BatchCommitmentMismatch {
+    pub rollup_id: RollupId,
+    pub batch_number: u64,
+    pub stored_batch_commitment: BatchCommitment,
+    pub computed_batch_commitment: BatchCommitment,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct VerifyReport {
+    pub verified_batch_count: u64,
+    pub mismatch_list: Vec<BatchCommitmentMismatch>,
+}
+
+/// Recomputes the commitment of every stored batch of the rollup, or of every
+/// rollup if none is given, from its raw transactions.
+pub async fn verify(rollup_id: Option<&RollupId>) -> Result<VerifyReport, Error> {
+    let rollup_id_list: Vec<RollupId> = match rollup_id {
+        Some(rollup_id) => vec![rollup_id.clone()],
+        None => RollupIdList::get_or(RollupIdList::default)?
+            .iter()
+            .cloned()
+            .collect(),
+    };
+
+    let mut verify_report = VerifyReport::default();
+    for rollup_id in rollup_id_list.iter() {
+        let rollup_metadata = RollupMetadata::get(rollup_id)?;
+        let retention_cursor = RetentionCursor::get_or(rollup_id, RetentionCursor::default)?;
+
+        for batch_number in retention_cursor.batch_number..rollup_metadata.batch_number {
+            let batch = match Batch::get(rollup_id, batch_number) {
+                Ok(batch) => batch,
+                Err(error) if error.is_none_type() => continue,
+                Err(error) => return Err(error.into()),
+            };
+
+            let computed_batch_commitment = compute_batch_commitment(&batch).await;
+            if computed_batch_commitment != batch.batch_commitment {
+                tracing::warn!(
+                    "Batch commitment mismatch - rollup_id: {:?} / batch_number: {:?}",
+                    rollup_id,
+                    batch_number
+                );
+
+                verify_report.mismatch_list.push(BatchCommitmentMismatch {
+                    rollup_id: rollup_id.clone(),
+                    batch_number,
+                    stored_batch_commitment: batch.batch_commitment,
+                    computed_batch_commitment,
+                });
+            }
+
+            verify_report.verified_batch_count += 1;
+        }
+    }
+
+    Ok(verify_report)
+}
+
+/// Builds the merkle root of the raw transaction hashes the same way the batch
+/// is finalized.
+async fn compute_batch_commitment(batch: &Batch) -> BatchCommitment {
+    let merkle_tree = MerkleTree::from_raw_transaction_list(&batch.raw_transaction_list).await;
+
+    BatchCommitment::from(merkle_tree.get_merkle_root().await)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use radius_sdk::signature::{ChainType, PrivateKeySigner};
+
+    use super::*;
+
+    async fn sealed_batch() -> Batch {
+        let raw_transaction_list: Vec<RawTransaction> = ["0x0201", "0x0202", "0x0203"]
+            .into_iter()
+            .map(|raw_transaction| {
+                RawTransaction::from(EthRawTransaction::from(raw_transaction.to_owned()))
+            })
+            .collect();
+
+        let merkle_tree = MerkleTree::new();
+        for raw_transaction in &raw_transaction_list {
+            merkle_tree
+                .add_data(raw_transaction.raw_transaction_hash().as_ref())
+                .await;
+        }
+        merkle_tree.finalize_tree().await;
+        let merkle_root = merkle_tree.get_merkle_root().await;
+
+        let leader = PrivateKeySigner::from_str(ChainType::Ethereum, DEFAULT_SIGNING_KEY).unwrap();
+        Batch::new(
+            0,
+            vec![None; raw_transaction_list.len()],
+            raw_transaction_list,
+            BatchCommitment::from(merkle_root),
+            leader.address().clone(),
+            leader.sign_message(&merkle_root).unwrap(),
+        )
+    }
+
+    #[tokio::test]
+    async fn batch_commitment_is_recomputed_from_the_raw_transactions() {
+        let batch = sealed_batch().await;
+        assert_eq!(
+            compute_batch_commitment(&batch).await,
+            batch.batch_commitment
+        );
+
+        // A transaction replaced after the batch was sealed.
+        let mut tampered_batch = batch.clone();
+        tampered_batch.raw_transaction_list[1] =
+            RawTransaction::from(EthRawTransaction::from("0x0204".to_owned()));
+        assert_ne!(
+            compute_batch_commitment(&tampered_batch).await,
+            batch.batch_commitment
+        );
+    }
+}
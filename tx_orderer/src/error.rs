@@ -11,6 +11,10 @@ pub enum Error {
     RpcClient(radius_sdk::json_rpc::client::RpcClientError),
     Internal(Box<dyn std::error::Error>),
     Signature(radius_sdk::signature::SignatureError),
+    Signer(crate::signer::SignerError),
+    AccessControl(crate::access_control::AccessControlError),
+    Metrics(crate::metrics::MetricsError),
+    Telemetry(crate::telemetry::TelemetryError),
     SerializeEthRawTransaction(serde_json::Error),
     LivenessServiceManagerClient(Box<dyn std::error::Error>),
     ValidationServiceManagerClient(Box<dyn std::error::Error>),
@@ -21,6 +25,7 @@ pub enum Error {
     RewardManager(crate::client::reward_manager::RewardManagerError),
     Seeder(crate::client::seeder::SeederError),
     Profiler(crate::profiler::ProfilerError),
+    BuilderSinkWebSocket(tokio_tungstenite::tungstenite::Error),
     MerkleTreeDoesNotExist(String),
     InitializeNewCluster(Box<dyn std::error::Error>),
     NoLeader,
@@ -38,6 +43,7 @@ pub enum Error {
     UnsupportedPlatform,
     UnsupportedValidationServiceProvider,
     UnsupportedRollupType,
+    UnsupportedTransactionType,
     UnsupportedOrderCommitmentType,
     InvalidURL(reqwest::Error),
     HealthCheck(reqwest::Error),
@@ -53,8 +59,24 @@ pub enum Error {
     DatabaseVersionMismatch,
     Parse,
     InvalidBatchNumber,
+    UnservedTransactionAcknowledged,
     ClusterMetadataNotFound,
     RollupMetadataNotFound,
+    TransactionNotFound,
+    InvalidBatchCommitment,
+    ForwardQueueFull,
+    LeaderChanged,
+    LeaderUnhealthy,
+    InvalidLeaderFailover,
+    Equivocation,
+    LeaderHandOffRequired(String),
+    DrainTimeout,
+    ShuttingDown,
+    ExpiredClusterMessage,
+    ReplayedClusterMessage,
+    UnknownClusterMember,
+    ExpiredExecutorMessage,
+    ReplayedExecutorMessage,
 
     GeneralError(String),
 
@@ -96,6 +118,30 @@ impl From<radius_sdk::signature::SignatureError> for Error {
     }
 }
 
+impl From<crate::signer::SignerError> for Error {
+    fn from(value: crate::signer::SignerError) -> Self {
+        Self::Signer(value)
+    }
+}
+
+impl From<crate::access_control::AccessControlError> for Error {
+    fn from(value: crate::access_control::AccessControlError) -> Self {
+        Self::AccessControl(value)
+    }
+}
+
+impl From<crate::metrics::MetricsError> for Error {
+    fn from(value: crate::metrics::MetricsError) -> Self {
+        Self::Metrics(value)
+    }
+}
+
+impl From<crate::telemetry::TelemetryError> for Error {
+    fn from(value: crate::telemetry::TelemetryError) -> Self {
+        Self::Telemetry(value)
+    }
+}
+
 impl From<crate::types::ConfigError> for Error {
     fn from(value: crate::types::ConfigError) -> Self {
         Self::Config(value)
@@ -0,0 +1,313 @@
+//! Liveness and readiness of the node, served at `/health` and `/ready` next
+//! to the metrics.
+//!
+//! Liveness only tells that the node is running. Readiness is checked every
+//! few seconds by [`run_health_monitor`] and tells whether the node can order
+//! raw transactions. The health of the distributed key generation is reported
+//! apart, as only the encrypted transactions depend on it. The leader reports
+//! both in the heartbeat, and the followers stop forwarding to a leader that
+//! is not ready, and the encrypted transactions to a leader whose distributed
+//! key generation is not healthy either. A node that is shutting down is never
+//! ready.
+
+mod server;
+
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use serde::{Deserialize, Serialize};
+pub use server::*;
+use tokio::time::timeout;
+
+use crate::{
+    client::liveness_service_manager::radius::LivenessServiceManagerClient, state::AppState,
+    types::*,
+};
+
+const READINESS_CHECK_INTERVAL: Duration = Duration::from_secs(5);
+const CHECK_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// The number of blocks a cluster may be synced behind the chain head.
+pub const MAX_CLUSTER_SYNC_LAG: u64 = 10;
+/// The number of encrypted transactions that may wait for decryption.
+pub const MAX_DECRYPTION_BACKLOG: usize = 10_000;
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Readiness {
+    /// Whether the node can order raw transactions, regardless of
+    /// `distributed_key_generation`.
+    pub is_ready: bool,
+    /// Unix timestamp in milliseconds.
+    pub checked_at: u64,
+    pub is_shutting_down: bool,
+
+    pub distributed_key_generation: ComponentHealth,
+    pub database: ComponentHealth,
+    pub liveness_listener_list: Vec<LivenessListenerHealth>,
+    pub cluster_list: Vec<ClusterHealth>,
+    pub decryption_backlog: usize,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct ComponentHealth {
+    pub is_healthy: bool,
+    pub error: Option<String>,
+}
+
+impl<T> From<Result<T, String>> for ComponentHealth {
+    fn from(result: Result<T, String>) -> Self {
+        Self {
+            is_healthy: result.is_ok(),
+            error: result.err(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct LivenessListenerHealth {
+    pub platform: Platform,
+    pub liveness_service_provider: LivenessServiceProvider,
+
+    pub is_listening: bool,
+    /// Unix timestamp in milliseconds of the last block event.
+    pub last_block_received_at: Option<u64>,
+    pub chain_head: Option<u64>,
+    pub error: Option<String>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct ClusterHealth {
+    pub platform: Platform,
+    pub liveness_service_provider: LivenessServiceProvider,
+    pub cluster_id: ClusterId,
+
+    pub is_leader: bool,
+    pub latest_synced_block_height: u64,
+    /// The number of blocks between the chain head and the latest synced
+    /// block, unknown while the chain head is.
+    pub sync_lag: Option<u64>,
+}
+
+/// Keeps the result of the latest readiness check.
+pub struct HealthMonitor {
+    inner: Arc<HealthMonitorInner>,
+}
+
+struct HealthMonitorInner {
+    readiness: Mutex<Option<Readiness>>,
+}
+
+impl Clone for HealthMonitor {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl Default for HealthMonitor {
+    fn default() -> Self {
+        let inner = HealthMonitorInner {
+            readiness: Mutex::new(None),
+        };
+
+        Self {
+            inner: Arc::new(inner),
+        }
+    }
+}
+
+impl HealthMonitor {
+    pub fn readiness(&self) -> Option<Readiness> {
+        self.inner.readiness.lock().unwrap().clone()
+    }
+
+    /// Returns `false` until the first check completes.
+    pub fn is_ready(&self) -> bool {
+        self.inner
+            .readiness
+            .lock()
+            .unwrap()
+            .as_ref()
+            .is_some_and(|readiness| readiness.is_ready)
+    }
+
+    /// Returns `false` until the first check completes.
+    pub fn is_distributed_key_generation_healthy(&self) -> bool {
+        self.inner
+            .readiness
+            .lock()
+            .unwrap()
+            .as_ref()
+            .is_some_and(|readiness| readiness.distributed_key_generation.is_healthy)
+    }
+
+    pub(crate) fn update(&self, readiness: Readiness) {
+        *self.inner.readiness.lock().unwrap() = Some(readiness);
+    }
+}
+
+pub fn run_health_monitor(context: AppState) {
+    tokio::spawn(async move {
+        loop {
+            let readiness = check_readiness(&context).await;
+            if !readiness.is_ready && context.health_monitor().is_ready() {
+                tracing::warn!("The node is not ready - readiness: {:?}", readiness);
+            }
+            context.health_monitor().update(readiness);
+
+            tokio::time::sleep(READINESS_CHECK_INTERVAL).await;
+        }
+    });
+}
+
+pub async fn check_readiness(context: &AppState) -> Readiness {
+    let distributed_key_generation: ComponentHealth = with_timeout(
+        context
+            .decryptor()
+            .distributed_key_generation_client()
+            .get_latest_key_id(),
+    )
+    .await
+    .into();
+
+    let sequencing_info_list = SequencingInfoList::get_or(SequencingInfoList::default)
+        .map_err(|error| format!("{:?}", error));
+    let database = ComponentHealth::from(sequencing_info_list.clone());
+
+    let mut liveness_listener_list = Vec::new();
+    let mut cluster_list = Vec::new();
+    for (platform, liveness_service_provider) in sequencing_info_list.unwrap_or_default().iter() {
+        let liveness_listener =
+            check_liveness_listener(context, *platform, *liveness_service_provider).await;
+
+        let cluster_id_list = ClusterIdList::get_or(
+            *platform,
+            *liveness_service_provider,
+            ClusterIdList::default,
+        )
+        .unwrap_or_default();
+        for cluster_id in cluster_id_list.iter() {
+            cluster_list.push(check_cluster(
+                *platform,
+                *liveness_service_provider,
+                cluster_id,
+                liveness_listener.chain_head,
+            ));
+        }
+
+        liveness_listener_list.push(liveness_listener);
+    }
+
+    let decryption_backlog = context.decryptor().backlog().await;
+    let is_shutting_down = context.shutdown_manager().is_shutting_down();
+
+    let is_ready = !is_shutting_down
+        && database.is_healthy
+        && liveness_listener_list
+            .iter()
+            .all(|liveness_listener| liveness_listener.is_listening)
+        && cluster_list.iter().all(|cluster| {
+            cluster
+                .sync_lag
+                .is_some_and(|sync_lag| sync_lag <= MAX_CLUSTER_SYNC_LAG)
+        })
+        && decryption_backlog <= MAX_DECRYPTION_BACKLOG;
+
+    Readiness {
+        is_ready,
+        checked_at: current_timestamp(),
+        is_shutting_down,
+        distributed_key_generation,
+        database,
+        liveness_listener_list,
+        cluster_list,
+        decryption_backlog,
+    }
+}
+
+async fn check_liveness_listener(
+    context: &AppState,
+    platform: Platform,
+    liveness_service_provider: LivenessServiceProvider,
+) -> LivenessListenerHealth {
+    let mut liveness_listener = LivenessListenerHealth {
+        platform,
+        liveness_service_provider,
+        is_listening: false,
+        last_block_received_at: None,
+        chain_head: None,
+        error: None,
+    };
+
+    let liveness_service_manager_client = match context
+        .get_liveness_service_manager_client::<LivenessServiceManagerClient>(
+            platform,
+            liveness_service_provider,
+        )
+        .await
+    {
+        Ok(liveness_service_manager_client) => liveness_service_manager_client,
+        Err(error) => {
+            liveness_listener.error = Some(format!("{:?}", error));
+            return liveness_listener;
+        }
+    };
+
+    liveness_listener.is_listening = liveness_service_manager_client.is_listening();
+    liveness_listener.last_block_received_at =
+        liveness_service_manager_client.last_block_received_at();
+
+    match with_timeout(
+        liveness_service_manager_client
+            .publisher()
+            .get_block_number(),
+    )
+    .await
+    {
+        Ok(chain_head) => liveness_listener.chain_head = Some(chain_head),
+        Err(error) => liveness_listener.error = Some(error),
+    }
+
+    liveness_listener
+}
+
+fn check_cluster(
+    platform: Platform,
+    liveness_service_provider: LivenessServiceProvider,
+    cluster_id: &ClusterId,
+    chain_head: Option<u64>,
+) -> ClusterHealth {
+    let is_leader = ClusterMetadata::get(platform, liveness_service_provider, cluster_id)
+        .is_ok_and(|cluster_metadata| cluster_metadata.is_leader);
+
+    let latest_synced_block_height =
+        LatestSyncedClusterBlockHeight::get(platform, liveness_service_provider, cluster_id)
+            .map(|block_height| block_height.get_block_height())
+            .unwrap_or_default();
+
+    ClusterHealth {
+        platform,
+        liveness_service_provider,
+        cluster_id: cluster_id.clone(),
+        is_leader,
+        latest_synced_block_height,
+        sync_lag: chain_head
+            .map(|chain_head| chain_head.saturating_sub(latest_synced_block_height)),
+    }
+}
+
+async fn with_timeout<T, E>(
+    future: impl std::future::Future<Output = Result<T, E>>,
+) -> Result<T, String>
+where
+    E: std::fmt::Debug,
+{
+    match timeout(CHECK_TIMEOUT, future).await {
+        Ok(Ok(value)) => Ok(value),
+        Ok(Err(error)) => Err(format!("{:?}", error)),
+        Err(_) => Err(format!("Timed out after {:?}", CHECK_TIMEOUT)),
+    }
+}
@@ -0,0 +1,35 @@
+use axum::{extract::State, http::StatusCode, response::IntoResponse, routing::get, Json, Router};
+
+use super::check_readiness;
+use crate::state::AppState;
+
+pub const LIVENESS_PATH: &str = "/health";
+pub const READINESS_PATH: &str = "/ready";
+
+/// Routes the liveness and readiness endpoints. Readiness answers `503 Service
+/// Unavailable` with the failed report, so that both can back the probes of an
+/// orchestrator.
+pub fn health_router() -> Router<AppState> {
+    Router::new()
+        .route(LIVENESS_PATH, get(liveness))
+        .route(READINESS_PATH, get(readiness))
+}
+
+async fn liveness() -> StatusCode {
+    StatusCode::OK
+}
+
+async fn readiness(State(context): State<AppState>) -> impl IntoResponse {
+    let readiness = match context.health_monitor().readiness() {
+        Some(readiness) => readiness,
+        None => check_readiness(&context).await,
+    };
+
+    let status_code = if readiness.is_ready {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+
+    (status_code, Json(readiness))
+}
@@ -0,0 +1,211 @@
+use std::{
+    fs,
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+use clap::{Parser, Subcommand};
+use serde::{Deserialize, Serialize};
+
+use super::*;
+use crate::types::{ConfigPath, SIGNING_KEY_PATH};
+
+#[derive(Debug, Deserialize, Parser, Serialize)]
+pub struct KeystoreOption {
+    #[clap(flatten)]
+    pub config_path: ConfigPath,
+
+    /// Defaults to `keystore.json` in the configuration directory
+    #[clap(long = "keystore-path")]
+    pub keystore_path: Option<PathBuf>,
+
+    /// Defaults to the `TX_ORDERER_KEYSTORE_PASSWORD` environment variable,
+    /// or a prompt
+    #[clap(long = "password-file")]
+    pub password_file: Option<PathBuf>,
+}
+
+impl KeystoreOption {
+    fn keystore_path(&self) -> PathBuf {
+        match &self.keystore_path {
+            Some(keystore_path) => keystore_path.clone(),
+            None => self.config_path.as_ref().join(KEYSTORE_FILE_NAME),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, Subcommand)]
+pub enum KeyCommand {
+    /// Generates a signing key into a new keystore
+    Generate {
+        #[clap(flatten)]
+        keystore_option: KeystoreOption,
+
+        /// Overwrites an existing keystore
+        #[clap(long = "force")]
+        force: bool,
+    },
+    /// Encrypts an existing signing key into a new keystore
+    Import {
+        #[clap(flatten)]
+        keystore_option: KeystoreOption,
+
+        /// Defaults to the plaintext `signing_key` file in the configuration
+        /// directory
+        #[clap(long = "private-key-file")]
+        private_key_file: Option<PathBuf>,
+
+        /// Deletes the plaintext key once it is encrypted
+        #[clap(long = "remove-plaintext")]
+        remove_plaintext: bool,
+
+        /// Overwrites an existing keystore
+        #[clap(long = "force")]
+        force: bool,
+    },
+    /// Prints the address of the key in a keystore
+    Inspect {
+        #[clap(flatten)]
+        keystore_option: KeystoreOption,
+    },
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct KeyReport {
+    pub keystore_path: PathBuf,
+    pub address: String,
+    /// The well-known development key the node is initialized with, which is
+    /// refused on mainnet platforms.
+    pub is_default_key: bool,
+}
+
+/// Runs the command and writes a [`KeyReport`] to `writer`. The private key
+/// itself is never written.
+pub fn run_key_command(command: KeyCommand, mut writer: impl Write) -> Result<(), KeystoreError> {
+    let (keystore_path, private_key) = match command {
+        KeyCommand::Generate {
+            keystore_option,
+            force,
+        } => {
+            let keystore_path = keystore_option.keystore_path();
+            check_overwrite(&keystore_path, force)?;
+
+            let password = read_password(keystore_option.password_file.as_deref(), true)?;
+            let private_key = create_keystore(&keystore_path, None, &password)?;
+
+            (keystore_path, private_key)
+        }
+        KeyCommand::Import {
+            keystore_option,
+            private_key_file,
+            remove_plaintext,
+            force,
+        } => {
+            let keystore_path = keystore_option.keystore_path();
+            check_overwrite(&keystore_path, force)?;
+
+            let private_key_file = private_key_file
+                .unwrap_or_else(|| keystore_option.config_path.as_ref().join(SIGNING_KEY_PATH));
+            let private_key = fs::read_to_string(&private_key_file)?;
+
+            let password = read_password(keystore_option.password_file.as_deref(), true)?;
+            let private_key = create_keystore(&keystore_path, Some(&private_key), &password)?;
+
+            if remove_plaintext {
+                fs::remove_file(&private_key_file)?;
+            }
+
+            (keystore_path, private_key)
+        }
+        KeyCommand::Inspect { keystore_option } => {
+            let keystore_path = keystore_option.keystore_path();
+
+            let password = read_password(keystore_option.password_file.as_deref(), false)?;
+            let private_key = decrypt_keystore(&keystore_path, &password)?;
+
+            (keystore_path, private_key)
+        }
+    };
+
+    let key_report = KeyReport {
+        keystore_path,
+        address: signer_address(&private_key)?,
+        is_default_key: is_default_signing_key(&private_key),
+    };
+    writeln!(
+        writer,
+        "{}",
+        serde_json::to_string_pretty(&key_report).unwrap_or_default()
+    )?;
+
+    Ok(())
+}
+
+fn check_overwrite(keystore_path: &Path, force: bool) -> Result<(), KeystoreError> {
+    if keystore_path.exists() && !force {
+        return Err(KeystoreError::AlreadyExists(keystore_path.to_owned()));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PRIVATE_KEY: &str = "0x59c6995e998f97a5a0044966f0945389dc9e86dae88c7a8412f4603b6b78690d";
+
+    fn keystore_option(config_dir: &Path) -> KeystoreOption {
+        KeystoreOption {
+            config_path: ConfigPath {
+                path: config_dir.to_string_lossy().into_owned(),
+            },
+            keystore_path: None,
+            password_file: Some(config_dir.join("password")),
+        }
+    }
+
+    fn run(command: KeyCommand) -> Result<KeyReport, KeystoreError> {
+        let mut output = Vec::new();
+        run_key_command(command, &mut output)?;
+
+        Ok(serde_json::from_slice(&output).unwrap())
+    }
+
+    #[test]
+    fn imported_plaintext_key_is_removed_and_inspected() {
+        let config_dir = std::env::temp_dir().join(format!("key-command-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&config_dir);
+        fs::create_dir_all(&config_dir).unwrap();
+        fs::write(config_dir.join("password"), "password\n").unwrap();
+        fs::write(config_dir.join(SIGNING_KEY_PATH), PRIVATE_KEY).unwrap();
+
+        let import = |force| KeyCommand::Import {
+            keystore_option: keystore_option(&config_dir),
+            private_key_file: None,
+            remove_plaintext: true,
+            force,
+        };
+        let import_report = run(import(false));
+        let is_plaintext_removed = !config_dir.join(SIGNING_KEY_PATH).exists();
+        let overwrite_result = run(import(false));
+        let inspect_report = run(KeyCommand::Inspect {
+            keystore_option: keystore_option(&config_dir),
+        });
+        fs::remove_dir_all(&config_dir).unwrap();
+
+        let import_report = import_report.unwrap();
+        assert_eq!(
+            import_report.keystore_path,
+            config_dir.join(KEYSTORE_FILE_NAME)
+        );
+        assert_eq!(import_report.address, signer_address(PRIVATE_KEY).unwrap());
+        assert!(!import_report.is_default_key);
+        assert!(is_plaintext_removed);
+        assert!(matches!(
+            overwrite_result,
+            Err(KeystoreError::AlreadyExists(_))
+        ));
+        assert_eq!(inspect_report.unwrap().address, import_report.address);
+    }
+}
@@ -0,0 +1,234 @@
+//! Encrypted JSON keystores (web3 secret storage) for the operator signing
+//! key.
+
+mod command;
+
+use std::{
+    env, fs,
+    io::{self, IsTerminal},
+    os::unix::fs::PermissionsExt,
+    path::{Path, PathBuf},
+};
+
+pub use command::*;
+use radius_sdk::signature::{ChainType, PrivateKeySigner};
+
+use crate::types::DEFAULT_SIGNING_KEY;
+
+pub const KEYSTORE_FILE_NAME: &str = "keystore.json";
+pub const KEYSTORE_PASSWORD_ENV: &str = "TX_ORDERER_KEYSTORE_PASSWORD";
+
+#[derive(Debug)]
+pub enum KeystoreError {
+    Io(io::Error),
+    Keystore(eth_keystore::KeystoreError),
+    InvalidPath(PathBuf),
+    InvalidPrivateKey,
+    AlreadyExists(PathBuf),
+    PasswordNotProvided,
+    PasswordMismatch,
+}
+
+impl std::fmt::Display for KeystoreError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(error) => write!(f, "{}", error),
+            Self::Keystore(error) => write!(f, "Failed to decrypt the keystore: {}", error),
+            Self::InvalidPath(path) => write!(f, "{:?} is not a valid keystore path", path),
+            Self::InvalidPrivateKey => write!(f, "The private key is not a valid secp256k1 key"),
+            Self::AlreadyExists(path) => {
+                write!(f, "{:?} already exists (use --force to overwrite it)", path)
+            }
+            Self::PasswordNotProvided => write!(
+                f,
+                "No keystore password: set {}, the password file or run in a terminal",
+                KEYSTORE_PASSWORD_ENV
+            ),
+            Self::PasswordMismatch => write!(f, "The passwords do not match"),
+        }
+    }
+}
+
+impl std::error::Error for KeystoreError {}
+
+impl From<io::Error> for KeystoreError {
+    fn from(value: io::Error) -> Self {
+        Self::Io(value)
+    }
+}
+
+impl From<eth_keystore::KeystoreError> for KeystoreError {
+    fn from(value: eth_keystore::KeystoreError) -> Self {
+        Self::Keystore(value)
+    }
+}
+
+/// Reads the keystore password from [`KEYSTORE_PASSWORD_ENV`], then from
+/// `password_file`, and prompts for it if neither is set and stdin is a
+/// terminal. A prompted password is asked twice if `confirm` is set.
+pub fn read_password(password_file: Option<&Path>, confirm: bool) -> Result<String, KeystoreError> {
+    if let Ok(password) = env::var(KEYSTORE_PASSWORD_ENV) {
+        return Ok(password);
+    }
+
+    if let Some(password_file) = password_file {
+        let password = fs::read_to_string(password_file)?;
+        return Ok(password.trim_end_matches(['\r', '\n']).to_owned());
+    }
+
+    if !io::stdin().is_terminal() {
+        return Err(KeystoreError::PasswordNotProvided);
+    }
+
+    let password = rpassword::prompt_password("Keystore password: ")?;
+    if confirm && password != rpassword::prompt_password("Repeat the password: ")? {
+        return Err(KeystoreError::PasswordMismatch);
+    }
+
+    Ok(password)
+}
+
+/// Encrypts `private_key` into a new keystore at `keystore_path`, or a newly
+/// generated key if none is given, and returns the key as a hex string. The
+/// file is only readable by its owner.
+pub fn create_keystore(
+    keystore_path: &Path,
+    private_key: Option<&str>,
+    password: &str,
+) -> Result<String, KeystoreError> {
+    let (directory, file_name) = match (keystore_path.parent(), keystore_path.file_name()) {
+        (Some(directory), Some(file_name)) => (directory, file_name.to_string_lossy()),
+        _ => return Err(KeystoreError::InvalidPath(keystore_path.to_owned())),
+    };
+    fs::create_dir_all(directory)?;
+
+    let mut rng = rand::thread_rng();
+    let private_key = match private_key {
+        Some(private_key) => {
+            let private_key = private_key.trim();
+            // Rejects the keys that cannot sign before they are stored.
+            signer_address(private_key)?;
+            let private_key_bytes =
+                const_hex::decode(private_key).map_err(|_| KeystoreError::InvalidPrivateKey)?;
+
+            eth_keystore::encrypt_key(
+                directory,
+                &mut rng,
+                &private_key_bytes,
+                password,
+                Some(&file_name),
+            )?;
+
+            private_key_bytes
+        }
+        None => eth_keystore::new(directory, &mut rng, password, Some(&file_name))?.0,
+    };
+
+    fs::set_permissions(keystore_path, fs::Permissions::from_mode(0o600))?;
+
+    Ok(const_hex::encode_prefixed(private_key))
+}
+
+/// Decrypts the keystore and returns the key as a hex string.
+pub fn decrypt_keystore(keystore_path: &Path, password: &str) -> Result<String, KeystoreError> {
+    let private_key = eth_keystore::decrypt_key(keystore_path, password)?;
+
+    Ok(const_hex::encode_prefixed(private_key))
+}
+
+pub fn signer_address(private_key: &str) -> Result<String, KeystoreError> {
+    let signer = PrivateKeySigner::from_str(ChainType::Ethereum, private_key.trim())
+        .map_err(|_| KeystoreError::InvalidPrivateKey)?;
+
+    Ok(signer.address().as_hex_string())
+}
+
+/// Whether the key is the well-known development key the node is initialized
+/// with.
+pub fn is_default_signing_key(private_key: &str) -> bool {
+    private_key
+        .trim()
+        .trim_start_matches("0x")
+        .eq_ignore_ascii_case(DEFAULT_SIGNING_KEY.trim_start_matches("0x"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PRIVATE_KEY: &str = "0x59c6995e998f97a5a0044966f0945389dc9e86dae88c7a8412f4603b6b78690d";
+
+    fn keystore_dir(name: &str) -> PathBuf {
+        let keystore_dir =
+            env::temp_dir().join(format!("keystore-{}-{}", std::process::id(), name));
+        let _ = fs::remove_dir_all(&keystore_dir);
+
+        keystore_dir
+    }
+
+    #[test]
+    fn imported_key_is_decrypted_only_with_its_password() {
+        let keystore_dir = keystore_dir("import");
+        let keystore_path = keystore_dir.join(KEYSTORE_FILE_NAME);
+
+        let private_key = create_keystore(
+            &keystore_path,
+            Some(&format!("{}\n", PRIVATE_KEY)),
+            "password",
+        )
+        .unwrap();
+        assert_eq!(private_key, PRIVATE_KEY);
+
+        let mode = fs::metadata(&keystore_path).unwrap().permissions().mode();
+        let decrypted_private_key = decrypt_keystore(&keystore_path, "password").unwrap();
+        let wrong_password_result = decrypt_keystore(&keystore_path, "wrong password");
+        fs::remove_dir_all(&keystore_dir).unwrap();
+
+        assert_eq!(mode & 0o777, 0o600);
+        assert_eq!(decrypted_private_key, PRIVATE_KEY);
+        assert!(matches!(
+            wrong_password_result,
+            Err(KeystoreError::Keystore(_))
+        ));
+    }
+
+    #[test]
+    fn generated_key_is_stored() {
+        let keystore_dir = keystore_dir("generate");
+        let keystore_path = keystore_dir.join(KEYSTORE_FILE_NAME);
+
+        let private_key = create_keystore(&keystore_path, None, "password").unwrap();
+        let decrypted_private_key = decrypt_keystore(&keystore_path, "password").unwrap();
+        fs::remove_dir_all(&keystore_dir).unwrap();
+
+        assert_eq!(decrypted_private_key, private_key);
+        assert!(signer_address(&private_key).is_ok());
+        assert!(!is_default_signing_key(&private_key));
+    }
+
+    #[test]
+    fn invalid_key_is_not_stored() {
+        let keystore_dir = keystore_dir("invalid");
+        let keystore_path = keystore_dir.join(KEYSTORE_FILE_NAME);
+
+        let result = create_keystore(&keystore_path, Some("0x1234"), "password");
+        let is_stored = keystore_path.exists();
+        let _ = fs::remove_dir_all(&keystore_dir);
+
+        assert!(matches!(result, Err(KeystoreError::InvalidPrivateKey)));
+        assert!(!is_stored);
+    }
+
+    #[test]
+    fn default_signing_key_is_recognized_in_any_form() {
+        let unprefixed_key = DEFAULT_SIGNING_KEY.trim_start_matches("0x");
+
+        assert!(is_default_signing_key(DEFAULT_SIGNING_KEY));
+        assert!(is_default_signing_key(unprefixed_key));
+        assert!(is_default_signing_key(&format!(
+            " 0x{}\n",
+            unprefixed_key.to_ascii_uppercase()
+        )));
+        assert!(!is_default_signing_key(PRIVATE_KEY));
+    }
+}
@@ -1,11 +1,21 @@
+pub mod access_control;
+pub mod admin;
 pub mod client;
+pub mod database;
 pub mod error;
+pub mod health;
+pub mod keystore;
 pub mod logger;
+pub mod migration;
 pub mod profiler;
 pub mod rpc;
+pub mod shutdown;
+pub mod signer;
 pub mod state;
 pub mod task;
+pub mod telemetry;
 pub mod types;
 pub extern crate skde;
 pub mod merkle_tree_manager;
+pub mod metrics;
 pub mod util;
@@ -5,8 +5,11 @@ use std::{
 };
 
 use chrono::{Days, Local, NaiveDate};
+use opentelemetry_sdk::trace::Tracer;
 use tracing::Level;
-use tracing_subscriber::fmt::writer::MakeWriter;
+use tracing_subscriber::{
+    filter::LevelFilter, fmt::writer::MakeWriter, layer::SubscriberExt, util::SubscriberInitExt,
+};
 
 #[derive(Debug)]
 pub struct Logger {
@@ -53,8 +56,13 @@ impl Logger {
         })
     }
 
-    pub fn init(self) {
-        tracing_subscriber::fmt().with_writer(self).init();
+    /// Installs the logger, with the spans recorded by the tracer.
+    pub fn init(self, tracer: Tracer) {
+        tracing_subscriber::registry()
+            .with(tracing_subscriber::fmt::layer().with_writer(self))
+            .with(tracing_opentelemetry::layer().with_tracer(tracer))
+            .with(LevelFilter::INFO)
+            .init();
         std::panic::set_hook(Box::new(|panic_info| {
             let panic_log: PanicLog = panic_info.into();
             tracing::error!("{:?}", panic_log);
@@ -112,6 +112,11 @@ impl MerkleTreeManager {
         lock.insert(rollup_id.to_owned(), merkle_tree);
     }
 
+    pub async fn remove(&self, rollup_id: &RollupId) {
+        let mut lock = self.inner.lock().await;
+        lock.remove(rollup_id);
+    }
+
     pub async fn get(&self, rollup_id: &RollupId) -> Result<MerkleTree, Error> {
         let lock = self.inner.lock().await;
         let merkle_tree = lock
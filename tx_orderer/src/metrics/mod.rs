@@ -0,0 +1,427 @@
+//! Prometheus metrics of the node, served at `/metrics` on `metrics_url`.
+//!
+//! Counters and histograms are recorded where the events happen. The gauges
+//! that mirror the state kept in the database (leader status, builder sink
+//! lag and dead letters) and in the censorship monitor are refreshed on every
+//! scrape.
+
+mod server;
+
+use std::{sync::Arc, time::Duration};
+
+use prometheus::{
+    Histogram, HistogramOpts, HistogramVec, IntCounterVec, IntGauge, IntGaugeVec, Opts, Registry,
+    TextEncoder,
+};
+pub use server::*;
+
+use crate::{state::AppState, types::*};
+
+#[derive(Debug)]
+pub enum MetricsError {
+    Register(prometheus::Error),
+    Encode(prometheus::Error),
+    Database(KvStoreError),
+    Serve(std::io::Error),
+}
+
+impl std::fmt::Display for MetricsError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+impl std::error::Error for MetricsError {}
+
+impl From<KvStoreError> for MetricsError {
+    fn from(value: KvStoreError) -> Self {
+        Self::Database(value)
+    }
+}
+
+pub struct Metrics {
+    inner: Arc<MetricsInner>,
+}
+
+struct MetricsInner {
+    registry: Registry,
+
+    ordered_transaction_count: IntCounterVec,
+    forward_to_leader_duration: HistogramVec,
+    batch_finalize_duration: HistogramVec,
+    batch_finalize_retry_count: IntCounterVec,
+    decryption_queue_depth: IntGauge,
+    decryption_lag: Histogram,
+    builder_delivery_lag: IntGaugeVec,
+    builder_dead_letter_count: IntGaugeVec,
+    sync_multicast_failure_count: IntCounterVec,
+    leader_status: IntGaugeVec,
+    l1_submission_count: IntCounterVec,
+    censorship_transaction_count: IntGaugeVec,
+}
+
+impl Clone for Metrics {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl Metrics {
+    pub fn new() -> Result<Self, MetricsError> {
+        let registry = Registry::new_custom(Some("tx_orderer".into()), None)
+            .map_err(MetricsError::Register)?;
+
+        let ordered_transaction_count = register(
+            &registry,
+            IntCounterVec::new(
+                Opts::new(
+                    "ordered_transactions_total",
+                    "Transactions ordered by this node as the leader",
+                ),
+                &["rollup_id", "transaction_type"],
+            ),
+        )?;
+        let forward_to_leader_duration = register(
+            &registry,
+            HistogramVec::new(
+                HistogramOpts::new(
+                    "forward_to_leader_duration_seconds",
+                    "Time to forward a transaction to the leader, retries included",
+                ),
+                &["method", "result"],
+            ),
+        )?;
+        let batch_finalize_duration = register(
+            &registry,
+            HistogramVec::new(
+                HistogramOpts::new(
+                    "batch_finalize_duration_seconds",
+                    "Time from the start of the batch finalization to the stored batch",
+                ),
+                &["rollup_id"],
+            ),
+        )?;
+        let batch_finalize_retry_count = register(
+            &registry,
+            IntCounterVec::new(
+                Opts::new(
+                    "batch_finalize_retries_total",
+                    "Attempts to build a batch that had to be retried",
+                ),
+                &["rollup_id"],
+            ),
+        )?;
+        let decryption_queue_depth = register(
+            &registry,
+            IntGauge::new(
+                "decryption_queue_depth",
+                "Encrypted transactions waiting for their decryption key or decryption",
+            ),
+        )?;
+        let decryption_lag = register(
+            &registry,
+            Histogram::with_opts(
+                HistogramOpts::new(
+                    "decryption_lag_seconds",
+                    "Time from queueing an encrypted transaction to its decryption",
+                )
+                .buckets(vec![0.1, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0, 60.0, 120.0]),
+            ),
+        )?;
+        let builder_delivery_lag = register(
+            &registry,
+            IntGaugeVec::new(
+                Opts::new(
+                    "builder_delivery_lag_batches",
+                    "Batches between the current batch and the cursor of a builder sink",
+                ),
+                &["rollup_id", "sink"],
+            ),
+        )?;
+        let builder_dead_letter_count = register(
+            &registry,
+            IntGaugeVec::new(
+                Opts::new(
+                    "builder_dead_letters",
+                    "Transactions a builder sink skipped after repeated delivery failures",
+                ),
+                &["rollup_id", "sink"],
+            ),
+        )?;
+        let sync_multicast_failure_count = register(
+            &registry,
+            IntCounterVec::new(
+                Opts::new(
+                    "sync_multicast_failures_total",
+                    "Cluster peers that did not accept a sync message",
+                ),
+                &["method"],
+            ),
+        )?;
+        let leader_status = register(
+            &registry,
+            IntGaugeVec::new(
+                Opts::new(
+                    "is_leader",
+                    "Whether this node is the leader of the cluster of the rollup",
+                ),
+                &["cluster_id", "rollup_id"],
+            ),
+        )?;
+        let l1_submission_count = register(
+            &registry,
+            IntCounterVec::new(
+                Opts::new(
+                    "l1_submissions_total",
+                    "Batch commitment submissions to the validation contract",
+                ),
+                &["rollup_id", "result"],
+            ),
+        )?;
+        let censorship_transaction_count = register(
+            &registry,
+            IntGaugeVec::new(
+                Opts::new(
+                    "censorship_transactions",
+                    "Transactions counted by the censorship monitor since the start",
+                ),
+                &["state"],
+            ),
+        )?;
+
+        Ok(Self {
+            inner: Arc::new(MetricsInner {
+                registry,
+                ordered_transaction_count,
+                forward_to_leader_duration,
+                batch_finalize_duration,
+                batch_finalize_retry_count,
+                decryption_queue_depth,
+                decryption_lag,
+                builder_delivery_lag,
+                builder_dead_letter_count,
+                sync_multicast_failure_count,
+                leader_status,
+                l1_submission_count,
+                censorship_transaction_count,
+            }),
+        })
+    }
+
+    /// `transaction_type` is either `raw` or `encrypted`.
+    pub fn record_ordered_transaction(&self, rollup_id: &RollupId, transaction_type: &str) {
+        self.inner
+            .ordered_transaction_count
+            .with_label_values(&[rollup_id.as_str(), transaction_type])
+            .inc();
+    }
+
+    pub fn record_forward_to_leader(&self, method: &str, is_success: bool, duration: Duration) {
+        self.inner
+            .forward_to_leader_duration
+            .with_label_values(&[method, result_label(is_success)])
+            .observe(duration.as_secs_f64());
+    }
+
+    pub fn record_batch_finalize(&self, rollup_id: &RollupId, duration: Duration) {
+        self.inner
+            .batch_finalize_duration
+            .with_label_values(&[rollup_id.as_str()])
+            .observe(duration.as_secs_f64());
+    }
+
+    pub fn record_batch_finalize_retry(&self, rollup_id: &RollupId) {
+        self.inner
+            .batch_finalize_retry_count
+            .with_label_values(&[rollup_id.as_str()])
+            .inc();
+    }
+
+    pub fn add_decryption_queue_depth(&self, count: i64) {
+        self.inner.decryption_queue_depth.add(count);
+    }
+
+    pub fn record_decryption(&self, lag: Duration) {
+        self.inner.decryption_queue_depth.dec();
+        self.inner.decryption_lag.observe(lag.as_secs_f64());
+    }
+
+    pub fn record_sync_multicast_failure(&self, method: &str) {
+        self.inner
+            .sync_multicast_failure_count
+            .with_label_values(&[method])
+            .inc();
+    }
+
+    pub fn record_l1_submission(&self, rollup_id: &RollupId, is_success: bool) {
+        self.inner
+            .l1_submission_count
+            .with_label_values(&[rollup_id.as_str(), result_label(is_success)])
+            .inc();
+    }
+
+    /// Refreshes the gauges that mirror the state of the node and encodes
+    /// every metric in the Prometheus text format.
+    pub fn encode(&self, context: &AppState) -> Result<String, MetricsError> {
+        self.refresh(context)?;
+        self.encode_registry()
+    }
+
+    fn encode_registry(&self) -> Result<String, MetricsError> {
+        TextEncoder::new()
+            .encode_to_string(&self.inner.registry.gather())
+            .map_err(MetricsError::Encode)
+    }
+
+    fn refresh(&self, context: &AppState) -> Result<(), MetricsError> {
+        let rollup_id_list = RollupIdList::get_or(RollupIdList::default)?;
+        let sink_name_list = context.builder_sink_manager().sink_name_list();
+
+        self.inner.leader_status.reset();
+        self.inner.builder_delivery_lag.reset();
+        for rollup_id in rollup_id_list.iter() {
+            let rollup = match Rollup::get(rollup_id) {
+                Ok(rollup) => rollup,
+                Err(error) if error.is_none_type() => continue,
+                Err(error) => return Err(error.into()),
+            };
+
+            if let Ok(cluster_metadata) = ClusterMetadata::get(
+                rollup.platform,
+                rollup.liveness_service_provider,
+                &rollup.cluster_id,
+            ) {
+                self.inner
+                    .leader_status
+                    .with_label_values(&[rollup.cluster_id.as_str(), rollup_id.as_str()])
+                    .set(cluster_metadata.is_leader as i64);
+            }
+
+            let Ok(rollup_metadata) = RollupMetadata::get(rollup_id) else {
+                continue;
+            };
+            for sink_name in sink_name_list.iter() {
+                let cursor_batch_number = BuilderSinkCursor::get(rollup_id, sink_name)
+                    .map(|cursor| cursor.batch_number)
+                    .ok();
+
+                self.inner
+                    .builder_delivery_lag
+                    .with_label_values(&[rollup_id.as_str(), *sink_name])
+                    .set(get_delivery_lag(
+                        rollup_metadata.batch_number,
+                        cursor_batch_number,
+                    ));
+
+                let dead_letter_count = BuilderSinkDeadLetterList::get(rollup_id, sink_name)
+                    .map(|dead_letter_list| dead_letter_list.len())
+                    .unwrap_or_default();
+                self.inner
+                    .builder_dead_letter_count
+                    .with_label_values(&[rollup_id.as_str(), *sink_name])
+                    .set(dead_letter_count as i64);
+            }
+        }
+
+        let censorship_metrics = context.censorship_monitor().metrics();
+        for (state, count) in [
+            ("forwarded", censorship_metrics.forwarded_transaction_count),
+            ("included", censorship_metrics.included_transaction_count),
+            ("violated", censorship_metrics.censorship_violation_count),
+            (
+                "reforwarded",
+                censorship_metrics.reforwarded_transaction_count,
+            ),
+            ("abandoned", censorship_metrics.abandoned_transaction_count),
+        ] {
+            self.inner
+                .censorship_transaction_count
+                .with_label_values(&[state])
+                .set(count as i64);
+        }
+
+        Ok(())
+    }
+}
+
+fn register<T>(registry: &Registry, metric: prometheus::Result<T>) -> Result<T, MetricsError>
+where
+    T: prometheus::core::Collector + Clone + 'static,
+{
+    let metric = metric.map_err(MetricsError::Register)?;
+    registry
+        .register(Box::new(metric.clone()))
+        .map_err(MetricsError::Register)?;
+
+    Ok(metric)
+}
+
+/// A sink that has not delivered anything yet starts from the current batch,
+/// so it is not behind.
+fn get_delivery_lag(current_batch_number: u64, cursor_batch_number: Option<u64>) -> i64 {
+    let cursor_batch_number = cursor_batch_number.unwrap_or(current_batch_number);
+
+    current_batch_number.saturating_sub(cursor_batch_number) as i64
+}
+
+fn result_label(is_success: bool) -> &'static str {
+    if is_success {
+        "success"
+    } else {
+        "failure"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn recorded_events_are_encoded_with_their_labels() {
+        let metrics = Metrics::new().unwrap();
+        let rollup_id = "rollup".to_owned();
+
+        metrics.record_ordered_transaction(&rollup_id, "raw");
+        metrics.record_ordered_transaction(&rollup_id, "raw");
+        metrics.record_ordered_transaction(&rollup_id, "encrypted");
+        metrics.record_l1_submission(&rollup_id, false);
+        metrics.record_sync_multicast_failure("sync_batch_creation");
+
+        let encoded = metrics.encode_registry().unwrap();
+        assert!(encoded.contains(
+            "tx_orderer_ordered_transactions_total{rollup_id=\"rollup\",transaction_type=\"raw\"} 2"
+        ));
+        assert!(encoded.contains(
+            "tx_orderer_ordered_transactions_total{rollup_id=\"rollup\",transaction_type=\"encrypted\"} 1"
+        ));
+        assert!(encoded.contains(
+            "tx_orderer_l1_submissions_total{result=\"failure\",rollup_id=\"rollup\"} 1"
+        ));
+        assert!(encoded.contains(
+            "tx_orderer_sync_multicast_failures_total{method=\"sync_batch_creation\"} 1"
+        ));
+    }
+
+    #[test]
+    fn decrypted_transactions_leave_the_queue() {
+        let metrics = Metrics::new().unwrap();
+
+        metrics.add_decryption_queue_depth(3);
+        metrics.record_decryption(Duration::from_millis(700));
+        metrics.record_decryption(Duration::from_secs(4));
+
+        let encoded = metrics.encode_registry().unwrap();
+        assert!(encoded.contains("tx_orderer_decryption_queue_depth 1"));
+        assert!(encoded.contains("tx_orderer_decryption_lag_seconds_bucket{le=\"1\"} 1"));
+        assert!(encoded.contains("tx_orderer_decryption_lag_seconds_count 2"));
+    }
+
+    #[test]
+    fn delivery_lag_is_counted_from_the_cursor() {
+        assert_eq!(get_delivery_lag(10, Some(7)), 3);
+        assert_eq!(get_delivery_lag(10, None), 0);
+        assert_eq!(get_delivery_lag(10, Some(12)), 0);
+    }
+}
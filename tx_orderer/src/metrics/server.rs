@@ -0,0 +1,62 @@
+use axum::{
+    extract::State,
+    http::{header, StatusCode},
+    response::IntoResponse,
+    routing::get,
+    Router,
+};
+use tokio::net::TcpListener;
+
+use super::MetricsError;
+use crate::{health::health_router, state::AppState};
+
+pub const METRICS_PATH: &str = "/metrics";
+
+/// Serves the metrics, along with the liveness and readiness of the node, on
+/// `metrics_url` if it is set.
+pub fn run_metrics_server(context: AppState) {
+    let Some(metrics_url) = context.config().metrics_url.clone() else {
+        return;
+    };
+
+    tokio::spawn(async move {
+        if let Err(error) = serve(context, &metrics_url).await {
+            tracing::error!(
+                "Metrics server terminated - metrics_url: {:?} / error: {:?}",
+                metrics_url,
+                error
+            );
+        }
+    });
+}
+
+async fn serve(context: AppState, metrics_url: &str) -> Result<(), MetricsError> {
+    let listener = TcpListener::bind(metrics_url)
+        .await
+        .map_err(MetricsError::Serve)?;
+    tracing::info!("Serving metrics on {}{}", metrics_url, METRICS_PATH);
+
+    let router = Router::new()
+        .route(METRICS_PATH, get(metrics))
+        .merge(health_router())
+        .with_state(context);
+
+    axum::serve(listener, router)
+        .await
+        .map_err(MetricsError::Serve)
+}
+
+async fn metrics(State(context): State<AppState>) -> impl IntoResponse {
+    match context.metrics().encode(&context) {
+        Ok(encoded) => (
+            StatusCode::OK,
+            [(header::CONTENT_TYPE, prometheus::TEXT_FORMAT.to_owned())],
+            encoded,
+        ),
+        Err(error) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            [(header::CONTENT_TYPE, "text/plain".to_owned())],
+            error.to_string(),
+        ),
+    }
+}
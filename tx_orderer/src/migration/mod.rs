@@ -0,0 +1,215 @@
+mod v0_0_3;
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use radius_sdk::kvstore::{kvstore, KvStoreBuilder};
+use serde::{Deserialize, Serialize};
+
+use crate::{error::Error, types::*};
+
+pub const BACKUP_DIR_NAME: &str = "backup";
+
+/// Rewrites the models whose layout changed in `to_version`. A step must be
+/// idempotent, as it runs again if the migration is interrupted before the
+/// database version is updated.
+pub struct MigrationStep {
+    pub from_version: &'static str,
+    pub to_version: &'static str,
+    pub description: &'static str,
+    pub migrate: fn(&mut MigrationContext) -> Result<(), Error>,
+}
+
+/// Every migration step in the order it has to run.
+pub const MIGRATION_STEP_LIST: &[MigrationStep] = &[v0_0_3::MIGRATION_STEP];
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct MigrationReport {
+    pub from_version: String,
+    pub to_version: String,
+    pub dry_run: bool,
+    pub backup_path: Option<PathBuf>,
+    pub step_report_list: Vec<MigrationStepReport>,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct MigrationStepReport {
+    pub from_version: String,
+    pub to_version: String,
+    pub description: String,
+    pub rewritten_count: u64,
+    pub removed_count: u64,
+    pub skipped_count: u64,
+}
+
+/// Writes done by a migration step. Nothing is written in dry-run mode, but
+/// the writes are still counted.
+pub struct MigrationContext {
+    dry_run: bool,
+    report: MigrationStepReport,
+}
+
+impl MigrationContext {
+    pub fn put<K, V>(&mut self, key: &K, value: &V) -> Result<(), Error>
+    where
+        K: Serialize,
+        V: Serialize,
+    {
+        if !self.dry_run {
+            kvstore()?.put(key, value)?;
+        }
+        self.report.rewritten_count += 1;
+
+        Ok(())
+    }
+
+    pub fn delete<K>(&mut self, key: &K) -> Result<(), Error>
+    where
+        K: Serialize,
+    {
+        if !self.dry_run {
+            kvstore()?.delete(key)?;
+        }
+        self.report.removed_count += 1;
+
+        Ok(())
+    }
+
+    /// Counts a model that is already in the target layout.
+    pub fn skip(&mut self) {
+        self.report.skipped_count += 1;
+    }
+}
+
+/// Returns the steps that bring `database_version` up to
+/// [`REQURIED_DATABASE_VERSION`].
+pub fn get_migration_plan(database_version: &str) -> Result<Vec<&'static MigrationStep>, Error> {
+    let mut migration_plan = Vec::new();
+    let mut version = database_version;
+
+    while version != REQURIED_DATABASE_VERSION {
+        let migration_step = MIGRATION_STEP_LIST
+            .iter()
+            .find(|migration_step| migration_step.from_version == version)
+            .ok_or(Error::DatabaseVersionMismatch)?;
+
+        migration_plan.push(migration_step);
+        version = migration_step.to_version;
+    }
+
+    Ok(migration_plan)
+}
+
+/// Migrates the database under `config_path` to
+/// [`REQURIED_DATABASE_VERSION`]. The database directory is copied to
+/// `backup/` before it is opened, unless `dry_run` is set. The database
+/// version is updated after every step, so an interrupted migration resumes
+/// from the step that failed.
+pub fn migrate(config_path: &Path, dry_run: bool) -> Result<MigrationReport, Error> {
+    let database_path = config_path.join(DATABASE_DIR_NAME);
+    if !database_path.exists() {
+        return Err(Error::GeneralError(format!(
+            "Database does not exist at {:?}",
+            database_path
+        )));
+    }
+
+    let backup_path = if dry_run {
+        None
+    } else {
+        Some(backup_database(config_path, &database_path)?)
+    };
+
+    let kv_store = KvStoreBuilder::default()
+        .set_default_lock_timeout(10000)
+        .set_txn_lock_timeout(10000)
+        .build(database_path.clone())
+        .map_err(Error::Database)?;
+    kv_store.init();
+
+    let mut version = Version::get_or(Version::default).map_err(Error::Database)?;
+    let migration_plan = get_migration_plan(&version.database_version)?;
+
+    let mut report = MigrationReport {
+        from_version: version.database_version.clone(),
+        to_version: REQURIED_DATABASE_VERSION.to_string(),
+        dry_run,
+        backup_path,
+        step_report_list: Vec::new(),
+    };
+
+    if migration_plan.is_empty() {
+        tracing::info!(
+            "Database is up to date - database_version: {:?}",
+            version.database_version
+        );
+
+        if let Some(backup_path) = report.backup_path.take() {
+            fs::remove_dir_all(backup_path)?;
+        }
+
+        return Ok(report);
+    }
+
+    for migration_step in migration_plan {
+        tracing::info!(
+            "Migrating database - from_version: {:?} / to_version: {:?} / description: {:?} / dry_run: {:?}",
+            migration_step.from_version,
+            migration_step.to_version,
+            migration_step.description,
+            dry_run
+        );
+
+        let mut context = MigrationContext {
+            dry_run,
+            report: MigrationStepReport {
+                from_version: migration_step.from_version.to_string(),
+                to_version: migration_step.to_version.to_string(),
+                description: migration_step.description.to_string(),
+                ..Default::default()
+            },
+        };
+        (migration_step.migrate)(&mut context)?;
+
+        if !dry_run {
+            version.database_version = migration_step.to_version.to_string();
+            version.put().map_err(Error::Database)?;
+        }
+
+        report.step_report_list.push(context.report);
+    }
+
+    Ok(report)
+}
+
+fn backup_database(config_path: &Path, database_path: &Path) -> Result<PathBuf, Error> {
+    let backup_path = config_path.join(BACKUP_DIR_NAME).join(format!(
+        "{}-{}",
+        DATABASE_DIR_NAME,
+        current_timestamp()
+    ));
+
+    copy_dir(database_path, &backup_path)?;
+    tracing::info!("Database backed up to {:?}", backup_path);
+
+    Ok(backup_path)
+}
+
+fn copy_dir(source: &Path, destination: &Path) -> Result<(), Error> {
+    fs::create_dir_all(destination)?;
+
+    for entry in fs::read_dir(source)? {
+        let entry = entry?;
+        let destination = destination.join(entry.file_name());
+
+        if entry.file_type()?.is_dir() {
+            copy_dir(&entry.path(), &destination)?;
+        } else {
+            fs::copy(entry.path(), destination)?;
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,369 @@
+//! v0.0.2 -> v0.0.3: the open data of encrypted Eth transactions is rebuilt
+//! from the typed envelope of the decrypted transaction. The open data of
+//! encrypted transactions that were never decrypted is converted from the
+//! legacy fields, and the migration fails if one of them cannot be converted.
+
+use radius_sdk::{
+    kvstore::kvstore,
+    signature::{Address, Signature},
+};
+use serde::{Deserialize, Serialize};
+
+use super::{MigrationContext, MigrationStep};
+use crate::{error::Error, types::*};
+
+pub const MIGRATION_STEP: MigrationStep = MigrationStep {
+    from_version: "v0.0.2",
+    to_version: "v0.0.3",
+    description: "Rebuild the open data of encrypted Eth transactions from typed envelopes",
+    migrate,
+};
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(tag = "type", content = "data")]
+#[serde(rename_all = "snake_case")]
+enum LegacyEncryptedTransaction {
+    Skde(LegacySkdeEncryptedTransaction),
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+struct LegacySkdeEncryptedTransaction {
+    transaction_data: LegacyTransactionData,
+    key_id: u64,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(tag = "type", content = "data")]
+#[serde(rename_all = "snake_case")]
+enum LegacyTransactionData {
+    Eth(LegacyEthTransactionData),
+    EthBundle(EthBundleTransactionData),
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+struct LegacyEthTransactionData {
+    encrypted_data: EncryptedData,
+    open_data: LegacyEthOpenData,
+    plain_data: Option<EthPlainData>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+struct LegacyBatch {
+    batch_number: u64,
+    encrypted_transaction_list: Vec<Option<LegacyEncryptedTransaction>>,
+    raw_transaction_list: Vec<RawTransaction>,
+    batch_commitment: BatchCommitment,
+    batch_creator_address: Address,
+    signature: Signature,
+}
+
+impl LegacyEncryptedTransaction {
+    /// Rebuilds the encrypted transaction with the open data of the decrypted
+    /// transaction at the same position, or converts the legacy open data if
+    /// the transaction was not decrypted.
+    fn migrate(
+        self,
+        raw_transaction: Option<&RawTransaction>,
+    ) -> Result<EncryptedTransaction, Error> {
+        let LegacyEncryptedTransaction::Skde(skde_encrypted_transaction) = self;
+
+        let transaction_data = match skde_encrypted_transaction.transaction_data {
+            LegacyTransactionData::Eth(eth_transaction_data) => {
+                let open_data = match raw_transaction.and_then(|raw_transaction| {
+                    rebuild_open_data(raw_transaction, &eth_transaction_data.open_data.raw_tx_hash)
+                }) {
+                    Some(open_data) => open_data,
+                    None => {
+                        let raw_tx_hash = eth_transaction_data.open_data.raw_tx_hash.clone();
+                        EthOpenData::try_from(eth_transaction_data.open_data).map_err(|error| {
+                            tracing::error!(
+                                "Failed to migrate open data - raw_tx_hash: {:?}",
+                                raw_tx_hash
+                            );
+                            error
+                        })?
+                    }
+                };
+
+                let mut transaction_data =
+                    EthTransactionData::new(eth_transaction_data.encrypted_data, open_data);
+                transaction_data.plain_data = eth_transaction_data.plain_data;
+
+                TransactionData::Eth(transaction_data)
+            }
+            LegacyTransactionData::EthBundle(eth_bundle_transaction_data) => {
+                TransactionData::EthBundle(eth_bundle_transaction_data)
+            }
+        };
+
+        Ok(EncryptedTransaction::Skde(SkdeEncryptedTransaction::new(
+            transaction_data,
+            skde_encrypted_transaction.key_id,
+        )))
+    }
+}
+
+/// Returns the open data of the decrypted transaction if it is the one the
+/// legacy open data was built from.
+fn rebuild_open_data(
+    raw_transaction: &RawTransaction,
+    legacy_raw_tx_hash: &RawTransactionHash,
+) -> Option<EthOpenData> {
+    let RawTransaction::Eth(eth_raw_transaction) = raw_transaction else {
+        return None;
+    };
+
+    let (open_data, _) = EthOpenData::split_raw_transaction(&eth_raw_transaction.0).ok()?;
+    let legacy_raw_tx_hash: &str = legacy_raw_tx_hash.as_ref();
+    let raw_tx_hash: &str = open_data.raw_tx_hash.as_ref();
+
+    legacy_raw_tx_hash
+        .eq_ignore_ascii_case(raw_tx_hash)
+        .then_some(open_data)
+}
+
+fn migrate(context: &mut MigrationContext) -> Result<(), Error> {
+    let rollup_id_list = RollupIdList::get_or(RollupIdList::default)?;
+
+    for rollup_id in rollup_id_list.iter() {
+        let rollup_metadata = match RollupMetadata::get(rollup_id) {
+            Ok(rollup_metadata) => rollup_metadata,
+            Err(error) if error.is_none_type() => continue,
+            Err(error) => return Err(error.into()),
+        };
+
+        for batch_number in 0..=rollup_metadata.batch_number {
+            migrate_batch(context, rollup_id, batch_number)?;
+            migrate_encrypted_transactions(context, rollup_id, batch_number)?;
+        }
+    }
+
+    Ok(())
+}
+
+fn migrate_batch(
+    context: &mut MigrationContext,
+    rollup_id: &RollupId,
+    batch_number: u64,
+) -> Result<(), Error> {
+    let key = &("Batch", rollup_id, batch_number);
+
+    // Batches written by an interrupted run are already migrated.
+    if kvstore()?.get::<_, Batch>(key).is_ok() {
+        context.skip();
+        return Ok(());
+    }
+
+    let legacy_batch: LegacyBatch = match kvstore()?.get(key) {
+        Ok(legacy_batch) => legacy_batch,
+        Err(error) if error.is_none_type() => return Ok(()),
+        Err(error) => return Err(error.into()),
+    };
+
+    let encrypted_transaction_list = legacy_batch
+        .encrypted_transaction_list
+        .into_iter()
+        .enumerate()
+        .map(|(transaction_order, encrypted_transaction)| {
+            encrypted_transaction
+                .map(|encrypted_transaction| {
+                    encrypted_transaction
+                        .migrate(legacy_batch.raw_transaction_list.get(transaction_order))
+                })
+                .transpose()
+        })
+        .collect::<Result<Vec<_>, Error>>()?;
+
+    let batch = Batch::new(
+        legacy_batch.batch_number,
+        encrypted_transaction_list,
+        legacy_batch.raw_transaction_list,
+        legacy_batch.batch_commitment,
+        legacy_batch.batch_creator_address,
+        legacy_batch.signature,
+    );
+
+    context.put(key, &batch)
+}
+
+fn migrate_encrypted_transactions(
+    context: &mut MigrationContext,
+    rollup_id: &RollupId,
+    batch_number: u64,
+) -> Result<(), Error> {
+    let mut transaction_order = 0;
+
+    loop {
+        let key = &(
+            EncryptedTransactionModel::ID,
+            rollup_id,
+            batch_number,
+            transaction_order,
+        );
+        transaction_order += 1;
+
+        if kvstore()?.get::<_, EncryptedTransaction>(key).is_ok() {
+            context.skip();
+            continue;
+        }
+
+        let legacy_encrypted_transaction: LegacyEncryptedTransaction = match kvstore()?.get(key) {
+            Ok(legacy_encrypted_transaction) => legacy_encrypted_transaction,
+            // Raw transactions are ordered in the same sequence, so the
+            // encrypted transactions of the batch may continue after a gap.
+            Err(error) if error.is_none_type() => {
+                match RawTransactionModel::get(rollup_id, batch_number, transaction_order - 1) {
+                    Ok(_) => continue,
+                    Err(error) if error.is_none_type() => return Ok(()),
+                    Err(error) => return Err(error.into()),
+                }
+            }
+            Err(error) => return Err(error.into()),
+        };
+
+        let LegacyEncryptedTransaction::Skde(skde_encrypted_transaction) =
+            &legacy_encrypted_transaction;
+        let raw_tx_hash = match &skde_encrypted_transaction.transaction_data {
+            LegacyTransactionData::Eth(eth_transaction_data) => {
+                eth_transaction_data.open_data.raw_tx_hash.clone()
+            }
+            LegacyTransactionData::EthBundle(eth_bundle_transaction_data) => {
+                eth_bundle_transaction_data.open_data.raw_tx_hash.clone()
+            }
+        };
+        let hash_key = &(EncryptedTransactionModel::ID, rollup_id, &raw_tx_hash);
+
+        let raw_transaction =
+            match RawTransactionModel::get(rollup_id, batch_number, transaction_order - 1) {
+                Ok((raw_transaction, _)) => Some(raw_transaction),
+                Err(error) if error.is_none_type() => None,
+                Err(error) => return Err(error.into()),
+            };
+
+        let encrypted_transaction =
+            legacy_encrypted_transaction.migrate(raw_transaction.as_ref())?;
+        context.put(key, &encrypted_transaction)?;
+        context.put(hash_key, &encrypted_transaction)?;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ethers_core::{
+        k256::ecdsa::SigningKey,
+        types::{
+            self as eth_types, transaction::eip2718::TypedTransaction, Eip1559TransactionRequest,
+            H160, U256,
+        },
+    };
+
+    use super::*;
+
+    /// Returns a signed EIP-1559 transaction with the given nonce.
+    fn signed_raw_transaction(nonce: u64) -> RawTransaction {
+        let transaction = TypedTransaction::Eip1559(
+            Eip1559TransactionRequest::new()
+                .to(H160::repeat_byte(7))
+                .nonce(nonce)
+                .gas(21_000)
+                .max_fee_per_gas(2)
+                .max_priority_fee_per_gas(1)
+                .chain_id(901),
+        );
+
+        let signing_key = SigningKey::from_slice(&[0x42; 32]).unwrap();
+        let (signature, recovery_id) = signing_key
+            .sign_prehash_recoverable(transaction.sighash().as_bytes())
+            .unwrap();
+        let signature = eth_types::Signature {
+            r: U256::from_big_endian(&signature.r().to_bytes()),
+            s: U256::from_big_endian(&signature.s().to_bytes()),
+            v: recovery_id.to_byte() as u64,
+        };
+
+        RawTransaction::from(EthRawTransaction::from(const_hex::encode_prefixed(
+            transaction.rlp_signed(&signature),
+        )))
+    }
+
+    fn legacy_encrypted_transaction(
+        raw_tx_hash: RawTransactionHash,
+        transaction_type: u64,
+    ) -> LegacyEncryptedTransaction {
+        let open_data = LegacyEthOpenData {
+            raw_tx_hash,
+            from: H160::repeat_byte(1),
+            nonce: U256::one(),
+            gas_price: None,
+            gas_limit: U256::from(21_000),
+            signature: eth_types::Signature {
+                r: U256::one(),
+                s: U256::one(),
+                v: 0,
+            },
+            block_hash: None,
+            block_number: None,
+            transaction_index: None,
+            transaction_type: Some(transaction_type.into()),
+            access_list: None,
+            max_priority_fee_per_gas: Some(U256::one()),
+            max_fee_per_gas: Some(U256::from(2)),
+            chain_id: Some(U256::from(901)),
+            other: eth_types::OtherFields::default(),
+        };
+
+        LegacyEncryptedTransaction::Skde(LegacySkdeEncryptedTransaction {
+            transaction_data: LegacyTransactionData::Eth(LegacyEthTransactionData {
+                encrypted_data: EncryptedData::from("0x00".to_owned()),
+                open_data,
+                plain_data: None,
+            }),
+            key_id: 3,
+        })
+    }
+
+    fn get_open_data(encrypted_transaction: &EncryptedTransaction) -> &EthOpenData {
+        match encrypted_transaction.transaction_data() {
+            TransactionData::Eth(eth_transaction_data) => &eth_transaction_data.open_data,
+            TransactionData::EthBundle(_) => panic!("expected an Eth transaction"),
+        }
+    }
+
+    #[test]
+    fn decrypted_transaction_rebuilds_the_open_data() {
+        let raw_transaction = signed_raw_transaction(0);
+        let RawTransaction::Eth(eth_raw_transaction) = &raw_transaction else {
+            unreachable!();
+        };
+        let (open_data, _) = EthOpenData::split_raw_transaction(&eth_raw_transaction.0).unwrap();
+
+        // The legacy open data of an unsupported type is not needed.
+        let encrypted_transaction =
+            legacy_encrypted_transaction(raw_transaction.raw_transaction_hash(), 0x7f)
+                .migrate(Some(&raw_transaction))
+                .unwrap();
+        assert_eq!(get_open_data(&encrypted_transaction), &open_data);
+    }
+
+    #[test]
+    fn other_decrypted_transaction_falls_back_to_the_legacy_open_data() {
+        let legacy_raw_tx_hash = signed_raw_transaction(0).raw_transaction_hash();
+
+        let encrypted_transaction = legacy_encrypted_transaction(legacy_raw_tx_hash.clone(), 2)
+            .migrate(Some(&signed_raw_transaction(1)))
+            .unwrap();
+
+        let open_data = get_open_data(&encrypted_transaction);
+        assert_eq!(open_data.raw_tx_hash, legacy_raw_tx_hash);
+        assert_eq!(open_data.transaction_type, EthTransactionType::DynamicFee);
+        assert_eq!(open_data.from, H160::repeat_byte(1));
+    }
+
+    #[test]
+    fn undecrypted_transaction_of_an_unsupported_type_fails_the_migration() {
+        let result =
+            legacy_encrypted_transaction(RawTransactionHash::default(), 0x7f).migrate(None);
+
+        assert!(matches!(result, Err(Error::UnsupportedTransactionType)));
+    }
+}
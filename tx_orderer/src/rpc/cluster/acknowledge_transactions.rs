@@ -0,0 +1,115 @@
+use radius_sdk::signature::Address;
+
+use super::{verify_executor_message, ExecutorMessage, TransactionCursor};
+use crate::rpc::prelude::*;
+
+/// Acknowledges that the executor has received every transaction before
+/// `(batch_number, transaction_order)`. The position cannot go past what
+/// [`super::GetTransactionsSince`] has served.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct TransactionAcknowledgement {
+    pub rollup_id: RollupId,
+    pub executor_address: Address,
+
+    pub batch_number: u64,
+    pub transaction_order: u64,
+
+    /// Unix timestamp in milliseconds.
+    pub timestamp: u64,
+    pub nonce: u64,
+}
+
+impl ExecutorMessage for TransactionAcknowledgement {
+    fn rollup_id(&self) -> &RollupId {
+        &self.rollup_id
+    }
+
+    fn timestamp(&self) -> u64 {
+        self.timestamp
+    }
+
+    fn nonce(&self) -> u64 {
+        self.nonce
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct AcknowledgeTransactions {
+    pub acknowledgement: TransactionAcknowledgement,
+    pub executor_signature: Signature,
+}
+
+impl RpcParameter<AppState> for AcknowledgeTransactions {
+    type Response = TransactionCursor;
+
+    fn method() -> &'static str {
+        "acknowledge_transactions"
+    }
+
+    async fn handler(self, context: AppState) -> Result<Self::Response, RpcError> {
+        let rollup_id = &self.acknowledgement.rollup_id;
+
+        let rollup = Rollup::get(rollup_id).map_err(|error| {
+            tracing::error!(
+                "Failed to get rollup - rollup_id: {:?} / error: {:?}",
+                rollup_id,
+                error
+            );
+            Error::RollupNotFound
+        })?;
+
+        let signer_address = verify_executor_message(
+            rollup.platform,
+            &rollup.executor_address_list,
+            &self.acknowledgement,
+            &self.executor_signature,
+            context.cluster_message_replay_cache(),
+        )?;
+        if signer_address != self.acknowledgement.executor_address {
+            return Err(Error::InvalidSignature.into());
+        }
+
+        let mut rollup_metadata = RollupMetadata::get_mut(rollup_id)?;
+
+        let current_cursor = TransactionCursor::acknowledged(&rollup_metadata);
+        let acknowledged_cursor = TransactionCursor::new(
+            self.acknowledgement.batch_number,
+            self.acknowledgement.transaction_order,
+        );
+
+        let served_transaction_cursor =
+            ServedTransactionCursor::get_or(rollup_id, ServedTransactionCursor::default)?;
+        let served_cursor = TransactionCursor::new(
+            served_transaction_cursor.batch_number,
+            served_transaction_cursor.transaction_order,
+        );
+        if acknowledged_cursor > served_cursor {
+            tracing::error!(
+                "Acknowledged transactions that were not served - rollup_id: {:?} / acknowledged_cursor: {:?} / served_cursor: {:?}",
+                rollup_id,
+                acknowledged_cursor,
+                served_cursor
+            );
+            return Err(Error::UnservedTransactionAcknowledged.into());
+        }
+
+        // Stale or repeated acknowledgements leave the cursor untouched.
+        if acknowledged_cursor <= current_cursor {
+            return Ok(current_cursor);
+        }
+
+        rollup_metadata.provided_batch_number = acknowledged_cursor.batch_number;
+        rollup_metadata.provided_transaction_order =
+            acknowledged_cursor.transaction_order as i64 - 1;
+        rollup_metadata.update()?;
+
+        tracing::info!(
+            "Transactions acknowledged - rollup_id: {:?} / batch_number: {:?} / transaction_order: {:?}",
+            rollup_id,
+            acknowledged_cursor.batch_number,
+            acknowledged_cursor.transaction_order
+        );
+
+        Ok(acknowledged_cursor)
+    }
+}
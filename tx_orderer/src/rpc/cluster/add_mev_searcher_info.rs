@@ -1,5 +1,9 @@
-use crate::rpc::prelude::*;
+use super::{verify_executor_message, ExecutorMessage};
+use crate::{rpc::prelude::*, util::multicast};
 
+/// Signed by an executor of the rollup. The tx_orderers relay the message
+/// unchanged, so every node checks the executor signature and the freshness of
+/// the message itself.
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct AddMevSearcherInfo {
     pub add_mev_searcher_info_message: AddMevSearcherInfoMessage,
@@ -10,6 +14,24 @@ pub struct AddMevSearcherInfo {
 pub struct AddMevSearcherInfoMessage {
     pub mev_searcher_ip: IP,
     pub rollup_id: RollupId,
+
+    /// Unix timestamp in milliseconds.
+    pub timestamp: u64,
+    pub nonce: u64,
+}
+
+impl ExecutorMessage for AddMevSearcherInfoMessage {
+    fn rollup_id(&self) -> &RollupId {
+        &self.rollup_id
+    }
+
+    fn timestamp(&self) -> u64 {
+        self.timestamp
+    }
+
+    fn nonce(&self) -> u64 {
+        self.nonce
+    }
 }
 
 impl RpcParameter<AppState> for AddMevSearcherInfo {
@@ -24,6 +46,14 @@ impl RpcParameter<AppState> for AddMevSearcherInfo {
 
         let rollup = Rollup::get(&msg.rollup_id)?;
 
+        verify_executor_message(
+            rollup.platform,
+            &rollup.executor_address_list,
+            msg,
+            &self.signature,
+            context.cluster_message_replay_cache(),
+        )?;
+
         let mut mut_mev_searcher_infos = MevSearcherInfos::get_mut_or(MevSearcherInfos::default)?;
 
         if mut_mev_searcher_infos.contains_rollup_id(&msg.mev_searcher_ip, &msg.rollup_id) {
@@ -62,10 +92,12 @@ impl AddMevSearcherInfo {
         let other_urls = cluster.get_other_cluster_rpc_url_list();
 
         if !other_urls.is_empty() {
-            context
-                .rpc_client()
-                .fire_and_forget_multicast(other_urls, AddMevSearcherInfo::method(), self, Id::Null)
-                .await;
+            multicast(
+                &context,
+                other_urls,
+                AddMevSearcherInfo::method(),
+                self.clone(),
+            );
         }
 
         Ok(())
@@ -0,0 +1,202 @@
+use super::{Authenticated, ClusterMessage};
+use crate::rpc::prelude::*;
+
+/// A follower signature over the order commitment data issued by the leader,
+/// collected by the leader into a [`QuorumOrderCommitment`].
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct AddOrderCommitmentSignature {
+    pub order_commitment_data: OrderCommitmentData,
+    pub signature: Signature,
+}
+
+impl ClusterMessage for AddOrderCommitmentSignature {
+    fn rollup_id(&self) -> &RollupId {
+        &self.order_commitment_data.rollup_id
+    }
+}
+
+impl RpcParameter<AppState> for AddOrderCommitmentSignature {
+    type Response = ();
+
+    fn method() -> &'static str {
+        "add_order_commitment_signature"
+    }
+
+    async fn handler(self, _context: AppState) -> Result<Self::Response, RpcError> {
+        let data = &self.order_commitment_data;
+        let rollup = Rollup::get(&data.rollup_id).map_err(|error| {
+            tracing::error!("Failed to get rollup: {:?}", error);
+            Error::RollupNotFound
+        })?;
+
+        if rollup.order_commitment_type != OrderCommitmentType::Quorum {
+            return Err(Error::UnsupportedOrderCommitmentType.into());
+        }
+
+        let sign_order_commitment =
+            match OrderCommitment::get(&data.rollup_id, data.batch_number, data.transaction_order)?
+            {
+                OrderCommitment::Single(SingleOrderCommitment::Sign(sign_order_commitment))
+                    if sign_order_commitment.data == *data =>
+                {
+                    sign_order_commitment
+                }
+                _ => return Err(Error::InvalidOrderCommitment.into()),
+            };
+
+        let cluster_metadata = ClusterMetadata::get(
+            rollup.platform,
+            rollup.liveness_service_provider,
+            &rollup.cluster_id,
+        )
+        .map_err(|error| {
+            tracing::error!("Failed to get cluster metadata: {:?}", error);
+            Error::ClusterMetadataNotFound
+        })?;
+        let cluster = Cluster::get(
+            rollup.platform,
+            rollup.liveness_service_provider,
+            &rollup.cluster_id,
+            cluster_metadata.platform_block_height,
+        )?;
+
+        let signer_address = self
+            .signature
+            .get_signer_address(rollup.platform.into(), data)
+            .map_err(Error::Signature)?;
+        if !cluster
+            .get_tx_orderer_address_list()
+            .contains(&signer_address)
+        {
+            return Err(Error::InvalidSignature.into());
+        }
+
+        let mut quorum_order_commitment = match QuorumOrderCommitment::get_mut(
+            &data.rollup_id,
+            data.batch_number,
+            data.transaction_order,
+        ) {
+            Ok(quorum_order_commitment) => quorum_order_commitment,
+            Err(error) if error.is_none_type() => {
+                QuorumOrderCommitment::new(
+                    &sign_order_commitment,
+                    rollup.platform.into(),
+                    cluster_metadata.platform_block_height,
+                    cluster.tx_orderer_rpc_infos.len(),
+                )
+                .put(&data.rollup_id, data.batch_number, data.transaction_order)?;
+
+                QuorumOrderCommitment::get_mut(
+                    &data.rollup_id,
+                    data.batch_number,
+                    data.transaction_order,
+                )?
+            }
+            Err(error) => return Err(error.into()),
+        };
+
+        let was_certified = quorum_order_commitment.is_certified();
+        if !quorum_order_commitment.add_signature(signer_address, self.signature) {
+            return Ok(());
+        }
+
+        if !was_certified && quorum_order_commitment.is_certified() {
+            tracing::info!(
+                "Order commitment certified - rollup_id: {:?} / batch_number: {:?} / transaction_order: {:?}",
+                data.rollup_id,
+                data.batch_number,
+                data.transaction_order
+            );
+        }
+
+        quorum_order_commitment.update()?;
+
+        Ok(())
+    }
+}
+
+/// Signs the order commitment issued by the current leader and sends the
+/// signature back to it, if the rollup uses quorum order commitments.
+pub fn co_sign_order_commitment(
+    context: AppState,
+    rollup: &Rollup,
+    cluster_metadata: &ClusterMetadata,
+    order_commitment: &OrderCommitment,
+) {
+    if rollup.order_commitment_type != OrderCommitmentType::Quorum {
+        return;
+    }
+
+    let OrderCommitment::Single(SingleOrderCommitment::Sign(sign_order_commitment)) =
+        order_commitment
+    else {
+        return;
+    };
+
+    let Some(leader_tx_orderer_rpc_info) = cluster_metadata.leader_tx_orderer_rpc_info.as_ref()
+    else {
+        return;
+    };
+    if sign_order_commitment.get_signer_address(rollup.platform.into())
+        != leader_tx_orderer_rpc_info.tx_orderer_address
+    {
+        return;
+    }
+    let Some(leader_cluster_rpc_url) = leader_tx_orderer_rpc_info.cluster_rpc_url.clone() else {
+        return;
+    };
+
+    let platform = rollup.platform;
+    let order_commitment_data = sign_order_commitment.data.clone();
+
+    context.shutdown_manager().clone().spawn(async move {
+        let signer = match context.get_signer(platform).await {
+            Ok(signer) => signer,
+            Err(error) => {
+                tracing::error!("Failed to co-sign order commitment: {:?}", error);
+                return;
+            }
+        };
+        let signature = match signer.sign_message(&order_commitment_data).await {
+            Ok(signature) => signature,
+            Err(error) => {
+                tracing::error!("Failed to co-sign order commitment: {:?}", error);
+                return;
+            }
+        };
+
+        let rollup_id = order_commitment_data.rollup_id.clone();
+        let parameter = match Authenticated::new(
+            &signer,
+            AddOrderCommitmentSignature {
+                order_commitment_data,
+                signature,
+            },
+        )
+        .await
+        {
+            Ok(parameter) => parameter,
+            Err(error) => {
+                tracing::error!("Failed to co-sign order commitment: {:?}", error);
+                return;
+            }
+        };
+
+        if let Err(error) = context
+            .rpc_client()
+            .request::<&Authenticated<AddOrderCommitmentSignature>, ()>(
+                leader_cluster_rpc_url,
+                AddOrderCommitmentSignature::method(),
+                &parameter,
+                Id::Null,
+            )
+            .await
+        {
+            tracing::warn!(
+                "Failed to send order commitment signature to the leader - rollup_id: {:?} / error: {:?}",
+                rollup_id,
+                error
+            );
+        }
+    });
+}
@@ -0,0 +1,116 @@
+use std::time::Duration;
+
+use radius_sdk::{json_rpc::server::ProcessPriority, signature::Address};
+
+use super::ClusterMessage;
+use crate::rpc::prelude::*;
+
+/// How long an attestation of a [`LeaderFailure`] can be acted on.
+pub const LEADER_FAILURE_TTL: Duration = Duration::from_secs(60);
+
+/// The failure of a leader as attested by a follower. It is bound to the
+/// position the successor continues from, and expires so that an attestation
+/// cannot be replayed for a later failover.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct LeaderFailure {
+    pub rollup_id: RollupId,
+    pub platform_block_height: u64,
+
+    pub failed_leader_tx_orderer_address: Address,
+
+    pub batch_number: u64,
+    pub transaction_order: u64,
+
+    /// Unix timestamp in milliseconds.
+    pub expires_at: u64,
+}
+
+impl LeaderFailure {
+    pub fn new(
+        rollup_id: RollupId,
+        platform_block_height: u64,
+        failed_leader_tx_orderer_address: Address,
+        batch_number: u64,
+        transaction_order: u64,
+    ) -> Self {
+        Self {
+            rollup_id,
+            platform_block_height,
+            failed_leader_tx_orderer_address,
+            batch_number,
+            transaction_order,
+            expires_at: current_timestamp() + LEADER_FAILURE_TTL.as_millis() as u64,
+        }
+    }
+
+    /// Whether the failure has not expired at `now`, and does not claim to be
+    /// valid for longer than [`LEADER_FAILURE_TTL`].
+    pub fn is_valid_at(&self, now: u64) -> bool {
+        now < self.expires_at && self.expires_at <= now + LEADER_FAILURE_TTL.as_millis() as u64
+    }
+}
+
+/// Asks a follower to sign the [`LeaderFailure`] if its own heartbeats show
+/// the leader as failed. The successor collects the attestations before it
+/// takes over.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct AttestLeaderFailure {
+    pub leader_failure: LeaderFailure,
+}
+
+impl ClusterMessage for AttestLeaderFailure {
+    fn rollup_id(&self) -> &RollupId {
+        &self.leader_failure.rollup_id
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct AttestLeaderFailureResponse {
+    /// `None` if the leader looks healthy from this node.
+    pub signature: Option<Signature>,
+}
+
+impl RpcParameter<AppState> for AttestLeaderFailure {
+    type Response = AttestLeaderFailureResponse;
+
+    fn method() -> &'static str {
+        "attest_leader_failure"
+    }
+
+    fn priority(&self) -> ProcessPriority {
+        ProcessPriority::High
+    }
+
+    async fn handler(self, context: AppState) -> Result<Self::Response, RpcError> {
+        let leader_failure = &self.leader_failure;
+
+        if !leader_failure.is_valid_at(current_timestamp()) {
+            return Err(Error::InvalidLeaderFailover.into());
+        }
+
+        let rollup = Rollup::get(&leader_failure.rollup_id).map_err(|error| {
+            tracing::error!("Failed to get rollup: {:?}", error);
+            Error::RollupNotFound
+        })?;
+
+        if !context.leader_health_tracker().has_failed(
+            &leader_failure.rollup_id,
+            &leader_failure.failed_leader_tx_orderer_address,
+        ) {
+            return Ok(AttestLeaderFailureResponse { signature: None });
+        }
+
+        let signer = context.get_signer(rollup.platform).await.map_err(|_| {
+            tracing::error!("Signer not found for platform {:?}", rollup.platform);
+            Error::SignerNotFound
+        })?;
+        let signature = signer
+            .sign_message(leader_failure)
+            .await
+            .map_err(Error::from)?;
+
+        Ok(AttestLeaderFailureResponse {
+            signature: Some(signature),
+        })
+    }
+}
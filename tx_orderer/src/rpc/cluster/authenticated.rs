@@ -0,0 +1,312 @@
+//! Signed envelopes for the cluster RPCs.
+//!
+//! The JSON-RPC server of the SDK exposes no TLS configuration, so the cluster
+//! RPC is not served over mutual TLS with certificates pinned to the operator
+//! addresses. The peers are authenticated by the signature of the envelope
+//! instead, which does not encrypt the messages: the cluster RPC must be
+//! reached over a private network or behind a TLS-terminating proxy if the
+//! traffic has to be confidential.
+
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+
+use radius_sdk::{json_rpc::server::ProcessPriority, signature::Address};
+use serde::{
+    de::{DeserializeOwned, Error as _},
+    Deserializer, Serializer,
+};
+use tracing::Instrument;
+
+use crate::{rpc::prelude::*, signer::TxOrdererSigner, telemetry::TraceContext};
+
+/// How far the timestamp of an envelope may be from the clock of the
+/// receiver, in milliseconds. Older envelopes are rejected, and the
+/// [`ReplayCache`] rejects the envelopes seen within this window.
+pub(super) const MAX_CLOCK_SKEW: u64 = 30_000;
+
+/// A cluster RPC parameter sent by another tx_orderer of the rollup.
+pub trait ClusterMessage {
+    fn rollup_id(&self) -> &RollupId;
+}
+
+#[derive(Serialize)]
+struct AuthenticatedPayload<'a> {
+    method: &'a str,
+    timestamp: u64,
+    nonce: u64,
+    message: &'a str,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct SignedClusterMessage {
+    /// The parameter as the JSON string that was signed, so that the signature
+    /// does not depend on how the receiver would serialize it again.
+    pub message: String,
+    /// Unix timestamp in milliseconds.
+    pub timestamp: u64,
+    /// A random number that tells apart the envelopes a sender signs for the
+    /// same message within a millisecond.
+    pub nonce: u64,
+    pub signature: Signature,
+    /// The trace context of the sender. It is not signed, as it only
+    /// correlates the spans.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trace_context: Option<TraceContext>,
+}
+
+/// Wraps a cluster RPC parameter in an envelope signed by the operator key of
+/// the sending tx_orderer. The handler of the parameter only runs once the
+/// signer has been found in the cluster of the rollup.
+#[derive(Clone, Debug)]
+pub struct Authenticated<T> {
+    parameter: T,
+    signed_message: SignedClusterMessage,
+}
+
+impl<T> Authenticated<T>
+where
+    T: RpcParameter<AppState> + ClusterMessage,
+{
+    pub async fn new(signer: &TxOrdererSigner, parameter: T) -> Result<Self, Error> {
+        let message = serde_json::to_string(&parameter).map_err(|_| Error::Convert)?;
+        let timestamp = current_timestamp();
+        let nonce = rand::random();
+        let signature = signer
+            .sign_message(&AuthenticatedPayload {
+                method: T::method(),
+                timestamp,
+                nonce,
+                message: &message,
+            })
+            .await?;
+
+        Ok(Self {
+            parameter,
+            signed_message: SignedClusterMessage {
+                message,
+                timestamp,
+                nonce,
+                signature,
+                trace_context: TraceContext::current(),
+            },
+        })
+    }
+
+    /// Signs the parameter with the signer of the platform of its rollup.
+    pub async fn sign(context: &AppState, parameter: T) -> Result<Self, Error> {
+        let rollup = Rollup::get(parameter.rollup_id())?;
+        let signer = context.get_signer(rollup.platform).await?;
+
+        Self::new(&signer, parameter).await
+    }
+
+    /// Returns the address of the tx_orderer that signed the parameter. An
+    /// envelope is only accepted once by `replay_cache`.
+    pub fn verify(&self, replay_cache: &ReplayCache) -> Result<Address, Error> {
+        let signed_message = &self.signed_message;
+        if current_timestamp().abs_diff(signed_message.timestamp) > MAX_CLOCK_SKEW {
+            return Err(Error::ExpiredClusterMessage);
+        }
+
+        let rollup = Rollup::get(self.parameter.rollup_id()).map_err(|_| Error::RollupNotFound)?;
+        let sender_address = signed_message
+            .signature
+            .get_signer_address(
+                rollup.platform.into(),
+                &AuthenticatedPayload {
+                    method: T::method(),
+                    timestamp: signed_message.timestamp,
+                    nonce: signed_message.nonce,
+                    message: &signed_message.message,
+                },
+            )
+            .map_err(Error::Signature)?;
+
+        if !is_cluster_member(&rollup, &sender_address)? {
+            tracing::warn!(
+                "Rejected a cluster message from outside the cluster - method: {:?} / rollup_id: {:?} / sender: {:?}",
+                T::method(),
+                rollup.rollup_id,
+                sender_address.as_hex_string()
+            );
+            return Err(Error::UnknownClusterMember);
+        }
+
+        if !replay_cache.insert(
+            &sender_address,
+            signed_message.nonce,
+            signed_message.timestamp,
+        ) {
+            tracing::warn!(
+                "Rejected a replayed cluster message - method: {:?} / rollup_id: {:?} / sender: {:?}",
+                T::method(),
+                rollup.rollup_id,
+                sender_address.as_hex_string()
+            );
+            return Err(Error::ReplayedClusterMessage);
+        }
+
+        Ok(sender_address)
+    }
+}
+
+impl<T> Serialize for Authenticated<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.signed_message.serialize(serializer)
+    }
+}
+
+impl<'de, T: DeserializeOwned> Deserialize<'de> for Authenticated<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let signed_message = SignedClusterMessage::deserialize(deserializer)?;
+        let parameter = serde_json::from_str(&signed_message.message).map_err(D::Error::custom)?;
+
+        Ok(Self {
+            parameter,
+            signed_message,
+        })
+    }
+}
+
+impl<T> RpcParameter<AppState> for Authenticated<T>
+where
+    T: RpcParameter<AppState> + ClusterMessage,
+{
+    type Response = T::Response;
+
+    fn method() -> &'static str {
+        T::method()
+    }
+
+    fn priority(&self) -> ProcessPriority {
+        self.parameter.priority()
+    }
+
+    async fn handler(self, context: AppState) -> Result<Self::Response, RpcError> {
+        let span = tracing::info_span!(
+            "cluster_message",
+            method = T::method(),
+            rollup_id = %self.parameter.rollup_id(),
+        );
+        if let Some(trace_context) = &self.signed_message.trace_context {
+            trace_context.attach(&span);
+        }
+
+        async move {
+            self.verify(context.cluster_message_replay_cache())?;
+
+            self.parameter.handler(context).await
+        }
+        .instrument(span)
+        .await
+    }
+}
+
+/// The envelopes and executor messages accepted within [`MAX_CLOCK_SKEW`],
+/// keyed by sender and nonce. Messages outside of the window are rejected by
+/// their timestamp, so the entries are dropped once it has passed.
+pub struct ReplayCache {
+    inner: Arc<Mutex<HashMap<(String, u64), u64>>>,
+}
+
+impl Clone for ReplayCache {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl Default for ReplayCache {
+    fn default() -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+}
+
+impl ReplayCache {
+    /// Returns `false` if the message has already been accepted.
+    pub(super) fn insert(&self, sender_address: &Address, nonce: u64, timestamp: u64) -> bool {
+        let now = current_timestamp();
+        let mut envelopes = self.inner.lock().unwrap();
+        envelopes.retain(|_, expires_at| *expires_at >= now);
+
+        let key = (sender_address.as_hex_string(), nonce);
+        if envelopes.contains_key(&key) {
+            return false;
+        }
+        envelopes.insert(key, timestamp + MAX_CLOCK_SKEW);
+
+        true
+    }
+}
+
+/// Checks the cluster the node currently follows and, as the membership may
+/// have changed on the platform since, the latest synced cluster.
+fn is_cluster_member(rollup: &Rollup, address: &Address) -> Result<bool, Error> {
+    let mut platform_block_height_list = Vec::new();
+
+    match ClusterMetadata::get(
+        rollup.platform,
+        rollup.liveness_service_provider,
+        &rollup.cluster_id,
+    ) {
+        Ok(cluster_metadata) => {
+            platform_block_height_list.push(cluster_metadata.platform_block_height)
+        }
+        Err(error) if error.is_none_type() => {}
+        Err(error) => return Err(error.into()),
+    }
+
+    match LatestSyncedClusterBlockHeight::get(
+        rollup.platform,
+        rollup.liveness_service_provider,
+        &rollup.cluster_id,
+    ) {
+        Ok(latest_synced_cluster_block_height) => {
+            platform_block_height_list.push(latest_synced_cluster_block_height.get_block_height())
+        }
+        Err(error) if error.is_none_type() => {}
+        Err(error) => return Err(error.into()),
+    }
+
+    for platform_block_height in platform_block_height_list {
+        let cluster = match Cluster::get(
+            rollup.platform,
+            rollup.liveness_service_provider,
+            &rollup.cluster_id,
+            platform_block_height,
+        ) {
+            Ok(cluster) => cluster,
+            Err(error) if error.is_none_type() => continue,
+            Err(error) => return Err(error.into()),
+        };
+
+        if cluster.get_tx_orderer_rpc_info(address).is_some() {
+            return Ok(true);
+        }
+    }
+
+    Ok(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use radius_sdk::signature::{ChainType, PrivateKeySigner};
+
+    use super::*;
+
+    #[test]
+    fn replay_cache_accepts_an_envelope_once() {
+        let signer = PrivateKeySigner::from_str(ChainType::Ethereum, DEFAULT_SIGNING_KEY).unwrap();
+        let timestamp = current_timestamp();
+
+        let replay_cache = ReplayCache::default();
+        assert!(replay_cache.insert(signer.address(), 1, timestamp));
+        assert!(!replay_cache.insert(signer.address(), 1, timestamp));
+        assert!(replay_cache.insert(signer.address(), 2, timestamp));
+    }
+}
@@ -0,0 +1,376 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use radius_sdk::{json_rpc::client::Priority, signature::Address};
+
+use super::{verify_executor_message, ExecutorMessage, ReplayCache, SyncLeaderTxOrderer};
+use crate::{
+    rpc::{
+        cluster::{Authenticated, GetOrderCommitmentInfo, GetOrderCommitmentInfoResponse},
+        prelude::*,
+    },
+    signer::TxOrdererSigner,
+    task::backfill_rollup,
+    util::multicast,
+};
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct ChangeLeaderTxOrderer {
+    pub leader_change_message: LeaderChangeMessage,
+    pub rollup_signature: Signature,
+}
+
+/// Signed by `executor_address`, one of the executors of the rollup.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct LeaderChangeMessage {
+    pub rollup_id: RollupId,
+    pub executor_address: Address,
+    pub platform_block_height: u64,
+
+    pub current_leader_tx_orderer_address: Address,
+    pub next_leader_tx_orderer_address: Address,
+
+    /// Unix timestamp in milliseconds.
+    pub timestamp: u64,
+    pub nonce: u64,
+}
+
+impl ExecutorMessage for LeaderChangeMessage {
+    fn rollup_id(&self) -> &RollupId {
+        &self.rollup_id
+    }
+
+    fn timestamp(&self) -> u64 {
+        self.timestamp
+    }
+
+    fn nonce(&self) -> u64 {
+        self.nonce
+    }
+}
+
+impl LeaderChangeMessage {
+    /// Checks that `rollup_signature` was signed by the executor of the
+    /// message, once and recently.
+    pub fn verify(
+        &self,
+        rollup: &Rollup,
+        rollup_signature: &Signature,
+        replay_cache: &ReplayCache,
+    ) -> Result<(), Error> {
+        let executor_address = verify_executor_message(
+            rollup.platform,
+            &rollup.executor_address_list,
+            self,
+            rollup_signature,
+            replay_cache,
+        )?;
+        if executor_address != self.executor_address {
+            return Err(Error::InvalidSignature);
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct SignMessage {
+    pub rollup_id: RollupId,
+    pub executor_address: String,
+    pub platform_block_height: u64,
+
+    pub current_leader_tx_orderer_address: Address,
+    pub next_leader_tx_orderer_address: Address,
+}
+
+impl RpcParameter<AppState> for ChangeLeaderTxOrderer {
+    type Response = ();
+
+    fn method() -> &'static str {
+        "change_leader_tx_orderer"
+    }
+
+    async fn handler(self, context: AppState) -> Result<Self::Response, RpcError> {
+        let rollup = Rollup::get(&self.leader_change_message.rollup_id)?;
+        self.leader_change_message.verify(
+            &rollup,
+            &self.rollup_signature,
+            context.cluster_message_replay_cache(),
+        )?;
+
+        self.change_leader(context).await
+    }
+}
+
+impl ChangeLeaderTxOrderer {
+    /// Changes the leader once the message has been verified.
+    pub(super) async fn change_leader(self, context: AppState) -> Result<(), RpcError> {
+        let rollup_id = self.leader_change_message.rollup_id.clone();
+
+        if let Err(err) = RollupMetadata::get(&rollup_id) {
+            tracing::error!(
+                "Failed to get rollup metadata - rollup_id: {:?} / error: {:?}",
+                rollup_id,
+                err,
+            );
+
+            return Ok(());
+        }
+
+        let rollup = Rollup::get(&rollup_id)?;
+
+        let cluster = Cluster::get(
+            rollup.platform,
+            rollup.liveness_service_provider,
+            &rollup.cluster_id,
+            self.leader_change_message.platform_block_height,
+        )?;
+
+        let mut mut_rollup_metadata = RollupMetadata::get_mut(&rollup_id)?;
+
+        let leader_tx_orderer_rpc_info = cluster
+            .get_tx_orderer_rpc_info(&self.leader_change_message.next_leader_tx_orderer_address)
+            .ok_or_else(|| {
+                tracing::error!(
+                    "TxOrderer RPC info not found for address {:?}",
+                    self.leader_change_message.next_leader_tx_orderer_address
+                );
+                Error::TxOrdererInfoNotFound
+            })?;
+
+        let signer = context.get_signer(rollup.platform).await.map_err(|_| {
+            tracing::error!("Signer not found for platform {:?}", rollup.platform);
+            Error::SignerNotFound
+        })?;
+
+        let tx_orderer_address = signer.address().clone();
+
+        let is_next_leader =
+            tx_orderer_address == self.leader_change_message.next_leader_tx_orderer_address;
+
+        let mut mut_cluster_metadata = ClusterMetadata::get_mut(
+            rollup.platform,
+            rollup.liveness_service_provider,
+            &rollup.cluster_id,
+        )?;
+
+        if !mut_cluster_metadata.is_leader {
+            if let Some(current_leader_tx_orderer_rpc_info) =
+                mut_cluster_metadata.leader_tx_orderer_rpc_info.clone()
+            {
+                let current_leader_tx_orderer_cluster_rpc_url = current_leader_tx_orderer_rpc_info
+                    .cluster_rpc_url
+                    .clone()
+                    .unwrap();
+
+                let parameter = Authenticated::new(
+                    &signer,
+                    GetOrderCommitmentInfo {
+                        rollup_id: self.leader_change_message.rollup_id.clone(),
+                    },
+                )
+                .await?;
+
+                match context
+                    .rpc_client()
+                    .request_with_priority::<&Authenticated<GetOrderCommitmentInfo>, GetOrderCommitmentInfoResponse>(
+                        current_leader_tx_orderer_cluster_rpc_url.clone(),
+                        GetOrderCommitmentInfo::method(),
+                        &parameter,
+                        Id::Null,
+                        Priority::High,
+                    )
+                    .await
+                {
+                    Ok(response) => {
+                        tracing::info!(
+                            "Get order commitment info - current leader external rpc response: {:?}",
+                            response
+                        );
+
+                        mut_rollup_metadata.batch_number = response.batch_number;
+                        mut_rollup_metadata.transaction_order = response.transaction_order;
+
+                        // Catch up on whatever the current leader ordered before
+                        // taking over.
+                        if is_next_leader {
+                            if let Err(error) = backfill_rollup(
+                                &context,
+                                &rollup,
+                                &cluster,
+                                mut_rollup_metadata.provided_batch_number,
+                                response.batch_number,
+                                response.transaction_order,
+                            )
+                            .await
+                            {
+                                tracing::warn!(
+                                    "Failed to backfill before becoming leader - rollup_id: {:?} / error: {:?}",
+                                    rollup_id,
+                                    error
+                                );
+                            }
+                        }
+                    }
+                    Err(error) => {
+                        tracing::error!(
+                            "Get order commitment info - current leader external rpc error: {:?}",
+                            error
+                        );
+                    }
+                }
+            } else {
+                tracing::warn!(
+                    "Current leader tx orderer RPC info not found for address {:?}",
+                    self.leader_change_message.current_leader_tx_orderer_address
+                );
+            }
+        }
+
+        mut_cluster_metadata.platform_block_height =
+            self.leader_change_message.platform_block_height;
+        mut_cluster_metadata.is_leader = is_next_leader;
+        mut_cluster_metadata.leader_tx_orderer_rpc_info = Some(leader_tx_orderer_rpc_info.clone());
+
+        // The provided cursor only moves on `AcknowledgeTransactions`, so the
+        // next leader continues from what the executor has acknowledged.
+        sync_leader_tx_orderer(
+            context.clone(),
+            cluster,
+            &signer,
+            self.leader_change_message.clone(),
+            self.rollup_signature,
+            mut_rollup_metadata.batch_number,
+            mut_rollup_metadata.transaction_order,
+            mut_rollup_metadata.provided_batch_number,
+            mut_rollup_metadata.provided_transaction_order,
+        )
+        .await;
+
+        mut_cluster_metadata.update()?;
+
+        LeaderState::new(
+            self.leader_change_message.platform_block_height,
+            self.leader_change_message
+                .next_leader_tx_orderer_address
+                .clone(),
+            mut_rollup_metadata.batch_number,
+            mut_rollup_metadata.transaction_order,
+        )
+        .record(&rollup_id)?;
+
+        let _ = mut_rollup_metadata.update().map_err(|error| {
+            tracing::error!(
+                "rollup_metadata update error - rollup id: {:?}, error: {:?}",
+                self.leader_change_message.rollup_id,
+                error
+            );
+        });
+
+        Ok(())
+    }
+}
+
+pub async fn sync_leader_tx_orderer(
+    context: AppState,
+    cluster: Cluster,
+    signer: &TxOrdererSigner,
+    leader_change_message: LeaderChangeMessage,
+    rollup_signature: Signature,
+    batch_number: u64,
+    transaction_order: u64,
+    provided_batch_number: u64,
+    provided_transaction_order: i64,
+) {
+    let mut other_cluster_rpc_url_list = cluster.get_other_cluster_rpc_url_list();
+    if other_cluster_rpc_url_list.is_empty() {
+        tracing::info!("No cluster RPC URLs available for synchronization");
+        return;
+    }
+
+    if let Some(next_leader_tx_orderer_rpc_info) =
+        cluster.get_tx_orderer_rpc_info(&leader_change_message.next_leader_tx_orderer_address)
+    {
+        let next_leader_tx_orderer_cluster_rpc_url = next_leader_tx_orderer_rpc_info
+            .cluster_rpc_url
+            .clone()
+            .unwrap();
+
+        // Filter out the next leader's cluster URL from the list
+        other_cluster_rpc_url_list = other_cluster_rpc_url_list
+            .into_iter()
+            .filter(|rpc_url| rpc_url != &next_leader_tx_orderer_cluster_rpc_url)
+            .collect();
+
+        let parameter = match Authenticated::new(
+            signer,
+            SyncLeaderTxOrderer {
+                leader_change_message: leader_change_message.clone(),
+                rollup_signature,
+                batch_number,
+                transaction_order,
+                provided_batch_number,
+                provided_transaction_order,
+            },
+        )
+        .await
+        {
+            Ok(parameter) => parameter,
+            Err(error) => {
+                tracing::error!(
+                    "Failed to sign the leader sync - rollup_id: {:?} / error: {:?}",
+                    leader_change_message.rollup_id,
+                    error
+                );
+                return;
+            }
+        };
+
+        if next_leader_tx_orderer_rpc_info.tx_orderer_address
+            != leader_change_message.current_leader_tx_orderer_address
+        {
+            // Directly request the next leader tx_orderer to sync
+            let start_sync_leader_tx_order_time = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .expect("Time went backwards")
+                .as_nanos();
+
+            let _result: Result<(), radius_sdk::json_rpc::client::RpcClientError> = context
+                .rpc_client()
+                .request_with_priority(
+                    next_leader_tx_orderer_cluster_rpc_url.clone(),
+                    SyncLeaderTxOrderer::method(),
+                    &parameter,
+                    Id::Null,
+                    Priority::High,
+                )
+                .await;
+
+            let end_sync_leader_tx_order_time = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .expect("Time went backwards")
+                .as_nanos();
+
+            tracing::info!(
+                "SyncLeaderTxOrderer - start: {:?} / end: {:?} / gap: {:?} / next_leader_tx_orderer_cluster_rpc_url: {:?}, parameter: {:?}",
+                start_sync_leader_tx_order_time,
+                end_sync_leader_tx_order_time,
+                end_sync_leader_tx_order_time - start_sync_leader_tx_order_time,
+                next_leader_tx_orderer_cluster_rpc_url,
+                parameter
+            );
+
+            // Fire and forget to the rest of the cluster nodes asynchronously
+            multicast(
+                &context,
+                other_cluster_rpc_url_list,
+                SyncLeaderTxOrderer::method(),
+                parameter,
+            );
+        }
+    } else {
+        tracing::error!(
+            "Next leader tx orderer RPC info not found for address {:?}",
+            leader_change_message.next_leader_tx_orderer_address
+        );
+    }
+}
@@ -1,3 +1,4 @@
+use super::ClusterMessage;
 use crate::{rpc::prelude::*, task::create_batch};
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
@@ -6,6 +7,12 @@ pub struct SyncBatchCreation {
     pub leader_tx_orderer_signature: Signature,
 }
 
+impl ClusterMessage for SyncBatchCreation {
+    fn rollup_id(&self) -> &RollupId {
+        &self.batch_creation_massage.rollup_id
+    }
+}
+
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct BatchCreationMessage {
     pub rollup_id: RollupId,
@@ -22,7 +29,51 @@ impl RpcParameter<AppState> for SyncBatchCreation {
     }
 
     async fn handler(self, context: AppState) -> Result<Self::Response, RpcError> {
-        let rollup_id = self.batch_creation_massage.rollup_id;
+        let rollup_id = self.batch_creation_massage.rollup_id.clone();
+        let rollup = Rollup::get(&rollup_id)?;
+
+        let leader_tx_orderer_address = self
+            .leader_tx_orderer_signature
+            .get_signer_address(rollup.platform.into(), &self.batch_creation_massage)
+            .map_err(Error::Signature)?;
+
+        let cluster_metadata = ClusterMetadata::get(
+            rollup.platform,
+            rollup.liveness_service_provider,
+            &rollup.cluster_id,
+        )?;
+        let cluster = Cluster::get(
+            rollup.platform,
+            rollup.liveness_service_provider,
+            &rollup.cluster_id,
+            cluster_metadata.platform_block_height,
+        )?;
+        if !cluster
+            .get_tx_orderer_address_list()
+            .contains(&leader_tx_orderer_address)
+        {
+            return Err(Error::InvalidSignature.into());
+        }
+
+        let signed_batch_creation = SignedBatchCreation {
+            rollup_id: rollup_id.clone(),
+            batch_number: self.batch_creation_massage.batch_number,
+            batch_commitment: self.batch_creation_massage.batch_commitment,
+            batch_creator_signature: self.batch_creation_massage.batch_creator_signature.clone(),
+            leader_tx_orderer_address,
+            leader_tx_orderer_signature: self.leader_tx_orderer_signature.clone(),
+        };
+
+        if let Some(equivocation_evidence) =
+            EquivocationEvidence::check_batch_creation(&signed_batch_creation)?
+        {
+            tracing::error!(
+                "Equivocation detected - rollup_id: {:?} / evidence: {:?}",
+                rollup_id,
+                equivocation_evidence
+            );
+            return Err(Error::Equivocation.into());
+        }
 
         create_batch(
             context,
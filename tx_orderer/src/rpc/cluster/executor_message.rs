@@ -0,0 +1,146 @@
+//! Messages signed by an executor of the rollup.
+//!
+//! The executors are not cluster members, so their messages are not wrapped in
+//! [`super::Authenticated`]. The signer is checked against the executors of
+//! the rollup instead, and the executor signs a timestamp and a nonce along
+//! with the message, so that the [`ReplayCache`] rejects a replayed message.
+
+use radius_sdk::signature::Address;
+
+use super::{ReplayCache, MAX_CLOCK_SKEW};
+use crate::rpc::prelude::*;
+
+/// A message an executor of the rollup signs.
+pub trait ExecutorMessage: Serialize {
+    fn rollup_id(&self) -> &RollupId;
+
+    /// Unix timestamp in milliseconds.
+    fn timestamp(&self) -> u64;
+
+    /// A random number that tells apart the messages an executor signs within
+    /// [`MAX_CLOCK_SKEW`].
+    fn nonce(&self) -> u64;
+}
+
+/// Returns the executor that signed `message`, one of the
+/// `executor_address_list` of the rollup. A message is only accepted once by
+/// `replay_cache`.
+pub fn verify_executor_message<T: ExecutorMessage>(
+    platform: Platform,
+    executor_address_list: &[Address],
+    message: &T,
+    signature: &Signature,
+    replay_cache: &ReplayCache,
+) -> Result<Address, Error> {
+    if current_timestamp().abs_diff(message.timestamp()) > MAX_CLOCK_SKEW {
+        return Err(Error::ExpiredExecutorMessage);
+    }
+
+    let executor_address = signature
+        .get_signer_address(platform.into(), message)
+        .map_err(Error::Signature)?;
+    if !executor_address_list.contains(&executor_address) {
+        tracing::error!(
+            "Unknown executor signed the message - rollup_id: {:?} / signer_address: {:?}",
+            message.rollup_id(),
+            executor_address.as_hex_string()
+        );
+        return Err(Error::ExecutorAddressNotFound);
+    }
+
+    if !replay_cache.insert(&executor_address, message.nonce(), message.timestamp()) {
+        tracing::warn!(
+            "Rejected a replayed executor message - rollup_id: {:?} / executor_address: {:?}",
+            message.rollup_id(),
+            executor_address.as_hex_string()
+        );
+        return Err(Error::ReplayedExecutorMessage);
+    }
+
+    Ok(executor_address)
+}
+
+#[cfg(test)]
+mod tests {
+    use radius_sdk::signature::{ChainType, PrivateKeySigner};
+
+    use super::*;
+
+    #[derive(Serialize)]
+    struct TestMessage {
+        rollup_id: RollupId,
+        timestamp: u64,
+        nonce: u64,
+    }
+
+    impl ExecutorMessage for TestMessage {
+        fn rollup_id(&self) -> &RollupId {
+            &self.rollup_id
+        }
+
+        fn timestamp(&self) -> u64 {
+            self.timestamp
+        }
+
+        fn nonce(&self) -> u64 {
+            self.nonce
+        }
+    }
+
+    #[test]
+    fn executor_message_is_accepted_once_and_only_while_fresh() {
+        let executor =
+            PrivateKeySigner::from_str(ChainType::Ethereum, DEFAULT_SIGNING_KEY).unwrap();
+        let executor_address_list = vec![executor.address().clone()];
+        let verify = |message: &TestMessage, signature: &Signature, replay_cache: &ReplayCache| {
+            verify_executor_message(
+                Platform::Ethereum,
+                &executor_address_list,
+                message,
+                signature,
+                replay_cache,
+            )
+        };
+        let replay_cache = ReplayCache::default();
+
+        let message = TestMessage {
+            rollup_id: RollupId::from("rollup_id"),
+            timestamp: current_timestamp(),
+            nonce: 1,
+        };
+        let signature = executor.sign_message(&message).unwrap();
+        let executor_address = verify(&message, &signature, &replay_cache).unwrap();
+        assert_eq!(&executor_address, executor.address());
+        assert!(matches!(
+            verify(&message, &signature, &replay_cache),
+            Err(Error::ReplayedExecutorMessage)
+        ));
+
+        let expired_message = TestMessage {
+            timestamp: current_timestamp() - 2 * MAX_CLOCK_SKEW,
+            nonce: 2,
+            ..message
+        };
+        let signature = executor.sign_message(&expired_message).unwrap();
+        assert!(matches!(
+            verify(&expired_message, &signature, &replay_cache),
+            Err(Error::ExpiredExecutorMessage)
+        ));
+
+        let other_signer = PrivateKeySigner::from_str(
+            ChainType::Ethereum,
+            "0x59c6995e998f97a5a0044966f0945389dc9e86dae88c7a8412f4603b6b78690d",
+        )
+        .unwrap();
+        let message = TestMessage {
+            timestamp: current_timestamp(),
+            nonce: 3,
+            ..expired_message
+        };
+        let signature = other_signer.sign_message(&message).unwrap();
+        assert!(matches!(
+            verify(&message, &signature, &replay_cache),
+            Err(Error::ExecutorAddressNotFound)
+        ));
+    }
+}
@@ -0,0 +1,67 @@
+use super::ClusterMessage;
+use crate::rpc::prelude::*;
+
+pub const MAX_BATCH_RANGE: u64 = 16;
+
+/// Returns the sealed batches in `[start_batch_number, end_batch_number)`
+/// together with the order commitment of every transaction. The response
+/// stops at the first batch this node has not sealed yet.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct GetBatchRange {
+    pub rollup_id: RollupId,
+    pub start_batch_number: u64,
+    pub end_batch_number: u64,
+}
+
+impl ClusterMessage for GetBatchRange {
+    fn rollup_id(&self) -> &RollupId {
+        &self.rollup_id
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct SyncedBatch {
+    pub batch: Batch,
+    pub order_commitment_list: Vec<Option<OrderCommitment>>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct GetBatchRangeResponse {
+    pub batch_list: Vec<SyncedBatch>,
+}
+
+impl RpcParameter<AppState> for GetBatchRange {
+    type Response = GetBatchRangeResponse;
+
+    fn method() -> &'static str {
+        "get_batch_range"
+    }
+
+    async fn handler(self, _context: AppState) -> Result<Self::Response, RpcError> {
+        let end_batch_number = self
+            .end_batch_number
+            .min(self.start_batch_number + MAX_BATCH_RANGE);
+
+        let mut batch_list = Vec::new();
+        for batch_number in self.start_batch_number..end_batch_number {
+            let batch = match Batch::get(&self.rollup_id, batch_number) {
+                Ok(batch) => batch,
+                Err(error) if error.is_none_type() => break,
+                Err(error) => return Err(error.into()),
+            };
+
+            let order_commitment_list = (0..batch.raw_transaction_list.len() as u64)
+                .map(|transaction_order| {
+                    OrderCommitment::get(&self.rollup_id, batch_number, transaction_order).ok()
+                })
+                .collect();
+
+            batch_list.push(SyncedBatch {
+                batch,
+                order_commitment_list,
+            });
+        }
+
+        Ok(GetBatchRangeResponse { batch_list })
+    }
+}
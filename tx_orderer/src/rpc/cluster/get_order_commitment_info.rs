@@ -1,5 +1,6 @@
 use radius_sdk::json_rpc::server::ProcessPriority;
 
+use super::ClusterMessage;
 use crate::rpc::prelude::*;
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
@@ -7,6 +8,12 @@ pub struct GetOrderCommitmentInfo {
     pub rollup_id: RollupId,
 }
 
+impl ClusterMessage for GetOrderCommitmentInfo {
+    fn rollup_id(&self) -> &RollupId {
+        &self.rollup_id
+    }
+}
+
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct GetOrderCommitmentInfoResponse {
     pub batch_number: u64,
@@ -0,0 +1,94 @@
+use super::ClusterMessage;
+use crate::rpc::prelude::*;
+
+pub const MAX_TRANSACTION_RANGE: u64 = 256;
+
+/// Returns the transactions in `[start_transaction_order,
+/// end_transaction_order)` of a batch together with their order commitments.
+/// Positions this node does not have are left out.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct GetTransactionRange {
+    pub rollup_id: RollupId,
+    pub batch_number: u64,
+    pub start_transaction_order: u64,
+    pub end_transaction_order: u64,
+}
+
+impl ClusterMessage for GetTransactionRange {
+    fn rollup_id(&self) -> &RollupId {
+        &self.rollup_id
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct SyncedTransaction {
+    pub transaction_order: u64,
+
+    pub raw_transaction: Option<RawTransaction>,
+    pub is_direct_sent: bool,
+    pub encrypted_transaction: Option<EncryptedTransaction>,
+
+    pub order_commitment: OrderCommitment,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct GetTransactionRangeResponse {
+    pub batch_number: u64,
+    pub transaction_list: Vec<SyncedTransaction>,
+}
+
+impl RpcParameter<AppState> for GetTransactionRange {
+    type Response = GetTransactionRangeResponse;
+
+    fn method() -> &'static str {
+        "get_transaction_range"
+    }
+
+    async fn handler(self, _context: AppState) -> Result<Self::Response, RpcError> {
+        let end_transaction_order = self
+            .end_transaction_order
+            .min(self.start_transaction_order + MAX_TRANSACTION_RANGE);
+
+        let mut transaction_list = Vec::new();
+        for transaction_order in self.start_transaction_order..end_transaction_order {
+            let order_commitment =
+                match OrderCommitment::get(&self.rollup_id, self.batch_number, transaction_order) {
+                    Ok(order_commitment) => order_commitment,
+                    Err(error) if error.is_none_type() => continue,
+                    Err(error) => return Err(error.into()),
+                };
+
+            let raw_transaction_info =
+                RawTransactionModel::get(&self.rollup_id, self.batch_number, transaction_order)
+                    .ok();
+            let encrypted_transaction = EncryptedTransactionModel::get(
+                &self.rollup_id,
+                self.batch_number,
+                transaction_order,
+            )
+            .ok();
+
+            if raw_transaction_info.is_none() && encrypted_transaction.is_none() {
+                continue;
+            }
+
+            let (raw_transaction, is_direct_sent) = match raw_transaction_info {
+                Some((raw_transaction, is_direct_sent)) => (Some(raw_transaction), is_direct_sent),
+                None => (None, false),
+            };
+
+            transaction_list.push(SyncedTransaction {
+                transaction_order,
+                raw_transaction,
+                is_direct_sent,
+                encrypted_transaction,
+                order_commitment,
+            });
+        }
+
+        Ok(GetTransactionRangeResponse {
+            batch_number: self.batch_number,
+            transaction_list,
+        })
+    }
+}
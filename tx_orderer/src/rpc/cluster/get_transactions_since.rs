@@ -0,0 +1,452 @@
+use std::{collections::BTreeSet, sync::Arc, time::Duration};
+
+use tokio::{sync::mpsc::UnboundedReceiver, time::Instant};
+
+use super::{verify_executor_message, ExecutorMessage};
+use crate::{
+    rpc::prelude::*,
+    task::{send_transaction_list_to_mev_searcher, MevTargetTransaction},
+};
+
+pub(super) const MAX_TRANSACTION_LIMIT: u64 = 1024;
+const BACKRUNNING_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Reads the ordered transactions starting at the given cursor (inclusive).
+///
+/// The handler does not move the provided cursor, so a lost response can be
+/// retried with the same cursor. The cursor is only moved forward by
+/// [`super::AcknowledgeTransactions`].
+///
+/// It is not free of side effects otherwise: a non-empty read is handed to the
+/// registered MEV searchers, and the response waits up to
+/// [`BACKRUNNING_TIMEOUT`] for their backrunning transactions. A retried read
+/// hands the same transactions to the searchers again.
+///
+/// The request is signed by an executor of the rollup, with a new nonce for
+/// every retry.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct GetTransactionsSince {
+    pub request: TransactionsSinceRequest,
+    pub executor_signature: Signature,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct TransactionsSinceRequest {
+    pub rollup_id: RollupId,
+    pub batch_number: u64,
+    pub transaction_order: u64,
+    pub limit: u64,
+
+    /// Unix timestamp in milliseconds.
+    pub timestamp: u64,
+    pub nonce: u64,
+}
+
+impl ExecutorMessage for TransactionsSinceRequest {
+    fn rollup_id(&self) -> &RollupId {
+        &self.rollup_id
+    }
+
+    fn timestamp(&self) -> u64 {
+        self.timestamp
+    }
+
+    fn nonce(&self) -> u64 {
+        self.nonce
+    }
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq, PartialOrd, Ord)]
+pub struct TransactionCursor {
+    pub batch_number: u64,
+    pub transaction_order: u64,
+}
+
+impl TransactionCursor {
+    pub fn new(batch_number: u64, transaction_order: u64) -> Self {
+        Self {
+            batch_number,
+            transaction_order,
+        }
+    }
+
+    /// The next position the executor has not acknowledged yet.
+    pub fn acknowledged(rollup_metadata: &RollupMetadata) -> Self {
+        Self::new(
+            rollup_metadata.provided_batch_number,
+            (rollup_metadata.provided_transaction_order + 1) as u64,
+        )
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct OrderedTransaction {
+    pub transaction_order: u64,
+    pub raw_transaction: String,
+    pub order_commitment: Option<OrderCommitment>,
+}
+
+/// Transactions of a single batch. `batch_commitment` is only set on the
+/// segment that reaches the end of a sealed batch, marking the batch boundary.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct BatchSegment {
+    pub batch_number: u64,
+    pub is_sealed: bool,
+    pub batch_commitment: Option<BatchCommitment>,
+    pub transaction_list: Vec<OrderedTransaction>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct GetTransactionsSinceResponse {
+    pub batch_segment_list: Vec<BatchSegment>,
+    pub backrunning_transaction_list: Vec<String>,
+
+    pub next_cursor: TransactionCursor,
+    pub acknowledged_cursor: TransactionCursor,
+}
+
+impl RpcParameter<AppState> for GetTransactionsSince {
+    type Response = GetTransactionsSinceResponse;
+
+    fn method() -> &'static str {
+        "get_transactions_since"
+    }
+
+    async fn handler(self, context: AppState) -> Result<Self::Response, RpcError> {
+        let request = &self.request;
+
+        let rollup = Rollup::get(&request.rollup_id).map_err(|error| {
+            tracing::error!(
+                "Failed to get rollup - rollup_id: {:?} / error: {:?}",
+                request.rollup_id,
+                error
+            );
+            Error::RollupNotFound
+        })?;
+        verify_executor_message(
+            rollup.platform,
+            &rollup.executor_address_list,
+            request,
+            &self.executor_signature,
+            context.cluster_message_replay_cache(),
+        )?;
+
+        let rollup_metadata = RollupMetadata::get(&request.rollup_id).map_err(|error| {
+            tracing::error!(
+                "Failed to get rollup metadata - rollup_id: {:?} / error: {:?}",
+                request.rollup_id,
+                error
+            );
+            Error::RollupMetadataNotFound
+        })?;
+
+        let mut cursor = TransactionCursor::new(request.batch_number, request.transaction_order);
+        let batch_segment_list = read_transactions(
+            &request.rollup_id,
+            &rollup,
+            &mut cursor,
+            request.limit.clamp(1, MAX_TRANSACTION_LIMIT),
+        )?;
+        ServedTransactionCursor::advance(
+            &request.rollup_id,
+            cursor.batch_number,
+            cursor.transaction_order,
+        )?;
+
+        let raw_transaction_list: Vec<String> = batch_segment_list
+            .iter()
+            .flat_map(|batch_segment| batch_segment.transaction_list.iter())
+            .map(|transaction| transaction.raw_transaction.clone())
+            .collect();
+
+        let backrunning_transaction_list = if raw_transaction_list.is_empty() {
+            Vec::new()
+        } else {
+            collect_backrunning_transactions(&context, &request.rollup_id, raw_transaction_list)
+                .await?
+        };
+
+        Ok(GetTransactionsSinceResponse {
+            batch_segment_list,
+            backrunning_transaction_list,
+            next_cursor: cursor,
+            acknowledged_cursor: TransactionCursor::acknowledged(&rollup_metadata),
+        })
+    }
+}
+
+/// Reads up to `limit` transactions from `cursor` and moves the cursor past
+/// them.
+pub(super) fn read_transactions(
+    rollup_id: &RollupId,
+    rollup: &Rollup,
+    cursor: &mut TransactionCursor,
+    limit: u64,
+) -> Result<Vec<BatchSegment>, RpcError> {
+    let mut remaining = limit;
+    let mut batch_segment_list = Vec::new();
+
+    while remaining > 0 {
+        let batch_segment = match Batch::get(rollup_id, cursor.batch_number) {
+            Ok(batch) => read_sealed_batch(rollup_id, batch, cursor, &mut remaining),
+            Err(error) if error.is_none_type() => {
+                read_unsealed_batch(rollup_id, rollup, cursor, &mut remaining)?
+            }
+            Err(error) => return Err(error.into()),
+        };
+
+        let is_sealed = batch_segment.is_sealed;
+        batch_segment_list.push(batch_segment);
+
+        if !is_sealed {
+            break;
+        }
+    }
+
+    batch_segment_list.retain(|batch_segment| {
+        batch_segment.is_sealed || !batch_segment.transaction_list.is_empty()
+    });
+
+    Ok(batch_segment_list)
+}
+
+fn read_sealed_batch(
+    rollup_id: &RollupId,
+    batch: Batch,
+    cursor: &mut TransactionCursor,
+    remaining: &mut u64,
+) -> BatchSegment {
+    let batch_number = batch.batch_number;
+    let transaction_count = batch.raw_transaction_list.len() as u64;
+
+    let transaction_list: Vec<OrderedTransaction> = batch
+        .raw_transaction_list
+        .into_iter()
+        .enumerate()
+        .skip(cursor.transaction_order as usize)
+        .take(*remaining as usize)
+        .map(|(transaction_order, raw_transaction)| {
+            let transaction_order = transaction_order as u64;
+
+            OrderedTransaction {
+                transaction_order,
+                raw_transaction: raw_transaction_to_string(raw_transaction),
+                order_commitment: OrderCommitment::get(rollup_id, batch_number, transaction_order)
+                    .ok(),
+            }
+        })
+        .collect();
+
+    *remaining -= transaction_list.len() as u64;
+    cursor.transaction_order += transaction_list.len() as u64;
+
+    let is_end_of_batch = cursor.transaction_order >= transaction_count;
+    if is_end_of_batch {
+        *cursor = TransactionCursor::new(batch_number + 1, 0);
+    }
+
+    BatchSegment {
+        batch_number,
+        is_sealed: true,
+        batch_commitment: is_end_of_batch.then_some(batch.batch_commitment),
+        transaction_list,
+    }
+}
+
+fn read_unsealed_batch(
+    rollup_id: &RollupId,
+    rollup: &Rollup,
+    cursor: &mut TransactionCursor,
+    remaining: &mut u64,
+) -> Result<BatchSegment, RpcError> {
+    let batch_number = cursor.batch_number;
+    let mut transaction_list = Vec::new();
+
+    let can_provide_transaction_orders = CanProvideTransactionInfo::get(rollup_id)
+        .ok()
+        .and_then(|mut can_provide_transaction_info| {
+            can_provide_transaction_info
+                .can_provide_transaction_orders_per_batch
+                .remove(&batch_number)
+        })
+        .unwrap_or_default();
+
+    let last_valid_transaction_order = get_last_valid_transaction_order(
+        &can_provide_transaction_orders,
+        cursor.transaction_order as i64 - 1,
+    );
+
+    while *remaining > 0 && (cursor.transaction_order as i64) <= last_valid_transaction_order {
+        let (raw_transaction, _) =
+            RawTransactionModel::get(rollup_id, batch_number, cursor.transaction_order)?;
+
+        transaction_list.push(OrderedTransaction {
+            transaction_order: cursor.transaction_order,
+            raw_transaction: raw_transaction_to_string(raw_transaction),
+            order_commitment: OrderCommitment::get(
+                rollup_id,
+                batch_number,
+                cursor.transaction_order,
+            )
+            .ok(),
+        });
+
+        *remaining -= 1;
+        cursor.transaction_order += 1;
+    }
+
+    // A full batch will be sealed with exactly these transactions, so the
+    // cursor can move on without waiting for the batch commitment.
+    if cursor.transaction_order >= rollup.max_transaction_count_per_batch {
+        *cursor = TransactionCursor::new(batch_number + 1, 0);
+    }
+
+    Ok(BatchSegment {
+        batch_number,
+        is_sealed: false,
+        batch_commitment: None,
+        transaction_list,
+    })
+}
+
+fn raw_transaction_to_string(raw_transaction: RawTransaction) -> String {
+    match raw_transaction {
+        RawTransaction::Eth(EthRawTransaction(data)) => data,
+        RawTransaction::EthBundle(EthRawBundleTransaction(data)) => data,
+    }
+}
+
+fn get_last_valid_transaction_order(
+    can_provide_transaction_orders: &BTreeSet<u64>,
+    provided_transaction_order: i64,
+) -> i64 {
+    let mut last_valid_transaction_order = provided_transaction_order;
+
+    for &transaction_order in can_provide_transaction_orders {
+        let transaction_order = transaction_order as i64;
+
+        if transaction_order == last_valid_transaction_order + 1 {
+            last_valid_transaction_order += 1;
+        } else if transaction_order > last_valid_transaction_order {
+            break;
+        }
+    }
+
+    last_valid_transaction_order
+}
+
+/// Hands the transaction list to the registered MEV searchers and waits for
+/// their backrunning transactions.
+pub(super) async fn collect_backrunning_transactions(
+    context: &AppState,
+    rollup_id: &RollupId,
+    raw_transaction_list: Vec<String>,
+) -> Result<Vec<String>, RpcError> {
+    let shared_channel_infos = context.shared_channel_infos();
+    let mev_searcher_infos = MevSearcherInfos::get_or(MevSearcherInfos::default)?;
+
+    send_transaction_list_to_mev_searcher(
+        rollup_id,
+        raw_transaction_list,
+        shared_channel_infos,
+        &mev_searcher_infos,
+    );
+
+    let ip_list = mev_searcher_infos.get_ip_list_by_rollup_id(rollup_id);
+    let receivers: Vec<Arc<tokio::sync::Mutex<UnboundedReceiver<MevTargetTransaction>>>> = {
+        let map = shared_channel_infos.lock().map_err(|_| Error::MutexError)?;
+        ip_list
+            .iter()
+            .filter_map(|ip| map.get(ip).map(|(_, rx)| Arc::clone(rx)))
+            .collect()
+    };
+
+    let collected_mev_target_transaction = Arc::new(tokio::sync::Mutex::new(Vec::new()));
+    let mut sub_tasks = vec![];
+
+    for receiver in receivers {
+        let collected_clone = Arc::clone(&collected_mev_target_transaction);
+        let rx = Arc::clone(&receiver);
+
+        let sub_task = tokio::spawn(async move {
+            let deadline = Instant::now() + BACKRUNNING_TIMEOUT;
+
+            tokio::select! {
+                _ = tokio::time::sleep_until(deadline) => {}
+                maybe_mev_target_transaction = async {
+                    let mut guard = rx.lock().await;
+                    guard.recv().await
+                } => {
+                    if let Some(mev_target_transaction) = maybe_mev_target_transaction {
+                        tracing::info!("Received mev target transaction: {:?}", mev_target_transaction);
+                        collected_clone.lock().await.push(mev_target_transaction);
+                    }
+                }
+            }
+        });
+
+        sub_tasks.push(sub_task);
+    }
+
+    let _ = futures::future::join_all(sub_tasks).await;
+
+    let result = collected_mev_target_transaction.lock().await;
+    tracing::info!("Collected mev target transactions: {:?}", *result);
+
+    Ok(result
+        .iter()
+        .flat_map(|mev_target_transaction| {
+            mev_target_transaction.backrunning_transaction_list.clone()
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cursor_orders_by_batch_then_transaction() {
+        assert!(TransactionCursor::new(1, 0) > TransactionCursor::new(0, 100));
+        assert!(TransactionCursor::new(1, 5) > TransactionCursor::new(1, 4));
+        assert_eq!(TransactionCursor::new(2, 3), TransactionCursor::new(2, 3));
+    }
+
+    #[test]
+    fn acknowledged_cursor_is_the_next_unacknowledged_position() {
+        let rollup_metadata = RollupMetadata {
+            provided_batch_number: 3,
+            provided_transaction_order: -1,
+            ..RollupMetadata::default()
+        };
+        assert_eq!(
+            TransactionCursor::acknowledged(&rollup_metadata),
+            TransactionCursor::new(3, 0)
+        );
+
+        let rollup_metadata = RollupMetadata {
+            provided_batch_number: 3,
+            provided_transaction_order: 7,
+            ..RollupMetadata::default()
+        };
+        assert_eq!(
+            TransactionCursor::acknowledged(&rollup_metadata),
+            TransactionCursor::new(3, 8)
+        );
+    }
+
+    #[test]
+    fn unsealed_batch_is_only_read_up_to_the_first_gap() {
+        let can_provide_transaction_orders = BTreeSet::from([0, 1, 2, 4, 5]);
+
+        assert_eq!(
+            get_last_valid_transaction_order(&can_provide_transaction_orders, -1),
+            2
+        );
+        assert_eq!(
+            get_last_valid_transaction_order(&can_provide_transaction_orders, 3),
+            5
+        );
+        assert_eq!(get_last_valid_transaction_order(&BTreeSet::new(), 6), 6);
+    }
+}
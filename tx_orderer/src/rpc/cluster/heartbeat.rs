@@ -0,0 +1,85 @@
+use radius_sdk::json_rpc::server::ProcessPriority;
+
+use super::ClusterMessage;
+use crate::rpc::prelude::*;
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Heartbeat {
+    pub rollup_id: RollupId,
+}
+
+impl ClusterMessage for Heartbeat {
+    fn rollup_id(&self) -> &RollupId {
+        &self.rollup_id
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct HeartbeatResponse {
+    pub is_leader: bool,
+
+    pub batch_number: u64,
+    pub transaction_order: u64,
+
+    /// Whether the node passes its readiness check. Nodes that do not report
+    /// it are taken as ready.
+    #[serde(default = "is_ready_by_default")]
+    pub is_ready: bool,
+    /// Whether the node reaches the distributed key generation, which the
+    /// encrypted transactions depend on. Nodes that do not report it are taken
+    /// as healthy.
+    #[serde(default = "is_ready_by_default")]
+    pub is_distributed_key_generation_healthy: bool,
+    /// Whether the node is draining before it exits. The successor takes over
+    /// a leader that is shutting down without waiting for the failover
+    /// timeout.
+    #[serde(default)]
+    pub is_shutting_down: bool,
+}
+
+fn is_ready_by_default() -> bool {
+    true
+}
+
+impl RpcParameter<AppState> for Heartbeat {
+    type Response = HeartbeatResponse;
+
+    fn method() -> &'static str {
+        "heartbeat"
+    }
+
+    fn priority(&self) -> ProcessPriority {
+        ProcessPriority::High
+    }
+
+    async fn handler(self, context: AppState) -> Result<Self::Response, RpcError> {
+        let rollup = Rollup::get(&self.rollup_id).map_err(|error| {
+            tracing::error!("Failed to get rollup: {:?}", error);
+            Error::RollupNotFound
+        })?;
+
+        let cluster_metadata = ClusterMetadata::get(
+            rollup.platform,
+            rollup.liveness_service_provider,
+            &rollup.cluster_id,
+        )
+        .map_err(|error| {
+            tracing::error!("Failed to get cluster metadata: {:?}", error);
+            Error::ClusterMetadataNotFound
+        })?;
+
+        let rollup_metadata = RollupMetadata::get(&self.rollup_id)?;
+
+        Ok(HeartbeatResponse {
+            is_leader: cluster_metadata.is_leader,
+            batch_number: rollup_metadata.batch_number,
+            transaction_order: rollup_metadata.transaction_order,
+            is_ready: context.health_monitor().is_ready()
+                && !context.shutdown_manager().is_shutting_down(),
+            is_distributed_key_generation_healthy: context
+                .health_monitor()
+                .is_distributed_key_generation_healthy(),
+            is_shutting_down: context.shutdown_manager().is_shutting_down(),
+        })
+    }
+}
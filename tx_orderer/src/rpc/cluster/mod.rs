@@ -1,23 +1,45 @@
+mod acknowledge_transactions;
 mod add_mev_searcher_info;
+mod add_order_commitment_signature;
+mod attest_leader_failure;
+mod authenticated;
+mod change_leader_tx_orderer;
 mod create_batch;
+mod executor_message;
+mod get_batch_range;
 mod get_order_commitment_info;
 mod get_raw_transaction_list;
+mod get_transaction_range;
+mod get_transactions_since;
+mod heartbeat;
 mod remove_mev_searcher_info;
 mod set_leader_tx_orderer;
 mod set_max_gas_limit;
 mod sync_encrypted_transaction;
+mod sync_leader_failover;
 mod sync_leader_tx_orderer;
 mod sync_max_gas_limit;
 mod sync_raw_transaction;
 
+pub use acknowledge_transactions::*;
 pub use add_mev_searcher_info::*;
+pub use add_order_commitment_signature::*;
+pub use attest_leader_failure::*;
+pub use authenticated::*;
+pub use change_leader_tx_orderer::*;
 pub use create_batch::*;
+pub use executor_message::*;
+pub use get_batch_range::*;
 pub use get_order_commitment_info::*;
 pub use get_raw_transaction_list::*;
+pub use get_transaction_range::*;
+pub use get_transactions_since::*;
+pub use heartbeat::*;
 pub use remove_mev_searcher_info::*;
 pub use set_leader_tx_orderer::*;
 pub use set_max_gas_limit::*;
 pub use sync_encrypted_transaction::*;
+pub use sync_leader_failover::*;
 pub use sync_leader_tx_orderer::*;
 pub use sync_max_gas_limit::*;
 pub use sync_raw_transaction::*;
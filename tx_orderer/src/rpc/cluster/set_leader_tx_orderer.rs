@@ -31,6 +31,11 @@ impl RpcParameter<AppState> for SetLeaderTxOrderer {
         };
 
         let rollup = Rollup::get(&rollup_id)?;
+        self.leader_change_message.verify(
+            &rollup,
+            &self.rollup_signature,
+            context.cluster_message_replay_cache(),
+        )?;
 
         let cluster = Cluster::get(
             rollup.platform,
@@ -70,13 +75,10 @@ impl RpcParameter<AppState> for SetLeaderTxOrderer {
         mut_cluster_metadata.is_leader = is_next_leader;
         mut_cluster_metadata.leader_tx_orderer_rpc_info = Some(leader_tx_orderer_rpc_info.clone());
 
-        let signer = context.get_signer(rollup.platform).await?;
-        let current_tx_orderer_address = signer.address();
-
         sync_leader_tx_orderer(
             context.clone(),
             cluster,
-            current_tx_orderer_address,
+            &signer,
             self.leader_change_message.clone(),
             self.rollup_signature,
             rollup_metadata.batch_number,
@@ -88,6 +90,16 @@ impl RpcParameter<AppState> for SetLeaderTxOrderer {
 
         mut_cluster_metadata.update()?;
 
+        LeaderState::new(
+            self.leader_change_message.platform_block_height,
+            self.leader_change_message
+                .next_leader_tx_orderer_address
+                .clone(),
+            rollup_metadata.batch_number,
+            rollup_metadata.transaction_order,
+        )
+        .record(&rollup_id)?;
+
         Ok(())
     }
 }
@@ -1,12 +1,43 @@
-use crate::rpc::{
-    cluster::{SyncMaxGasLimit, SyncMaxGasLimitMessage},
-    prelude::*,
+use crate::{
+    rpc::{
+        cluster::{
+            verify_executor_message, Authenticated, ExecutorMessage, SyncMaxGasLimit,
+            SyncMaxGasLimitMessage,
+        },
+        prelude::*,
+    },
+    util::multicast,
 };
 
+/// Signed by an executor of the rollup.
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct SetMaxGasLimit {
+    pub message: SetMaxGasLimitMessage,
+    pub executor_signature: Signature,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct SetMaxGasLimitMessage {
     pub rollup_id: RollupId,
     pub max_gas_limit: u64,
+
+    /// Unix timestamp in milliseconds.
+    pub timestamp: u64,
+    pub nonce: u64,
+}
+
+impl ExecutorMessage for SetMaxGasLimitMessage {
+    fn rollup_id(&self) -> &RollupId {
+        &self.rollup_id
+    }
+
+    fn timestamp(&self) -> u64 {
+        self.timestamp
+    }
+
+    fn nonce(&self) -> u64 {
+        self.nonce
+    }
 }
 
 impl RpcParameter<AppState> for SetMaxGasLimit {
@@ -17,11 +48,20 @@ impl RpcParameter<AppState> for SetMaxGasLimit {
     }
 
     async fn handler(self, context: AppState) -> Result<Self::Response, RpcError> {
-        let mut locked_rollup = Rollup::get_mut(&self.rollup_id)?;
+        let message = &self.message;
+
+        let mut locked_rollup = Rollup::get_mut(&message.rollup_id)?;
         let platform = locked_rollup.platform;
         let service_provider = locked_rollup.liveness_service_provider;
+        verify_executor_message(
+            platform,
+            &locked_rollup.executor_address_list,
+            message,
+            &self.executor_signature,
+            context.cluster_message_replay_cache(),
+        )?;
 
-        let rollup_metadata = RollupMetadata::get(&self.rollup_id)?;
+        let rollup_metadata = RollupMetadata::get(&message.rollup_id)?;
         let cluster_metadata =
             ClusterMetadata::get(platform, service_provider, &rollup_metadata.cluster_id)?;
 
@@ -32,15 +72,15 @@ impl RpcParameter<AppState> for SetMaxGasLimit {
             cluster_metadata.platform_block_height,
         )?;
 
-        locked_rollup.max_gas_limit = self.max_gas_limit;
+        locked_rollup.max_gas_limit = message.max_gas_limit;
         locked_rollup.update()?;
 
         sync_set_max_gas_limit(
             cluster,
             context.clone(),
             platform,
-            self.rollup_id.clone(),
-            self.max_gas_limit,
+            message.rollup_id.clone(),
+            message.max_gas_limit,
         );
 
         Ok(())
@@ -55,7 +95,7 @@ pub fn sync_set_max_gas_limit(
     rollup_id: RollupId,
     max_gas_limit: u64,
 ) {
-    tokio::spawn(async move {
+    context.shutdown_manager().clone().spawn(async move {
         let other_cluster_rpc_url_list: Vec<String> = cluster.get_other_cluster_rpc_url_list();
 
         if !other_cluster_rpc_url_list.is_empty() {
@@ -63,23 +103,39 @@ pub fn sync_set_max_gas_limit(
                 rollup_id,
                 max_gas_limit,
             };
-            let signature = context
-                .get_signer(platform)
-                .await
-                .unwrap()
-                .sign_message(&message)
-                .unwrap();
-            let params = SyncMaxGasLimit { message, signature };
-
-            context
-                .rpc_client()
-                .fire_and_forget_multicast(
-                    other_cluster_rpc_url_list,
-                    SyncMaxGasLimit::method(),
-                    &params,
-                    Id::Null,
-                )
-                .await;
+            let signer = match context.get_signer(platform).await {
+                Ok(signer) => signer,
+                Err(error) => {
+                    tracing::error!("Failed to get signer: {:?}", error);
+                    return;
+                }
+            };
+            let signature = match signer.sign_message(&message).await {
+                Ok(signature) => signature,
+                Err(error) => {
+                    tracing::error!(
+                        "Failed to sign the max gas limit sync - rollup_id: {:?} / error: {:?}",
+                        message.rollup_id,
+                        error
+                    );
+                    return;
+                }
+            };
+            let params =
+                match Authenticated::new(&signer, SyncMaxGasLimit { message, signature }).await {
+                    Ok(params) => params,
+                    Err(error) => {
+                        tracing::error!("Failed to sign the max gas limit sync: {:?}", error);
+                        return;
+                    }
+                };
+
+            multicast(
+                &context,
+                other_cluster_rpc_url_list,
+                SyncMaxGasLimit::method(),
+                params,
+            );
         }
     });
 }
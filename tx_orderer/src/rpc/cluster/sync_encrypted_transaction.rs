@@ -1,5 +1,6 @@
 use std::time::{SystemTime, UNIX_EPOCH};
 
+use super::{co_sign_order_commitment, ClusterMessage};
 use crate::rpc::prelude::*;
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
@@ -13,6 +14,12 @@ pub struct SyncEncryptedTransaction {
     pub order_commitment: OrderCommitment,
 }
 
+impl ClusterMessage for SyncEncryptedTransaction {
+    fn rollup_id(&self) -> &RollupId {
+        &self.rollup_id
+    }
+}
+
 impl RpcParameter<AppState> for SyncEncryptedTransaction {
     type Response = ();
 
@@ -72,6 +79,22 @@ impl RpcParameter<AppState> for SyncEncryptedTransaction {
             return Err(Error::InvalidOrderCommitment.into());
         }
 
+        if let OrderCommitment::Single(SingleOrderCommitment::Sign(sign_order_commitment)) =
+            &self.order_commitment
+        {
+            if let Some(equivocation_evidence) = EquivocationEvidence::check_order_commitment(
+                rollup.platform.into(),
+                sign_order_commitment,
+            )? {
+                tracing::error!(
+                    "Equivocation detected - rollup_id: {:?} / evidence: {:?}",
+                    rollup_id,
+                    equivocation_evidence
+                );
+                return Err(Error::Equivocation.into());
+            }
+        }
+
         let transaction_hash = self.encrypted_transaction.raw_transaction_hash();
 
         EncryptedTransactionModel::put_with_transaction_hash(
@@ -95,12 +118,31 @@ impl RpcParameter<AppState> for SyncEncryptedTransaction {
             Error::Database(error)
         })?;
 
+        TransactionStatusInfo::new(self.batch_number, self.transaction_order, true)
+            .put(&rollup_id, transaction_hash.as_ref())
+            .map_err(|error| {
+                tracing::error!("Failed to put transaction status info: {:?}", error);
+                Error::Database(error)
+            })?;
+
         self.order_commitment
             .put(&rollup_id, self.batch_number, self.transaction_order)
             .map_err(|error| {
                 tracing::error!("Failed to put order commitment: {:?}", error);
                 Error::Database(error)
             })?;
+        context.subscription_manager().publish_order_commitment(
+            &rollup_id,
+            self.batch_number,
+            self.transaction_order,
+            &self.order_commitment,
+        );
+        co_sign_order_commitment(
+            context.clone(),
+            &rollup,
+            &cluster_metadata,
+            &self.order_commitment,
+        );
 
         let _ = context
             .decryptor()
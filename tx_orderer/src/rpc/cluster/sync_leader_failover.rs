@@ -0,0 +1,168 @@
+use radius_sdk::{json_rpc::server::ProcessPriority, signature::Address};
+
+use super::{ClusterMessage, LeaderFailure};
+use crate::{rpc::prelude::*, task::is_attested_by_quorum};
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct LeaderFailoverMessage {
+    pub rollup_id: RollupId,
+    pub platform_block_height: u64,
+
+    pub failed_leader_tx_orderer_address: Address,
+    pub next_leader_tx_orderer_address: Address,
+
+    pub batch_number: u64,
+    pub transaction_order: u64,
+
+    /// The expiry of the attested [`LeaderFailure`].
+    pub expires_at: u64,
+}
+
+impl LeaderFailoverMessage {
+    pub fn leader_failure(&self) -> LeaderFailure {
+        LeaderFailure {
+            rollup_id: self.rollup_id.clone(),
+            platform_block_height: self.platform_block_height,
+            failed_leader_tx_orderer_address: self.failed_leader_tx_orderer_address.clone(),
+            batch_number: self.batch_number,
+            transaction_order: self.transaction_order,
+            expires_at: self.expires_at,
+        }
+    }
+}
+
+/// Announces that the next tx_orderer in the cluster order has taken over from
+/// a leader that stopped responding to heartbeats.
+///
+/// A receiver only accepts it if its own heartbeats show the leader as failed,
+/// or if a majority of the other tx_orderers attested the failure at the same
+/// position before it expired, so a successor that is only cut off from the
+/// leader cannot take over.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct SyncLeaderFailover {
+    pub leader_failover_message: LeaderFailoverMessage,
+    pub next_leader_signature: Signature,
+    /// Signatures of [`LeaderFailure`] by the tx_orderers that saw the leader
+    /// fail, the successor included.
+    #[serde(default)]
+    pub attestation_list: Vec<Signature>,
+}
+
+impl ClusterMessage for SyncLeaderFailover {
+    fn rollup_id(&self) -> &RollupId {
+        &self.leader_failover_message.rollup_id
+    }
+}
+
+impl RpcParameter<AppState> for SyncLeaderFailover {
+    type Response = ();
+
+    fn method() -> &'static str {
+        "sync_leader_failover"
+    }
+
+    fn priority(&self) -> ProcessPriority {
+        ProcessPriority::High
+    }
+
+    async fn handler(self, context: AppState) -> Result<Self::Response, RpcError> {
+        let message = &self.leader_failover_message;
+        let rollup_id = &message.rollup_id;
+
+        let rollup = Rollup::get(rollup_id).map_err(|error| {
+            tracing::error!("Failed to get rollup: {:?}", error);
+            Error::RollupNotFound
+        })?;
+
+        let cluster = Cluster::get(
+            rollup.platform,
+            rollup.liveness_service_provider,
+            &rollup.cluster_id,
+            message.platform_block_height,
+        )?;
+
+        let signer_address = self
+            .next_leader_signature
+            .get_signer_address(rollup.platform.into(), message)
+            .map_err(Error::Signature)?;
+        if signer_address != message.next_leader_tx_orderer_address {
+            return Err(Error::InvalidSignature.into());
+        }
+
+        let next_leader_tx_orderer_rpc_info = cluster
+            .get_next_tx_orderer_rpc_info(&message.failed_leader_tx_orderer_address)
+            .filter(|tx_orderer_rpc_info| {
+                tx_orderer_rpc_info.tx_orderer_address == message.next_leader_tx_orderer_address
+            })
+            .ok_or(Error::InvalidLeaderFailover)?;
+
+        let is_failure_confirmed = context
+            .leader_health_tracker()
+            .has_failed(rollup_id, &message.failed_leader_tx_orderer_address)
+            || (message.leader_failure().is_valid_at(current_timestamp())
+                && is_attested_by_quorum(
+                    rollup.platform,
+                    &cluster.get_tx_orderer_address_list(),
+                    &message.leader_failure(),
+                    &self.attestation_list,
+                ));
+        if !is_failure_confirmed {
+            tracing::warn!(
+                "Leader failover - failure not confirmed - rollup_id: {:?} / failed_leader: {:?} / next_leader: {:?}",
+                rollup_id,
+                message.failed_leader_tx_orderer_address,
+                message.next_leader_tx_orderer_address
+            );
+            return Err(Error::InvalidLeaderFailover.into());
+        }
+
+        // Only a failover from the leader this node knows about is accepted.
+        if let Ok(leader_state) = LeaderState::get(rollup_id) {
+            if leader_state.leader_tx_orderer_address != message.failed_leader_tx_orderer_address
+                && leader_state.leader_tx_orderer_address != message.next_leader_tx_orderer_address
+            {
+                return Err(Error::InvalidLeaderFailover.into());
+            }
+        }
+
+        let signer = context.get_signer(rollup.platform).await.map_err(|_| {
+            tracing::error!("Signer not found for platform {:?}", rollup.platform);
+            Error::SignerNotFound
+        })?;
+
+        let mut mut_cluster_metadata = ClusterMetadata::get_mut(
+            rollup.platform,
+            rollup.liveness_service_provider,
+            &rollup.cluster_id,
+        )?;
+        mut_cluster_metadata.platform_block_height = message.platform_block_height;
+        mut_cluster_metadata.is_leader =
+            signer.address() == &message.next_leader_tx_orderer_address;
+        mut_cluster_metadata.leader_tx_orderer_rpc_info = Some(next_leader_tx_orderer_rpc_info);
+        mut_cluster_metadata.update()?;
+
+        let mut mut_rollup_metadata = RollupMetadata::get_mut(rollup_id)?;
+        mut_rollup_metadata.batch_number = message.batch_number;
+        mut_rollup_metadata.transaction_order = message.transaction_order;
+        mut_rollup_metadata.update()?;
+
+        LeaderState::new(
+            message.platform_block_height,
+            message.next_leader_tx_orderer_address.clone(),
+            message.batch_number,
+            message.transaction_order,
+        )
+        .record(rollup_id)?;
+
+        context.leader_health_tracker().reset(rollup_id);
+
+        tracing::warn!(
+            "Leader failover - rollup_id: {:?} / failed_leader: {:?} / next_leader: {:?}",
+            rollup_id,
+            message.failed_leader_tx_orderer_address,
+            message.next_leader_tx_orderer_address
+        );
+
+        Ok(())
+    }
+}
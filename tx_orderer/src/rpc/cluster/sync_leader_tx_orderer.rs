@@ -2,8 +2,8 @@ use std::time::{SystemTime, UNIX_EPOCH};
 
 use radius_sdk::json_rpc::server::ProcessPriority;
 
-use super::LeaderChangeMessage;
-use crate::rpc::prelude::*;
+use super::{ClusterMessage, LeaderChangeMessage};
+use crate::{rpc::prelude::*, task::backfill_rollup};
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct SyncLeaderTxOrderer {
@@ -17,6 +17,12 @@ pub struct SyncLeaderTxOrderer {
     pub provided_transaction_order: i64,
 }
 
+impl ClusterMessage for SyncLeaderTxOrderer {
+    fn rollup_id(&self) -> &RollupId {
+        &self.leader_change_message.rollup_id
+    }
+}
+
 impl RpcParameter<AppState> for SyncLeaderTxOrderer {
     type Response = ();
 
@@ -66,6 +72,26 @@ impl RpcParameter<AppState> for SyncLeaderTxOrderer {
                 Error::TxOrdererInfoNotFound
             })?;
 
+        // Catch up on whatever the previous leader ordered before taking over.
+        if is_leader {
+            if let Err(error) = backfill_rollup(
+                &context,
+                &rollup,
+                &cluster,
+                self.provided_batch_number,
+                self.batch_number,
+                self.transaction_order,
+            )
+            .await
+            {
+                tracing::warn!(
+                    "Failed to backfill before becoming leader - rollup_id: {:?} / error: {:?}",
+                    rollup_id,
+                    error
+                );
+            }
+        }
+
         let mut mut_cluster_metadata = ClusterMetadata::get_mut(
             rollup.platform,
             rollup.liveness_service_provider,
@@ -87,6 +113,16 @@ impl RpcParameter<AppState> for SyncLeaderTxOrderer {
 
         mut_rollup_metadata.update()?;
 
+        LeaderState::new(
+            self.leader_change_message.platform_block_height,
+            self.leader_change_message
+                .next_leader_tx_orderer_address
+                .clone(),
+            self.batch_number,
+            self.transaction_order,
+        )
+        .record(&rollup_id)?;
+
         let end_sync_leader_tx_orderer_time = SystemTime::now()
             .duration_since(UNIX_EPOCH)
             .expect("Time went backwards")
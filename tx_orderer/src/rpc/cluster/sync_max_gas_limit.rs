@@ -1,3 +1,4 @@
+use super::ClusterMessage;
 use crate::rpc::prelude::*;
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
@@ -6,6 +7,12 @@ pub struct SyncMaxGasLimit {
     pub signature: Signature,
 }
 
+impl ClusterMessage for SyncMaxGasLimit {
+    fn rollup_id(&self) -> &RollupId {
+        &self.message.rollup_id
+    }
+}
+
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct SyncMaxGasLimitMessage {
     pub rollup_id: RollupId,
@@ -1,5 +1,6 @@
 use std::time::{SystemTime, UNIX_EPOCH};
 
+use super::{co_sign_order_commitment, ClusterMessage};
 use crate::rpc::prelude::*;
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
@@ -15,6 +16,12 @@ pub struct SyncRawTransaction {
     pub is_direct_sent: bool,
 }
 
+impl ClusterMessage for SyncRawTransaction {
+    fn rollup_id(&self) -> &RollupId {
+        &self.rollup_id
+    }
+}
+
 impl RpcParameter<AppState> for SyncRawTransaction {
     type Response = ();
 
@@ -22,7 +29,7 @@ impl RpcParameter<AppState> for SyncRawTransaction {
         "sync_raw_transaction"
     }
 
-    async fn handler(self, _context: AppState) -> Result<Self::Response, RpcError> {
+    async fn handler(self, context: AppState) -> Result<Self::Response, RpcError> {
         let start_sync_raw_transaction_time = SystemTime::now()
             .duration_since(UNIX_EPOCH)
             .expect("Time went backwards")
@@ -77,6 +84,22 @@ impl RpcParameter<AppState> for SyncRawTransaction {
             return Err(Error::InvalidOrderCommitment.into());
         }
 
+        if let OrderCommitment::Single(SingleOrderCommitment::Sign(sign_order_commitment)) =
+            &self.order_commitment
+        {
+            if let Some(equivocation_evidence) = EquivocationEvidence::check_order_commitment(
+                rollup.platform.into(),
+                sign_order_commitment,
+            )? {
+                tracing::error!(
+                    "Equivocation detected - rollup_id: {:?} / evidence: {:?}",
+                    rollup_id,
+                    equivocation_evidence
+                );
+                return Err(Error::Equivocation.into());
+            }
+        }
+
         let transaction_hash = self.raw_transaction.raw_transaction_hash();
 
         RawTransactionModel::put_with_transaction_hash(
@@ -102,12 +125,31 @@ impl RpcParameter<AppState> for SyncRawTransaction {
             Error::Database(error)
         })?;
 
+        TransactionStatusInfo::new(self.batch_number, self.transaction_order, false)
+            .put(&rollup_id, transaction_hash.as_ref())
+            .map_err(|error| {
+                tracing::error!("Failed to put transaction status info: {:?}", error);
+                Error::Database(error)
+            })?;
+
         self.order_commitment
             .put(&rollup_id, self.batch_number, self.transaction_order)
             .map_err(|error| {
                 tracing::error!("Failed to put order commitment: {:?}", error);
                 Error::Database(error)
             })?;
+        context.subscription_manager().publish_order_commitment(
+            &rollup_id,
+            self.batch_number,
+            self.transaction_order,
+            &self.order_commitment,
+        );
+        co_sign_order_commitment(
+            context.clone(),
+            &rollup,
+            &cluster_metadata,
+            &self.order_commitment,
+        );
 
         CanProvideTransactionInfo::add_can_provide_transaction_orders(
             &rollup_id,
@@ -0,0 +1,34 @@
+use crate::{rpc::prelude::*, task::CensorshipMetrics};
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct GetCensorshipViolationList {
+    pub rollup_id: RollupId,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct GetCensorshipViolationListResponse {
+    pub censorship_violation_list: Vec<CensorshipViolation>,
+    pub pending_transaction_count: usize,
+    pub metrics: CensorshipMetrics,
+}
+
+impl RpcParameter<AppState> for GetCensorshipViolationList {
+    type Response = GetCensorshipViolationListResponse;
+
+    fn method() -> &'static str {
+        "get_censorship_violation_list"
+    }
+
+    async fn handler(self, context: AppState) -> Result<Self::Response, RpcError> {
+        let censorship_violation_list =
+            CensorshipViolationList::get_or(&self.rollup_id, CensorshipViolationList::default)?;
+        let forwarded_transaction_list =
+            ForwardedTransactionList::get_or(&self.rollup_id, ForwardedTransactionList::default)?;
+
+        Ok(GetCensorshipViolationListResponse {
+            censorship_violation_list: censorship_violation_list.into_inner(),
+            pending_transaction_count: forwarded_transaction_list.len(),
+            metrics: context.censorship_monitor().metrics(),
+        })
+    }
+}
@@ -0,0 +1,28 @@
+use crate::rpc::prelude::*;
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct GetEquivocationEvidenceList {
+    pub rollup_id: RollupId,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct GetEquivocationEvidenceListResponse {
+    pub equivocation_evidence_list: Vec<EquivocationEvidence>,
+}
+
+impl RpcParameter<AppState> for GetEquivocationEvidenceList {
+    type Response = GetEquivocationEvidenceListResponse;
+
+    fn method() -> &'static str {
+        "get_equivocation_evidence_list"
+    }
+
+    async fn handler(self, _context: AppState) -> Result<Self::Response, RpcError> {
+        let equivocation_evidence_list =
+            EquivocationEvidenceList::get_or(&self.rollup_id, EquivocationEvidenceList::default)?;
+
+        Ok(GetEquivocationEvidenceListResponse {
+            equivocation_evidence_list: equivocation_evidence_list.into_inner(),
+        })
+    }
+}
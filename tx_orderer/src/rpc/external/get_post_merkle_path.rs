@@ -15,7 +15,6 @@ impl RpcParameter<AppState> for GetPostMerklePath {
     }
 
     async fn handler(self, context: AppState) -> Result<Self::Response, RpcError> {
-        let merkle_tree = MerkleTree::new();
         let rpc_client = context.rpc_client();
 
         let rollup = Rollup::get(&self.rollup_id)?;
@@ -41,13 +40,12 @@ impl RpcParameter<AppState> for GetPostMerklePath {
         )
         .await?;
 
-        for (raw_transaction, _) in &raw_transaction_info_list {
-            merkle_tree
-                .add_data(raw_transaction.raw_transaction_hash().as_ref())
-                .await;
-        }
-
-        merkle_tree.finalize_tree().await;
+        let merkle_tree = MerkleTree::from_raw_transaction_list(
+            raw_transaction_info_list
+                .iter()
+                .map(|(raw_transaction, _)| raw_transaction),
+        )
+        .await;
 
         let post_merkle_path = merkle_tree
             .get_post_merkle_path(self.transaction_order)
@@ -0,0 +1,34 @@
+use crate::rpc::prelude::*;
+
+/// Returns the quorum order commitment of a transaction, which replaces the
+/// leader-signed order commitment once `is_certified` is set.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct GetQuorumOrderCommitment {
+    pub rollup_id: RollupId,
+    pub batch_number: u64,
+    pub transaction_order: u64,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct GetQuorumOrderCommitmentResponse {
+    pub quorum_order_commitment: QuorumOrderCommitment,
+    pub is_certified: bool,
+}
+
+impl RpcParameter<AppState> for GetQuorumOrderCommitment {
+    type Response = GetQuorumOrderCommitmentResponse;
+
+    fn method() -> &'static str {
+        "get_quorum_order_commitment"
+    }
+
+    async fn handler(self, _context: AppState) -> Result<Self::Response, RpcError> {
+        let quorum_order_commitment =
+            QuorumOrderCommitment::get(&self.rollup_id, self.batch_number, self.transaction_order)?;
+
+        Ok(GetQuorumOrderCommitmentResponse {
+            is_certified: quorum_order_commitment.is_certified(),
+            quorum_order_commitment,
+        })
+    }
+}
@@ -0,0 +1,190 @@
+use crate::rpc::prelude::*;
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct GetTransactionStatus {
+    pub rollup_id: RollupId,
+    pub transaction_hash: String,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct GetTransactionStatusResponse {
+    pub status: TransactionStatus,
+
+    pub batch_number: u64,
+    pub transaction_order: u64,
+
+    pub ordered_at: u64,
+    pub decrypted_at: Option<u64>,
+    pub batch_commitment_submitted_at: Option<u64>,
+    pub task_responded_at: Option<u64>,
+}
+
+impl RpcParameter<AppState> for GetTransactionStatus {
+    type Response = GetTransactionStatusResponse;
+
+    fn method() -> &'static str {
+        "get_transaction_status"
+    }
+
+    async fn handler(self, _context: AppState) -> Result<Self::Response, RpcError> {
+        let transaction_hash = RawTransactionHash::normalize(&self.transaction_hash);
+        let transaction_status_info =
+            TransactionStatusInfo::get(&self.rollup_id, transaction_hash.as_ref()).map_err(
+                |error| {
+                    tracing::error!(
+                        "Failed to get transaction status info - rollup_id: {:?} / transaction_hash: {:?} / error: {:?}",
+                        self.rollup_id,
+                        transaction_hash,
+                        error
+                    );
+                    Error::TransactionNotFound
+                },
+            )?;
+
+        let batch_number = transaction_status_info.batch_number;
+
+        let is_batch_sealed = Batch::get(&self.rollup_id, batch_number).is_ok();
+        let batch_commitment_submission = if is_batch_sealed {
+            BatchCommitmentSubmission::get(&self.rollup_id, batch_number).ok()
+        } else {
+            None
+        };
+
+        let is_decrypted = transaction_status_info.decrypted_at.is_some()
+            || CanProvideTransactionInfo::get(&self.rollup_id)
+                .ok()
+                .and_then(|can_provide_transaction_info| {
+                    can_provide_transaction_info
+                        .can_provide_transaction_orders_per_batch
+                        .get(&batch_number)
+                        .map(|transaction_orders| {
+                            transaction_orders.contains(&transaction_status_info.transaction_order)
+                        })
+                })
+                .unwrap_or(false)
+            || RawTransactionModel::get_with_transaction_hash(
+                &self.rollup_id,
+                transaction_hash.as_ref(),
+            )
+            .is_ok();
+
+        let status = transaction_status(
+            &transaction_status_info,
+            is_batch_sealed,
+            batch_commitment_submission.as_ref(),
+            is_decrypted,
+        );
+
+        Ok(GetTransactionStatusResponse {
+            status,
+            batch_number,
+            transaction_order: transaction_status_info.transaction_order,
+            ordered_at: transaction_status_info.ordered_at,
+            decrypted_at: transaction_status_info.decrypted_at,
+            batch_commitment_submitted_at: batch_commitment_submission
+                .as_ref()
+                .and_then(|submission| submission.submitted_at),
+            task_responded_at: batch_commitment_submission
+                .as_ref()
+                .and_then(|submission| submission.task_responded_at),
+        })
+    }
+}
+
+/// The latest stage the transaction reached.
+fn transaction_status(
+    transaction_status_info: &TransactionStatusInfo,
+    is_batch_sealed: bool,
+    batch_commitment_submission: Option<&BatchCommitmentSubmission>,
+    is_decrypted: bool,
+) -> TransactionStatus {
+    if transaction_status_info.is_invalid {
+        TransactionStatus::Invalid
+    } else if let Some(batch_commitment_submission) = batch_commitment_submission {
+        if batch_commitment_submission.task_responded_at.is_some() {
+            TransactionStatus::TaskResponded
+        } else if batch_commitment_submission.submitted_at.is_some() {
+            TransactionStatus::BatchCommitmentSubmitted
+        } else {
+            TransactionStatus::InSealedBatch
+        }
+    } else if is_batch_sealed {
+        TransactionStatus::InSealedBatch
+    } else if !transaction_status_info.is_encrypted {
+        TransactionStatus::Ordered
+    } else if is_decrypted {
+        TransactionStatus::Decrypted
+    } else {
+        TransactionStatus::DecryptionPending
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encrypted_transaction_goes_through_every_stage() {
+        let transaction_status_info = TransactionStatusInfo::new(1, 0, true);
+        let mut batch_commitment_submission = BatchCommitmentSubmission::default();
+
+        let status_list = [
+            transaction_status(&transaction_status_info, false, None, false),
+            transaction_status(&transaction_status_info, false, None, true),
+            transaction_status(&transaction_status_info, true, None, true),
+            transaction_status(
+                &transaction_status_info,
+                true,
+                Some(&batch_commitment_submission),
+                true,
+            ),
+        ];
+        assert!(matches!(
+            status_list,
+            [
+                TransactionStatus::DecryptionPending,
+                TransactionStatus::Decrypted,
+                TransactionStatus::InSealedBatch,
+                TransactionStatus::InSealedBatch,
+            ]
+        ));
+
+        batch_commitment_submission.submitted_at = Some(1);
+        assert!(matches!(
+            transaction_status(
+                &transaction_status_info,
+                true,
+                Some(&batch_commitment_submission),
+                true,
+            ),
+            TransactionStatus::BatchCommitmentSubmitted
+        ));
+
+        batch_commitment_submission.task_responded_at = Some(2);
+        assert!(matches!(
+            transaction_status(
+                &transaction_status_info,
+                true,
+                Some(&batch_commitment_submission),
+                true,
+            ),
+            TransactionStatus::TaskResponded
+        ));
+    }
+
+    #[test]
+    fn raw_transaction_is_ordered_until_its_batch_is_sealed() {
+        let transaction_status_info = TransactionStatusInfo::new(1, 0, false);
+        assert!(matches!(
+            transaction_status(&transaction_status_info, false, None, false),
+            TransactionStatus::Ordered
+        ));
+
+        let mut invalid_transaction_status_info = TransactionStatusInfo::new(1, 1, true);
+        invalid_transaction_status_info.is_invalid = true;
+        assert!(matches!(
+            transaction_status(&invalid_transaction_status_info, true, None, false),
+            TransactionStatus::Invalid
+        ));
+    }
+}
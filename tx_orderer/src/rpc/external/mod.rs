@@ -1,33 +1,45 @@
 mod get_batch;
 mod get_can_provide_transaction_info;
+mod get_censorship_violation_list;
 mod get_cluster_metadata;
 mod get_encrypted_transaction_list;
 mod get_encrypted_transaction_with_order_commitment;
 mod get_encrypted_transaction_with_transaction_hash;
+mod get_equivocation_evidence_list;
 mod get_order_commitment;
 mod get_post_merkle_path;
+mod get_quorum_order_commitment;
 mod get_raw_transaction_list;
 mod get_raw_transaction_with_order_commitment;
 mod get_raw_transaction_with_transaction_hash;
 mod get_rollup;
 mod get_rollup_metadata;
+mod get_transaction_status;
 mod get_version;
+mod report_order_commitment;
 mod send_encrypted_transaction;
 mod send_raw_transaction;
+mod verify_order_commitment;
 
 pub use get_batch::*;
 pub use get_can_provide_transaction_info::*;
+pub use get_censorship_violation_list::*;
 pub use get_cluster_metadata::*;
 pub use get_encrypted_transaction_list::*;
 pub use get_encrypted_transaction_with_order_commitment::*;
 pub use get_encrypted_transaction_with_transaction_hash::*;
+pub use get_equivocation_evidence_list::*;
 pub use get_order_commitment::*;
 pub use get_post_merkle_path::*;
+pub use get_quorum_order_commitment::*;
 pub use get_raw_transaction_list::*;
 pub use get_raw_transaction_with_order_commitment::*;
 pub use get_raw_transaction_with_transaction_hash::*;
 pub use get_rollup::*;
 pub use get_rollup_metadata::*;
+pub use get_transaction_status::*;
 pub use get_version::*;
+pub use report_order_commitment::*;
 pub use send_encrypted_transaction::*;
 pub use send_raw_transaction::*;
+pub use verify_order_commitment::*;
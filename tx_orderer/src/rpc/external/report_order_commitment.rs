@@ -0,0 +1,95 @@
+use crate::rpc::prelude::*;
+
+/// Lets a user compare a signed order commitment they received with the one
+/// this node has stored for the same position.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct ReportOrderCommitment {
+    pub order_commitment: SignOrderCommitment,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum OrderCommitmentReportResult {
+    Consistent,
+    Equivocation,
+    Unknown,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct ReportOrderCommitmentResponse {
+    pub result: OrderCommitmentReportResult,
+    pub equivocation_evidence: Option<EquivocationEvidence>,
+}
+
+impl RpcParameter<AppState> for ReportOrderCommitment {
+    type Response = ReportOrderCommitmentResponse;
+
+    fn method() -> &'static str {
+        "report_order_commitment"
+    }
+
+    async fn handler(self, _context: AppState) -> Result<Self::Response, RpcError> {
+        let data = &self.order_commitment.data;
+        let rollup = Rollup::get(&data.rollup_id).map_err(|error| {
+            tracing::error!("Failed to get rollup: {:?}", error);
+            Error::RollupNotFound
+        })?;
+
+        let cluster_metadata = ClusterMetadata::get(
+            rollup.platform,
+            rollup.liveness_service_provider,
+            &rollup.cluster_id,
+        )
+        .map_err(|error| {
+            tracing::error!("Failed to get cluster metadata: {:?}", error);
+            Error::ClusterMetadataNotFound
+        })?;
+        let cluster = Cluster::get(
+            rollup.platform,
+            rollup.liveness_service_provider,
+            &rollup.cluster_id,
+            cluster_metadata.platform_block_height,
+        )?;
+
+        let signer_address = self
+            .order_commitment
+            .get_signer_address(rollup.platform.into());
+        if !cluster
+            .get_tx_orderer_address_list()
+            .contains(&signer_address)
+        {
+            return Err(Error::InvalidOrderCommitment.into());
+        }
+
+        if let Some(equivocation_evidence) = EquivocationEvidence::check_order_commitment(
+            rollup.platform.into(),
+            &self.order_commitment,
+        )? {
+            tracing::error!(
+                "Equivocation reported - rollup_id: {:?} / evidence: {:?}",
+                data.rollup_id,
+                equivocation_evidence
+            );
+
+            return Ok(ReportOrderCommitmentResponse {
+                result: OrderCommitmentReportResult::Equivocation,
+                equivocation_evidence: Some(equivocation_evidence),
+            });
+        }
+
+        let result = match OrderCommitment::get(
+            &data.rollup_id,
+            data.batch_number,
+            data.transaction_order,
+        ) {
+            Ok(_) => OrderCommitmentReportResult::Consistent,
+            Err(error) if error.is_none_type() => OrderCommitmentReportResult::Unknown,
+            Err(error) => return Err(error.into()),
+        };
+
+        Ok(ReportOrderCommitmentResponse {
+            result,
+            equivocation_evidence: None,
+        })
+    }
+}
@@ -1,13 +1,25 @@
+use tracing::Instrument;
+
 use crate::{
-    rpc::{cluster::SyncEncryptedTransaction, prelude::*},
-    task::finalize_batch,
+    rpc::{
+        cluster::{Authenticated, SyncEncryptedTransaction},
+        prelude::*,
+    },
+    task::{
+        finalize_batch, forward_to_leader, is_watched_forward_result, watch_forwarded_transaction,
+    },
+    telemetry::TraceContext,
     types::*,
+    util::multicast,
 };
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct SendEncryptedTransaction {
     pub rollup_id: RollupId,
     pub encrypted_transaction: EncryptedTransaction,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trace_context: Option<TraceContext>,
 }
 
 impl RpcParameter<AppState> for SendEncryptedTransaction {
@@ -17,7 +29,34 @@ impl RpcParameter<AppState> for SendEncryptedTransaction {
         "send_encrypted_transaction"
     }
 
-    async fn handler(self, context: AppState) -> Result<Self::Response, RpcError> {
+    async fn handler(mut self, context: AppState) -> Result<Self::Response, RpcError> {
+        let span = tracing::info_span!(
+            "send_encrypted_transaction",
+            rollup_id = %self.rollup_id,
+            transaction_hash = %self.encrypted_transaction.raw_transaction_hash().as_string(),
+        );
+        if let Some(trace_context) = &self.trace_context {
+            trace_context.attach(&span);
+        }
+        // A follower forwards the transaction to the leader in this span.
+        self.trace_context = TraceContext::from_span(&span);
+
+        self.handle(context).instrument(span).await
+    }
+}
+
+impl SendEncryptedTransaction {
+    async fn handle(self, context: AppState) -> Result<OrderCommitment, RpcError> {
+        if context.shutdown_manager().is_shutting_down() {
+            return Err(Error::ShuttingDown.into());
+        }
+
+        // A rollup that is being deregistered is still stored until its data
+        // is archived or purged, but no longer takes transactions.
+        if !RollupIdList::get_or(RollupIdList::default)?.contains(&self.rollup_id) {
+            return Err(Error::RollupNotFound.into());
+        }
+
         let rollup = Rollup::get(&self.rollup_id)?;
 
         // 1. Check supported encrypted transaction
@@ -46,6 +85,9 @@ impl RpcParameter<AppState> for SendEncryptedTransaction {
             let is_updated = mut_rollup_metadata.check_and_update_batch_info();
 
             mut_rollup_metadata.update()?;
+            context
+                .metrics()
+                .record_ordered_transaction(&self.rollup_id, "encrypted");
 
             if is_updated {
                 context
@@ -69,6 +111,9 @@ impl RpcParameter<AppState> for SendEncryptedTransaction {
                 &self.encrypted_transaction,
             )?;
 
+            TransactionStatusInfo::new(batch_number, transaction_order, true)
+                .put(&self.rollup_id, transaction_hash.as_ref())?;
+
             let merkle_tree = context.merkle_tree_manager().get(&self.rollup_id).await?;
             let (_, pre_merkle_path) = merkle_tree.add_data(transaction_hash.as_ref()).await;
 
@@ -84,6 +129,12 @@ impl RpcParameter<AppState> for SendEncryptedTransaction {
             )
             .await?;
             order_commitment.put(&self.rollup_id, batch_number, transaction_order)?;
+            context.subscription_manager().publish_order_commitment(
+                &self.rollup_id,
+                batch_number,
+                transaction_order,
+                &order_commitment,
+            );
 
             sync_encrypted_transaction(
                 context.clone(),
@@ -112,31 +163,25 @@ impl RpcParameter<AppState> for SendEncryptedTransaction {
         } else {
             drop(mut_rollup_metadata);
 
-            match cluster_metadata.leader_tx_orderer_rpc_info {
-                Some(leader_tx_orderer_rpc_info) => {
-                    let leader_external_rpc_url = leader_tx_orderer_rpc_info
-                        .external_rpc_url
-                        .clone()
-                        .ok_or(Error::EmptyLeaderClusterRpcUrl)?;
-
-                    match context
-                        .rpc_client()
-                        .request(
-                            leader_external_rpc_url,
-                            SendEncryptedTransaction::method(),
-                            &self,
-                            Id::Null,
-                        )
-                        .await
-                    {
-                        Ok(response) => Ok(response),
-                        Err(error) => Err(error.into()),
-                    }
-                }
-                None => {
-                    return Err(Error::EmptyLeader)?;
-                }
+            let result = forward_to_leader(
+                &context,
+                &rollup,
+                SendEncryptedTransaction::method(),
+                &self,
+                &self.encrypted_transaction.raw_transaction_hash(),
+            )
+            .await;
+
+            if is_watched_forward_result(&result) {
+                watch_forwarded_transaction(
+                    &context,
+                    &rollup,
+                    &self.encrypted_transaction.raw_transaction_hash(),
+                    ForwardedTransactionPayload::Encrypted(self.encrypted_transaction),
+                );
             }
+
+            Ok(result?)
         }
     }
 }
@@ -171,7 +216,7 @@ pub fn sync_encrypted_transaction(
     encrypted_transaction: EncryptedTransaction,
     order_commitment: OrderCommitment,
 ) {
-    tokio::spawn(async move {
+    context.shutdown_manager().clone().spawn(async move {
         let cluster = Cluster::get(
             platform,
             liveness_service_provider,
@@ -185,23 +230,31 @@ pub fn sync_encrypted_transaction(
             return;
         }
 
-        let sync_encypted_transaction = SyncEncryptedTransaction {
-            rollup_id,
-            batch_number,
-            transaction_order,
-            encrypted_transaction,
-            order_commitment,
+        let sync_encypted_transaction = match Authenticated::sign(
+            &context,
+            SyncEncryptedTransaction {
+                rollup_id,
+                batch_number,
+                transaction_order,
+                encrypted_transaction,
+                order_commitment,
+            },
+        )
+        .await
+        {
+            Ok(sync_encypted_transaction) => sync_encypted_transaction,
+            Err(error) => {
+                tracing::error!("Failed to sign the transaction sync: {:?}", error);
+                return;
+            }
         };
 
-        context
-            .rpc_client()
-            .fire_and_forget_multicast(
-                other_cluster_rpc_url_list,
-                SyncEncryptedTransaction::method(),
-                &sync_encypted_transaction,
-                Id::Null,
-            )
-            .await
+        multicast(
+            &context,
+            other_cluster_rpc_url_list,
+            SyncEncryptedTransaction::method(),
+            sync_encypted_transaction,
+        );
     });
 }
 
@@ -222,7 +275,7 @@ pub async fn issue_order_commitment(
                 transaction_hash.as_string(),
             )),
         )),
-        OrderCommitmentType::Sign => {
+        OrderCommitmentType::Sign | OrderCommitmentType::Quorum => {
             let signer = context.get_signer(platform).await?;
             let order_commitment_data = OrderCommitmentData {
                 rollup_id,
@@ -233,7 +286,7 @@ pub async fn issue_order_commitment(
             };
             let order_commitment = SignOrderCommitment {
                 data: order_commitment_data.clone(),
-                signature: signer.sign_message(&order_commitment_data)?,
+                signature: signer.sign_message(&order_commitment_data).await?,
             };
 
             Ok(OrderCommitment::Single(SingleOrderCommitment::Sign(
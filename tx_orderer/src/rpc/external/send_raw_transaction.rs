@@ -1,17 +1,26 @@
+use tracing::Instrument;
+
 use crate::{
     rpc::{
-        cluster::{BatchCreationMessage, SyncBatchCreation, SyncRawTransaction},
+        cluster::{Authenticated, BatchCreationMessage, SyncBatchCreation, SyncRawTransaction},
         external::issue_order_commitment,
         prelude::*,
     },
-    task::finalize_batch,
+    task::{
+        finalize_batch, forward_to_leader, is_watched_forward_result, watch_forwarded_transaction,
+    },
+    telemetry::TraceContext,
     types::*,
+    util::multicast,
 };
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct SendRawTransaction {
     pub rollup_id: RollupId,
     pub raw_transaction: RawTransaction,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trace_context: Option<TraceContext>,
 }
 
 impl RpcParameter<AppState> for SendRawTransaction {
@@ -21,8 +30,38 @@ impl RpcParameter<AppState> for SendRawTransaction {
         "send_raw_transaction"
     }
 
-    async fn handler(self, context: AppState) -> Result<Self::Response, RpcError> {
+    async fn handler(mut self, context: AppState) -> Result<Self::Response, RpcError> {
+        let span = tracing::info_span!(
+            "send_raw_transaction",
+            rollup_id = %self.rollup_id,
+            transaction_hash = %self.raw_transaction.raw_transaction_hash().as_string(),
+        );
+        if let Some(trace_context) = &self.trace_context {
+            trace_context.attach(&span);
+        }
+        // A follower forwards the transaction to the leader in this span.
+        self.trace_context = TraceContext::from_span(&span);
+
+        self.handle(context).instrument(span).await
+    }
+}
+
+impl SendRawTransaction {
+    async fn handle(self, context: AppState) -> Result<OrderCommitment, RpcError> {
+        if context.shutdown_manager().is_shutting_down() {
+            return Err(Error::ShuttingDown.into());
+        }
+
+        // A rollup that is being deregistered is still stored until its data
+        // is archived or purged, but no longer takes transactions.
+        if !RollupIdList::get_or(RollupIdList::default)?.contains(&self.rollup_id) {
+            return Err(Error::RollupNotFound.into());
+        }
+
         let rollup = Rollup::get(&self.rollup_id)?;
+        rollup
+            .rollup_type
+            .validate_raw_transaction(&self.raw_transaction)?;
 
         let mut mut_rollup_metadata = RollupMetadata::get_mut(&self.rollup_id)?;
 
@@ -67,6 +106,9 @@ impl RpcParameter<AppState> for SendRawTransaction {
                 true,
             )?;
 
+            TransactionStatusInfo::new(batch_number, transaction_order, false)
+                .put(&self.rollup_id, transaction_hash.as_ref())?;
+
             let merkle_tree = context.merkle_tree_manager().get(&self.rollup_id).await?;
             let (_, pre_merkle_path) = merkle_tree.add_data(transaction_hash.as_ref()).await;
             drop(merkle_tree);
@@ -81,6 +123,9 @@ impl RpcParameter<AppState> for SendRawTransaction {
             let is_updated = mut_rollup_metadata.check_and_update_batch_info();
 
             mut_rollup_metadata.update()?;
+            context
+                .metrics()
+                .record_ordered_transaction(&self.rollup_id, "raw");
 
             if is_updated {
                 context
@@ -104,6 +149,12 @@ impl RpcParameter<AppState> for SendRawTransaction {
             .await?;
 
             order_commitment.put(&self.rollup_id, batch_number, transaction_order)?;
+            context.subscription_manager().publish_order_commitment(
+                &self.rollup_id,
+                batch_number,
+                transaction_order,
+                &order_commitment,
+            );
 
             sync_raw_transaction(
                 context.clone(),
@@ -116,36 +167,7 @@ impl RpcParameter<AppState> for SendRawTransaction {
                 true,
             );
 
-            let builder_rpc_url = context.config().builder_rpc_url.clone();
-            let cloned_rpc_client = context.rpc_client();
-
-            if builder_rpc_url.is_some() {
-                match self.raw_transaction {
-                    RawTransaction::Eth(eth_raw_transaction) => {
-                        let params = serde_json::json!([
-                            eth_raw_transaction.0,
-                            batch_number,
-                            transaction_order
-                        ]);
-
-                        let transaction_hash: String = cloned_rpc_client
-                            .request(
-                                &builder_rpc_url.unwrap(),
-                                "eth_sendRawTransaction",
-                                &params,
-                                Id::Null,
-                            )
-                            .await
-                            .map_err(|error| {
-                                tracing::error!("Failed to send raw transaction: {:?}", error);
-                                Error::RpcClient(error)
-                            })?;
-                    }
-                    RawTransaction::EthBundle(_eth_bundle_raw_transaction) => {
-                        unimplemented!("EthBundle raw transaction is not supported yet");
-                    }
-                }
-            }
+            context.builder_sink_manager().notify();
 
             match rollup.order_commitment_type {
                 OrderCommitmentType::TransactionHash => Ok(OrderCommitment::Single(
@@ -153,43 +175,36 @@ impl RpcParameter<AppState> for SendRawTransaction {
                         transaction_hash.as_string(),
                     )),
                 )),
-                OrderCommitmentType::Sign => Ok(order_commitment),
+                OrderCommitmentType::Sign | OrderCommitmentType::Quorum => Ok(order_commitment),
             }
         } else {
             drop(mut_rollup_metadata);
 
-            match cluster_metadata.leader_tx_orderer_rpc_info {
-                Some(leader_tx_orderer_rpc_info) => {
-                    let leader_external_rpc_url = leader_tx_orderer_rpc_info
-                        .external_rpc_url
-                        .clone()
-                        .ok_or(Error::EmptyLeaderClusterRpcUrl)?;
-
-                    match context
-                        .rpc_client()
-                        .request(
-                            leader_external_rpc_url,
-                            SendRawTransaction::method(),
-                            &self,
-                            Id::Null,
-                        )
-                        .await
-                    {
-                        Ok(response) => Ok(response),
-                        Err(error) => {
-                            tracing::error!(
-                                "Send raw transaction - leader external rpc error: {:?}",
-                                error
-                            );
-                            Err(error.into())
-                        }
-                    }
-                }
-                None => {
-                    tracing::error!("Send raw transaction - leader tx orderer rpc info is None");
-                    return Err(Error::EmptyLeader)?;
-                }
+            let result = forward_to_leader(
+                &context,
+                &rollup,
+                SendRawTransaction::method(),
+                &self,
+                &self.raw_transaction.raw_transaction_hash(),
+            )
+            .await;
+
+            if is_watched_forward_result(&result) {
+                watch_forwarded_transaction(
+                    &context,
+                    &rollup,
+                    &self.raw_transaction.raw_transaction_hash(),
+                    ForwardedTransactionPayload::Raw(self.raw_transaction),
+                );
             }
+
+            result.map_err(|error| {
+                tracing::error!(
+                    "Send raw transaction - forward to leader error: {:?}",
+                    error
+                );
+                error.into()
+            })
         }
     }
 }
@@ -205,30 +220,38 @@ pub fn sync_raw_transaction(
     order_commitment: OrderCommitment,
     is_direct_sent: bool,
 ) {
-    tokio::spawn(async move {
+    context.shutdown_manager().clone().spawn(async move {
         let other_cluster_rpc_url_list = cluster.get_other_cluster_rpc_url_list();
         if other_cluster_rpc_url_list.is_empty() {
             return;
         }
 
-        let sync_raw_transaction = SyncRawTransaction {
-            rollup_id,
-            batch_number,
-            transaction_order,
-            raw_transaction,
-            order_commitment: order_commitment,
-            is_direct_sent,
+        let sync_raw_transaction = match Authenticated::sign(
+            &context,
+            SyncRawTransaction {
+                rollup_id,
+                batch_number,
+                transaction_order,
+                raw_transaction,
+                order_commitment: order_commitment,
+                is_direct_sent,
+            },
+        )
+        .await
+        {
+            Ok(sync_raw_transaction) => sync_raw_transaction,
+            Err(error) => {
+                tracing::error!("Failed to sign the transaction sync: {:?}", error);
+                return;
+            }
         };
 
-        context
-            .rpc_client()
-            .fire_and_forget_multicast(
-                other_cluster_rpc_url_list,
-                SyncRawTransaction::method(),
-                &sync_raw_transaction,
-                Id::Null,
-            )
-            .await
+        multicast(
+            &context,
+            other_cluster_rpc_url_list,
+            SyncRawTransaction::method(),
+            sync_raw_transaction,
+        );
     });
 }
 
@@ -242,7 +265,7 @@ pub fn sync_batch_creation(
     batch_commitment: [u8; 32],
     batch_creator_signature: Signature,
 ) {
-    tokio::spawn(async move {
+    context.shutdown_manager().clone().spawn(async move {
         tracing::info!(
             "Sync batch creation - rollup_id: {:?} / batch_number: {:?}",
             rollup_id,
@@ -260,32 +283,42 @@ pub fn sync_batch_creation(
             batch_commitment,
             batch_creator_signature,
         };
-        let leader_tx_orderer_signature = match context
-            .get_signer(platform)
-            .await
-            .map_err(|e| tracing::error!("Failed to get signer: {}", e))
-            .and_then(|signer| {
-                signer
-                    .sign_message(&batch_creation_massage)
-                    .map_err(|e| tracing::error!("Failed to sign message: {}", e))
-            }) {
+        let signer = match context.get_signer(platform).await {
+            Ok(signer) => signer,
+            Err(e) => {
+                tracing::error!("Failed to get signer: {}", e);
+                return;
+            }
+        };
+        let leader_tx_orderer_signature = match signer.sign_message(&batch_creation_massage).await {
             Ok(signature) => signature,
-            Err(_) => return,
+            Err(e) => {
+                tracing::error!("Failed to sign message: {}", e);
+                return;
+            }
         };
 
-        let sync_batch_creation = SyncBatchCreation {
-            batch_creation_massage,
-            leader_tx_orderer_signature,
+        let sync_batch_creation = match Authenticated::new(
+            &signer,
+            SyncBatchCreation {
+                batch_creation_massage,
+                leader_tx_orderer_signature,
+            },
+        )
+        .await
+        {
+            Ok(sync_batch_creation) => sync_batch_creation,
+            Err(e) => {
+                tracing::error!("Failed to sign message: {:?}", e);
+                return;
+            }
         };
 
-        context
-            .rpc_client()
-            .fire_and_forget_multicast(
-                other_cluster_rpc_url_list.clone(),
-                SyncBatchCreation::method(),
-                &sync_batch_creation,
-                Id::Null,
-            )
-            .await
+        multicast(
+            &context,
+            other_cluster_rpc_url_list,
+            SyncBatchCreation::method(),
+            sync_batch_creation,
+        );
     });
 }
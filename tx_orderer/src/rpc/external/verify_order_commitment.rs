@@ -0,0 +1,18 @@
+use crate::{rpc::prelude::*, types::verify_order_commitment};
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct VerifyOrderCommitment {
+    pub order_commitment: SignOrderCommitment,
+}
+
+impl RpcParameter<AppState> for VerifyOrderCommitment {
+    type Response = OrderCommitmentVerdict;
+
+    fn method() -> &'static str {
+        "verify_order_commitment"
+    }
+
+    async fn handler(self, _context: AppState) -> Result<Self::Response, RpcError> {
+        Ok(verify_order_commitment(&self.order_commitment).await?)
+    }
+}
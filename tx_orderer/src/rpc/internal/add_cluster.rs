@@ -1,5 +1,3 @@
-use radius_sdk::signature::PrivateKeySigner;
-
 use crate::{client::liveness_service_manager::radius::initialize_new_cluster, rpc::prelude::*};
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
@@ -27,8 +25,7 @@ impl RpcParameter<AppState> for AddCluster {
         let seeder_client = context.seeder_client();
         match self.platform {
             Platform::Ethereum => {
-                let signing_key = &context.config().signing_key;
-                let signer = PrivateKeySigner::from_str(self.platform.into(), signing_key)?;
+                let signer = context.get_signer(self.platform).await?;
 
                 seeder_client
                     .register_tx_orderer(
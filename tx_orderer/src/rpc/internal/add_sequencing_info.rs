@@ -22,6 +22,8 @@ impl RpcParameter<AppState> for AddSequencingInfo {
             self.payload
         );
 
+        context.config().check_signing_key(self.platform)?;
+
         // Save `LivenessServiceManagerClient` metadata.
         let mut sequencing_info_list = SequencingInfoList::get_mut_or(SequencingInfoList::default)?;
         sequencing_info_list.insert(self.platform, self.liveness_service_provider);
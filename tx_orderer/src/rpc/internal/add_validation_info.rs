@@ -22,6 +22,8 @@ impl RpcParameter<AppState> for AddValidationInfo {
             self.validation_info
         );
 
+        context.config().check_signing_key(self.platform)?;
+
         // Save `ValidationServiceManagerClient` metadata.
         let mut validation_service_providers =
             ValidationServiceProviders::get_mut_or(ValidationServiceProviders::default)?;
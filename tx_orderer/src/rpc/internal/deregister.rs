@@ -1,16 +1,75 @@
-use radius_sdk::signature::PrivateKeySigner;
+use std::{fs, io::BufWriter, ops::RangeInclusive, path::PathBuf, str::FromStr, time::Duration};
 
-use crate::rpc::prelude::*;
+use tokio::time::Instant;
 
+use crate::{
+    database,
+    rpc::prelude::*,
+    task::{ignore_none_type, purge_rollup},
+};
+
+/// How long to wait for the batches that were closed before the request to be
+/// sealed.
+const DRAIN_TIMEOUT: Duration = Duration::from_secs(20);
+const DRAIN_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// What happens to the rollups of the cluster once the node left it.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum RollupDataAction {
+    /// Leaves the data in the database, where it can still be exported.
+    #[default]
+    Keep,
+    /// Exports the data to `archive/` and deletes it.
+    Archive,
+    /// Deletes the data.
+    Purge,
+}
+
+impl FromStr for RollupDataAction {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "keep" => Ok(Self::Keep),
+            "archive" => Ok(Self::Archive),
+            "purge" => Ok(Self::Purge),
+            _ => Err(Error::Parse),
+        }
+    }
+}
+
+/// Leaves a cluster. The node first stops taking transactions for the rollups
+/// of the cluster and gives up the leader role, then waits for the batches
+/// that are being finalized, stops the tasks of the rollups, keeps, archives
+/// or purges the rollup data and deregisters from the seeder last. If the
+/// leader check or the drain fails, the node takes transactions again.
+///
+/// The leader role can only be moved by the executor, so a leader that still
+/// has unsealed transactions refuses to leave unless `force` is set.
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct Deregister {
     pub platform: Platform,
     pub liveness_service_provider: LivenessServiceProvider,
     pub cluster_id: ClusterId,
+
+    #[serde(default)]
+    pub rollup_data: RollupDataAction,
+
+    /// Leaves even if the node is the leader of unsealed transactions or the
+    /// drain times out.
+    #[serde(default)]
+    pub force: bool,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct DeregisterResponse {
+    pub rollup_id_list: Vec<RollupId>,
+    pub archive_path: Option<PathBuf>,
 }
 
 impl RpcParameter<AppState> for Deregister {
-    type Response = ();
+    type Response = DeregisterResponse;
 
     fn method() -> &'static str {
         "deregister"
@@ -18,19 +77,60 @@ impl RpcParameter<AppState> for Deregister {
 
     async fn handler(self, context: AppState) -> Result<Self::Response, RpcError> {
         tracing::info!(
-            "Deregister - platform: {:?}, service provider: {:?}, cluster id: {:?}",
+            "Deregister - platform: {:?}, service provider: {:?}, cluster id: {:?}, rollup data: {:?}",
             self.platform,
             self.liveness_service_provider,
-            self.cluster_id
+            self.cluster_id,
+            self.rollup_data
         );
 
-        let seeder_client = context.seeder_client();
+        let cluster_id_list = ClusterIdList::get_or(
+            self.platform,
+            self.liveness_service_provider,
+            ClusterIdList::default,
+        )?;
+        if !cluster_id_list.contains(&self.cluster_id) {
+            return Err(Error::ClusterNotFound.into());
+        }
+
+        let rollup_id_list = self.get_rollup_id_list()?;
+
+        let was_leader = self.stop_intake(&rollup_id_list)?;
+        let drain_result = match self.check_leader(&rollup_id_list, was_leader) {
+            Ok(()) => self.drain(&rollup_id_list).await,
+            Err(error) => Err(error),
+        };
+        if let Err(error) = drain_result {
+            self.resume_intake(&rollup_id_list, was_leader)?;
+
+            return Err(error.into());
+        }
+
+        for rollup_id in rollup_id_list.iter() {
+            stop_rollup(&context, rollup_id).await?;
+        }
+
+        let archive_path = match self.rollup_data {
+            RollupDataAction::Keep => None,
+            RollupDataAction::Archive => {
+                let archive_path = self.archive(&context, &rollup_id_list)?;
+                self.purge(&context, &rollup_id_list)?;
+
+                Some(archive_path)
+            }
+            RollupDataAction::Purge => {
+                self.purge(&context, &rollup_id_list)?;
+
+                None
+            }
+        };
+
         match self.platform {
             Platform::Ethereum => {
-                let signing_key = &context.config().signing_key;
-                let signer = PrivateKeySigner::from_str(self.platform.into(), signing_key)?;
+                let signer = context.get_signer(self.platform).await?;
 
-                seeder_client
+                context
+                    .seeder_client()
                     .deregister_tx_orderer(
                         self.platform,
                         self.liveness_service_provider,
@@ -38,16 +138,347 @@ impl RpcParameter<AppState> for Deregister {
                         &signer,
                     )
                     .await?;
-
-                let mut cluster_id_list =
-                    ClusterIdList::get_mut(self.platform, self.liveness_service_provider)?;
-                cluster_id_list.remove(&self.cluster_id);
-                cluster_id_list.update()?;
             }
             Platform::Holesky => unimplemented!("Holesky client needs to be implemented."),
             Platform::Local => unimplemented!("Local client needs to be implemented."),
         }
 
+        tracing::info!(
+            "Deregistered - cluster id: {:?}, rollup_id_list: {:?}",
+            self.cluster_id,
+            rollup_id_list
+        );
+
+        Ok(DeregisterResponse {
+            rollup_id_list,
+            archive_path,
+        })
+    }
+}
+
+impl Deregister {
+    fn get_rollup_id_list(&self) -> Result<Vec<RollupId>, Error> {
+        let mut cluster_rollup_id_list = Vec::new();
+
+        let rollup_id_list = RollupIdList::get_or(RollupIdList::default)?;
+        for rollup_id in rollup_id_list.iter() {
+            let rollup = match Rollup::get(rollup_id) {
+                Ok(rollup) => rollup,
+                Err(error) if error.is_none_type() => continue,
+                Err(error) => return Err(error.into()),
+            };
+
+            if rollup.platform == self.platform
+                && rollup.liveness_service_provider == self.liveness_service_provider
+                && rollup.cluster_id == self.cluster_id
+            {
+                cluster_rollup_id_list.push(rollup_id.clone());
+            }
+        }
+
+        Ok(cluster_rollup_id_list)
+    }
+
+    /// Fails if the node was the leader and a rollup has transactions in the
+    /// batch that is being filled, as nobody else could seal them.
+    fn check_leader(&self, rollup_id_list: &[RollupId], was_leader: bool) -> Result<(), Error> {
+        if !was_leader {
+            return Ok(());
+        }
+
+        for rollup_id in rollup_id_list.iter() {
+            let rollup_metadata = RollupMetadata::get(rollup_id)?;
+            self.check_unsealed_transactions(rollup_id, &rollup_metadata)?;
+        }
+
+        Ok(())
+    }
+
+    fn check_unsealed_transactions(
+        &self,
+        rollup_id: &RollupId,
+        rollup_metadata: &RollupMetadata,
+    ) -> Result<(), Error> {
+        if rollup_metadata.transaction_order == 0 {
+            return Ok(());
+        }
+
+        if !self.force {
+            return Err(Error::LeaderHandOffRequired(rollup_id.clone()));
+        }
+
+        tracing::warn!(
+            "Leaving as the leader with unsealed transactions - rollup_id: {:?} / batch_number: {:?} / transaction_order: {:?}",
+            rollup_id,
+            rollup_metadata.batch_number,
+            rollup_metadata.transaction_order
+        );
+
         Ok(())
     }
+
+    /// Stops taking transactions for the rollups and gives up the leader role.
+    /// The cluster is removed from [`ClusterIdList`], as the liveness event
+    /// listener only syncs the clusters in the list and would otherwise make
+    /// the node the leader again. Returns whether the node was the leader.
+    fn stop_intake(&self, rollup_id_list: &[RollupId]) -> Result<bool, Error> {
+        let mut cluster_id_list =
+            ClusterIdList::get_mut(self.platform, self.liveness_service_provider)?;
+        cluster_id_list.remove(&self.cluster_id);
+        cluster_id_list.update()?;
+
+        let mut stored_rollup_id_list = RollupIdList::get_mut_or(RollupIdList::default)?;
+        for rollup_id in rollup_id_list.iter() {
+            stored_rollup_id_list.remove(rollup_id);
+        }
+        stored_rollup_id_list.update()?;
+
+        let mut cluster_metadata = match ClusterMetadata::get_mut(
+            self.platform,
+            self.liveness_service_provider,
+            &self.cluster_id,
+        ) {
+            Ok(cluster_metadata) => cluster_metadata,
+            Err(error) if error.is_none_type() => return Ok(false),
+            Err(error) => return Err(error.into()),
+        };
+        let was_leader = cluster_metadata.is_leader;
+        cluster_metadata.is_leader = false;
+        cluster_metadata.update()?;
+
+        Ok(was_leader)
+    }
+
+    /// Undoes [`Self::stop_intake`] when the node does not leave after all.
+    fn resume_intake(&self, rollup_id_list: &[RollupId], was_leader: bool) -> Result<(), Error> {
+        if was_leader {
+            match ClusterMetadata::get_mut(
+                self.platform,
+                self.liveness_service_provider,
+                &self.cluster_id,
+            ) {
+                Ok(mut cluster_metadata) => {
+                    cluster_metadata.is_leader = true;
+                    cluster_metadata.update()?;
+                }
+                Err(error) if error.is_none_type() => {}
+                Err(error) => return Err(error.into()),
+            }
+        }
+
+        let mut stored_rollup_id_list = RollupIdList::get_mut_or(RollupIdList::default)?;
+        for rollup_id in rollup_id_list.iter() {
+            stored_rollup_id_list.insert(rollup_id);
+        }
+        stored_rollup_id_list.update()?;
+
+        let mut cluster_id_list =
+            ClusterIdList::get_mut(self.platform, self.liveness_service_provider)?;
+        cluster_id_list.insert(&self.cluster_id);
+        cluster_id_list.update()?;
+
+        Ok(())
+    }
+
+    /// Waits until the last closed batch of every rollup has been sealed.
+    async fn drain(&self, rollup_id_list: &[RollupId]) -> Result<(), Error> {
+        let deadline = Instant::now() + DRAIN_TIMEOUT;
+
+        for rollup_id in rollup_id_list.iter() {
+            let rollup_metadata = RollupMetadata::get(rollup_id)?;
+            let batch_number = match rollup_metadata.batch_number.checked_sub(1) {
+                Some(batch_number) => batch_number,
+                None => continue,
+            };
+
+            loop {
+                match Batch::get(rollup_id, batch_number) {
+                    Ok(_) => break,
+                    Err(error) if error.is_none_type() => {}
+                    Err(error) => return Err(error.into()),
+                }
+
+                if Instant::now() >= deadline {
+                    if self.force {
+                        tracing::warn!(
+                            "Leaving before the batch is sealed - rollup_id: {:?} / batch_number: {:?}",
+                            rollup_id,
+                            batch_number
+                        );
+                        break;
+                    }
+
+                    return Err(Error::DrainTimeout);
+                }
+
+                tokio::time::sleep(DRAIN_POLL_INTERVAL).await;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Exports every rollup of the cluster to its own file under
+    /// `archive/<cluster_id>-<timestamp>`.
+    fn archive(&self, context: &AppState, rollup_id_list: &[RollupId]) -> Result<PathBuf, Error> {
+        let archive_path = context.config().archive_path().join(format!(
+            "{}-{}",
+            self.cluster_id,
+            current_timestamp()
+        ));
+        fs::create_dir_all(&archive_path)?;
+
+        for rollup_id in rollup_id_list.iter() {
+            let file = fs::File::create(archive_path.join(format!("{}.jsonl", rollup_id)))?;
+            let export_report = database::export(rollup_id, None, None, BufWriter::new(file))?;
+
+            tracing::info!(
+                "Archived rollup - rollup_id: {:?} / record_count: {:?}",
+                rollup_id,
+                export_report.record_count
+            );
+        }
+
+        Ok(archive_path)
+    }
+
+    fn purge(&self, context: &AppState, rollup_id_list: &[RollupId]) -> Result<(), Error> {
+        for rollup_id in rollup_id_list.iter() {
+            purge_rollup(context, rollup_id)?;
+        }
+
+        if let Ok(latest_synced_cluster_block_height) = LatestSyncedClusterBlockHeight::get(
+            self.platform,
+            self.liveness_service_provider,
+            &self.cluster_id,
+        ) {
+            let latest_block_height = latest_synced_cluster_block_height.get_block_height();
+            let block_margin = Cluster::get(
+                self.platform,
+                self.liveness_service_provider,
+                &self.cluster_id,
+                latest_block_height,
+            )
+            .map(|cluster| cluster.block_margin)
+            .unwrap_or_default();
+
+            for block_height in get_cluster_block_height_range(latest_block_height, block_margin) {
+                ignore_none_type(Cluster::delete(
+                    self.platform,
+                    self.liveness_service_provider,
+                    &self.cluster_id,
+                    block_height,
+                ))?;
+            }
+
+            LatestSyncedClusterBlockHeight::delete(
+                self.platform,
+                self.liveness_service_provider,
+                &self.cluster_id,
+            )?;
+        }
+
+        ignore_none_type(ClusterMetadata::delete(
+            self.platform,
+            self.liveness_service_provider,
+            &self.cluster_id,
+        ))?;
+
+        Ok(())
+    }
+}
+
+/// The block heights of the cluster snapshots that may still be stored, as
+/// the snapshots within twice the block margin are kept.
+fn get_cluster_block_height_range(
+    latest_block_height: u64,
+    block_margin: u64,
+) -> RangeInclusive<u64> {
+    latest_block_height.saturating_sub(block_margin.saturating_mul(2))..=latest_block_height
+}
+
+/// Drops the in-memory state and searcher subscriptions of a rollup that was
+/// already removed from [`RollupIdList`].
+async fn stop_rollup(context: &AppState, rollup_id: &RollupId) -> Result<(), Error> {
+    context.merkle_tree_manager().remove(rollup_id).await;
+    context.leader_health_tracker().reset(rollup_id);
+
+    let mut mev_searcher_infos = MevSearcherInfos::get_mut_or(MevSearcherInfos::default)?;
+    for ip in mev_searcher_infos.get_ip_list_by_rollup_id(rollup_id) {
+        mev_searcher_infos.remove_rollup_id(&ip, rollup_id);
+
+        // Dropping the channel stops the forwarding to a searcher that is left
+        // without rollups. Its connection is closed on its next message.
+        if !mev_searcher_infos.contains_ip(&ip) {
+            context
+                .shared_channel_infos()
+                .lock()
+                .map_err(|_| Error::MutexError)?
+                .remove(&ip);
+        }
+    }
+    mev_searcher_infos.update()?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn deregister(force: bool) -> Deregister {
+        Deregister {
+            platform: Platform::Ethereum,
+            liveness_service_provider: LivenessServiceProvider::Radius,
+            cluster_id: "cluster".to_owned(),
+            rollup_data: RollupDataAction::Keep,
+            force,
+        }
+    }
+
+    #[test]
+    fn leader_with_unsealed_transactions_only_leaves_by_force() {
+        let rollup_id = "rollup".to_owned();
+        let mut rollup_metadata = RollupMetadata {
+            batch_number: 4,
+            ..RollupMetadata::default()
+        };
+
+        assert!(deregister(false)
+            .check_unsealed_transactions(&rollup_id, &rollup_metadata)
+            .is_ok());
+
+        rollup_metadata.transaction_order = 2;
+        assert!(matches!(
+            deregister(false).check_unsealed_transactions(&rollup_id, &rollup_metadata),
+            Err(Error::LeaderHandOffRequired(unsealed_rollup_id)) if unsealed_rollup_id == rollup_id
+        ));
+        assert!(deregister(true)
+            .check_unsealed_transactions(&rollup_id, &rollup_metadata)
+            .is_ok());
+    }
+
+    #[test]
+    fn request_without_options_keeps_the_rollup_data() {
+        let deregister: Deregister = serde_json::from_value(serde_json::json!({
+            "platform": "ethereum",
+            "liveness_service_provider": "radius",
+            "cluster_id": "cluster",
+        }))
+        .unwrap();
+
+        assert_eq!(deregister.rollup_data, RollupDataAction::Keep);
+        assert!(!deregister.force);
+        assert_eq!(
+            RollupDataAction::from_str("Archive").unwrap(),
+            RollupDataAction::Archive
+        );
+    }
+
+    #[test]
+    fn cluster_snapshots_within_twice_the_block_margin_are_deleted() {
+        assert_eq!(get_cluster_block_height_range(100, 10), 80..=100);
+        assert_eq!(get_cluster_block_height_range(5, 10), 0..=5);
+        assert_eq!(get_cluster_block_height_range(5, u64::MAX), 0..=5);
+    }
 }
@@ -0,0 +1,49 @@
+use std::collections::BTreeMap;
+
+use crate::rpc::prelude::*;
+
+/// Exports the equivocation evidence of every rollup so that it can be
+/// submitted for slashing on the validation contracts.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct ExportEquivocationEvidence {
+    pub detected_after: Option<u64>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct ExportEquivocationEvidenceResponse {
+    pub equivocation_evidence_per_rollup: BTreeMap<RollupId, Vec<EquivocationEvidence>>,
+}
+
+impl RpcParameter<AppState> for ExportEquivocationEvidence {
+    type Response = ExportEquivocationEvidenceResponse;
+
+    fn method() -> &'static str {
+        "export_equivocation_evidence"
+    }
+
+    async fn handler(self, _context: AppState) -> Result<Self::Response, RpcError> {
+        let rollup_id_list = RollupIdList::get_or(RollupIdList::default)?;
+        let detected_after = self.detected_after.unwrap_or_default();
+
+        let mut equivocation_evidence_per_rollup = BTreeMap::new();
+        for rollup_id in rollup_id_list.iter() {
+            let equivocation_evidence_list: Vec<EquivocationEvidence> =
+                EquivocationEvidenceList::get_or(rollup_id, EquivocationEvidenceList::default)?
+                    .into_inner()
+                    .into_iter()
+                    .filter(|equivocation_evidence| {
+                        equivocation_evidence.detected_at > detected_after
+                    })
+                    .collect();
+
+            if !equivocation_evidence_list.is_empty() {
+                equivocation_evidence_per_rollup
+                    .insert(rollup_id.clone(), equivocation_evidence_list);
+            }
+        }
+
+        Ok(ExportEquivocationEvidenceResponse {
+            equivocation_evidence_per_rollup,
+        })
+    }
+}
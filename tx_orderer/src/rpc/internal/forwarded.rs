@@ -0,0 +1,39 @@
+use radius_sdk::json_rpc::server::ProcessPriority;
+
+use crate::{access_control::GatewayToken, rpc::prelude::*};
+
+/// An internal RPC parameter forwarded by the gateway, which authenticates the
+/// caller and records the mutations in the audit log. The handler of the
+/// parameter only runs if the request carries the token of the gateway.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Forwarded<T> {
+    gateway_token: GatewayToken,
+    params: T,
+}
+
+impl<T> RpcParameter<AppState> for Forwarded<T>
+where
+    T: RpcParameter<AppState>,
+{
+    type Response = T::Response;
+
+    fn method() -> &'static str {
+        T::method()
+    }
+
+    fn priority(&self) -> ProcessPriority {
+        self.params.priority()
+    }
+
+    async fn handler(self, context: AppState) -> Result<Self::Response, RpcError> {
+        if let Err(error) = context.gateway_token().verify(&self.gateway_token) {
+            tracing::warn!(
+                "Rejected an internal RPC request that bypassed the gateway - method: {:?}",
+                T::method()
+            );
+            return Err(Error::from(error).into());
+        }
+
+        self.params.handler(context).await
+    }
+}
@@ -0,0 +1,16 @@
+use crate::{rpc::prelude::*, task::DiskUsage};
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct GetDiskUsage {}
+
+impl RpcParameter<AppState> for GetDiskUsage {
+    type Response = DiskUsage;
+
+    fn method() -> &'static str {
+        "get_disk_usage"
+    }
+
+    async fn handler(self, context: AppState) -> Result<Self::Response, RpcError> {
+        Ok(context.disk_usage_cache().get(context.config()).await?)
+    }
+}
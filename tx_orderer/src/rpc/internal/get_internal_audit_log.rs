@@ -0,0 +1,38 @@
+use crate::rpc::prelude::*;
+
+const DEFAULT_LIMIT: u64 = 100;
+
+/// Returns the records of the internal audit log from `from_index`, or the
+/// latest ones if not set. The records older than the last
+/// [`MAX_INTERNAL_AUDIT_LOG_LENGTH`] have been deleted.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct GetInternalAuditLog {
+    pub from_index: Option<u64>,
+    pub limit: Option<u64>,
+}
+
+impl RpcParameter<AppState> for GetInternalAuditLog {
+    type Response = Vec<InternalAuditRecord>;
+
+    fn method() -> &'static str {
+        "get_internal_audit_log"
+    }
+
+    async fn handler(self, _context: AppState) -> Result<Self::Response, RpcError> {
+        let internal_audit_log_length =
+            InternalAuditLogLength::get_or(InternalAuditLogLength::default)?;
+        let length = internal_audit_log_length.get_length();
+        let limit = self.limit.unwrap_or(DEFAULT_LIMIT);
+        let from_index = self
+            .from_index
+            .unwrap_or_else(|| length.saturating_sub(limit))
+            .max(internal_audit_log_length.get_first_index());
+        let to_index = from_index.saturating_add(limit).min(length);
+
+        let internal_audit_record_list = (from_index..to_index)
+            .map(InternalAuditRecord::get)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(internal_audit_record_list)
+    }
+}
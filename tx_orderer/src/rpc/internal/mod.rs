@@ -2,16 +2,26 @@ mod add_cluster;
 mod add_sequencing_info;
 mod add_validation_info;
 mod deregister;
+mod export_equivocation_evidence;
+mod forwarded;
 mod get_cluster;
 mod get_cluster_id_list;
+mod get_disk_usage;
+mod get_internal_audit_log;
 mod get_sequencing_info;
 mod get_sequencing_infos;
+mod set_retention_policy;
 
 pub use add_cluster::*;
 pub use add_sequencing_info::*;
 pub use add_validation_info::*;
 pub use deregister::*;
+pub use export_equivocation_evidence::*;
+pub use forwarded::*;
 pub use get_cluster::*;
 pub use get_cluster_id_list::*;
+pub use get_disk_usage::*;
+pub use get_internal_audit_log::*;
 pub use get_sequencing_info::*;
 pub use get_sequencing_infos::*;
+pub use set_retention_policy::*;
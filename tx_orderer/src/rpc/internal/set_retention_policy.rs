@@ -0,0 +1,26 @@
+use crate::rpc::prelude::*;
+
+/// Sets the retention policy of a rollup. The pruner applies it on its next
+/// pass, and batches that were already pruned are not restored.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct SetRetentionPolicy {
+    pub rollup_id: RollupId,
+    pub retention_policy: RetentionPolicy,
+}
+
+impl RpcParameter<AppState> for SetRetentionPolicy {
+    type Response = ();
+
+    fn method() -> &'static str {
+        "set_retention_policy"
+    }
+
+    async fn handler(self, _context: AppState) -> Result<Self::Response, RpcError> {
+        // Fails if the rollup does not exist.
+        Rollup::get(&self.rollup_id)?;
+
+        self.retention_policy.put(&self.rollup_id)?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,299 @@
+//! Coordinated shutdown on SIGTERM or Ctrl-C.
+//!
+//! The node first stops accepting external transactions and reports itself not
+//! ready, so the followers stop forwarding to it and, with failover enabled,
+//! the successor takes over the rollups it leads right away. It then waits, up
+//! to `shutdown_timeout`, for the batches being finalized and submitted to L1,
+//! the cluster syncs, the decryptions and the builder sinks, while the RPC
+//! servers keep answering the cluster. What is still pending at the deadline
+//! is reported in the [`ShutdownReport`].
+
+use std::{
+    future::Future,
+    sync::{
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+use tokio::{
+    signal::unix::{signal, SignalKind},
+    task::JoinHandle,
+    time::Instant,
+};
+use tracing::Instrument;
+
+use crate::{health::check_readiness, state::AppState, types::*};
+
+const DRAIN_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+pub struct ShutdownManager {
+    inner: Arc<ShutdownManagerInner>,
+}
+
+struct ShutdownManagerInner {
+    is_shutting_down: AtomicBool,
+    in_flight_task_count: AtomicUsize,
+}
+
+impl Clone for ShutdownManager {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl Default for ShutdownManager {
+    fn default() -> Self {
+        let inner = ShutdownManagerInner {
+            is_shutting_down: AtomicBool::new(false),
+            in_flight_task_count: AtomicUsize::new(0),
+        };
+
+        Self {
+            inner: Arc::new(inner),
+        }
+    }
+}
+
+impl ShutdownManager {
+    pub fn is_shutting_down(&self) -> bool {
+        self.inner.is_shutting_down.load(Ordering::Relaxed)
+    }
+
+    /// Spawns a task the shutdown waits for before the node exits. The task
+    /// runs in the current span, so that it stays in the trace that started
+    /// it.
+    pub fn spawn<F>(&self, future: F) -> JoinHandle<F::Output>
+    where
+        F: Future + Send + 'static,
+        F::Output: Send + 'static,
+    {
+        let in_flight_task = InFlightTask::new(self.clone());
+
+        tokio::spawn(
+            async move {
+                let _in_flight_task = in_flight_task;
+                future.await
+            }
+            .in_current_span(),
+        )
+    }
+
+    pub fn in_flight_task_count(&self) -> usize {
+        self.inner.in_flight_task_count.load(Ordering::Relaxed)
+    }
+}
+
+/// Counts a task as in flight until it is dropped, which also covers the
+/// tasks that panic.
+struct InFlightTask(ShutdownManager);
+
+impl InFlightTask {
+    fn new(shutdown_manager: ShutdownManager) -> Self {
+        shutdown_manager
+            .inner
+            .in_flight_task_count
+            .fetch_add(1, Ordering::Relaxed);
+
+        Self(shutdown_manager)
+    }
+}
+
+impl Drop for InFlightTask {
+    fn drop(&mut self) {
+        self.0
+            .inner
+            .in_flight_task_count
+            .fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct ShutdownReport {
+    pub elapsed: Duration,
+    pub is_drained: bool,
+
+    /// Batch finalizations, L1 submissions and cluster syncs.
+    pub in_flight_task_count: usize,
+    pub decryption_backlog: usize,
+    /// The (rollup, sink) pairs that have not delivered every ordered
+    /// transaction.
+    pub pending_builder_delivery_count: usize,
+    /// The rollups this node still leads.
+    pub leader_rollup_id_list: Vec<RollupId>,
+}
+
+impl ShutdownReport {
+    /// Without failover nobody takes over the rollups this node leads, so the
+    /// node does not wait for it.
+    fn check_drained(&self, is_failover_enabled: bool) -> bool {
+        let is_handed_over = self.leader_rollup_id_list.is_empty() || !is_failover_enabled;
+
+        self.in_flight_task_count == 0
+            && self.decryption_backlog == 0
+            && self.pending_builder_delivery_count == 0
+            && is_handed_over
+    }
+}
+
+pub async fn wait_for_shutdown_signal() -> Result<(), std::io::Error> {
+    let mut sigterm = signal(SignalKind::terminate())?;
+
+    tokio::select! {
+        _ = sigterm.recv() => tracing::info!("Received SIGTERM"),
+        result = tokio::signal::ctrl_c() => {
+            result?;
+            tracing::info!("Received Ctrl-C");
+        }
+    }
+
+    Ok(())
+}
+
+/// Stops accepting external transactions and drains the node until everything
+/// in flight is done or `shutdown_timeout` passes.
+pub async fn shutdown(context: &AppState) -> ShutdownReport {
+    let started_at = Instant::now();
+    let deadline = started_at + Duration::from_secs(context.config().shutdown_timeout);
+
+    context
+        .shutdown_manager()
+        .inner
+        .is_shutting_down
+        .store(true, Ordering::Relaxed);
+    context
+        .health_monitor()
+        .update(check_readiness(context).await);
+    tracing::info!(
+        "Shutting down - draining for up to {:?} seconds",
+        context.config().shutdown_timeout
+    );
+
+    loop {
+        let shutdown_report = check_drain(context, started_at).await;
+        if shutdown_report.is_drained || Instant::now() >= deadline {
+            return shutdown_report;
+        }
+
+        tokio::time::sleep(DRAIN_POLL_INTERVAL).await;
+    }
+}
+
+async fn check_drain(context: &AppState, started_at: Instant) -> ShutdownReport {
+    let in_flight_task_count = context.shutdown_manager().in_flight_task_count();
+    let decryption_backlog = context.decryptor().backlog().await;
+
+    let pending_builder_delivery_count = context
+        .builder_sink_manager()
+        .pending_delivery_count()
+        .unwrap_or_else(|error| {
+            tracing::warn!("Failed to check the builder sinks: {:?}", error);
+            0
+        });
+
+    let leader_rollup_id_list = get_leader_rollup_id_list().unwrap_or_else(|error| {
+        tracing::warn!("Failed to check the led rollups: {:?}", error);
+        Vec::new()
+    });
+
+    let mut shutdown_report = ShutdownReport {
+        elapsed: started_at.elapsed(),
+        is_drained: false,
+        in_flight_task_count,
+        decryption_backlog,
+        pending_builder_delivery_count,
+        leader_rollup_id_list,
+    };
+    shutdown_report.is_drained =
+        shutdown_report.check_drained(context.config().leader_failover_timeout.is_some());
+
+    shutdown_report
+}
+
+fn get_leader_rollup_id_list() -> Result<Vec<RollupId>, KvStoreError> {
+    let mut leader_rollup_id_list = Vec::new();
+
+    let rollup_id_list = RollupIdList::get_or(RollupIdList::default)?;
+    for rollup_id in rollup_id_list.iter() {
+        let rollup = match Rollup::get(rollup_id) {
+            Ok(rollup) => rollup,
+            Err(error) if error.is_none_type() => continue,
+            Err(error) => return Err(error),
+        };
+
+        let is_leader = match ClusterMetadata::get(
+            rollup.platform,
+            rollup.liveness_service_provider,
+            &rollup.cluster_id,
+        ) {
+            Ok(cluster_metadata) => cluster_metadata.is_leader,
+            Err(error) if error.is_none_type() => false,
+            Err(error) => return Err(error),
+        };
+
+        if is_leader {
+            leader_rollup_id_list.push(rollup_id.clone());
+        }
+    }
+
+    Ok(leader_rollup_id_list)
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::sync::oneshot;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn spawned_task_is_in_flight_until_it_ends() {
+        let shutdown_manager = ShutdownManager::default();
+        let (sender, receiver) = oneshot::channel::<()>();
+
+        let finished_task = shutdown_manager.spawn(async move {
+            let _ = receiver.await;
+        });
+        let panicked_task = shutdown_manager.spawn(async { panic!("task failed") });
+        assert!(panicked_task.await.is_err());
+        assert_eq!(shutdown_manager.in_flight_task_count(), 1);
+
+        sender.send(()).unwrap();
+        finished_task.await.unwrap();
+        assert_eq!(shutdown_manager.in_flight_task_count(), 0);
+    }
+
+    #[test]
+    fn led_rollups_hold_the_shutdown_only_with_failover() {
+        let shutdown_report = ShutdownReport {
+            leader_rollup_id_list: vec!["rollup".to_owned()],
+            ..ShutdownReport::default()
+        };
+        assert!(!shutdown_report.check_drained(true));
+        assert!(shutdown_report.check_drained(false));
+
+        assert!(ShutdownReport::default().check_drained(true));
+    }
+
+    #[test]
+    fn pending_work_holds_the_shutdown() {
+        for shutdown_report in [
+            ShutdownReport {
+                in_flight_task_count: 1,
+                ..ShutdownReport::default()
+            },
+            ShutdownReport {
+                decryption_backlog: 1,
+                ..ShutdownReport::default()
+            },
+            ShutdownReport {
+                pending_builder_delivery_count: 1,
+                ..ShutdownReport::default()
+            },
+        ] {
+            assert!(!shutdown_report.check_drained(false));
+        }
+    }
+}
@@ -0,0 +1,330 @@
+use std::{sync::Arc, time::Duration};
+
+use axum::{extract::State, http::StatusCode, routing::post, Json, Router};
+use ethers_core::{
+    k256::ecdsa::SigningKey,
+    types::{transaction::eip2718::TypedTransaction, Bytes, H160},
+    utils::{rlp::Rlp, secret_key_to_address},
+};
+use reqwest::Client;
+use serde_json::Value;
+use tokio::net::TcpListener;
+
+use super::{RemoteSigner, SignerError};
+
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+const SEND_RAW_TRANSACTION_METHOD: &str = "eth_sendRawTransaction";
+
+/// Loopback JSON-RPC proxy in front of an L1 RPC URL, for the SDK publishers
+/// that only sign with a private key.
+///
+/// The publisher is created with a throwaway key. The relay swaps its address
+/// for the address of the remote signer in the requests, so that the nonce,
+/// balance and gas estimates are those of the remote signer, and has the
+/// remote signer sign the raw transactions again before they are sent.
+pub struct L1SigningRelay {
+    url: String,
+    signing_key: String,
+}
+
+struct L1SigningRelayState {
+    remote_signer: RemoteSigner,
+    remote_signer_address: H160,
+    relay_address: H160,
+    upstream_rpc_url: String,
+    http_client: Client,
+}
+
+impl L1SigningRelay {
+    pub async fn run(
+        remote_signer: RemoteSigner,
+        upstream_rpc_url: impl AsRef<str>,
+    ) -> Result<Self, SignerError> {
+        let remote_signer_address = parse_address(&remote_signer.address().as_hex_string())?;
+        let relay_signing_key = SigningKey::random(&mut rand::thread_rng());
+        let http_client = Client::builder()
+            .timeout(REQUEST_TIMEOUT)
+            .build()
+            .map_err(SignerError::Request)?;
+
+        let listener = TcpListener::bind("127.0.0.1:0")
+            .await
+            .map_err(SignerError::RelayServer)?;
+        let url = format!(
+            "http://{}",
+            listener.local_addr().map_err(SignerError::RelayServer)?
+        );
+
+        let state = Arc::new(L1SigningRelayState {
+            remote_signer,
+            remote_signer_address,
+            relay_address: secret_key_to_address(&relay_signing_key),
+            upstream_rpc_url: upstream_rpc_url.as_ref().to_owned(),
+            http_client,
+        });
+        tracing::info!(
+            "L1 signing relay for {:?} listening on {:?}",
+            state.upstream_rpc_url,
+            url
+        );
+
+        let router = Router::new().route("/", post(relay)).with_state(state);
+        tokio::spawn(async move {
+            if let Err(error) = axum::serve(listener, router).await {
+                tracing::error!("L1 signing relay stopped - {:?}", error);
+            }
+        });
+
+        Ok(Self {
+            url,
+            signing_key: const_hex::encode_prefixed(relay_signing_key.to_bytes()),
+        })
+    }
+
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// The throwaway key the publisher signs with.
+    pub fn signing_key(&self) -> &str {
+        &self.signing_key
+    }
+}
+
+impl L1SigningRelayState {
+    async fn rewrite_request(&self, request: &mut Value) -> Result<(), SignerError> {
+        let is_send_raw_transaction =
+            request.get("method").and_then(Value::as_str) == Some(SEND_RAW_TRANSACTION_METHOD);
+        let Some(params) = request.get_mut("params") else {
+            return Ok(());
+        };
+
+        if !is_send_raw_transaction {
+            replace_address(
+                params,
+                &format!("{:?}", self.relay_address),
+                &format!("{:?}", self.remote_signer_address),
+            );
+
+            return Ok(());
+        }
+
+        if let Some(raw_transaction) = params.get_mut(0) {
+            let signed_transaction = self
+                .sign_again(raw_transaction.as_str().unwrap_or_default())
+                .await?;
+            *raw_transaction = Value::String(const_hex::encode_prefixed(signed_transaction));
+        }
+
+        Ok(())
+    }
+
+    /// Only the transactions signed with the relay key are signed by the
+    /// remote signer, so that other local processes cannot use the relay.
+    async fn sign_again(&self, raw_transaction: &str) -> Result<Bytes, SignerError> {
+        let invalid_transaction =
+            || SignerError::InvalidSignedTransaction(raw_transaction.to_owned());
+
+        let raw_transaction_bytes =
+            const_hex::decode(raw_transaction).map_err(|_| invalid_transaction())?;
+        let (mut transaction, signature) =
+            TypedTransaction::decode_signed(&Rlp::new(&raw_transaction_bytes))
+                .map_err(|_| invalid_transaction())?;
+
+        let signer_address = signature
+            .recover(transaction.sighash())
+            .map_err(|_| invalid_transaction())?;
+        if signer_address != self.relay_address {
+            return Err(SignerError::UnknownTransactionSigner(format!(
+                "{:?}",
+                signer_address
+            )));
+        }
+
+        transaction.set_from(self.remote_signer_address);
+        self.remote_signer.sign_transaction(&transaction).await
+    }
+}
+
+async fn relay(
+    State(state): State<Arc<L1SigningRelayState>>,
+    Json(mut request): Json<Value>,
+) -> Result<Json<Value>, (StatusCode, String)> {
+    let result = match &mut request {
+        Value::Array(request_list) => {
+            let mut result = Ok(());
+            for request in request_list.iter_mut() {
+                result = state.rewrite_request(request).await;
+                if result.is_err() {
+                    break;
+                }
+            }
+            result
+        }
+        request => state.rewrite_request(request).await,
+    };
+    if let Err(error) = result {
+        tracing::error!("L1 signing relay refused a request - {:?}", error);
+        return Err((StatusCode::BAD_REQUEST, error.to_string()));
+    }
+
+    let response = state
+        .http_client
+        .post(&state.upstream_rpc_url)
+        .json(&request)
+        .send()
+        .await
+        .map_err(|error| (StatusCode::BAD_GATEWAY, error.to_string()))?
+        .json::<Value>()
+        .await
+        .map_err(|error| (StatusCode::BAD_GATEWAY, error.to_string()))?;
+
+    Ok(Json(response))
+}
+
+fn replace_address(value: &mut Value, from: &str, to: &str) {
+    match value {
+        Value::String(string) if string.eq_ignore_ascii_case(from) => *string = to.to_owned(),
+        Value::Array(value_list) => value_list
+            .iter_mut()
+            .for_each(|value| replace_address(value, from, to)),
+        Value::Object(value_map) => value_map
+            .values_mut()
+            .for_each(|value| replace_address(value, from, to)),
+        _ => {}
+    }
+}
+
+fn parse_address(address: &str) -> Result<H160, SignerError> {
+    const_hex::decode(address)
+        .ok()
+        .filter(|address_bytes| address_bytes.len() == H160::len_bytes())
+        .map(|address_bytes| H160::from_slice(&address_bytes))
+        .ok_or_else(|| SignerError::InvalidPublicKey(address.to_owned()))
+}
+
+#[cfg(test)]
+mod tests {
+    use ethers_core::types::{Eip1559TransactionRequest, Signature, U256};
+    use serde_json::json;
+
+    use super::*;
+    use crate::{
+        signer::run_mock_signer,
+        types::{Platform, DEFAULT_SIGNING_KEY},
+    };
+
+    fn sign(private_key: &str, transaction: &TypedTransaction) -> String {
+        let signing_key = SigningKey::from_slice(&const_hex::decode(private_key).unwrap()).unwrap();
+        let (signature, recovery_id) = signing_key
+            .sign_prehash_recoverable(transaction.sighash().as_bytes())
+            .unwrap();
+        let signature = Signature {
+            r: U256::from_big_endian(&signature.r().to_bytes()),
+            s: U256::from_big_endian(&signature.s().to_bytes()),
+            v: recovery_id.to_byte() as u64,
+        };
+
+        const_hex::encode_prefixed(transaction.rlp_signed(&signature))
+    }
+
+    /// Answers every request with the request the upstream received.
+    async fn run_echo_upstream() -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let router = Router::new().route(
+            "/",
+            post(|Json(request): Json<Value>| async move {
+                Json(json!({ "jsonrpc": "2.0", "id": 1, "result": request }))
+            }),
+        );
+        tokio::spawn(async move { axum::serve(listener, router).await });
+
+        url
+    }
+
+    #[tokio::test]
+    async fn relay_sends_the_transactions_of_the_remote_signer() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let signer_url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(run_mock_signer(listener, DEFAULT_SIGNING_KEY.to_owned()));
+        let remote_signer = RemoteSigner::connect(&signer_url, Platform::Ethereum, None)
+            .await
+            .unwrap();
+        let remote_signer_address =
+            parse_address(&remote_signer.address().as_hex_string()).unwrap();
+
+        let relay = L1SigningRelay::run(remote_signer, run_echo_upstream().await)
+            .await
+            .unwrap();
+        let relay_address = secret_key_to_address(
+            &SigningKey::from_slice(&const_hex::decode(relay.signing_key()).unwrap()).unwrap(),
+        );
+        let http_client = Client::new();
+
+        let response: Value = http_client
+            .post(relay.url())
+            .json(&json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "method": "eth_getTransactionCount",
+                "params": [format!("{:?}", relay_address), "pending"],
+            }))
+            .send()
+            .await
+            .unwrap()
+            .json()
+            .await
+            .unwrap();
+        assert_eq!(
+            response["result"]["params"][0],
+            format!("{:?}", remote_signer_address)
+        );
+
+        let transaction = TypedTransaction::Eip1559(
+            Eip1559TransactionRequest::new()
+                .from(relay_address)
+                .to(H160::repeat_byte(7))
+                .nonce(3)
+                .gas(21_000)
+                .chain_id(1),
+        );
+        let send_raw_transaction = |raw_transaction: String| {
+            http_client
+                .post(relay.url())
+                .json(&json!({
+                    "jsonrpc": "2.0",
+                    "id": 1,
+                    "method": SEND_RAW_TRANSACTION_METHOD,
+                    "params": [raw_transaction],
+                }))
+                .send()
+        };
+
+        let response: Value = send_raw_transaction(sign(relay.signing_key(), &transaction))
+            .await
+            .unwrap()
+            .json()
+            .await
+            .unwrap();
+        let raw_transaction =
+            const_hex::decode(response["result"]["params"][0].as_str().unwrap()).unwrap();
+        let (sent_transaction, signature) =
+            TypedTransaction::decode_signed(&Rlp::new(&raw_transaction)).unwrap();
+        assert_eq!(sent_transaction.nonce(), transaction.nonce());
+        assert_eq!(
+            signature.recover(sent_transaction.sighash()).unwrap(),
+            remote_signer_address
+        );
+
+        // A transaction that the publisher did not sign is not relayed.
+        let response = send_raw_transaction(sign(
+            "0x59c6995e998f97a5a0044966f0945389dc9e86dae88c7a8412f4603b6b78690d",
+            &transaction,
+        ))
+        .await
+        .unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+}
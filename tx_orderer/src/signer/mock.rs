@@ -0,0 +1,148 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    routing::{get, post},
+    Json, Router,
+};
+use ethers_core::{
+    k256::ecdsa::SigningKey,
+    types::{transaction::eip2718::TypedTransaction, Signature, H160, U256, U64},
+    utils::{hash_message, secret_key_to_address},
+};
+use serde_json::{json, Value};
+use tokio::net::TcpListener;
+
+use super::{
+    SignRequest, SignerError, PUBLIC_KEYS_PATH, SIGN_PATH, SIGN_TRANSACTION_METHOD, UPCHECK_PATH,
+};
+
+struct MockSignerState {
+    signing_key: SigningKey,
+    public_key: String,
+    address: H160,
+}
+
+/// Serves the endpoints of [`super::RemoteSigner`] with a single in-memory key,
+/// for tests and local clusters. It is not meant to hold a production key.
+pub async fn run_mock_signer(
+    listener: TcpListener,
+    private_key: String,
+) -> Result<(), SignerError> {
+    let private_key_bytes =
+        const_hex::decode(private_key.trim()).map_err(|_| SignerError::InvalidPrivateKey)?;
+    let signing_key =
+        SigningKey::from_slice(&private_key_bytes).map_err(|_| SignerError::InvalidPrivateKey)?;
+    let public_key = const_hex::encode_prefixed(
+        signing_key
+            .verifying_key()
+            .to_encoded_point(false)
+            .as_bytes(),
+    );
+
+    tracing::info!(
+        "Mock signer listening on {:?} - public key: {}",
+        listener.local_addr(),
+        public_key
+    );
+
+    let state = Arc::new(MockSignerState {
+        address: secret_key_to_address(&signing_key),
+        signing_key,
+        public_key,
+    });
+    let router = Router::new()
+        .route("/", post(sign_transaction))
+        .route(UPCHECK_PATH, get(|| async { "OK" }))
+        .route(PUBLIC_KEYS_PATH, get(public_keys))
+        .route(&format!("{}/:identifier", SIGN_PATH), post(sign))
+        .with_state(state);
+
+    axum::serve(listener, router)
+        .await
+        .map_err(SignerError::MockServer)
+}
+
+async fn public_keys(State(state): State<Arc<MockSignerState>>) -> Json<Vec<String>> {
+    Json(vec![state.public_key.clone()])
+}
+
+async fn sign(
+    State(state): State<Arc<MockSignerState>>,
+    Path(identifier): Path<String>,
+    Json(request): Json<SignRequest>,
+) -> Result<String, (StatusCode, String)> {
+    if !identifier.eq_ignore_ascii_case(&state.public_key) {
+        return Err((StatusCode::NOT_FOUND, format!("Unknown key {}", identifier)));
+    }
+
+    let data = const_hex::decode(&request.data)
+        .map_err(|error| (StatusCode::BAD_REQUEST, error.to_string()))?;
+    let (signature, recovery_id) = state
+        .signing_key
+        .sign_prehash_recoverable(hash_message(data).as_bytes())
+        .map_err(|error| (StatusCode::INTERNAL_SERVER_ERROR, error.to_string()))?;
+
+    let mut signature_bytes = signature.to_bytes().to_vec();
+    signature_bytes.push(recovery_id.to_byte() + 27);
+
+    Ok(const_hex::encode_prefixed(signature_bytes))
+}
+
+/// Handles `eth_signTransaction` on the JSON-RPC endpoint.
+async fn sign_transaction(
+    State(state): State<Arc<MockSignerState>>,
+    Json(request): Json<Value>,
+) -> Result<Json<Value>, (StatusCode, String)> {
+    if request.get("method").and_then(Value::as_str) != Some(SIGN_TRANSACTION_METHOD) {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            format!("Unsupported request {}", request),
+        ));
+    }
+
+    let transaction: TypedTransaction = request
+        .get("params")
+        .and_then(|params| params.get(0))
+        .cloned()
+        .ok_or_else(|| (StatusCode::BAD_REQUEST, "Missing transaction".to_owned()))
+        .and_then(|transaction| {
+            serde_json::from_value(transaction)
+                .map_err(|error| (StatusCode::BAD_REQUEST, error.to_string()))
+        })?;
+    if transaction
+        .from()
+        .is_some_and(|from| *from != state.address)
+    {
+        return Err((
+            StatusCode::NOT_FOUND,
+            format!("Unknown key {:?}", transaction.from()),
+        ));
+    }
+
+    let (signature, recovery_id) = state
+        .signing_key
+        .sign_prehash_recoverable(transaction.sighash().as_bytes())
+        .map_err(|error| (StatusCode::INTERNAL_SERVER_ERROR, error.to_string()))?;
+
+    // EIP-155 `v`, which the typed transactions normalize back to the parity
+    // with the same chain ID fallback.
+    let v = match (&transaction, transaction.chain_id()) {
+        (TypedTransaction::Legacy(_), None) => 27 + recovery_id.to_byte() as u64,
+        (_, chain_id) => {
+            35 + 2 * chain_id.unwrap_or_else(U64::one).as_u64() + recovery_id.to_byte() as u64
+        }
+    };
+    let signature = Signature {
+        r: U256::from_big_endian(&signature.r().to_bytes()),
+        s: U256::from_big_endian(&signature.s().to_bytes()),
+        v,
+    };
+
+    Ok(Json(json!({
+        "jsonrpc": "2.0",
+        "id": request.get("id").cloned().unwrap_or(Value::Null),
+        "result": const_hex::encode_prefixed(transaction.rlp_signed(&signature)),
+    })))
+}
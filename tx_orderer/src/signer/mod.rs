@@ -0,0 +1,202 @@
+//! Signers for the messages the node signs: order commitments, batch
+//! creations, leader sync messages, the seeder registration and the L1
+//! transactions to the liveness and validation contracts.
+//!
+//! The key either lives on the host ([`TxOrdererSigner::Local`]) or in a
+//! remote signer that exposes a web3signer-style API
+//! ([`TxOrdererSigner::Remote`]), e.g. in front of an HSM. The SDK publishers
+//! sign with a private key, so with a remote signer they are pointed at an
+//! [`L1SigningRelay`] that re-signs their transactions with the remote signer.
+
+mod l1_relay;
+mod mock;
+mod remote;
+
+pub use l1_relay::*;
+pub use mock::*;
+use radius_sdk::signature::{Address, PrivateKeySigner, Signature, SignatureError};
+pub use remote::*;
+use serde::Serialize;
+
+use crate::types::{Config, Platform};
+
+#[derive(Debug)]
+pub enum SignerError {
+    Local(SignatureError),
+    Encode(bincode::Error),
+    Request(reqwest::Error),
+    Response {
+        status: u16,
+        body: String,
+    },
+    InvalidPrivateKey,
+    InvalidPublicKey(String),
+    InvalidSignature(String),
+    InvalidSignedTransaction(String),
+    PublicKeyNotFound(String),
+    AmbiguousPublicKey(usize),
+    MissingSigningKey,
+    UnknownTransactionSigner(String),
+    MockServer(std::io::Error),
+    RelayServer(std::io::Error),
+}
+
+impl std::fmt::Display for SignerError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+impl std::error::Error for SignerError {}
+
+impl From<SignatureError> for SignerError {
+    fn from(value: SignatureError) -> Self {
+        Self::Local(value)
+    }
+}
+
+/// The RPC URL and the private key an SDK publisher is created with.
+pub struct PublisherEndpoint {
+    pub rpc_url: String,
+    pub signing_key: String,
+}
+
+#[derive(Clone)]
+pub enum TxOrdererSigner {
+    Local(PrivateKeySigner),
+    Remote(RemoteSigner),
+}
+
+impl TxOrdererSigner {
+    /// Connects to the remote signer if `remote_signer_url` is set, and uses
+    /// the signing key of the configuration otherwise.
+    pub async fn from_config(config: &Config, platform: Platform) -> Result<Self, SignerError> {
+        match &config.remote_signer_url {
+            Some(remote_signer_url) => {
+                let remote_signer = RemoteSigner::connect(
+                    remote_signer_url,
+                    platform,
+                    config.remote_signer_public_key.as_deref(),
+                )
+                .await?;
+
+                Ok(Self::Remote(remote_signer))
+            }
+            None => {
+                let signing_key = config
+                    .signing_key
+                    .as_deref()
+                    .ok_or(SignerError::MissingSigningKey)?;
+
+                Ok(Self::Local(PrivateKeySigner::from_str(
+                    platform.into(),
+                    signing_key,
+                )?))
+            }
+        }
+    }
+
+    /// Returns where the SDK publisher for the contracts at `rpc_url` sends its
+    /// transactions, so that they are signed by this signer: the RPC URL
+    /// itself with the local key, or a relay to it with a remote signer.
+    pub async fn publisher_endpoint(
+        &self,
+        config: &Config,
+        rpc_url: impl AsRef<str>,
+    ) -> Result<PublisherEndpoint, SignerError> {
+        match self {
+            Self::Local(_) => Ok(PublisherEndpoint {
+                rpc_url: rpc_url.as_ref().to_owned(),
+                signing_key: config
+                    .signing_key
+                    .clone()
+                    .ok_or(SignerError::MissingSigningKey)?,
+            }),
+            Self::Remote(signer) => {
+                let relay = L1SigningRelay::run(signer.clone(), rpc_url).await?;
+
+                Ok(PublisherEndpoint {
+                    rpc_url: relay.url().to_owned(),
+                    signing_key: relay.signing_key().to_owned(),
+                })
+            }
+        }
+    }
+
+    pub fn address(&self) -> &Address {
+        match self {
+            Self::Local(signer) => signer.address(),
+            Self::Remote(signer) => signer.address(),
+        }
+    }
+
+    pub fn is_remote(&self) -> bool {
+        matches!(self, Self::Remote(_))
+    }
+
+    /// Signs the message the same way [`PrivateKeySigner`] does, so that
+    /// [`Signature::get_signer_address`] recovers the address of the signer
+    /// regardless of the backend.
+    pub async fn sign_message<T: Serialize>(&self, message: &T) -> Result<Signature, SignerError> {
+        match self {
+            Self::Local(signer) => Ok(signer.sign_message(message)?),
+            Self::Remote(signer) => {
+                let data = bincode::serialize(message).map_err(SignerError::Encode)?;
+
+                signer.sign(&data).await
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use radius_sdk::signature::ChainType;
+
+    use super::*;
+    use crate::types::DEFAULT_SIGNING_KEY;
+
+    #[tokio::test]
+    async fn remote_signature_recovers_the_signer_address() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let signer_url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(run_mock_signer(listener, DEFAULT_SIGNING_KEY.to_owned()));
+
+        let local_signer = PrivateKeySigner::from_str(ChainType::Ethereum, DEFAULT_SIGNING_KEY)
+            .map(TxOrdererSigner::Local)
+            .unwrap();
+        let remote_signer = RemoteSigner::connect(&signer_url, Platform::Ethereum, None)
+            .await
+            .map(TxOrdererSigner::Remote)
+            .unwrap();
+        assert_eq!(remote_signer.address(), local_signer.address());
+
+        let message = (String::from("rollup_id"), 7_u64, [1_u8; 32]);
+        let signature = remote_signer.sign_message(&message).await.unwrap();
+        let signer_address = signature
+            .get_signer_address(ChainType::Ethereum, &message)
+            .unwrap();
+        assert_eq!(&signer_address, local_signer.address());
+    }
+
+    #[tokio::test]
+    async fn remote_signer_does_not_need_the_signing_key() {
+        let private_key = "0x59c6995e998f97a5a0044966f0945389dc9e86dae88c7a8412f4603b6b78690d";
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let signer_url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(run_mock_signer(listener, private_key.to_owned()));
+
+        let config = Config {
+            signing_key: None,
+            remote_signer_url: Some(signer_url),
+            ..Config::default()
+        };
+        let signer = TxOrdererSigner::from_config(&config, Platform::Ethereum)
+            .await
+            .unwrap();
+
+        let expected_signer = PrivateKeySigner::from_str(ChainType::Ethereum, private_key).unwrap();
+        assert!(signer.is_remote());
+        assert_eq!(signer.address(), expected_signer.address());
+    }
+}
@@ -0,0 +1,227 @@
+use std::{sync::Arc, time::Duration};
+
+use ethers_core::{
+    k256::ecdsa::VerifyingKey,
+    types::{transaction::eip2718::TypedTransaction, Bytes},
+    utils::public_key_to_address,
+};
+use radius_sdk::signature::{Address, Signature};
+use reqwest::{Client, Response};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use super::SignerError;
+use crate::types::Platform;
+
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+
+pub const PUBLIC_KEYS_PATH: &str = "/api/v1/eth1/publicKeys";
+pub const SIGN_PATH: &str = "/api/v1/eth1/sign";
+pub const UPCHECK_PATH: &str = "/upcheck";
+pub const SIGN_TRANSACTION_METHOD: &str = "eth_signTransaction";
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct SignRequest {
+    /// Hex encoded bytes, which the signer hashes as an EIP-191 personal
+    /// message.
+    pub data: String,
+}
+
+/// Signs through an HTTP signer that implements the eth1 endpoints of the
+/// web3signer API. The key is identified by its uncompressed public key.
+pub struct RemoteSigner {
+    inner: Arc<RemoteSignerInner>,
+}
+
+struct RemoteSignerInner {
+    url: String,
+    public_key: String,
+    address: Address,
+    http_client: Client,
+}
+
+impl Clone for RemoteSigner {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl RemoteSigner {
+    /// Looks up the keys the signer holds and selects `public_key`, or the
+    /// only key if none is given.
+    pub async fn connect(
+        url: impl AsRef<str>,
+        platform: Platform,
+        public_key: Option<&str>,
+    ) -> Result<Self, SignerError> {
+        let url = url.as_ref().trim_end_matches('/').to_owned();
+        let http_client = Client::builder()
+            .timeout(REQUEST_TIMEOUT)
+            .build()
+            .map_err(SignerError::Request)?;
+
+        let response = http_client
+            .get(format!("{}{}", url, PUBLIC_KEYS_PATH))
+            .send()
+            .await
+            .map_err(SignerError::Request)?;
+        let public_key_list: Vec<String> = check_status(response)
+            .await?
+            .json()
+            .await
+            .map_err(SignerError::Request)?;
+
+        let public_key = match public_key {
+            Some(public_key) => public_key_list
+                .into_iter()
+                .find(|listed| listed.eq_ignore_ascii_case(public_key))
+                .ok_or_else(|| SignerError::PublicKeyNotFound(public_key.to_owned()))?,
+            None if public_key_list.len() == 1 => public_key_list[0].clone(),
+            None => return Err(SignerError::AmbiguousPublicKey(public_key_list.len())),
+        };
+
+        let address = Address::from_str(platform.into(), &public_key_to_hex_address(&public_key)?)
+            .map_err(|_| SignerError::InvalidPublicKey(public_key.clone()))?;
+
+        Ok(Self {
+            inner: Arc::new(RemoteSignerInner {
+                url,
+                public_key,
+                address,
+                http_client,
+            }),
+        })
+    }
+
+    pub fn url(&self) -> &str {
+        &self.inner.url
+    }
+
+    pub fn address(&self) -> &Address {
+        &self.inner.address
+    }
+
+    /// Signs the bytes as an EIP-191 personal message.
+    pub async fn sign(&self, data: &[u8]) -> Result<Signature, SignerError> {
+        let request = SignRequest {
+            data: const_hex::encode_prefixed(data),
+        };
+
+        let response = self
+            .inner
+            .http_client
+            .post(format!(
+                "{}{}/{}",
+                self.inner.url, SIGN_PATH, self.inner.public_key
+            ))
+            .json(&request)
+            .send()
+            .await
+            .map_err(SignerError::Request)?;
+        let signature = check_status(response)
+            .await?
+            .text()
+            .await
+            .map_err(SignerError::Request)?;
+
+        parse_signature(signature.trim().trim_matches('"'))
+    }
+
+    /// Signs the L1 transaction with `eth_signTransaction` on the JSON-RPC
+    /// endpoint of the signer, and returns the signed RLP encoding.
+    pub async fn sign_transaction(
+        &self,
+        transaction: &TypedTransaction,
+    ) -> Result<Bytes, SignerError> {
+        let request = json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": SIGN_TRANSACTION_METHOD,
+            "params": [transaction],
+        });
+
+        let response = self
+            .inner
+            .http_client
+            .post(&self.inner.url)
+            .json(&request)
+            .send()
+            .await
+            .map_err(SignerError::Request)?;
+        let response: Value = check_status(response)
+            .await?
+            .json()
+            .await
+            .map_err(SignerError::Request)?;
+
+        let signed_transaction = response
+            .get("result")
+            .and_then(Value::as_str)
+            .ok_or_else(|| SignerError::InvalidSignedTransaction(response.to_string()))?;
+
+        const_hex::decode(signed_transaction)
+            .map(Bytes::from)
+            .map_err(|_| SignerError::InvalidSignedTransaction(signed_transaction.to_owned()))
+    }
+
+    pub async fn upcheck(&self) -> Result<(), SignerError> {
+        let response = self
+            .inner
+            .http_client
+            .get(format!("{}{}", self.inner.url, UPCHECK_PATH))
+            .send()
+            .await
+            .map_err(SignerError::Request)?;
+        check_status(response).await?;
+
+        Ok(())
+    }
+}
+
+async fn check_status(response: Response) -> Result<Response, SignerError> {
+    let status = response.status();
+    if status.is_success() {
+        return Ok(response);
+    }
+
+    Err(SignerError::Response {
+        status: status.as_u16(),
+        body: response.text().await.unwrap_or_default(),
+    })
+}
+
+/// Accepts the uncompressed key with or without its `0x04` prefix.
+pub(crate) fn public_key_to_hex_address(public_key: &str) -> Result<String, SignerError> {
+    let mut public_key_bytes = const_hex::decode(public_key)
+        .map_err(|_| SignerError::InvalidPublicKey(public_key.to_owned()))?;
+    if public_key_bytes.len() == 64 {
+        public_key_bytes.insert(0, 0x04);
+    }
+
+    let verifying_key = VerifyingKey::from_sec1_bytes(&public_key_bytes)
+        .map_err(|_| SignerError::InvalidPublicKey(public_key.to_owned()))?;
+
+    Ok(const_hex::encode_prefixed(public_key_to_address(
+        &verifying_key,
+    )))
+}
+
+/// Parses an `r || s || v` signature. Signers that return the recovery id as
+/// 0/1 are normalized to 27/28 like the local signer.
+fn parse_signature(signature: &str) -> Result<Signature, SignerError> {
+    let mut signature_bytes = const_hex::decode(signature)
+        .map_err(|_| SignerError::InvalidSignature(signature.to_owned()))?;
+    if signature_bytes.len() != 65 {
+        return Err(SignerError::InvalidSignature(signature.to_owned()));
+    }
+    if signature_bytes[64] < 27 {
+        signature_bytes[64] += 27;
+    }
+
+    serde_json::from_value(serde_json::Value::String(const_hex::encode_prefixed(
+        &signature_bytes,
+    )))
+    .map_err(|_| SignerError::InvalidSignature(signature.to_owned()))
+}
@@ -3,15 +3,23 @@ use std::{any::Any, sync::Arc};
 use radius_sdk::{
     json_rpc::client::RpcClient,
     kvstore::{CachedKvStore, CachedKvStoreError},
-    signature::PrivateKeySigner,
 };
 use skde::delay_encryption::SkdeParams;
 
 use crate::{
+    access_control::GatewayToken,
     client::{reward_manager::RewardManagerClient, seeder::SeederClient},
+    health::HealthMonitor,
     merkle_tree_manager::MerkleTreeManager,
+    metrics::Metrics,
     profiler::Profiler,
-    task::{Decryptor, SharedChannelInfos},
+    rpc::cluster::ReplayCache,
+    shutdown::ShutdownManager,
+    signer::TxOrdererSigner,
+    task::{
+        BuilderSinkManager, CensorshipMonitor, Decryptor, DiskUsageCache, LeaderHealthTracker,
+        SharedChannelInfos, SubscriptionManager,
+    },
     types::*,
 };
 
@@ -32,6 +40,16 @@ struct AppStateInner {
     rpc_client: Arc<RpcClient>,
     merkle_tree_manager: MerkleTreeManager,
     shared_channel_infos: SharedChannelInfos,
+    subscription_manager: SubscriptionManager,
+    leader_health_tracker: LeaderHealthTracker,
+    censorship_monitor: CensorshipMonitor,
+    builder_sink_manager: BuilderSinkManager,
+    metrics: Metrics,
+    health_monitor: HealthMonitor,
+    shutdown_manager: ShutdownManager,
+    disk_usage_cache: DiskUsageCache,
+    cluster_message_replay_cache: ReplayCache,
+    gateway_token: GatewayToken,
 }
 
 impl Clone for AppState {
@@ -57,6 +75,16 @@ impl AppState {
         rpc_client: Arc<RpcClient>,
         merkle_tree_manager: MerkleTreeManager,
         shared_channel_infos: SharedChannelInfos,
+        subscription_manager: SubscriptionManager,
+        leader_health_tracker: LeaderHealthTracker,
+        censorship_monitor: CensorshipMonitor,
+        builder_sink_manager: BuilderSinkManager,
+        metrics: Metrics,
+        health_monitor: HealthMonitor,
+        shutdown_manager: ShutdownManager,
+        disk_usage_cache: DiskUsageCache,
+        cluster_message_replay_cache: ReplayCache,
+        gateway_token: GatewayToken,
     ) -> Self {
         let inner = AppStateInner {
             config,
@@ -71,6 +99,16 @@ impl AppState {
             rpc_client,
             merkle_tree_manager,
             shared_channel_infos,
+            subscription_manager,
+            leader_health_tracker,
+            censorship_monitor,
+            builder_sink_manager,
+            metrics,
+            health_monitor,
+            shutdown_manager,
+            disk_usage_cache,
+            cluster_message_replay_cache,
+            gateway_token,
         };
 
         Self {
@@ -113,6 +151,46 @@ impl AppState {
     pub fn decryptor(&self) -> &Arc<Decryptor> {
         &self.inner.decryptor
     }
+
+    pub fn subscription_manager(&self) -> &SubscriptionManager {
+        &self.inner.subscription_manager
+    }
+
+    pub fn leader_health_tracker(&self) -> &LeaderHealthTracker {
+        &self.inner.leader_health_tracker
+    }
+
+    pub fn censorship_monitor(&self) -> &CensorshipMonitor {
+        &self.inner.censorship_monitor
+    }
+
+    pub fn builder_sink_manager(&self) -> &BuilderSinkManager {
+        &self.inner.builder_sink_manager
+    }
+
+    pub fn metrics(&self) -> &Metrics {
+        &self.inner.metrics
+    }
+
+    pub fn health_monitor(&self) -> &HealthMonitor {
+        &self.inner.health_monitor
+    }
+
+    pub fn shutdown_manager(&self) -> &ShutdownManager {
+        &self.inner.shutdown_manager
+    }
+
+    pub fn disk_usage_cache(&self) -> &DiskUsageCache {
+        &self.inner.disk_usage_cache
+    }
+
+    pub fn cluster_message_replay_cache(&self) -> &ReplayCache {
+        &self.inner.cluster_message_replay_cache
+    }
+
+    pub fn gateway_token(&self) -> &GatewayToken {
+        &self.inner.gateway_token
+    }
 }
 
 /// Validation client functions
@@ -186,7 +264,7 @@ impl AppState {
     pub async fn add_signer(
         &self,
         platform: Platform,
-        signer: PrivateKeySigner,
+        signer: TxOrdererSigner,
     ) -> Result<(), CachedKvStoreError> {
         let key = &(platform);
 
@@ -196,7 +274,7 @@ impl AppState {
     pub async fn get_signer(
         &self,
         platform: Platform,
-    ) -> Result<PrivateKeySigner, CachedKvStoreError> {
+    ) -> Result<TxOrdererSigner, CachedKvStoreError> {
         let key = &(platform);
 
         self.inner.signers.get(key).await
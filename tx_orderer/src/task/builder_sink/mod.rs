@@ -0,0 +1,416 @@
+mod sink;
+
+use std::{
+    collections::HashSet,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+pub use sink::*;
+use tokio::sync::Notify;
+use tracing::Instrument;
+
+use crate::{error::Error, telemetry::TraceContext, types::*};
+
+const ROLLUP_CHECK_INTERVAL: Duration = Duration::from_secs(5);
+const DELIVERY_POLL_INTERVAL: Duration = Duration::from_millis(200);
+
+const RETRY_BASE_DELAY: Duration = Duration::from_millis(200);
+const RETRY_MAX_DELAY: Duration = Duration::from_secs(30);
+/// The number of failed deliveries after which a transaction is recorded as a
+/// [`BuilderSinkDeadLetter`] and skipped.
+const MAX_DELIVERY_ATTEMPT_COUNT: u32 = 10;
+
+/// Delivers the ordered transactions of every rollup to the configured builder
+/// sinks. Each (rollup, sink) pair has its own worker that walks the
+/// transactions strictly in `(batch_number, transaction_order)` order and
+/// persists its cursor after every delivery, so a restart resumes where it
+/// left off.
+///
+/// Only the leader of a rollup delivers. A node that becomes the leader
+/// resumes from its own cursor, so the builders may receive a transaction the
+/// previous leader already delivered and have to deduplicate by position.
+pub struct BuilderSinkManager {
+    inner: Arc<BuilderSinkManagerInner>,
+}
+
+struct BuilderSinkManagerInner {
+    sink_list: Vec<BuilderSink>,
+    notify: Notify,
+    running_rollup_id_set: Mutex<HashSet<RollupId>>,
+}
+
+impl Clone for BuilderSinkManager {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl BuilderSinkManager {
+    pub fn new(config: &Config) -> Result<Self, Error> {
+        let mut sink_list = Vec::new();
+
+        if let Some(builder_rpc_url) = &config.builder_rpc_url {
+            sink_list.push(BuilderSink::JsonRpc(JsonRpcBuilderSink::new(
+                builder_rpc_url,
+            )?));
+        }
+        if let Some(builder_sink_file_path) = &config.builder_sink_file_path {
+            sink_list.push(BuilderSink::File(FileBuilderSink::new(
+                builder_sink_file_path,
+            )));
+        }
+        if let Some(builder_sink_websocket_url) = &config.builder_sink_websocket_url {
+            sink_list.push(BuilderSink::WebSocket(WebSocketBuilderSink::new(
+                builder_sink_websocket_url,
+            )));
+        }
+
+        let inner = BuilderSinkManagerInner {
+            sink_list,
+            notify: Notify::new(),
+            running_rollup_id_set: Mutex::new(HashSet::new()),
+        };
+
+        Ok(Self {
+            inner: Arc::new(inner),
+        })
+    }
+
+    /// Wakes up the workers after a transaction became available for delivery.
+    pub fn notify(&self) {
+        self.inner.notify.notify_waiters();
+    }
+
+    pub fn sink_name_list(&self) -> Vec<&'static str> {
+        self.inner
+            .sink_list
+            .iter()
+            .map(|sink| sink.name())
+            .collect()
+    }
+
+    /// Returns the number of (rollup, sink) pairs whose cursor is behind the
+    /// ordered transactions of a rollup this node leads. A sink that has not
+    /// delivered anything yet starts from the current position, so it is not
+    /// behind.
+    pub fn pending_delivery_count(&self) -> Result<usize, Error> {
+        let mut pending_delivery_count = 0;
+
+        let rollup_id_list = RollupIdList::get_or(RollupIdList::default)?;
+        for rollup_id in rollup_id_list.iter() {
+            if !is_leader(rollup_id)? {
+                continue;
+            }
+
+            let rollup_metadata = match RollupMetadata::get(rollup_id) {
+                Ok(rollup_metadata) => rollup_metadata,
+                Err(error) if error.is_none_type() => continue,
+                Err(error) => return Err(error.into()),
+            };
+
+            for sink in self.inner.sink_list.iter() {
+                let cursor = match BuilderSinkCursor::get(rollup_id, sink.name()) {
+                    Ok(cursor) => cursor,
+                    Err(error) if error.is_none_type() => continue,
+                    Err(error) => return Err(error.into()),
+                };
+
+                if (cursor.batch_number, cursor.transaction_order)
+                    < (
+                        rollup_metadata.batch_number,
+                        rollup_metadata.transaction_order,
+                    )
+                {
+                    pending_delivery_count += 1;
+                }
+            }
+        }
+
+        Ok(pending_delivery_count)
+    }
+
+    /// Starts a worker for every sink of the rollups that do not have one yet.
+    pub fn start(&self) {
+        if self.inner.sink_list.is_empty() {
+            return;
+        }
+
+        let builder_sink_manager = self.clone();
+        tokio::spawn(async move {
+            loop {
+                if let Ok(rollup_id_list) = RollupIdList::get() {
+                    for rollup_id in rollup_id_list.iter() {
+                        builder_sink_manager.start_rollup(rollup_id);
+                    }
+                }
+
+                tokio::time::sleep(ROLLUP_CHECK_INTERVAL).await;
+            }
+        });
+    }
+
+    fn start_rollup(&self, rollup_id: &RollupId) {
+        if !self
+            .inner
+            .running_rollup_id_set
+            .lock()
+            .unwrap()
+            .insert(rollup_id.clone())
+        {
+            return;
+        }
+
+        for sink in self.inner.sink_list.iter() {
+            let builder_sink_manager = self.clone();
+            let rollup_id = rollup_id.clone();
+            let sink = sink.clone();
+
+            tokio::spawn(async move {
+                builder_sink_manager.run_worker(rollup_id, sink).await;
+            });
+        }
+    }
+
+    async fn run_worker(&self, rollup_id: RollupId, sink: BuilderSink) {
+        tracing::info!(
+            "Builder sink started - rollup_id: {:?} / sink: {:?}",
+            rollup_id,
+            sink.name()
+        );
+
+        loop {
+            match deliver_next(&rollup_id, &sink).await {
+                Ok(true) => continue,
+                Ok(false) => {
+                    let notified = self.inner.notify.notified();
+                    tokio::select! {
+                        _ = notified => {}
+                        _ = tokio::time::sleep(DELIVERY_POLL_INTERVAL) => {}
+                    }
+                }
+                Err(error) => {
+                    tracing::warn!(
+                        "Builder sink failed - rollup_id: {:?} / sink: {:?} / error: {:?}",
+                        rollup_id,
+                        sink.name(),
+                        error
+                    );
+                    tokio::time::sleep(DELIVERY_POLL_INTERVAL).await;
+                }
+            }
+        }
+    }
+}
+
+enum NextPosition {
+    Deliver(RawTransaction),
+    Skip,
+    NextBatch,
+    Wait,
+}
+
+/// Delivers the transaction at the cursor of the sink, retrying with backoff
+/// up to [`MAX_DELIVERY_ATTEMPT_COUNT`] times. Returns `false` if nothing is
+/// available yet or this node does not lead the rollup.
+async fn deliver_next(rollup_id: &RollupId, sink: &BuilderSink) -> Result<bool, Error> {
+    if !is_leader(rollup_id)? {
+        return Ok(false);
+    }
+
+    let mut cursor = match BuilderSinkCursor::get(rollup_id, sink.name()) {
+        Ok(cursor) => cursor,
+        Err(error) if error.is_none_type() => {
+            // Start from the current position instead of replaying the
+            // history of the rollup to a newly configured sink.
+            let rollup_metadata = RollupMetadata::get(rollup_id)?;
+            BuilderSinkCursor::new(
+                rollup_metadata.batch_number,
+                rollup_metadata.transaction_order,
+            )
+        }
+        Err(error) => return Err(error.into()),
+    };
+
+    match get_next_position(rollup_id, &cursor)? {
+        NextPosition::Deliver(raw_transaction) => {
+            // The delivery reads the transaction back from the database, so it
+            // starts a trace of its own that carries the transaction hash.
+            let transaction_hash = raw_transaction.raw_transaction_hash().as_string();
+            let span = tracing::info_span!(
+                "deliver_to_builder",
+                rollup_id = %rollup_id,
+                sink = sink.name(),
+                batch_number = cursor.batch_number,
+                transaction_order = cursor.transaction_order,
+                transaction_hash = %transaction_hash,
+            );
+
+            let rollup = Rollup::get(rollup_id)?;
+            let mut builder_transaction = BuilderTransaction::new(
+                rollup_id.clone(),
+                rollup.rollup_type,
+                cursor.batch_number,
+                cursor.transaction_order,
+                raw_transaction,
+            );
+            builder_transaction.trace_context = TraceContext::from_span(&span);
+
+            let mut attempt_count: u32 = 0;
+            while let Err(error) = sink
+                .deliver(&builder_transaction)
+                .instrument(span.clone())
+                .await
+            {
+                attempt_count += 1;
+
+                if attempt_count >= MAX_DELIVERY_ATTEMPT_COUNT {
+                    tracing::error!(
+                        "Skipping transaction the builder did not accept - rollup_id: {:?} / sink: {:?} / batch_number: {:?} / transaction_order: {:?} / attempt_count: {:?} / error: {:?}",
+                        rollup_id,
+                        sink.name(),
+                        cursor.batch_number,
+                        cursor.transaction_order,
+                        attempt_count,
+                        error
+                    );
+
+                    BuilderSinkDeadLetterList::add(
+                        rollup_id,
+                        sink.name(),
+                        BuilderSinkDeadLetter {
+                            batch_number: cursor.batch_number,
+                            transaction_order: cursor.transaction_order,
+                            transaction_hash: transaction_hash.clone(),
+                            attempt_count,
+                            error: error.to_string(),
+                            failed_at: current_timestamp(),
+                        },
+                    )?;
+                    break;
+                }
+
+                tracing::warn!(
+                    "Failed to deliver transaction to builder - rollup_id: {:?} / sink: {:?} / batch_number: {:?} / transaction_order: {:?} / attempt_count: {:?} / error: {:?}",
+                    rollup_id,
+                    sink.name(),
+                    cursor.batch_number,
+                    cursor.transaction_order,
+                    attempt_count,
+                    error
+                );
+
+                tokio::time::sleep(get_retry_delay(attempt_count)).await;
+
+                // The new leader takes over the delivery.
+                if !is_leader(rollup_id)? {
+                    return Ok(false);
+                }
+            }
+
+            cursor.transaction_order += 1;
+        }
+        NextPosition::Skip => cursor.transaction_order += 1,
+        NextPosition::NextBatch => {
+            cursor = BuilderSinkCursor::new(cursor.batch_number + 1, 0);
+        }
+        NextPosition::Wait => return Ok(false),
+    }
+
+    cursor.put(rollup_id, sink.name())?;
+
+    Ok(true)
+}
+
+/// Doubles the delay after every failed attempt, up to [`RETRY_MAX_DELAY`].
+fn get_retry_delay(attempt_count: u32) -> Duration {
+    RETRY_BASE_DELAY
+        .saturating_mul(2_u32.saturating_pow(attempt_count.saturating_sub(1)))
+        .min(RETRY_MAX_DELAY)
+}
+
+fn is_leader(rollup_id: &RollupId) -> Result<bool, Error> {
+    let rollup = match Rollup::get(rollup_id) {
+        Ok(rollup) => rollup,
+        Err(error) if error.is_none_type() => return Ok(false),
+        Err(error) => return Err(error.into()),
+    };
+
+    match ClusterMetadata::get(
+        rollup.platform,
+        rollup.liveness_service_provider,
+        &rollup.cluster_id,
+    ) {
+        Ok(cluster_metadata) => Ok(cluster_metadata.is_leader),
+        Err(error) if error.is_none_type() => Ok(false),
+        Err(error) => Err(error.into()),
+    }
+}
+
+fn get_next_position(
+    rollup_id: &RollupId,
+    cursor: &BuilderSinkCursor,
+) -> Result<NextPosition, Error> {
+    match RawTransactionModel::get(rollup_id, cursor.batch_number, cursor.transaction_order) {
+        Ok((raw_transaction, _)) => return Ok(NextPosition::Deliver(raw_transaction)),
+        Err(error) if error.is_none_type() => {}
+        Err(error) => return Err(error.into()),
+    }
+
+    match Batch::get(rollup_id, cursor.batch_number) {
+        Ok(batch) if cursor.transaction_order >= batch.raw_transaction_list.len() as u64 => {
+            return Ok(NextPosition::NextBatch);
+        }
+        Ok(batch) => {
+            let raw_transaction =
+                batch.raw_transaction_list[cursor.transaction_order as usize].clone();
+            return Ok(NextPosition::Deliver(raw_transaction));
+        }
+        Err(error) if error.is_none_type() => {}
+        Err(error) => return Err(error.into()),
+    }
+
+    // An encrypted transaction is delivered once decrypted, and skipped if it
+    // cannot be decrypted.
+    match EncryptedTransactionModel::get(rollup_id, cursor.batch_number, cursor.transaction_order) {
+        Ok(encrypted_transaction) => {
+            let is_invalid = TransactionStatusInfo::get(
+                rollup_id,
+                encrypted_transaction.raw_transaction_hash().as_ref(),
+            )
+            .map(|transaction_status_info| transaction_status_info.is_invalid)
+            .unwrap_or(false);
+
+            return Ok(if is_invalid {
+                NextPosition::Skip
+            } else {
+                NextPosition::Wait
+            });
+        }
+        Err(error) if error.is_none_type() => {}
+        Err(error) => return Err(error.into()),
+    }
+
+    let rollup = Rollup::get(rollup_id)?;
+    if cursor.transaction_order >= rollup.max_transaction_count_per_batch {
+        return Ok(NextPosition::NextBatch);
+    }
+
+    Ok(NextPosition::Wait)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn retry_delay_doubles_up_to_the_maximum() {
+        assert_eq!(get_retry_delay(1), RETRY_BASE_DELAY);
+        assert_eq!(get_retry_delay(2), RETRY_BASE_DELAY * 2);
+        assert_eq!(get_retry_delay(3), RETRY_BASE_DELAY * 4);
+        assert_eq!(get_retry_delay(MAX_DELIVERY_ATTEMPT_COUNT), RETRY_MAX_DELAY);
+        assert_eq!(get_retry_delay(u32::MAX), RETRY_MAX_DELAY);
+    }
+}
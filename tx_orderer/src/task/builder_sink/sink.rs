@@ -0,0 +1,317 @@
+use std::{path::PathBuf, sync::Arc};
+
+use futures::SinkExt;
+use radius_sdk::json_rpc::client::{Id, RpcClient};
+use serde::{Deserialize, Serialize};
+use tokio::{fs::OpenOptions, io::AsyncWriteExt, net::TcpStream, sync::Mutex};
+use tokio_tungstenite::{connect_async, tungstenite::Message, MaybeTlsStream, WebSocketStream};
+
+use crate::{error::Error, telemetry::TraceContext, types::*};
+
+/// A transaction delivered to the block builder, in the order it was ordered.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct BuilderTransaction {
+    pub rollup_id: RollupId,
+    pub rollup_type: RollupType,
+    pub batch_number: u64,
+    pub transaction_order: u64,
+    pub raw_transaction: String,
+
+    /// The trace context of the delivery, so that the builder can continue
+    /// the trace.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trace_context: Option<TraceContext>,
+}
+
+impl BuilderTransaction {
+    pub fn new(
+        rollup_id: RollupId,
+        rollup_type: RollupType,
+        batch_number: u64,
+        transaction_order: u64,
+        raw_transaction: RawTransaction,
+    ) -> Self {
+        let raw_transaction = match raw_transaction {
+            RawTransaction::Eth(EthRawTransaction(data)) => data,
+            RawTransaction::EthBundle(EthRawBundleTransaction(data)) => data,
+        };
+
+        Self {
+            rollup_id,
+            rollup_type,
+            batch_number,
+            transaction_order,
+            raw_transaction,
+            trace_context: None,
+        }
+    }
+}
+
+/// A destination for ordered transactions. Each sink keeps its own delivery
+/// cursor per rollup.
+#[derive(Clone)]
+pub enum BuilderSink {
+    /// Sends the transaction to the builder in the format of the rollup stack.
+    JsonRpc(JsonRpcBuilderSink),
+    /// Appends one JSON object per line, so the stream can be replayed.
+    File(FileBuilderSink),
+    /// Pushes one JSON text message per transaction.
+    WebSocket(WebSocketBuilderSink),
+}
+
+impl BuilderSink {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::JsonRpc(_) => "json_rpc",
+            Self::File(_) => "file",
+            Self::WebSocket(_) => "websocket",
+        }
+    }
+
+    pub async fn deliver(&self, builder_transaction: &BuilderTransaction) -> Result<(), Error> {
+        match self {
+            Self::JsonRpc(sink) => sink.deliver(builder_transaction).await,
+            Self::File(sink) => sink.deliver(builder_transaction).await,
+            Self::WebSocket(sink) => sink.deliver(builder_transaction).await,
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct JsonRpcBuilderSink {
+    rpc_url: String,
+    rpc_client: Arc<RpcClient>,
+}
+
+impl JsonRpcBuilderSink {
+    pub fn new(rpc_url: impl AsRef<str>) -> Result<Self, Error> {
+        Ok(Self {
+            rpc_url: rpc_url.as_ref().to_owned(),
+            rpc_client: RpcClient::new().map_err(Error::RpcClient)?,
+        })
+    }
+
+    async fn deliver(&self, builder_transaction: &BuilderTransaction) -> Result<(), Error> {
+        let executor_request = builder_transaction.rollup_type.stack().executor_request(
+            &builder_transaction.raw_transaction,
+            builder_transaction.batch_number,
+            builder_transaction.transaction_order,
+        );
+
+        let _: serde_json::Value = self
+            .rpc_client
+            .request(
+                &self.rpc_url,
+                executor_request.method,
+                &executor_request.params,
+                Id::Null,
+            )
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(Clone)]
+pub struct FileBuilderSink {
+    path: PathBuf,
+}
+
+impl FileBuilderSink {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    async fn deliver(&self, builder_transaction: &BuilderTransaction) -> Result<(), Error> {
+        let mut line = serde_json::to_vec(builder_transaction).map_err(|_| Error::Convert)?;
+        line.push(b'\n');
+
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .await?;
+        file.write_all(&line).await?;
+        file.flush().await?;
+
+        Ok(())
+    }
+}
+
+type WebSocketConnection = WebSocketStream<MaybeTlsStream<TcpStream>>;
+
+#[derive(Clone)]
+pub struct WebSocketBuilderSink {
+    url: String,
+    connection: Arc<Mutex<Option<WebSocketConnection>>>,
+}
+
+impl WebSocketBuilderSink {
+    pub fn new(url: impl AsRef<str>) -> Self {
+        Self {
+            url: url.as_ref().to_owned(),
+            connection: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    /// Sends the transaction over the current connection, connecting first if
+    /// needed. A failed send drops the connection so the retry reconnects.
+    async fn deliver(&self, builder_transaction: &BuilderTransaction) -> Result<(), Error> {
+        let message = serde_json::to_string(builder_transaction).map_err(|_| Error::Convert)?;
+
+        let mut connection = self.connection.lock().await;
+        if connection.is_none() {
+            let (websocket_stream, _) = connect_async(self.url.as_str())
+                .await
+                .map_err(Error::BuilderSinkWebSocket)?;
+            *connection = Some(websocket_stream);
+        }
+
+        let result = connection
+            .as_mut()
+            .unwrap()
+            .send(Message::Text(message))
+            .await;
+        if let Err(error) = result {
+            *connection = None;
+            return Err(Error::BuilderSinkWebSocket(error));
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::{routing::post, Json, Router};
+    use futures::StreamExt;
+    use serde_json::{json, Value};
+    use tokio::{
+        net::TcpListener,
+        sync::mpsc::{unbounded_channel, UnboundedReceiver},
+    };
+    use tokio_tungstenite::accept_async;
+
+    use super::*;
+
+    fn builder_transaction(transaction_order: u64) -> BuilderTransaction {
+        BuilderTransaction::new(
+            "rollup".to_owned(),
+            RollupType::PolygonCdk,
+            2,
+            transaction_order,
+            RawTransaction::from(EthRawTransaction::from(format!(
+                "0x02{:02x}",
+                transaction_order
+            ))),
+        )
+    }
+
+    /// Returns the URL of a JSON-RPC builder and the requests it receives.
+    async fn run_json_rpc_builder() -> (String, UnboundedReceiver<Value>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let (sender, receiver) = unbounded_channel();
+        let router = Router::new().route(
+            "/",
+            post(move |Json(request): Json<Value>| async move {
+                sender.send(request).unwrap();
+                Json(json!({ "jsonrpc": "2.0", "id": null, "result": null }))
+            }),
+        );
+        tokio::spawn(async move { axum::serve(listener, router).await });
+
+        (url, receiver)
+    }
+
+    /// Returns the URL of a websocket builder and the messages it receives.
+    /// Every connection is closed after its first message.
+    async fn run_websocket_builder() -> (String, UnboundedReceiver<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("ws://{}", listener.local_addr().unwrap());
+        let (sender, receiver) = unbounded_channel();
+        tokio::spawn(async move {
+            while let Ok((tcp_stream, _)) = listener.accept().await {
+                let mut websocket_stream = accept_async(tcp_stream).await.unwrap();
+                if let Some(Ok(Message::Text(message))) = websocket_stream.next().await {
+                    sender.send(message).unwrap();
+                }
+            }
+        });
+
+        (url, receiver)
+    }
+
+    #[tokio::test]
+    async fn json_rpc_sink_sends_the_request_of_the_rollup_stack() {
+        let (url, mut receiver) = run_json_rpc_builder().await;
+        let sink = BuilderSink::JsonRpc(JsonRpcBuilderSink::new(url).unwrap());
+
+        sink.deliver(&builder_transaction(3)).await.unwrap();
+
+        let request = receiver.recv().await.unwrap();
+        assert_eq!(request["method"], "eth_sendRawTransaction");
+        assert_eq!(request["params"], json!(["0x0203", 2, 3]));
+    }
+
+    #[tokio::test]
+    async fn file_sink_appends_one_line_per_transaction() {
+        let path = std::env::temp_dir().join(format!("builder-sink-{}.jsonl", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let sink = BuilderSink::File(FileBuilderSink::new(&path));
+
+        for transaction_order in 0..3 {
+            sink.deliver(&builder_transaction(transaction_order))
+                .await
+                .unwrap();
+        }
+
+        let content = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        let transaction_order_list: Vec<u64> = content
+            .lines()
+            .map(|line| {
+                serde_json::from_str::<BuilderTransaction>(line)
+                    .unwrap()
+                    .transaction_order
+            })
+            .collect();
+        assert_eq!(transaction_order_list, vec![0, 1, 2]);
+        assert!(!content.contains("trace_context"));
+    }
+
+    #[tokio::test]
+    async fn websocket_sink_reconnects_after_a_failed_send() {
+        let (url, mut receiver) = run_websocket_builder().await;
+        let sink = BuilderSink::WebSocket(WebSocketBuilderSink::new(url));
+
+        sink.deliver(&builder_transaction(0)).await.unwrap();
+        let message = receiver.recv().await.unwrap();
+        assert_eq!(
+            serde_json::from_str::<BuilderTransaction>(&message)
+                .unwrap()
+                .transaction_order,
+            0
+        );
+
+        // The builder closed the connection, so a send over it fails within a
+        // few attempts.
+        let mut attempt_count = 0;
+        while sink.deliver(&builder_transaction(1)).await.is_ok() {
+            attempt_count += 1;
+            assert!(attempt_count < 20);
+            tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+        }
+
+        // The next delivery goes over a new connection.
+        sink.deliver(&builder_transaction(2)).await.unwrap();
+        let message = receiver.recv().await.unwrap();
+        assert_eq!(
+            serde_json::from_str::<BuilderTransaction>(&message)
+                .unwrap()
+                .transaction_order,
+            2
+        );
+    }
+}
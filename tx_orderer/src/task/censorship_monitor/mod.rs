@@ -0,0 +1,490 @@
+use std::{
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+use radius_sdk::json_rpc::{client::RpcClientError, server::RpcParameter};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    error::Error,
+    rpc::external::{SendEncryptedTransaction, SendRawTransaction},
+    state::AppState,
+    types::*,
+};
+
+const CENSORSHIP_CHECK_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Number of leaders a transaction is forwarded to before the follower gives
+/// up on it.
+const MAX_FORWARD_COUNT: u64 = 3;
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize)]
+pub struct CensorshipMetrics {
+    pub forwarded_transaction_count: u64,
+    pub included_transaction_count: u64,
+    pub censorship_violation_count: u64,
+    pub reforwarded_transaction_count: u64,
+    pub abandoned_transaction_count: u64,
+}
+
+/// Counts what happened to the transactions this follower forwarded to the
+/// leader. The forwarded transactions themselves are kept in the database.
+pub struct CensorshipMonitor {
+    inner: Arc<CensorshipMonitorInner>,
+}
+
+#[derive(Default)]
+struct CensorshipMonitorInner {
+    forwarded_transaction_count: AtomicU64,
+    included_transaction_count: AtomicU64,
+    censorship_violation_count: AtomicU64,
+    reforwarded_transaction_count: AtomicU64,
+    abandoned_transaction_count: AtomicU64,
+}
+
+impl Clone for CensorshipMonitor {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl Default for CensorshipMonitor {
+    fn default() -> Self {
+        Self {
+            inner: Arc::new(CensorshipMonitorInner::default()),
+        }
+    }
+}
+
+impl CensorshipMonitor {
+    pub fn metrics(&self) -> CensorshipMetrics {
+        CensorshipMetrics {
+            forwarded_transaction_count: self
+                .inner
+                .forwarded_transaction_count
+                .load(Ordering::Relaxed),
+            included_transaction_count: self
+                .inner
+                .included_transaction_count
+                .load(Ordering::Relaxed),
+            censorship_violation_count: self
+                .inner
+                .censorship_violation_count
+                .load(Ordering::Relaxed),
+            reforwarded_transaction_count: self
+                .inner
+                .reforwarded_transaction_count
+                .load(Ordering::Relaxed),
+            abandoned_transaction_count: self
+                .inner
+                .abandoned_transaction_count
+                .load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// Whether a transaction forwarded to the leader has to be watched: the leader
+/// accepted it, or could not be reached. A transaction the leader answered with
+/// an error for, and one that was not forwarded at all, is not the leader's to
+/// include.
+pub fn is_watched_forward_result<T>(result: &Result<T, Error>) -> bool {
+    match result {
+        Ok(_) => true,
+        Err(Error::RpcClient(RpcClientError::Response(_))) => false,
+        Err(
+            Error::RpcClient(_)
+            | Error::EmptyLeader
+            | Error::EmptyLeaderClusterRpcUrl
+            | Error::LeaderUnhealthy,
+        ) => true,
+        Err(_) => false,
+    }
+}
+
+/// Records a transaction forwarded to the leader with the batch number by
+/// which it has to be in a sealed batch. Forwarding the same transaction again
+/// moves the deadline and keeps the forward count.
+pub fn watch_forwarded_transaction(
+    context: &AppState,
+    rollup: &Rollup,
+    transaction_hash: &RawTransactionHash,
+    payload: ForwardedTransactionPayload,
+) {
+    if let Err(error) = try_watch_forwarded_transaction(context, rollup, transaction_hash, payload)
+    {
+        tracing::warn!(
+            "Failed to watch forwarded transaction - rollup_id: {:?} / transaction_hash: {:?} / error: {:?}",
+            rollup.rollup_id,
+            transaction_hash,
+            error
+        );
+    }
+}
+
+fn try_watch_forwarded_transaction(
+    context: &AppState,
+    rollup: &Rollup,
+    transaction_hash: &RawTransactionHash,
+    payload: ForwardedTransactionPayload,
+) -> Result<(), Error> {
+    let rollup_id = &rollup.rollup_id;
+    let cluster_metadata = ClusterMetadata::get(
+        rollup.platform,
+        rollup.liveness_service_provider,
+        &rollup.cluster_id,
+    )?;
+    let Some(leader_tx_orderer_rpc_info) = cluster_metadata.leader_tx_orderer_rpc_info else {
+        return Err(Error::EmptyLeader);
+    };
+
+    // Followers only learn the current batch number of the leader through
+    // heartbeats, so the last hand-over is used until the first one arrives.
+    let rollup_metadata = RollupMetadata::get(rollup_id)?;
+    let batch_number = context
+        .leader_health_tracker()
+        .get(rollup_id)
+        .map(|leader_health| leader_health.batch_number)
+        .unwrap_or_default()
+        .max(rollup_metadata.batch_number);
+
+    let forward_count = match ForwardedTransaction::get(rollup_id, transaction_hash.as_ref()) {
+        Ok(forwarded_transaction) => forwarded_transaction.forward_count + 1,
+        Err(error) if error.is_none_type() => 1,
+        Err(error) => return Err(error.into()),
+    };
+
+    let forwarded_transaction = ForwardedTransaction {
+        payload,
+        leader_tx_orderer_address: leader_tx_orderer_rpc_info.tx_orderer_address,
+        forwarded_batch_number: batch_number,
+        deadline_batch_number: batch_number + context.config().censorship_deadline_batch_count,
+        forward_count,
+        is_violated: false,
+        forwarded_at: current_timestamp(),
+    };
+    forwarded_transaction.put(rollup_id, transaction_hash.as_ref())?;
+
+    let mut forwarded_transaction_list =
+        ForwardedTransactionList::get_mut_or(rollup_id, ForwardedTransactionList::default)?;
+    forwarded_transaction_list.insert(transaction_hash);
+    forwarded_transaction_list.update()?;
+
+    context
+        .censorship_monitor()
+        .inner
+        .forwarded_transaction_count
+        .fetch_add(1, Ordering::Relaxed);
+
+    Ok(())
+}
+
+/// Periodically checks whether the forwarded transactions made it into a
+/// sealed batch. A transaction that is still missing once the deadline batch
+/// is sealed raises a censorship alert, and is forwarded again as soon as
+/// another tx_orderer has become the leader.
+pub fn run_censorship_monitor(context: AppState) {
+    tokio::spawn(async move {
+        loop {
+            tokio::time::sleep(CENSORSHIP_CHECK_INTERVAL).await;
+
+            let rollup_id_list = match RollupIdList::get() {
+                Ok(rollup_id_list) => rollup_id_list,
+                Err(_) => continue,
+            };
+
+            for rollup_id in rollup_id_list.iter() {
+                if let Err(error) = check_forwarded_transactions(&context, rollup_id).await {
+                    tracing::warn!(
+                        "Censorship check failed - rollup_id: {:?} / error: {:?}",
+                        rollup_id,
+                        error
+                    );
+                }
+            }
+        }
+    });
+}
+
+async fn check_forwarded_transactions(
+    context: &AppState,
+    rollup_id: &RollupId,
+) -> Result<(), Error> {
+    let forwarded_transaction_list =
+        ForwardedTransactionList::get_or(rollup_id, ForwardedTransactionList::default)?;
+    if forwarded_transaction_list.is_empty() {
+        return Ok(());
+    }
+
+    let rollup = Rollup::get(rollup_id)?;
+    let cluster_metadata = ClusterMetadata::get(
+        rollup.platform,
+        rollup.liveness_service_provider,
+        &rollup.cluster_id,
+    )?;
+    let current_leader_tx_orderer_address = cluster_metadata
+        .leader_tx_orderer_rpc_info
+        .map(|leader_tx_orderer_rpc_info| leader_tx_orderer_rpc_info.tx_orderer_address);
+
+    let censorship_monitor = context.censorship_monitor();
+
+    for transaction_hash in forwarded_transaction_list.iter() {
+        let mut forwarded_transaction = match ForwardedTransaction::get(rollup_id, transaction_hash)
+        {
+            Ok(forwarded_transaction) => forwarded_transaction,
+            Err(error) if error.is_none_type() => {
+                remove_forwarded_transaction(rollup_id, transaction_hash)?;
+                continue;
+            }
+            Err(error) => return Err(error.into()),
+        };
+
+        let is_included = is_in_sealed_batch(rollup_id, transaction_hash)?;
+        let is_deadline_sealed =
+            !is_included && is_sealed(rollup_id, forwarded_transaction.deadline_batch_number)?;
+        let is_rotated =
+            current_leader_tx_orderer_address
+                .as_ref()
+                .is_some_and(|leader_tx_orderer_address| {
+                    *leader_tx_orderer_address != forwarded_transaction.leader_tx_orderer_address
+                });
+
+        let action = get_forwarded_transaction_action(
+            is_included,
+            is_deadline_sealed,
+            is_rotated,
+            forwarded_transaction.forward_count,
+        );
+
+        match action {
+            ForwardedTransactionAction::Included => {
+                remove_forwarded_transaction(rollup_id, transaction_hash)?;
+                censorship_monitor
+                    .inner
+                    .included_transaction_count
+                    .fetch_add(1, Ordering::Relaxed);
+                continue;
+            }
+            ForwardedTransactionAction::Pending => continue,
+            _ => {}
+        }
+
+        if !forwarded_transaction.is_violated {
+            tracing::error!(
+                "Censorship alert - rollup_id: {:?} / transaction_hash: {:?} / leader: {:?} / forwarded_batch_number: {:?} / deadline_batch_number: {:?}",
+                rollup_id,
+                transaction_hash,
+                forwarded_transaction.leader_tx_orderer_address,
+                forwarded_transaction.forwarded_batch_number,
+                forwarded_transaction.deadline_batch_number
+            );
+
+            CensorshipViolationList::add(
+                rollup_id,
+                CensorshipViolation {
+                    transaction_hash: transaction_hash.clone(),
+                    leader_tx_orderer_address: forwarded_transaction
+                        .leader_tx_orderer_address
+                        .clone(),
+                    forwarded_batch_number: forwarded_transaction.forwarded_batch_number,
+                    deadline_batch_number: forwarded_transaction.deadline_batch_number,
+                    forward_count: forwarded_transaction.forward_count,
+                    detected_at: current_timestamp(),
+                },
+            )?;
+            censorship_monitor
+                .inner
+                .censorship_violation_count
+                .fetch_add(1, Ordering::Relaxed);
+
+            forwarded_transaction.is_violated = true;
+        }
+
+        if action == ForwardedTransactionAction::Watch {
+            forwarded_transaction.put(rollup_id, transaction_hash)?;
+            continue;
+        }
+
+        if action == ForwardedTransactionAction::Abandon {
+            tracing::warn!(
+                "Giving up on censored transaction - rollup_id: {:?} / transaction_hash: {:?} / forward_count: {:?}",
+                rollup_id,
+                transaction_hash,
+                forwarded_transaction.forward_count
+            );
+
+            remove_forwarded_transaction(rollup_id, transaction_hash)?;
+            censorship_monitor
+                .inner
+                .abandoned_transaction_count
+                .fetch_add(1, Ordering::Relaxed);
+            continue;
+        }
+
+        forwarded_transaction.put(rollup_id, transaction_hash)?;
+
+        // The send handlers either order the transaction on this node if it
+        // became the leader or forward it to the new leader and watch it again.
+        let result = match forwarded_transaction.payload {
+            ForwardedTransactionPayload::Raw(raw_transaction) => SendRawTransaction {
+                rollup_id: rollup_id.clone(),
+                raw_transaction,
+                trace_context: None,
+            }
+            .handler(context.clone())
+            .await
+            .map(|_| ()),
+            ForwardedTransactionPayload::Encrypted(encrypted_transaction) => {
+                SendEncryptedTransaction {
+                    rollup_id: rollup_id.clone(),
+                    encrypted_transaction,
+                    trace_context: None,
+                }
+                .handler(context.clone())
+                .await
+                .map(|_| ())
+            }
+        };
+
+        match result {
+            Ok(()) => {
+                censorship_monitor
+                    .inner
+                    .reforwarded_transaction_count
+                    .fetch_add(1, Ordering::Relaxed);
+            }
+            Err(error) => {
+                tracing::warn!(
+                    "Failed to forward censored transaction again - rollup_id: {:?} / transaction_hash: {:?} / error: {:?}",
+                    rollup_id,
+                    transaction_hash,
+                    error
+                );
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// What a check does with a forwarded transaction.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum ForwardedTransactionAction {
+    /// The transaction is in a sealed batch and is no longer watched.
+    Included,
+    /// The deadline batch is not sealed yet.
+    Pending,
+    /// The deadline has passed, but the same leader is still in charge.
+    Watch,
+    /// The deadline has passed and another tx_orderer became the leader.
+    Reforward,
+    /// The deadline has passed and the transaction has been forwarded to
+    /// [`MAX_FORWARD_COUNT`] leaders already.
+    Abandon,
+}
+
+fn get_forwarded_transaction_action(
+    is_included: bool,
+    is_deadline_sealed: bool,
+    is_rotated: bool,
+    forward_count: u64,
+) -> ForwardedTransactionAction {
+    if is_included {
+        ForwardedTransactionAction::Included
+    } else if !is_deadline_sealed {
+        ForwardedTransactionAction::Pending
+    } else if !is_rotated {
+        ForwardedTransactionAction::Watch
+    } else if forward_count >= MAX_FORWARD_COUNT {
+        ForwardedTransactionAction::Abandon
+    } else {
+        ForwardedTransactionAction::Reforward
+    }
+}
+
+fn is_in_sealed_batch(rollup_id: &RollupId, transaction_hash: &str) -> Result<bool, Error> {
+    match TransactionStatusInfo::get(rollup_id, transaction_hash) {
+        Ok(transaction_status_info) => is_sealed(rollup_id, transaction_status_info.batch_number),
+        Err(error) if error.is_none_type() => Ok(false),
+        Err(error) => Err(error.into()),
+    }
+}
+
+fn is_sealed(rollup_id: &RollupId, batch_number: u64) -> Result<bool, Error> {
+    match Batch::get(rollup_id, batch_number) {
+        Ok(_) => Ok(true),
+        Err(error) if error.is_none_type() => Ok(false),
+        Err(error) => Err(error.into()),
+    }
+}
+
+fn remove_forwarded_transaction(rollup_id: &RollupId, transaction_hash: &str) -> Result<(), Error> {
+    ForwardedTransaction::delete(rollup_id, transaction_hash)?;
+
+    let mut forwarded_transaction_list =
+        ForwardedTransactionList::get_mut_or(rollup_id, ForwardedTransactionList::default)?;
+    forwarded_transaction_list.remove(transaction_hash);
+    forwarded_transaction_list.update()?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn transaction_is_pending_until_the_deadline_batch_is_sealed() {
+        assert_eq!(
+            get_forwarded_transaction_action(false, false, true, 1),
+            ForwardedTransactionAction::Pending
+        );
+        assert_eq!(
+            get_forwarded_transaction_action(true, false, false, 1),
+            ForwardedTransactionAction::Included
+        );
+        // A transaction included in time is never reported.
+        assert_eq!(
+            get_forwarded_transaction_action(true, true, true, MAX_FORWARD_COUNT),
+            ForwardedTransactionAction::Included
+        );
+    }
+
+    #[test]
+    fn missed_deadline_is_reforwarded_once_the_leader_rotates() {
+        assert_eq!(
+            get_forwarded_transaction_action(false, true, false, 1),
+            ForwardedTransactionAction::Watch
+        );
+        assert_eq!(
+            get_forwarded_transaction_action(false, true, true, 1),
+            ForwardedTransactionAction::Reforward
+        );
+        assert_eq!(
+            get_forwarded_transaction_action(false, true, true, MAX_FORWARD_COUNT - 1),
+            ForwardedTransactionAction::Reforward
+        );
+        assert_eq!(
+            get_forwarded_transaction_action(false, true, true, MAX_FORWARD_COUNT),
+            ForwardedTransactionAction::Abandon
+        );
+    }
+
+    #[test]
+    fn unreachable_leader_is_watched_but_rejected_transaction_is_not() {
+        assert!(is_watched_forward_result(&Ok(())));
+        assert!(is_watched_forward_result::<()>(&Err(Error::EmptyLeader)));
+        assert!(is_watched_forward_result::<()>(&Err(
+            Error::LeaderUnhealthy
+        )));
+        assert!(!is_watched_forward_result::<()>(&Err(Error::LeaderChanged)));
+        assert!(!is_watched_forward_result::<()>(&Err(
+            Error::ForwardQueueFull
+        )));
+    }
+}
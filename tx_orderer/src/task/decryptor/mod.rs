@@ -1,23 +1,40 @@
-use std::{collections::HashMap, sync::Arc, time::Duration};
+use std::{
+    collections::HashMap,
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
 use futures::future::try_join_all;
-use radius_sdk::json_rpc::client::{Id, RpcClient};
 use skde::delay_encryption::{decrypt, SkdeParams};
 use tokio::{
     sync::{Mutex, Notify, RwLock},
     time::sleep,
 };
+use tracing::Instrument;
 
 use crate::{
     client::distributed_key_generation::DistributedKeyGenerationClient,
-    error::{self, Error},
+    error::Error,
+    metrics::Metrics,
+    task::{BuilderSinkManager, SubscriptionManager},
     types::{
-        to_raw_tx, CanProvideTransactionInfo, EncryptedTransaction, EthPlainData,
-        EthRawTransaction, PlainData, RawTransaction, RawTransactionModel, RollupId,
-        SkdeEncryptedTransaction, TransactionData,
+        CanProvideTransactionInfo, EncryptedTransaction, EthPlainData, PlainData, RawTransaction,
+        RawTransactionModel, RollupId, SkdeEncryptedTransaction, TransactionData,
+        TransactionStatusInfo,
     },
 };
 
+/// An encrypted transaction with its rollup id, batch number and transaction
+/// order, the time it was queued at and the span of its decryption.
+type QueuedTransaction = (
+    String,
+    u64,
+    u64,
+    SkdeEncryptedTransaction,
+    Instant,
+    tracing::Span,
+);
+
 pub struct Decryptor {
     inner: Arc<DecryptorInner>,
 }
@@ -27,10 +44,12 @@ struct DecryptorInner {
     latest_decryption_key_id: RwLock<u64>,
     decryption_keys: Mutex<HashMap<u64, String>>,
     distributed_key_generation_client: DistributedKeyGenerationClient,
-    encrypted_transactions: Mutex<HashMap<u64, Vec<(String, u64, u64, SkdeEncryptedTransaction)>>>,
+    /// The transactions waiting for each decryption key.
+    encrypted_transactions: Mutex<HashMap<u64, Vec<QueuedTransaction>>>,
     notify: Notify,
-    rpc_client: Arc<RpcClient>,
-    builder_rpc_url: Option<String>,
+    subscription_manager: SubscriptionManager,
+    builder_sink_manager: BuilderSinkManager,
+    metrics: Metrics,
 }
 
 impl Decryptor {
@@ -38,7 +57,9 @@ impl Decryptor {
         distributed_key_generation_client: DistributedKeyGenerationClient,
         skde_params: SkdeParams,
         latest_decryption_key_id: u64,
-        builder_rpc_url: Option<String>,
+        subscription_manager: SubscriptionManager,
+        builder_sink_manager: BuilderSinkManager,
+        metrics: Metrics,
     ) -> Result<Arc<Self>, Error> {
         let decryptor = Arc::new(Self {
             inner: Arc::new(DecryptorInner {
@@ -48,8 +69,9 @@ impl Decryptor {
                 encrypted_transactions: Mutex::new(HashMap::new()),
                 distributed_key_generation_client,
                 notify: Notify::new(),
-                rpc_client: RpcClient::new().map_err(error::Error::RpcClient)?,
-                builder_rpc_url,
+                subscription_manager,
+                builder_sink_manager,
+                metrics,
             }),
         });
 
@@ -93,97 +115,120 @@ impl Decryptor {
                     let mut decryption_handle_list = Vec::new();
                     let decrypted_transaction_order_list: Arc<Mutex<Vec<(String, u64, u64)>>> =
                         Arc::new(Mutex::new(Vec::new()));
-                    for (rollup_id, batch_number, transaction_order, encrypted_transaction) in
-                        encrypted_transactions
+                    for (
+                        rollup_id,
+                        batch_number,
+                        transaction_order,
+                        encrypted_transaction,
+                        queued_at,
+                        span,
+                    ) in encrypted_transactions
                     {
                         let skde_params = self.inner.skde_params.clone();
                         let decryption_key = decryption_key.clone();
                         let cloned_decrypted_transaction_order_list =
                             Arc::clone(&decrypted_transaction_order_list);
 
-                        let cloned_builder_rpc_url = self.inner.builder_rpc_url.clone();
-                        let cloned_rpc_client = Arc::clone(&self.inner.rpc_client);
-
-                        let decryption_handle = tokio::spawn(async move {
-                            match decrypt_skde_transaction(
-                                &skde_params,
-                                &decryption_key,
-                                &encrypted_transaction,
-                            )
-                            .await
-                            {
-                                Ok((raw_transaction, _plain_data)) => {
-                                    let raw_transaction_hash = encrypted_transaction
-                                        .transaction_data
-                                        .raw_transaction_hash();
-
-                                    let _ = RawTransactionModel::put_with_transaction_hash(
-                                        &rollup_id,
-                                        &raw_transaction_hash,
-                                        raw_transaction.clone(),
-                                        false,
-                                    )
-                                    .map_err(|error| {
-                                        tracing::error!(
-                                            "Failed to put raw transaction with hash: {:?}",
-                                            error
-                                        );
-                                        Error::Database(error)
-                                    });
-
-                                    let _ = RawTransactionModel::put(
-                                        &rollup_id,
-                                        batch_number,
-                                        transaction_order,
-                                        raw_transaction.clone(),
-                                        false,
-                                    )
-                                    .map_err(|error| {
-                                        tracing::error!(
-                                            "Failed to put raw transaction: {:?}",
-                                            error
-                                        );
-                                        Error::Database(error)
-                                    });
-
-                                    cloned_decrypted_transaction_order_list.lock().await.push((
-                                        rollup_id,
-                                        batch_number,
-                                        transaction_order,
-                                    ));
-
-                                    if cloned_builder_rpc_url.is_some() {
-                                        let params = serde_json::json!([
-                                            raw_transaction,
+                        let cloned_subscription_manager = self.inner.subscription_manager.clone();
+                        let cloned_builder_sink_manager = self.inner.builder_sink_manager.clone();
+                        let cloned_metrics = self.inner.metrics.clone();
+
+                        let decryption_handle = tokio::spawn(
+                            async move {
+                                let decryption_result = decrypt_skde_transaction(
+                                    &skde_params,
+                                    &decryption_key,
+                                    &encrypted_transaction,
+                                )
+                                .await;
+                                cloned_metrics.record_decryption(queued_at.elapsed());
+
+                                match decryption_result {
+                                    Ok((raw_transaction, _plain_data)) => {
+                                        let raw_transaction_hash = encrypted_transaction
+                                            .transaction_data
+                                            .raw_transaction_hash();
+
+                                        let _ = RawTransactionModel::put_with_transaction_hash(
+                                            &rollup_id,
+                                            &raw_transaction_hash,
+                                            raw_transaction.clone(),
+                                            false,
+                                        )
+                                        .map_err(|error| {
+                                            tracing::error!(
+                                                "Failed to put raw transaction with hash: {:?}",
+                                                error
+                                            );
+                                            Error::Database(error)
+                                        });
+
+                                        let _ = RawTransactionModel::put(
+                                            &rollup_id,
+                                            batch_number,
+                                            transaction_order,
+                                            raw_transaction.clone(),
+                                            false,
+                                        )
+                                        .map_err(|error| {
+                                            tracing::error!(
+                                                "Failed to put raw transaction: {:?}",
+                                                error
+                                            );
+                                            Error::Database(error)
+                                        });
+
+                                        let _ = TransactionStatusInfo::set_decrypted(
+                                            &rollup_id,
+                                            &raw_transaction_hash,
+                                        )
+                                        .map_err(|error| {
+                                            tracing::error!(
+                                                "Failed to update transaction status info: {:?}",
+                                                error
+                                            );
+                                        });
+
+                                        cloned_subscription_manager.publish_decrypted_transaction(
+                                            &rollup_id,
                                             batch_number,
-                                            transaction_order
-                                        ]);
-
-                                        let _: String = cloned_rpc_client
-                                            .request(
-                                                &cloned_builder_rpc_url.clone().unwrap(),
-                                                "eth_sendRawTransaction",
-                                                &params,
-                                                Id::Null,
-                                            )
+                                            transaction_order,
+                                            &raw_transaction,
+                                        );
+
+                                        cloned_decrypted_transaction_order_list
+                                            .lock()
                                             .await
-                                            .map_err(|error| {
-                                                tracing::error!(
-                                                    "Failed to send raw transaction: {:?}",
-                                                    error
-                                                );
-                                                Error::RpcClient(error)
-                                            })
-                                            .unwrap();
+                                            .push((rollup_id, batch_number, transaction_order));
+
+                                        cloned_builder_sink_manager.notify();
+                                    }
+                                    Err(e) => {
+                                        tracing::error!("Failed to decrypt transaction: {:?}", e);
+
+                                        let raw_transaction_hash = encrypted_transaction
+                                            .transaction_data
+                                            .raw_transaction_hash();
+
+                                        let _ = TransactionStatusInfo::set_invalid(
+                                            &rollup_id,
+                                            &raw_transaction_hash,
+                                        )
+                                        .map_err(|error| {
+                                            tracing::error!(
+                                                "Failed to update transaction status info: {:?}",
+                                                error
+                                            );
+                                        });
+
+                                        cloned_builder_sink_manager.notify();
                                     }
                                 }
-                                Err(e) => {
-                                    tracing::error!("Failed to decrypt transaction: {:?}", e);
-                                }
-                            }
 
-                            ()
-                        });
+                                ()
+                            }
+                            .instrument(span),
+                        );
                         decryption_handle_list.push(decryption_handle);
                     }
 
@@ -266,6 +311,21 @@ impl Decryptor {
         }
     }
 
+    pub fn distributed_key_generation_client(&self) -> &DistributedKeyGenerationClient {
+        &self.inner.distributed_key_generation_client
+    }
+
+    /// Returns the number of encrypted transactions waiting for decryption.
+    pub async fn backlog(&self) -> usize {
+        self.inner
+            .encrypted_transactions
+            .lock()
+            .await
+            .values()
+            .map(Vec::len)
+            .sum()
+    }
+
     pub async fn add_encrypted_transaction_to_decrypt(
         &self,
         rollup_id: RollupId,
@@ -273,6 +333,16 @@ impl Decryptor {
         transaction_order: u64,
         encrypted_transaction: EncryptedTransaction,
     ) -> Result<(), Error> {
+        // The span is entered once the decryption key is available, in the
+        // trace of the transaction that queued it.
+        let span = tracing::info_span!(
+            "decrypt_transaction",
+            rollup_id = %rollup_id,
+            batch_number,
+            transaction_order,
+            transaction_hash = %encrypted_transaction.raw_transaction_hash().as_string(),
+        );
+
         {
             match encrypted_transaction {
                 EncryptedTransaction::Skde(encrypted_transaction) => {
@@ -285,7 +355,10 @@ impl Decryptor {
                             batch_number,
                             transaction_order,
                             encrypted_transaction,
+                            Instant::now(),
+                            span,
                         ));
+                    self.inner.metrics.add_decryption_queue_depth(1);
                 }
             }
         }
@@ -323,11 +396,9 @@ async fn decrypt_skde_transaction(
                     Error::Deserialize
                 })?;
 
-            let rollup_transaction = transaction_data
+            let eth_raw_transaction = transaction_data
                 .open_data
-                .convert_to_rollup_transaction(&eth_plain_data);
-
-            let eth_raw_transaction = EthRawTransaction::from(to_raw_tx(rollup_transaction));
+                .to_raw_transaction(&eth_plain_data)?;
             let raw_transaction = RawTransaction::from(eth_raw_transaction);
 
             Ok((raw_transaction, PlainData::from(eth_plain_data)))
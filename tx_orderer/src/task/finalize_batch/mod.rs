@@ -2,7 +2,7 @@
 mod validation;
 
 use radius_sdk::{json_rpc::client::RpcClient, signature::Signature};
-use tokio::time::Duration;
+use tokio::time::{Duration, Instant};
 use validation::submit_batch_commitment;
 
 use crate::{
@@ -24,7 +24,7 @@ pub fn finalize_batch(context: AppState, rollup_id: &RollupId, batch_number: u64
     }
 
     let rollup_id = rollup_id.to_string();
-    tokio::spawn(async move {
+    context.shutdown_manager().clone().spawn(async move {
         if let Err(error) = finalize_batch_task(context, &rollup_id, batch_number).await {
             tracing::error!(
                 "Failed to finalize batch - rollup_id: {:?}, batch_number: {:?}, error: {:?}",
@@ -41,6 +41,7 @@ async fn finalize_batch_task(
     rollup_id: &RollupId,
     batch_number: u64,
 ) -> Result<(), Error> {
+    let started_at = Instant::now();
     let rollup = Rollup::get(rollup_id)?;
     let max_transaction_count_per_batch = rollup.max_transaction_count_per_batch;
     let cluster_meta = ClusterMetadata::get(
@@ -74,13 +75,14 @@ async fn finalize_batch_task(
         } = match result {
             Ok(data) => data,
             Err(_) => {
+                context.metrics().record_batch_finalize_retry(rollup_id);
                 tokio::time::sleep(Duration::from_secs(1)).await;
                 continue;
             }
         };
 
         let signer = context.get_signer(rollup.platform).await?;
-        let batch_creator_signature = signer.sign_message(&batch_commitment)?;
+        let batch_creator_signature = signer.sign_message(&batch_commitment).await?;
 
         let batch = Batch::new(
             batch_number,
@@ -105,7 +107,13 @@ async fn finalize_batch_task(
             .expect("Failed to delete CanProvideTransactionInfo");
 
         Batch::put(&batch, rollup_id, batch_number)?;
+        context
+            .subscription_manager()
+            .publish_batch(rollup_id, &batch);
         tracing::info!("Finalize batch DONE - {}, {}", rollup_id, batch_number);
+        context
+            .metrics()
+            .record_batch_finalize(rollup_id, started_at.elapsed());
 
         submit_batch_commitment(context, &rollup, batch_number, &batch_commitment).await;
 
@@ -132,7 +140,7 @@ pub fn create_batch(
     }
 
     let rollup_id = rollup_id.to_string();
-    tokio::spawn(async move {
+    context.shutdown_manager().clone().spawn(async move {
         if let Err(error) = create_batch_task(
             context,
             &rollup_id,
@@ -235,6 +243,9 @@ pub async fn create_batch_task(
                 .expect("Failed to delete CanProvideTransactionInfo");
 
                 Batch::put(&batch, rollup_id, batch_number)?;
+                context
+                    .subscription_manager()
+                    .publish_batch(rollup_id, &batch);
             } else {
                 tracing::error!(
                     "Failed to verify leader tx orderer signature - rollup_id: {:?}, batch_number: {:?} / tx_orderer_address_list: {:?} / signer_address: {:?} / batch_commitment: {:?} / raw_transaction_list_count: {:?}",
@@ -310,13 +321,12 @@ async fn build_batch_data(
         }
     }
 
-    let merkle_tree = MerkleTree::new();
-    for (raw_transaction, _) in &raw_transaction_info_list {
-        merkle_tree
-            .add_data(raw_transaction.raw_transaction_hash().as_ref())
-            .await;
-    }
-    merkle_tree.finalize_tree().await;
+    let merkle_tree = MerkleTree::from_raw_transaction_list(
+        raw_transaction_info_list
+            .iter()
+            .map(|(raw_transaction, _)| raw_transaction),
+    )
+    .await;
     let batch_commitment = merkle_tree.get_merkle_root().await;
 
     let raw_transaction_list: Vec<RawTransaction> = raw_transaction_info_list
@@ -2,7 +2,7 @@ use std::time::Duration;
 
 use tokio::time::sleep;
 
-use super::{Rollup, ValidationInfo, ValidationServiceProvider};
+use super::{BatchCommitmentSubmission, Rollup, ValidationInfo, ValidationServiceProvider};
 use crate::{client::validation_service_manager, state::AppState};
 
 pub async fn submit_batch_commitment(
@@ -84,13 +84,31 @@ pub async fn submit_batch_commitment(
                     .map_err(|error| error.to_string())
                 {
                     Ok(transaction_hash) => {
+                        context
+                            .metrics()
+                            .record_l1_submission(&rollup.rollup_id, true);
                         tracing::info!(
                             "Registered batch commitment - transaction hash: {:?}",
                             transaction_hash
                         );
+
+                        let _ = BatchCommitmentSubmission::set_submitted(
+                            &rollup.rollup_id,
+                            batch_number,
+                            format!("{:?}", transaction_hash),
+                        )
+                        .map_err(|error| {
+                            tracing::error!(
+                                "Failed to put batch commitment submission: {:?}",
+                                error
+                            );
+                        });
                         break;
                     }
                     Err(error) => {
+                        context
+                            .metrics()
+                            .record_l1_submission(&rollup.rollup_id, false);
                         tracing::warn!("{:?}", error);
                         sleep(Duration::from_secs(2)).await;
                     }
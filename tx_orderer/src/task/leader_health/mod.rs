@@ -0,0 +1,889 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use radius_sdk::{
+    json_rpc::{client::Id, server::RpcParameter},
+    signature::{Address, Signature},
+};
+use serde::Serialize;
+use tokio::sync::{Semaphore, SemaphorePermit};
+
+use crate::{
+    error::Error,
+    merkle_tree_manager::MerkleTreeManager,
+    rpc::{
+        cluster::{
+            AttestLeaderFailure, AttestLeaderFailureResponse, Authenticated, Heartbeat,
+            HeartbeatResponse, LeaderFailoverMessage, LeaderFailure, SyncLeaderFailover,
+        },
+        external::{
+            GetOrderCommitment, GetOrderCommitmentResponse, GetTransactionStatus,
+            GetTransactionStatusResponse, SendEncryptedTransaction,
+        },
+    },
+    signer::TxOrdererSigner,
+    state::AppState,
+    task::backfill_rollup,
+    types::*,
+    util::multicast,
+};
+
+const HEARTBEAT_INTERVAL: Duration = Duration::from_secs(2);
+
+const FORWARD_QUEUE_CAPACITY: usize = 1024;
+const FORWARD_MAX_ATTEMPTS: u64 = 3;
+const FORWARD_RETRY_DELAY: Duration = Duration::from_millis(500);
+/// The number of consecutive failures after which the leader is not forwarded
+/// to until a heartbeat succeeds again.
+const UNHEALTHY_FAILURE_COUNT: u64 = 3;
+
+#[derive(Clone, Debug)]
+pub struct LeaderHealth {
+    pub leader_tx_orderer_address: Address,
+
+    /// Unix timestamp in milliseconds of the last successful heartbeat, or of
+    /// the first observation of this leader.
+    pub last_heartbeat_at: u64,
+    pub consecutive_failure_count: u64,
+    /// Whether the leader passed its readiness check at the last heartbeat.
+    pub is_ready: bool,
+    /// Whether the leader reached the distributed key generation at the last
+    /// heartbeat.
+    pub is_distributed_key_generation_healthy: bool,
+
+    pub batch_number: u64,
+    pub transaction_order: u64,
+}
+
+impl LeaderHealth {
+    fn new(leader_tx_orderer_address: Address) -> Self {
+        Self {
+            leader_tx_orderer_address,
+            last_heartbeat_at: current_timestamp(),
+            consecutive_failure_count: 0,
+            is_ready: true,
+            is_distributed_key_generation_healthy: true,
+            batch_number: 0,
+            transaction_order: 0,
+        }
+    }
+
+    pub fn is_healthy(&self) -> bool {
+        self.is_ready && self.consecutive_failure_count < UNHEALTHY_FAILURE_COUNT
+    }
+
+    /// Whether the request for `method` can be forwarded to the leader. Only
+    /// the encrypted transactions need its distributed key generation.
+    fn accepts(&self, method: &str) -> bool {
+        self.is_healthy()
+            && (self.is_distributed_key_generation_healthy
+                || !requires_distributed_key_generation(method))
+    }
+}
+
+fn requires_distributed_key_generation(method: &str) -> bool {
+    method == SendEncryptedTransaction::method()
+}
+
+/// Tracks the leader of every rollup as seen by this follower and bounds the
+/// number of transactions being forwarded to it at the same time.
+pub struct LeaderHealthTracker {
+    inner: Arc<LeaderHealthTrackerInner>,
+}
+
+struct LeaderHealthTrackerInner {
+    leader_health_map: Mutex<HashMap<RollupId, LeaderHealth>>,
+    forward_semaphore: Semaphore,
+}
+
+impl Clone for LeaderHealthTracker {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl Default for LeaderHealthTracker {
+    fn default() -> Self {
+        let inner = LeaderHealthTrackerInner {
+            leader_health_map: Mutex::new(HashMap::new()),
+            forward_semaphore: Semaphore::new(FORWARD_QUEUE_CAPACITY),
+        };
+
+        Self {
+            inner: Arc::new(inner),
+        }
+    }
+}
+
+impl LeaderHealthTracker {
+    pub fn get(&self, rollup_id: &RollupId) -> Option<LeaderHealth> {
+        self.inner
+            .leader_health_map
+            .lock()
+            .unwrap()
+            .get(rollup_id)
+            .cloned()
+    }
+
+    pub fn reset(&self, rollup_id: &RollupId) {
+        self.inner
+            .leader_health_map
+            .lock()
+            .unwrap()
+            .remove(rollup_id);
+    }
+
+    pub fn record_heartbeat(
+        &self,
+        rollup_id: &RollupId,
+        leader_tx_orderer_address: &Address,
+        batch_number: u64,
+        transaction_order: u64,
+        is_ready: bool,
+        is_distributed_key_generation_healthy: bool,
+    ) {
+        let mut leader_health_map = self.inner.leader_health_map.lock().unwrap();
+        let leader_health =
+            Self::entry(&mut leader_health_map, rollup_id, leader_tx_orderer_address);
+
+        leader_health.last_heartbeat_at = current_timestamp();
+        leader_health.consecutive_failure_count = 0;
+        leader_health.is_ready = is_ready;
+        leader_health.is_distributed_key_generation_healthy = is_distributed_key_generation_healthy;
+        leader_health.batch_number = batch_number;
+        leader_health.transaction_order = transaction_order;
+    }
+
+    /// Whether the heartbeats of this node show the given leader as failed.
+    pub fn has_failed(&self, rollup_id: &RollupId, leader_tx_orderer_address: &Address) -> bool {
+        self.get(rollup_id).is_some_and(|leader_health| {
+            leader_health.leader_tx_orderer_address == *leader_tx_orderer_address
+                && !leader_health.is_healthy()
+        })
+    }
+
+    pub fn record_failure(&self, rollup_id: &RollupId, leader_tx_orderer_address: &Address) {
+        let mut leader_health_map = self.inner.leader_health_map.lock().unwrap();
+        let leader_health =
+            Self::entry(&mut leader_health_map, rollup_id, leader_tx_orderer_address);
+
+        leader_health.consecutive_failure_count += 1;
+    }
+
+    fn entry<'a>(
+        leader_health_map: &'a mut HashMap<RollupId, LeaderHealth>,
+        rollup_id: &RollupId,
+        leader_tx_orderer_address: &Address,
+    ) -> &'a mut LeaderHealth {
+        let leader_health = leader_health_map
+            .entry(rollup_id.clone())
+            .or_insert_with(|| LeaderHealth::new(leader_tx_orderer_address.clone()));
+
+        if leader_health.leader_tx_orderer_address != *leader_tx_orderer_address {
+            *leader_health = LeaderHealth::new(leader_tx_orderer_address.clone());
+        }
+
+        leader_health
+    }
+
+    fn try_acquire_forward_permit(&self) -> Result<SemaphorePermit<'_>, Error> {
+        self.inner
+            .forward_semaphore
+            .try_acquire()
+            .map_err(|_| Error::ForwardQueueFull)
+    }
+}
+
+/// Forwards a transaction to the current leader of the rollup. Failed attempts
+/// are retried with the leader read again from [`ClusterMetadata`], so a leader
+/// change during the retries is picked up. Before a retry, the leader is asked
+/// for `transaction_hash`, since a failed attempt may still have been ordered.
+/// A leader that is not ready or keeps failing is not forwarded to, nor are the
+/// encrypted transactions to a leader whose distributed key generation is not
+/// healthy.
+pub async fn forward_to_leader<P>(
+    context: &AppState,
+    rollup: &Rollup,
+    method: &'static str,
+    parameter: &P,
+    transaction_hash: &RawTransactionHash,
+) -> Result<OrderCommitment, Error>
+where
+    P: Serialize + Send + Sync,
+{
+    let started_at = Instant::now();
+    let result = try_forward_to_leader(context, rollup, method, parameter, transaction_hash).await;
+    context
+        .metrics()
+        .record_forward_to_leader(method, result.is_ok(), started_at.elapsed());
+
+    result
+}
+
+async fn try_forward_to_leader<P>(
+    context: &AppState,
+    rollup: &Rollup,
+    method: &'static str,
+    parameter: &P,
+    transaction_hash: &RawTransactionHash,
+) -> Result<OrderCommitment, Error>
+where
+    P: Serialize + Send + Sync,
+{
+    let leader_health_tracker = context.leader_health_tracker();
+    let _permit = leader_health_tracker.try_acquire_forward_permit()?;
+
+    let mut is_sent = false;
+    let mut last_error = Error::EmptyLeader;
+    for attempt in 0..FORWARD_MAX_ATTEMPTS {
+        if attempt > 0 {
+            tokio::time::sleep(FORWARD_RETRY_DELAY * attempt as u32).await;
+        }
+
+        let cluster_metadata = ClusterMetadata::get(
+            rollup.platform,
+            rollup.liveness_service_provider,
+            &rollup.cluster_id,
+        )
+        .map_err(|error| {
+            tracing::error!("Failed to get cluster metadata: {:?}", error);
+            Error::ClusterMetadataNotFound
+        })?;
+
+        if cluster_metadata.is_leader {
+            return Err(Error::LeaderChanged);
+        }
+
+        let Some(leader_tx_orderer_rpc_info) = cluster_metadata.leader_tx_orderer_rpc_info else {
+            last_error = Error::EmptyLeader;
+            continue;
+        };
+
+        let Some(leader_external_rpc_url) = leader_tx_orderer_rpc_info.external_rpc_url.clone()
+        else {
+            last_error = Error::EmptyLeaderClusterRpcUrl;
+            continue;
+        };
+
+        let is_leader_unhealthy =
+            leader_health_tracker
+                .get(&rollup.rollup_id)
+                .is_some_and(|leader_health| {
+                    leader_health.leader_tx_orderer_address
+                        == leader_tx_orderer_rpc_info.tx_orderer_address
+                        && !leader_health.accepts(method)
+                });
+        if is_leader_unhealthy {
+            last_error = Error::LeaderUnhealthy;
+            continue;
+        }
+
+        // A request that timed out may have been ordered by the leader.
+        if is_sent {
+            if let Some(order_commitment) = get_ordered_transaction(
+                context,
+                leader_external_rpc_url.clone(),
+                &rollup.rollup_id,
+                transaction_hash,
+            )
+            .await
+            {
+                return Ok(order_commitment);
+            }
+        }
+        is_sent = true;
+
+        match context
+            .rpc_client()
+            .request(leader_external_rpc_url, method, parameter, Id::Null)
+            .await
+        {
+            Ok(response) => return Ok(response),
+            Err(error) => {
+                tracing::warn!(
+                    "Forward to leader failed - method: {:?} / rollup_id: {:?} / attempt: {:?} / error: {:?}",
+                    method,
+                    rollup.rollup_id,
+                    attempt + 1,
+                    error
+                );
+
+                leader_health_tracker.record_failure(
+                    &rollup.rollup_id,
+                    &leader_tx_orderer_rpc_info.tx_orderer_address,
+                );
+                last_error = error.into();
+            }
+        }
+    }
+
+    Err(last_error)
+}
+
+/// Returns the order commitment of the transaction if the leader has already
+/// ordered it.
+async fn get_ordered_transaction(
+    context: &AppState,
+    leader_external_rpc_url: String,
+    rollup_id: &RollupId,
+    transaction_hash: &RawTransactionHash,
+) -> Option<OrderCommitment> {
+    let transaction_status: GetTransactionStatusResponse = context
+        .rpc_client()
+        .request(
+            leader_external_rpc_url.clone(),
+            GetTransactionStatus::method(),
+            &GetTransactionStatus {
+                rollup_id: rollup_id.clone(),
+                transaction_hash: transaction_hash.clone().as_string(),
+            },
+            Id::Null,
+        )
+        .await
+        .ok()?;
+
+    let response: GetOrderCommitmentResponse = context
+        .rpc_client()
+        .request(
+            leader_external_rpc_url,
+            GetOrderCommitment::method(),
+            &GetOrderCommitment {
+                rollup_id: rollup_id.clone(),
+                batch_number: transaction_status.batch_number,
+                transaction_order: transaction_status.transaction_order,
+            },
+            Id::Null,
+        )
+        .await
+        .ok()?;
+
+    tracing::info!(
+        "Forward to leader - already ordered - rollup_id: {:?} / transaction_hash: {:?}",
+        rollup_id,
+        transaction_hash
+    );
+
+    Some(response.order_commitment)
+}
+
+/// Sends heartbeats to the leader of every rollup this node follows and, when
+/// `leader_failover_timeout` is configured, lets the next tx_orderer in the
+/// cluster take over from a leader that stopped responding.
+pub fn run_leader_health_monitor(context: AppState) {
+    tokio::spawn(async move {
+        loop {
+            tokio::time::sleep(HEARTBEAT_INTERVAL).await;
+
+            let rollup_id_list = match RollupIdList::get() {
+                Ok(rollup_id_list) => rollup_id_list,
+                Err(_) => continue,
+            };
+
+            for rollup_id in rollup_id_list.iter() {
+                if let Err(error) = check_leader_health(&context, rollup_id).await {
+                    tracing::warn!(
+                        "Leader health check failed - rollup_id: {:?} / error: {:?}",
+                        rollup_id,
+                        error
+                    );
+                }
+            }
+        }
+    });
+}
+
+async fn check_leader_health(context: &AppState, rollup_id: &RollupId) -> Result<(), Error> {
+    let rollup = Rollup::get(rollup_id)?;
+    let cluster_metadata = ClusterMetadata::get(
+        rollup.platform,
+        rollup.liveness_service_provider,
+        &rollup.cluster_id,
+    )?;
+
+    let leader_health_tracker = context.leader_health_tracker();
+
+    if cluster_metadata.is_leader {
+        leader_health_tracker.reset(rollup_id);
+        return Ok(());
+    }
+
+    let Some(leader_tx_orderer_rpc_info) = cluster_metadata.leader_tx_orderer_rpc_info else {
+        return Ok(());
+    };
+    let leader_tx_orderer_address = &leader_tx_orderer_rpc_info.tx_orderer_address;
+
+    let signer = context.get_signer(rollup.platform).await?;
+    let parameter = Authenticated::new(
+        &signer,
+        Heartbeat {
+            rollup_id: rollup_id.clone(),
+        },
+    )
+    .await?;
+    let heartbeat_result = match leader_tx_orderer_rpc_info.cluster_rpc_url.as_ref() {
+        Some(leader_cluster_rpc_url) => context
+            .rpc_client()
+            .request::<&Authenticated<Heartbeat>, HeartbeatResponse>(
+                leader_cluster_rpc_url,
+                Heartbeat::method(),
+                &parameter,
+                Id::Null,
+            )
+            .await
+            .map_err(Error::from),
+        None => Err(Error::EmptyLeaderClusterRpcUrl),
+    };
+
+    match heartbeat_result {
+        Ok(response) if response.is_leader => {
+            leader_health_tracker.record_heartbeat(
+                rollup_id,
+                leader_tx_orderer_address,
+                response.batch_number,
+                response.transaction_order,
+                response.is_ready,
+                response.is_distributed_key_generation_healthy,
+            );
+
+            // Take over a leader that is draining before it exits, unless this
+            // node is going down as well.
+            if response.is_shutting_down
+                && context.config().leader_failover_timeout.is_some()
+                && !context.shutdown_manager().is_shutting_down()
+            {
+                if let Some(leader_health) = leader_health_tracker.get(rollup_id) {
+                    return failover(context, &rollup, leader_health).await;
+                }
+            }
+
+            return Ok(());
+        }
+        Ok(_) => {
+            tracing::warn!(
+                "Leader does not consider itself leader - rollup_id: {:?} / leader: {:?}",
+                rollup_id,
+                leader_tx_orderer_address
+            );
+            leader_health_tracker.record_failure(rollup_id, leader_tx_orderer_address);
+        }
+        Err(error) => {
+            tracing::warn!(
+                "Leader heartbeat failed - rollup_id: {:?} / leader: {:?} / error: {:?}",
+                rollup_id,
+                leader_tx_orderer_address,
+                error
+            );
+            leader_health_tracker.record_failure(rollup_id, leader_tx_orderer_address);
+        }
+    }
+
+    let Some(leader_failover_timeout) = context.config().leader_failover_timeout else {
+        return Ok(());
+    };
+
+    let Some(leader_health) = leader_health_tracker.get(rollup_id) else {
+        return Ok(());
+    };
+
+    if current_timestamp().saturating_sub(leader_health.last_heartbeat_at)
+        < leader_failover_timeout * 1000
+    {
+        return Ok(());
+    }
+
+    failover(context, &rollup, leader_health).await
+}
+
+/// Takes over the leadership if this node is the successor of the failed
+/// leader in the cluster order. The successor is derived from the latest
+/// [`LeaderState`], so every follower agrees on it without a vote. The failure
+/// itself must be attested by a majority of the other tx_orderers, so that a
+/// successor cut off from a healthy leader does not take over.
+async fn failover(
+    context: &AppState,
+    rollup: &Rollup,
+    leader_health: LeaderHealth,
+) -> Result<(), Error> {
+    let rollup_id = &rollup.rollup_id;
+
+    // Without a hand-over record there is no agreed successor.
+    let Ok(leader_state) = LeaderState::get(rollup_id) else {
+        return Ok(());
+    };
+    if leader_state.leader_tx_orderer_address != leader_health.leader_tx_orderer_address {
+        return Ok(());
+    }
+
+    let cluster = Cluster::get(
+        rollup.platform,
+        rollup.liveness_service_provider,
+        &rollup.cluster_id,
+        leader_state.platform_block_height,
+    )?;
+
+    let next_leader_tx_orderer_rpc_info = cluster
+        .get_next_tx_orderer_rpc_info(&leader_state.leader_tx_orderer_address)
+        .ok_or(Error::TxOrdererInfoNotFound)?;
+
+    let signer = context.get_signer(rollup.platform).await?;
+    if &next_leader_tx_orderer_rpc_info.tx_orderer_address != signer.address() {
+        return Ok(());
+    }
+
+    let (mut batch_number, mut transaction_order) =
+        (leader_state.batch_number, leader_state.transaction_order)
+            .max((leader_health.batch_number, leader_health.transaction_order));
+
+    // Pull what the other followers received from the failed leader, then
+    // continue right after the last transaction that has an order commitment.
+    let provided_batch_number = RollupMetadata::get(rollup_id)?.provided_batch_number;
+    if let Err(error) = backfill_rollup(
+        context,
+        rollup,
+        &cluster,
+        provided_batch_number,
+        batch_number,
+        rollup.max_transaction_count_per_batch,
+    )
+    .await
+    {
+        tracing::warn!(
+            "Leader failover - backfill failed - rollup_id: {:?} / error: {:?}",
+            rollup_id,
+            error
+        );
+    }
+
+    while OrderCommitment::get(rollup_id, batch_number, transaction_order).is_ok() {
+        transaction_order += 1;
+
+        if transaction_order == rollup.max_transaction_count_per_batch {
+            batch_number += 1;
+            transaction_order = 0;
+        }
+    }
+
+    // The attestations are bound to the position the successor continues
+    // from, so they are collected once it is known.
+    let leader_failure = LeaderFailure::new(
+        rollup_id.clone(),
+        leader_state.platform_block_height,
+        leader_state.leader_tx_orderer_address.clone(),
+        batch_number,
+        transaction_order,
+    );
+    let attestation_list =
+        collect_leader_failure_attestations(context, &signer, &cluster, &leader_failure).await?;
+    if !is_attested_by_quorum(
+        rollup.platform,
+        &cluster.get_tx_orderer_address_list(),
+        &leader_failure,
+        &attestation_list,
+    ) {
+        tracing::warn!(
+            "Leader failover - not attested by a quorum - rollup_id: {:?} / failed_leader: {:?} / attestation_count: {:?}",
+            rollup_id,
+            leader_state.leader_tx_orderer_address,
+            attestation_list.len()
+        );
+        return Ok(());
+    }
+
+    tracing::warn!(
+        "Leader failover - taking over - rollup_id: {:?} / failed_leader: {:?}",
+        rollup_id,
+        leader_state.leader_tx_orderer_address
+    );
+
+    let mut mut_rollup_metadata = RollupMetadata::get_mut(rollup_id)?;
+    mut_rollup_metadata.batch_number = batch_number;
+    mut_rollup_metadata.transaction_order = transaction_order;
+    mut_rollup_metadata.update()?;
+
+    let merkle_tree =
+        MerkleTreeManager::initilize_merkle_tree(rollup_id, context.rpc_client()).await;
+    context
+        .merkle_tree_manager()
+        .insert(rollup_id, merkle_tree)
+        .await;
+
+    let mut mut_cluster_metadata = ClusterMetadata::get_mut(
+        rollup.platform,
+        rollup.liveness_service_provider,
+        &rollup.cluster_id,
+    )?;
+    mut_cluster_metadata.is_leader = true;
+    mut_cluster_metadata.leader_tx_orderer_rpc_info = Some(next_leader_tx_orderer_rpc_info);
+    mut_cluster_metadata.update()?;
+
+    LeaderState::new(
+        leader_state.platform_block_height,
+        signer.address().clone(),
+        batch_number,
+        transaction_order,
+    )
+    .record(rollup_id)?;
+
+    context.leader_health_tracker().reset(rollup_id);
+
+    let leader_failover_message = LeaderFailoverMessage {
+        rollup_id: rollup_id.clone(),
+        platform_block_height: leader_state.platform_block_height,
+        failed_leader_tx_orderer_address: leader_state.leader_tx_orderer_address,
+        next_leader_tx_orderer_address: signer.address().clone(),
+        batch_number,
+        transaction_order,
+        expires_at: leader_failure.expires_at,
+    };
+    let next_leader_signature = signer.sign_message(&leader_failover_message).await?;
+
+    let parameter = Authenticated::new(
+        &signer,
+        SyncLeaderFailover {
+            leader_failover_message,
+            next_leader_signature,
+            attestation_list,
+        },
+    )
+    .await?;
+
+    multicast(
+        context,
+        cluster.get_other_cluster_rpc_url_list(),
+        SyncLeaderFailover::method(),
+        parameter,
+    );
+
+    Ok(())
+}
+
+/// Signs the failure of the leader and asks the other tx_orderers of the
+/// cluster to do the same.
+async fn collect_leader_failure_attestations(
+    context: &AppState,
+    signer: &TxOrdererSigner,
+    cluster: &Cluster,
+    leader_failure: &LeaderFailure,
+) -> Result<Vec<Signature>, Error> {
+    let mut attestation_list = vec![signer.sign_message(leader_failure).await?];
+
+    let parameter = Authenticated::new(
+        signer,
+        AttestLeaderFailure {
+            leader_failure: leader_failure.clone(),
+        },
+    )
+    .await?;
+
+    let response_list =
+        futures::future::join_all(cluster.get_other_cluster_rpc_url_list().into_iter().map(
+            |cluster_rpc_url| {
+                context
+                    .rpc_client()
+                    .request::<&Authenticated<AttestLeaderFailure>, AttestLeaderFailureResponse>(
+                        cluster_rpc_url,
+                        AttestLeaderFailure::method(),
+                        &parameter,
+                        Id::Null,
+                    )
+            },
+        ))
+        .await;
+
+    attestation_list.extend(
+        response_list
+            .into_iter()
+            .filter_map(|response| response.ok().and_then(|response| response.signature)),
+    );
+
+    Ok(attestation_list)
+}
+
+/// Whether a majority of the tx_orderers other than the failed leader signed
+/// its failure.
+pub fn is_attested_by_quorum(
+    platform: Platform,
+    tx_orderer_address_list: &[Address],
+    leader_failure: &LeaderFailure,
+    attestation_list: &[Signature],
+) -> bool {
+    let voter_count = tx_orderer_address_list
+        .iter()
+        .filter(|address| **address != leader_failure.failed_leader_tx_orderer_address)
+        .count();
+
+    let mut attester_list: Vec<Address> = Vec::new();
+    for attestation in attestation_list {
+        let Ok(attester) = attestation.get_signer_address(platform.into(), leader_failure) else {
+            continue;
+        };
+
+        if attester != leader_failure.failed_leader_tx_orderer_address
+            && tx_orderer_address_list.contains(&attester)
+            && !attester_list.contains(&attester)
+        {
+            attester_list.push(attester);
+        }
+    }
+
+    attester_list.len() > voter_count / 2
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use radius_sdk::signature::{ChainType, PrivateKeySigner};
+
+    use super::*;
+    use crate::rpc::{cluster::LEADER_FAILURE_TTL, external::SendRawTransaction};
+
+    const SIGNING_KEY_LIST: [&str; 3] = [
+        "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80",
+        "0x59c6995e998f97a5a0044966f0945389dc9e86dae88c7a8412f4603b6b78690d",
+        "0x5de4111afa1a4b94908f83103eb1f1706367c2e68ca870fc3fb9a804cdab365a",
+    ];
+
+    fn signer_list() -> Vec<PrivateKeySigner> {
+        SIGNING_KEY_LIST
+            .iter()
+            .map(|signing_key| {
+                PrivateKeySigner::from_str(ChainType::Ethereum, signing_key).unwrap()
+            })
+            .collect()
+    }
+
+    /// The leader A keeps answering the follower C, but the successor B is cut
+    /// off from it. B alone cannot take over, and C rejects the failover.
+    #[test]
+    fn successor_cut_off_from_the_leader_cannot_take_over() {
+        let signer_list = signer_list();
+        let (leader, successor, follower) = (&signer_list[0], &signer_list[1], &signer_list[2]);
+        let tx_orderer_address_list = vec![
+            leader.address().clone(),
+            successor.address().clone(),
+            follower.address().clone(),
+        ];
+        let rollup_id = RollupId::from("rollup");
+        let leader_failure =
+            LeaderFailure::new(rollup_id.clone(), 1, leader.address().clone(), 0, 0);
+
+        let successor_tracker = LeaderHealthTracker::default();
+        for _ in 0..UNHEALTHY_FAILURE_COUNT {
+            successor_tracker.record_failure(&rollup_id, leader.address());
+        }
+        assert!(successor_tracker.has_failed(&rollup_id, leader.address()));
+
+        let follower_tracker = LeaderHealthTracker::default();
+        follower_tracker.record_heartbeat(&rollup_id, leader.address(), 0, 0, true, true);
+        assert!(!follower_tracker.has_failed(&rollup_id, leader.address()));
+
+        let successor_attestation = successor.sign_message(&leader_failure).unwrap();
+        assert!(!is_attested_by_quorum(
+            Platform::Ethereum,
+            &tx_orderer_address_list,
+            &leader_failure,
+            &[successor_attestation.clone(), successor_attestation.clone()],
+        ));
+
+        // Once C loses the leader as well, the failover is attested.
+        for _ in 0..UNHEALTHY_FAILURE_COUNT {
+            follower_tracker.record_failure(&rollup_id, leader.address());
+        }
+        assert!(follower_tracker.has_failed(&rollup_id, leader.address()));
+
+        let follower_attestation = follower.sign_message(&leader_failure).unwrap();
+        assert!(is_attested_by_quorum(
+            Platform::Ethereum,
+            &tx_orderer_address_list,
+            &leader_failure,
+            &[successor_attestation, follower_attestation],
+        ));
+    }
+
+    #[test]
+    fn failed_leader_cannot_attest_its_own_failure() {
+        let signer_list = signer_list();
+        let (leader, successor) = (&signer_list[0], &signer_list[1]);
+        let tx_orderer_address_list = signer_list
+            .iter()
+            .map(|signer| signer.address().clone())
+            .collect::<Vec<_>>();
+        let leader_failure =
+            LeaderFailure::new(RollupId::from("rollup"), 1, leader.address().clone(), 0, 0);
+
+        assert!(!is_attested_by_quorum(
+            Platform::Ethereum,
+            &tx_orderer_address_list,
+            &leader_failure,
+            &[
+                successor.sign_message(&leader_failure).unwrap(),
+                leader.sign_message(&leader_failure).unwrap(),
+            ],
+        ));
+    }
+
+    #[test]
+    fn attestation_is_bound_to_the_position_and_expires() {
+        let signer_list = signer_list();
+        let (leader, successor, follower) = (&signer_list[0], &signer_list[1], &signer_list[2]);
+        let tx_orderer_address_list = signer_list
+            .iter()
+            .map(|signer| signer.address().clone())
+            .collect::<Vec<_>>();
+        let leader_failure =
+            LeaderFailure::new(RollupId::from("rollup"), 1, leader.address().clone(), 4, 2);
+        let attestation_list = vec![
+            successor.sign_message(&leader_failure).unwrap(),
+            follower.sign_message(&leader_failure).unwrap(),
+        ];
+
+        assert!(is_attested_by_quorum(
+            Platform::Ethereum,
+            &tx_orderer_address_list,
+            &leader_failure,
+            &attestation_list,
+        ));
+
+        // The same attestations do not cover a failover from another position.
+        let other_leader_failure = LeaderFailure {
+            transaction_order: 3,
+            ..leader_failure.clone()
+        };
+        assert!(!is_attested_by_quorum(
+            Platform::Ethereum,
+            &tx_orderer_address_list,
+            &other_leader_failure,
+            &attestation_list,
+        ));
+
+        let now = current_timestamp();
+        assert!(leader_failure.is_valid_at(now));
+        assert!(!leader_failure.is_valid_at(leader_failure.expires_at));
+        assert!(!LeaderFailure {
+            expires_at: now + 2 * LEADER_FAILURE_TTL.as_millis() as u64,
+            ..leader_failure
+        }
+        .is_valid_at(now));
+    }
+
+    #[test]
+    fn raw_transactions_are_forwarded_without_distributed_key_generation() {
+        let signer_list = signer_list();
+        let leader = &signer_list[0];
+        let rollup_id = RollupId::from("rollup");
+
+        let follower_tracker = LeaderHealthTracker::default();
+        follower_tracker.record_heartbeat(&rollup_id, leader.address(), 0, 0, true, false);
+        let leader_health = follower_tracker.get(&rollup_id).unwrap();
+
+        assert!(!follower_tracker.has_failed(&rollup_id, leader.address()));
+        assert!(leader_health.accepts(SendRawTransaction::method()));
+        assert!(!leader_health.accepts(SendEncryptedTransaction::method()));
+    }
+}
@@ -1,7 +1,19 @@
 mod backrunning;
+mod builder_sink;
+mod censorship_monitor;
 mod decryptor;
 mod finalize_batch;
+mod leader_health;
+mod pruner;
+mod range_sync;
+mod subscription;
 
 pub use backrunning::*;
+pub use builder_sink::*;
+pub use censorship_monitor::*;
 pub use decryptor::*;
 pub use finalize_batch::*;
+pub use leader_health::*;
+pub use pruner::*;
+pub use range_sync::*;
+pub use subscription::*;
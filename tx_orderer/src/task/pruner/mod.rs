@@ -0,0 +1,553 @@
+use std::{fs, path::Path, sync::Arc, time::Duration};
+
+use radius_sdk::kvstore::KvStoreError;
+use serde::{Deserialize, Serialize};
+use tokio::sync::Mutex;
+
+use crate::{error::Error, state::AppState, types::*};
+
+const PRUNE_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Upper bound on the batches pruned per rollup in a single pass, so that a
+/// large backlog does not hold the database for too long.
+const MAX_PRUNED_BATCH_COUNT_PER_PASS: u64 = 100;
+
+/// How long a measured [`DiskUsage`] is served before the database is walked
+/// again.
+const DISK_USAGE_CACHE_DURATION: Duration = Duration::from_secs(60);
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct DiskUsage {
+    /// Size of the database directory on disk.
+    pub database_size: u64,
+    pub model_usage_list: Vec<ModelUsage>,
+    /// Timestamp in milliseconds of the walk the usage comes from.
+    pub measured_at: u64,
+}
+
+/// Serves the last [`DiskUsage`] for [`DISK_USAGE_CACHE_DURATION`], as
+/// measuring it walks every retained model and the database directory.
+pub struct DiskUsageCache {
+    inner: Arc<Mutex<Option<DiskUsage>>>,
+}
+
+impl Clone for DiskUsageCache {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl Default for DiskUsageCache {
+    fn default() -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(None)),
+        }
+    }
+}
+
+impl DiskUsageCache {
+    /// Returns the cached usage, or measures it on a blocking thread once it
+    /// is stale. Concurrent callers wait for the same measurement.
+    pub async fn get(&self, config: &Config) -> Result<DiskUsage, Error> {
+        let mut cached_disk_usage = self.inner.lock().await;
+
+        if let Some(disk_usage) = cached_disk_usage.as_ref() {
+            if current_timestamp().saturating_sub(disk_usage.measured_at)
+                < DISK_USAGE_CACHE_DURATION.as_millis() as u64
+            {
+                return Ok(disk_usage.clone());
+            }
+        }
+
+        let config = config.clone();
+        let disk_usage = tokio::task::spawn_blocking(move || get_disk_usage(&config))
+            .await
+            .map_err(|error| Error::GeneralError(error.to_string()))??;
+        *cached_disk_usage = Some(disk_usage.clone());
+
+        Ok(disk_usage)
+    }
+}
+
+/// Prunes the sealed batches of every rollup according to its
+/// [`RetentionPolicy`]. A batch is pruned only after every builder sink has
+/// delivered it, and batches are pruned in order so that the retained batches
+/// of a rollup are always contiguous.
+pub fn run_pruner(context: AppState) {
+    tokio::spawn(async move {
+        loop {
+            tokio::time::sleep(PRUNE_INTERVAL).await;
+
+            let rollup_id_list = match RollupIdList::get() {
+                Ok(rollup_id_list) => rollup_id_list,
+                Err(_) => continue,
+            };
+
+            for rollup_id in rollup_id_list.iter() {
+                if let Err(error) = prune_rollup(&context, rollup_id) {
+                    tracing::warn!(
+                        "Pruning failed - rollup_id: {:?} / error: {:?}",
+                        rollup_id,
+                        error
+                    );
+                }
+            }
+        }
+    });
+}
+
+fn prune_rollup(context: &AppState, rollup_id: &RollupId) -> Result<(), Error> {
+    let retention_policy = RetentionPolicy::get_or_default(rollup_id, context.config())?;
+    if retention_policy == RetentionPolicy::KeepAll {
+        return Ok(());
+    }
+
+    let prune_limit = get_prune_limit(context, rollup_id, retention_policy)?;
+    let mut retention_cursor = RetentionCursor::get_or(rollup_id, RetentionCursor::default)?;
+    let from_batch_number = retention_cursor.batch_number;
+
+    while retention_cursor.batch_number < prune_limit
+        && retention_cursor.batch_number - from_batch_number < MAX_PRUNED_BATCH_COUNT_PER_PASS
+    {
+        if retention_policy == RetentionPolicy::KeepUntilFinalized
+            && !is_finalized(rollup_id, retention_cursor.batch_number)?
+        {
+            break;
+        }
+
+        prune_batch(rollup_id, retention_cursor.batch_number)?;
+
+        retention_cursor.batch_number += 1;
+        retention_cursor.put(rollup_id)?;
+    }
+
+    if retention_cursor.batch_number > from_batch_number {
+        let mut leader_history = LeaderHistory::get_mut_or(rollup_id, LeaderHistory::default)?;
+        leader_history.prune(retention_cursor.batch_number);
+        leader_history.update()?;
+
+        tracing::info!(
+            "Pruned batches - rollup_id: {:?} / from_batch_number: {:?} / to_batch_number: {:?}",
+            rollup_id,
+            from_batch_number,
+            retention_cursor.batch_number - 1
+        );
+    }
+
+    Ok(())
+}
+
+/// Returns the batch number below which the batches of the rollup may be
+/// pruned.
+fn get_prune_limit(
+    context: &AppState,
+    rollup_id: &RollupId,
+    retention_policy: RetentionPolicy,
+) -> Result<u64, Error> {
+    let rollup_metadata = RollupMetadata::get(rollup_id)?;
+
+    let mut cursor_batch_number_list = Vec::new();
+    for sink_name in context.builder_sink_manager().sink_name_list() {
+        match BuilderSinkCursor::get(rollup_id, sink_name) {
+            Ok(cursor) => cursor_batch_number_list.push(cursor.batch_number),
+            Err(error) if error.is_none_type() => {}
+            Err(error) => return Err(error.into()),
+        }
+    }
+
+    Ok(calculate_prune_limit(
+        retention_policy,
+        rollup_metadata.batch_number,
+        cursor_batch_number_list,
+    ))
+}
+
+/// Returns the batch number below which the policy allows pruning, held back
+/// by the batch each builder sink is delivering.
+fn calculate_prune_limit(
+    retention_policy: RetentionPolicy,
+    current_batch_number: u64,
+    cursor_batch_number_list: impl IntoIterator<Item = u64>,
+) -> u64 {
+    // The current batch is still being filled.
+    let prune_limit = match retention_policy {
+        RetentionPolicy::KeepAll => 0,
+        RetentionPolicy::KeepBatchCount { batch_count } => {
+            current_batch_number.saturating_sub(batch_count)
+        }
+        RetentionPolicy::KeepUntilFinalized => current_batch_number,
+    };
+
+    cursor_batch_number_list
+        .into_iter()
+        .fold(prune_limit, u64::min)
+}
+
+fn is_finalized(rollup_id: &RollupId, batch_number: u64) -> Result<bool, Error> {
+    match BatchCommitmentSubmission::get(rollup_id, batch_number) {
+        Ok(submission) => Ok(submission.task_responded_at.is_some()),
+        Err(error) if error.is_none_type() => Ok(false),
+        Err(error) => Err(error.into()),
+    }
+}
+
+/// Deletes the batch along with every transaction, order commitment and
+/// transaction status in it.
+fn prune_batch(rollup_id: &RollupId, batch_number: u64) -> Result<(), Error> {
+    let batch = match Batch::get(rollup_id, batch_number) {
+        Ok(batch) => batch,
+        Err(error) if error.is_none_type() => return Ok(()),
+        Err(error) => return Err(error.into()),
+    };
+
+    for (transaction_order, raw_transaction) in batch.raw_transaction_list.iter().enumerate() {
+        // Encrypted transactions are indexed by the hash in their open data.
+        let transaction_hash = match batch
+            .encrypted_transaction_list
+            .get(transaction_order)
+            .and_then(Option::as_ref)
+        {
+            Some(encrypted_transaction) => encrypted_transaction.raw_transaction_hash(),
+            None => raw_transaction.raw_transaction_hash(),
+        };
+
+        delete_transaction(
+            rollup_id,
+            batch_number,
+            transaction_order as u64,
+            transaction_hash.as_ref(),
+        )?;
+    }
+
+    ignore_none_type(BatchCommitmentSubmission::delete(rollup_id, batch_number))?;
+    Batch::delete(rollup_id, batch_number)?;
+
+    Ok(())
+}
+
+/// Deletes every batch and transaction of the rollup, including the ones of
+/// the batch that is still being filled, along with its rollup, metadata and
+/// cursors. The equivocation evidence and censorship violations are kept.
+pub(crate) fn purge_rollup(context: &AppState, rollup_id: &RollupId) -> Result<(), Error> {
+    let rollup_metadata = match RollupMetadata::get(rollup_id) {
+        Ok(rollup_metadata) => rollup_metadata,
+        Err(error) if error.is_none_type() => return Ok(()),
+        Err(error) => return Err(error.into()),
+    };
+    let retention_cursor = RetentionCursor::get_or(rollup_id, RetentionCursor::default)?;
+
+    for batch_number in retention_cursor.batch_number..rollup_metadata.batch_number {
+        prune_batch(rollup_id, batch_number)?;
+    }
+
+    // The current batch is not sealed, so its transactions are only reachable
+    // by their order.
+    let batch_number = rollup_metadata.batch_number;
+    for transaction_order in 0..rollup_metadata.transaction_order {
+        let transaction_hash =
+            match EncryptedTransactionModel::get(rollup_id, batch_number, transaction_order) {
+                Ok(encrypted_transaction) => encrypted_transaction.raw_transaction_hash(),
+                Err(error) if error.is_none_type() => {
+                    match RawTransactionModel::get(rollup_id, batch_number, transaction_order) {
+                        Ok((raw_transaction, _)) => raw_transaction.raw_transaction_hash(),
+                        Err(error) if error.is_none_type() => continue,
+                        Err(error) => return Err(error.into()),
+                    }
+                }
+                Err(error) => return Err(error.into()),
+            };
+
+        delete_transaction(
+            rollup_id,
+            batch_number,
+            transaction_order,
+            transaction_hash.as_ref(),
+        )?;
+    }
+    prune_batch(rollup_id, batch_number)?;
+
+    for sink_name in context.builder_sink_manager().sink_name_list() {
+        ignore_none_type(BuilderSinkCursor::delete(rollup_id, sink_name))?;
+        ignore_none_type(BuilderSinkDeadLetterList::delete(rollup_id, sink_name))?;
+    }
+    match ForwardedTransactionList::get(rollup_id) {
+        Ok(forwarded_transaction_list) => {
+            for transaction_hash in forwarded_transaction_list.iter() {
+                ignore_none_type(ForwardedTransaction::delete(rollup_id, transaction_hash))?;
+            }
+            ForwardedTransactionList::delete(rollup_id)?;
+        }
+        Err(error) if error.is_none_type() => {}
+        Err(error) => return Err(error.into()),
+    }
+    ignore_none_type(CanProvideTransactionInfo::delete(rollup_id))?;
+    ignore_none_type(ServedTransactionCursor::delete(rollup_id))?;
+    ignore_none_type(LeaderState::delete(rollup_id))?;
+    ignore_none_type(LeaderHistory::delete(rollup_id))?;
+    ignore_none_type(RetentionPolicy::delete(rollup_id))?;
+    ignore_none_type(RetentionCursor::delete(rollup_id))?;
+    ignore_none_type(RollupMetadata::delete(rollup_id))?;
+    ignore_none_type(Rollup::delete(rollup_id))?;
+
+    Ok(())
+}
+
+fn delete_transaction(
+    rollup_id: &RollupId,
+    batch_number: u64,
+    transaction_order: u64,
+    transaction_hash: &str,
+) -> Result<(), KvStoreError> {
+    ignore_none_type(RawTransactionModel::delete(
+        rollup_id,
+        batch_number,
+        transaction_order,
+    ))?;
+    ignore_none_type(RawTransactionModel::delete_with_transaction_hash(
+        rollup_id,
+        transaction_hash,
+    ))?;
+    ignore_none_type(EncryptedTransactionModel::delete(
+        rollup_id,
+        batch_number,
+        transaction_order,
+    ))?;
+    ignore_none_type(EncryptedTransactionModel::delete_with_transaction_hash(
+        rollup_id,
+        transaction_hash,
+    ))?;
+    ignore_none_type(OrderCommitment::delete(
+        rollup_id,
+        batch_number,
+        transaction_order,
+    ))?;
+    ignore_none_type(QuorumOrderCommitment::delete(
+        rollup_id,
+        batch_number,
+        transaction_order,
+    ))?;
+    ignore_none_type(TransactionStatusInfo::delete(rollup_id, transaction_hash))?;
+
+    Ok(())
+}
+
+pub(crate) fn ignore_none_type(result: Result<(), KvStoreError>) -> Result<(), KvStoreError> {
+    match result {
+        Err(error) if error.is_none_type() => Ok(()),
+        result => result,
+    }
+}
+
+/// Counts the retained entries of the models that grow with the number of
+/// transactions and cluster snapshots. The size of an entry is the size of its
+/// JSON encoding, so it is an estimate of the space it takes in the database.
+pub fn get_disk_usage(config: &Config) -> Result<DiskUsage, Error> {
+    let mut batch_usage = ModelUsage::new(stringify!(Batch));
+    let mut raw_transaction_usage = ModelUsage::new(RawTransactionModel::ID);
+    let mut encrypted_transaction_usage = ModelUsage::new(EncryptedTransactionModel::ID);
+    let mut order_commitment_usage = ModelUsage::new(stringify!(OrderCommitment));
+    let mut quorum_order_commitment_usage = ModelUsage::new(stringify!(QuorumOrderCommitment));
+    let mut transaction_status_usage = ModelUsage::new(stringify!(TransactionStatusInfo));
+    let mut cluster_usage = ModelUsage::new(stringify!(Cluster));
+
+    let rollup_id_list = RollupIdList::get_or(RollupIdList::default)?;
+    for rollup_id in rollup_id_list.iter() {
+        let rollup_metadata = match RollupMetadata::get(rollup_id) {
+            Ok(rollup_metadata) => rollup_metadata,
+            Err(error) if error.is_none_type() => continue,
+            Err(error) => return Err(error.into()),
+        };
+        let retention_cursor = RetentionCursor::get_or(rollup_id, RetentionCursor::default)?;
+
+        for batch_number in retention_cursor.batch_number..=rollup_metadata.batch_number {
+            let transaction_count = match Batch::get(rollup_id, batch_number) {
+                Ok(batch) => {
+                    batch_usage.add(&batch);
+                    batch.raw_transaction_list.len() as u64
+                }
+                Err(error) if error.is_none_type() => {
+                    if batch_number == rollup_metadata.batch_number {
+                        rollup_metadata.transaction_order
+                    } else {
+                        0
+                    }
+                }
+                Err(error) => return Err(error.into()),
+            };
+
+            for transaction_order in 0..transaction_count {
+                let mut transaction_hash = None;
+
+                match EncryptedTransactionModel::get(rollup_id, batch_number, transaction_order) {
+                    Ok(encrypted_transaction) => {
+                        encrypted_transaction_usage.add(&encrypted_transaction);
+                        transaction_hash = Some(encrypted_transaction.raw_transaction_hash());
+                    }
+                    Err(error) if error.is_none_type() => {}
+                    Err(error) => return Err(error.into()),
+                }
+
+                match RawTransactionModel::get(rollup_id, batch_number, transaction_order) {
+                    Ok((raw_transaction, is_direct_sent)) => {
+                        raw_transaction_usage.add(&(&raw_transaction, is_direct_sent));
+                        transaction_hash
+                            .get_or_insert_with(|| raw_transaction.raw_transaction_hash());
+                    }
+                    Err(error) if error.is_none_type() => {}
+                    Err(error) => return Err(error.into()),
+                }
+
+                add_usage(
+                    &mut order_commitment_usage,
+                    OrderCommitment::get(rollup_id, batch_number, transaction_order),
+                )?;
+                add_usage(
+                    &mut quorum_order_commitment_usage,
+                    QuorumOrderCommitment::get(rollup_id, batch_number, transaction_order),
+                )?;
+
+                if let Some(transaction_hash) = transaction_hash {
+                    let transaction_hash: &str = transaction_hash.as_ref();
+
+                    add_usage(
+                        &mut encrypted_transaction_usage,
+                        EncryptedTransactionModel::get_with_transaction_hash(
+                            rollup_id,
+                            transaction_hash,
+                        ),
+                    )?;
+                    add_usage(
+                        &mut raw_transaction_usage,
+                        RawTransactionModel::get_with_transaction_hash(rollup_id, transaction_hash),
+                    )?;
+                    add_usage(
+                        &mut transaction_status_usage,
+                        TransactionStatusInfo::get(rollup_id, transaction_hash),
+                    )?;
+                }
+            }
+        }
+    }
+
+    let sequencing_info_list = SequencingInfoList::get_or(SequencingInfoList::default)?;
+    for (platform, liveness_service_provider) in sequencing_info_list.iter() {
+        let cluster_id_list = ClusterIdList::get_or(
+            *platform,
+            *liveness_service_provider,
+            ClusterIdList::default,
+        )?;
+
+        for cluster_id in cluster_id_list.iter() {
+            let latest_block_height = match LatestSyncedClusterBlockHeight::get(
+                *platform,
+                *liveness_service_provider,
+                cluster_id,
+            ) {
+                Ok(block_height) => block_height.get_block_height(),
+                Err(error) if error.is_none_type() => continue,
+                Err(error) => return Err(error.into()),
+            };
+            let block_margin = match Cluster::get(
+                *platform,
+                *liveness_service_provider,
+                cluster_id,
+                latest_block_height,
+            ) {
+                Ok(cluster) => cluster.block_margin,
+                Err(error) if error.is_none_type() => continue,
+                Err(error) => return Err(error.into()),
+            };
+
+            for block_height in
+                latest_block_height.saturating_sub(block_margin * 2)..=latest_block_height
+            {
+                add_usage(
+                    &mut cluster_usage,
+                    Cluster::get(
+                        *platform,
+                        *liveness_service_provider,
+                        cluster_id,
+                        block_height,
+                    ),
+                )?;
+            }
+        }
+    }
+
+    Ok(DiskUsage {
+        database_size: get_directory_size(&config.database_path())?,
+        measured_at: current_timestamp(),
+        model_usage_list: vec![
+            batch_usage,
+            raw_transaction_usage,
+            encrypted_transaction_usage,
+            order_commitment_usage,
+            quorum_order_commitment_usage,
+            transaction_status_usage,
+            cluster_usage,
+        ],
+    })
+}
+
+fn add_usage<V: Serialize>(
+    model_usage: &mut ModelUsage,
+    result: Result<V, KvStoreError>,
+) -> Result<(), KvStoreError> {
+    match result {
+        Ok(value) => {
+            model_usage.add(&value);
+            Ok(())
+        }
+        Err(error) if error.is_none_type() => Ok(()),
+        Err(error) => Err(error),
+    }
+}
+
+fn get_directory_size(path: &Path) -> Result<u64, Error> {
+    let mut size = 0;
+
+    for entry in fs::read_dir(path)? {
+        let entry = entry?;
+        let metadata = entry.metadata()?;
+
+        if metadata.is_dir() {
+            size += get_directory_size(&entry.path())?;
+        } else {
+            size += metadata.len();
+        }
+    }
+
+    Ok(size)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn prune_limit_follows_the_retention_policy() {
+        assert_eq!(calculate_prune_limit(RetentionPolicy::KeepAll, 10, []), 0);
+        assert_eq!(
+            calculate_prune_limit(RetentionPolicy::KeepBatchCount { batch_count: 3 }, 10, []),
+            7
+        );
+        assert_eq!(
+            calculate_prune_limit(RetentionPolicy::KeepBatchCount { batch_count: 30 }, 10, []),
+            0
+        );
+        assert_eq!(
+            calculate_prune_limit(RetentionPolicy::KeepUntilFinalized, 10, []),
+            10
+        );
+    }
+
+    #[test]
+    fn prune_limit_waits_for_the_slowest_builder_sink() {
+        let retention_policy = RetentionPolicy::KeepBatchCount { batch_count: 3 };
+
+        assert_eq!(calculate_prune_limit(retention_policy, 10, [9, 4, 8]), 4);
+        assert_eq!(calculate_prune_limit(retention_policy, 10, [9, 8]), 7);
+    }
+}
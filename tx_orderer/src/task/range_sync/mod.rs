@@ -0,0 +1,759 @@
+use std::{future::Future, time::Duration};
+
+use radius_sdk::{
+    json_rpc::{client::Id, server::RpcParameter},
+    signature::Address,
+};
+
+use crate::{
+    error::Error,
+    rpc::cluster::{
+        Authenticated, GetBatchRange, GetBatchRangeResponse, GetOrderCommitmentInfo,
+        GetOrderCommitmentInfoResponse, GetTransactionRange, GetTransactionRangeResponse,
+        SyncedBatch, SyncedTransaction, MAX_BATCH_RANGE, MAX_TRANSACTION_RANGE,
+    },
+    state::AppState,
+    types::*,
+};
+
+const RANGE_SYNC_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Periodically compares the local [`RollupMetadata`] with the peers and
+/// backfills the batches and transactions this node has missed.
+pub fn run_range_sync(context: AppState) {
+    tokio::spawn(async move {
+        loop {
+            tokio::time::sleep(RANGE_SYNC_INTERVAL).await;
+
+            let rollup_id_list = match RollupIdList::get() {
+                Ok(rollup_id_list) => rollup_id_list,
+                Err(error) => {
+                    if !error.is_none_type() {
+                        tracing::error!("Range sync - failed to get rollup id list: {:?}", error);
+                    }
+                    continue;
+                }
+            };
+
+            for rollup_id in rollup_id_list.iter() {
+                if let Err(error) = sync_rollup(&context, rollup_id).await {
+                    tracing::warn!(
+                        "Range sync - failed to sync rollup - rollup_id: {:?} / error: {:?}",
+                        rollup_id,
+                        error
+                    );
+                }
+            }
+        }
+    });
+}
+
+async fn sync_rollup(context: &AppState, rollup_id: &RollupId) -> Result<(), Error> {
+    let rollup = Rollup::get(rollup_id)?;
+    let cluster_metadata = ClusterMetadata::get(
+        rollup.platform,
+        rollup.liveness_service_provider,
+        &rollup.cluster_id,
+    )?;
+
+    // The leader is the source of the ordering, there is nothing to catch up.
+    if cluster_metadata.is_leader {
+        return Ok(());
+    }
+
+    let cluster = Cluster::get(
+        rollup.platform,
+        rollup.liveness_service_provider,
+        &rollup.cluster_id,
+        cluster_metadata.platform_block_height,
+    )?;
+
+    let peer_cluster_rpc_url_list = match cluster_metadata
+        .leader_tx_orderer_rpc_info
+        .and_then(|leader_tx_orderer_rpc_info| leader_tx_orderer_rpc_info.cluster_rpc_url)
+    {
+        Some(leader_cluster_rpc_url) => vec![leader_cluster_rpc_url],
+        None => cluster.get_other_cluster_rpc_url_list(),
+    };
+    if peer_cluster_rpc_url_list.is_empty() {
+        return Ok(());
+    }
+
+    let signer = context.get_signer(rollup.platform).await?;
+    let parameter = Authenticated::new(
+        &signer,
+        GetOrderCommitmentInfo {
+            rollup_id: rollup_id.clone(),
+        },
+    )
+    .await?;
+    let peer_rollup_metadata: GetOrderCommitmentInfoResponse = context
+        .rpc_client()
+        .fetch(
+            peer_cluster_rpc_url_list,
+            GetOrderCommitmentInfo::method(),
+            &parameter,
+            Id::Null,
+        )
+        .await?;
+
+    let rollup_metadata = RollupMetadata::get(rollup_id)?;
+
+    backfill_rollup(
+        context,
+        &rollup,
+        &cluster,
+        rollup_metadata.provided_batch_number,
+        peer_rollup_metadata.batch_number,
+        peer_rollup_metadata.transaction_order,
+    )
+    .await
+}
+
+/// Fetches every batch in `[start_batch_number, batch_number)` that has not
+/// been sealed locally and every transaction before `transaction_order` in
+/// `batch_number` from the other tx_orderers of the cluster.
+pub async fn backfill_rollup(
+    context: &AppState,
+    rollup: &Rollup,
+    cluster: &Cluster,
+    start_batch_number: u64,
+    batch_number: u64,
+    transaction_order: u64,
+) -> Result<(), Error> {
+    let rollup_id = &rollup.rollup_id;
+
+    // The filter is evaluated lazily, so batches stored by an earlier range
+    // request are skipped.
+    let mut missing_batch_number_list = (start_batch_number..batch_number)
+        .filter(|batch_number| Batch::get(rollup_id, *batch_number).is_err());
+
+    while let Some(start_batch_number) = missing_batch_number_list.next() {
+        let end_batch_number = (start_batch_number + MAX_BATCH_RANGE).min(batch_number);
+        let response = fetch_batch_range(
+            context,
+            rollup,
+            cluster,
+            start_batch_number,
+            end_batch_number,
+        )
+        .await?;
+
+        let mut synced_batch_number_list = Vec::new();
+        for synced_batch in response.batch_list {
+            let synced_batch_number = synced_batch.batch.batch_number;
+            if let Err(error) = store_batch(rollup, cluster, synced_batch).await {
+                tracing::warn!(
+                    "Range sync - rejected batch - rollup_id: {:?} / batch_number: {:?} / error: {:?}",
+                    rollup_id,
+                    synced_batch_number,
+                    error
+                );
+                continue;
+            }
+            synced_batch_number_list.push(synced_batch_number);
+        }
+
+        // Batches the peers have not sealed yet are filled transaction by
+        // transaction.
+        if !synced_batch_number_list.contains(&start_batch_number) {
+            backfill_transactions(
+                context,
+                rollup,
+                cluster,
+                start_batch_number,
+                rollup.max_transaction_count_per_batch,
+            )
+            .await?;
+        }
+    }
+
+    backfill_transactions(context, rollup, cluster, batch_number, transaction_order).await
+}
+
+async fn backfill_transactions(
+    context: &AppState,
+    rollup: &Rollup,
+    cluster: &Cluster,
+    batch_number: u64,
+    end_transaction_order: u64,
+) -> Result<(), Error> {
+    let rollup_id = &rollup.rollup_id;
+    let Some(start_transaction_order) = (0..end_transaction_order).find(|transaction_order| {
+        RawTransactionModel::get(rollup_id, batch_number, *transaction_order).is_err()
+            && EncryptedTransactionModel::get(rollup_id, batch_number, *transaction_order).is_err()
+    }) else {
+        return Ok(());
+    };
+
+    let signer = &context.get_signer(rollup.platform).await?;
+
+    backfill_in_pages(
+        start_transaction_order,
+        end_transaction_order,
+        move |start_transaction_order, end_transaction_order| {
+            async move {
+                let parameter = Authenticated::new(
+                    signer,
+                    GetTransactionRange {
+                        rollup_id: rollup_id.clone(),
+                        batch_number,
+                        start_transaction_order,
+                        end_transaction_order,
+                    },
+                )
+                .await?;
+                let response: GetTransactionRangeResponse = context
+                    .rpc_client()
+                    .fetch(
+                        cluster.get_other_cluster_rpc_url_list(),
+                        GetTransactionRange::method(),
+                        &parameter,
+                        Id::Null,
+                    )
+                    .await?;
+
+                let last_transaction_order = response
+                    .transaction_list
+                    .last()
+                    .map(|synced_transaction| synced_transaction.transaction_order);
+
+                tracing::info!(
+                    "Range sync - backfilled transactions - rollup_id: {:?} / batch_number: {:?} / count: {:?}",
+                    rollup_id,
+                    batch_number,
+                    response.transaction_list.len()
+                );
+
+                for synced_transaction in response.transaction_list {
+                    store_transaction(rollup, cluster, batch_number, synced_transaction)?;
+                }
+
+                Ok(last_transaction_order)
+            }
+        },
+    )
+    .await
+}
+
+/// Walks `[start_transaction_order, end_transaction_order)` in pages of at
+/// most [`MAX_TRANSACTION_RANGE`], the most a peer returns at once. Each page
+/// starts after the last transaction the previous one returned, and
+/// `backfill_page` returns `None` when the peer has nothing more.
+async fn backfill_in_pages<F, Fut>(
+    start_transaction_order: u64,
+    end_transaction_order: u64,
+    mut backfill_page: F,
+) -> Result<(), Error>
+where
+    F: FnMut(u64, u64) -> Fut,
+    Fut: Future<Output = Result<Option<u64>, Error>>,
+{
+    let mut start_transaction_order = start_transaction_order;
+
+    while start_transaction_order < end_transaction_order {
+        let page_end_transaction_order =
+            end_transaction_order.min(start_transaction_order + MAX_TRANSACTION_RANGE);
+
+        match backfill_page(start_transaction_order, page_end_transaction_order).await? {
+            // A page that does not move forward would be requested again.
+            Some(last_transaction_order) if last_transaction_order >= start_transaction_order => {
+                start_transaction_order = last_transaction_order + 1;
+            }
+            _ => break,
+        }
+    }
+
+    Ok(())
+}
+
+async fn fetch_batch_range(
+    context: &AppState,
+    rollup: &Rollup,
+    cluster: &Cluster,
+    start_batch_number: u64,
+    end_batch_number: u64,
+) -> Result<GetBatchRangeResponse, Error> {
+    let signer = context.get_signer(rollup.platform).await?;
+    let parameter = Authenticated::new(
+        &signer,
+        GetBatchRange {
+            rollup_id: rollup.rollup_id.clone(),
+            start_batch_number,
+            end_batch_number,
+        },
+    )
+    .await?;
+
+    Ok(context
+        .rpc_client()
+        .fetch(
+            cluster.get_other_cluster_rpc_url_list(),
+            GetBatchRange::method(),
+            &parameter,
+            Id::Null,
+        )
+        .await?)
+}
+
+/// Stores a batch received from a peer after checking that it was signed by
+/// the leader that sealed it and that its transactions match the commitment.
+async fn store_batch(
+    rollup: &Rollup,
+    cluster: &Cluster,
+    synced_batch: SyncedBatch,
+) -> Result<(), Error> {
+    let rollup_id = &rollup.rollup_id;
+    let SyncedBatch {
+        batch,
+        order_commitment_list,
+    } = synced_batch;
+
+    let tx_orderer_address_list = cluster.get_tx_orderer_address_list();
+    if !tx_orderer_address_list.contains(&batch.batch_creator_address) {
+        return Err(Error::TxOrdererInfoNotFound);
+    }
+
+    let merkle_tree = MerkleTree::from_raw_transaction_list(&batch.raw_transaction_list).await;
+    let merkle_root = merkle_tree.get_merkle_root().await;
+    if BatchCommitment::from(merkle_root) != batch.batch_commitment {
+        return Err(Error::InvalidBatchCommitment);
+    }
+
+    let leader_address = get_batch_leader_address(rollup, &batch)?;
+    check_batch_signer(rollup.platform, &batch, &merkle_root, &leader_address)?;
+
+    for (transaction_order, raw_transaction) in batch.raw_transaction_list.iter().enumerate() {
+        let transaction_order = transaction_order as u64;
+        let encrypted_transaction = batch
+            .encrypted_transaction_list
+            .get(transaction_order as usize)
+            .cloned()
+            .flatten();
+        let is_direct_sent = encrypted_transaction.is_none();
+
+        if let Some(encrypted_transaction) = &encrypted_transaction {
+            EncryptedTransactionModel::put(
+                rollup_id,
+                batch.batch_number,
+                transaction_order,
+                encrypted_transaction,
+            )?;
+        }
+
+        let transaction_hash = raw_transaction.raw_transaction_hash();
+        RawTransactionModel::put_with_transaction_hash(
+            rollup_id,
+            &transaction_hash,
+            raw_transaction.clone(),
+            is_direct_sent,
+        )?;
+        RawTransactionModel::put(
+            rollup_id,
+            batch.batch_number,
+            transaction_order,
+            raw_transaction.clone(),
+            is_direct_sent,
+        )?;
+
+        if TransactionStatusInfo::get(rollup_id, transaction_hash.as_ref()).is_err() {
+            TransactionStatusInfo::new(batch.batch_number, transaction_order, !is_direct_sent)
+                .put(rollup_id, transaction_hash.as_ref())?;
+        }
+
+        if let Some(Some(order_commitment)) = order_commitment_list.get(transaction_order as usize)
+        {
+            if is_valid_order_commitment(
+                rollup.platform,
+                rollup_id,
+                &tx_orderer_address_list,
+                order_commitment,
+                batch.batch_number,
+                transaction_order,
+                &transaction_hash,
+            ) {
+                order_commitment.put(rollup_id, batch.batch_number, transaction_order)?;
+            } else {
+                tracing::warn!(
+                    "Range sync - rejected order commitment - rollup_id: {:?} / batch_number: {:?} / transaction_order: {:?}",
+                    rollup_id,
+                    batch.batch_number,
+                    transaction_order
+                );
+            }
+        }
+    }
+
+    Batch::put(&batch, rollup_id, batch.batch_number)?;
+    CanProvideTransactionInfo::remove_can_provide_transaction_orders(
+        rollup_id,
+        batch.batch_number,
+    )?;
+
+    tracing::info!(
+        "Range sync - backfilled batch - rollup_id: {:?} / batch_number: {:?}",
+        rollup_id,
+        batch.batch_number
+    );
+
+    Ok(())
+}
+
+fn store_transaction(
+    rollup: &Rollup,
+    cluster: &Cluster,
+    batch_number: u64,
+    synced_transaction: SyncedTransaction,
+) -> Result<(), Error> {
+    let SyncedTransaction {
+        transaction_order,
+        raw_transaction,
+        is_direct_sent,
+        encrypted_transaction,
+        order_commitment,
+    } = synced_transaction;
+    let rollup_id = &rollup.rollup_id;
+
+    let transaction_hash = match (&raw_transaction, &encrypted_transaction) {
+        (Some(raw_transaction), _) => raw_transaction.raw_transaction_hash(),
+        (None, Some(encrypted_transaction)) => encrypted_transaction.raw_transaction_hash(),
+        (None, None) => return Err(Error::TransactionNotFound),
+    };
+
+    if !is_valid_order_commitment(
+        rollup.platform,
+        rollup_id,
+        &cluster.get_tx_orderer_address_list(),
+        &order_commitment,
+        batch_number,
+        transaction_order,
+        &transaction_hash,
+    ) {
+        return Err(Error::InvalidOrderCommitment);
+    }
+
+    if let Some(encrypted_transaction) = &encrypted_transaction {
+        let transaction_hash = encrypted_transaction.raw_transaction_hash();
+
+        EncryptedTransactionModel::put_with_transaction_hash(
+            rollup_id,
+            &transaction_hash,
+            encrypted_transaction,
+        )?;
+        EncryptedTransactionModel::put(
+            rollup_id,
+            batch_number,
+            transaction_order,
+            encrypted_transaction,
+        )?;
+
+        if TransactionStatusInfo::get(rollup_id, transaction_hash.as_ref()).is_err() {
+            TransactionStatusInfo::new(batch_number, transaction_order, true)
+                .put(rollup_id, transaction_hash.as_ref())?;
+        }
+    }
+
+    if let Some(raw_transaction) = raw_transaction {
+        let transaction_hash = raw_transaction.raw_transaction_hash();
+
+        RawTransactionModel::put_with_transaction_hash(
+            rollup_id,
+            &transaction_hash,
+            raw_transaction.clone(),
+            is_direct_sent,
+        )?;
+        RawTransactionModel::put(
+            rollup_id,
+            batch_number,
+            transaction_order,
+            raw_transaction,
+            is_direct_sent,
+        )?;
+
+        if TransactionStatusInfo::get(rollup_id, transaction_hash.as_ref()).is_err() {
+            TransactionStatusInfo::new(batch_number, transaction_order, false)
+                .put(rollup_id, transaction_hash.as_ref())?;
+        }
+
+        CanProvideTransactionInfo::add_can_provide_transaction_orders(
+            rollup_id,
+            batch_number,
+            vec![transaction_order],
+        )?;
+    }
+
+    order_commitment.put(rollup_id, batch_number, transaction_order)?;
+
+    Ok(())
+}
+
+/// Returns the leader that sealed the batch, that is the leader that ordered
+/// its last transaction. Without a known hand-over for that position, the
+/// batch must come from the current leader.
+fn get_batch_leader_address(rollup: &Rollup, batch: &Batch) -> Result<Address, Error> {
+    let last_transaction_order = (batch.raw_transaction_list.len() as u64).saturating_sub(1);
+
+    let leader_history = LeaderHistory::get_or(&rollup.rollup_id, LeaderHistory::default)?;
+    if let Some(leader_state) =
+        leader_history.get_leader_state(batch.batch_number, last_transaction_order)
+    {
+        return Ok(leader_state.leader_tx_orderer_address.clone());
+    }
+
+    ClusterMetadata::get(
+        rollup.platform,
+        rollup.liveness_service_provider,
+        &rollup.cluster_id,
+    )?
+    .leader_tx_orderer_rpc_info
+    .map(|leader_tx_orderer_rpc_info| leader_tx_orderer_rpc_info.tx_orderer_address)
+    .ok_or(Error::NoLeader)
+}
+
+/// The batch creator signs the merkle root of the batch when it seals it.
+fn check_batch_signer(
+    platform: Platform,
+    batch: &Batch,
+    merkle_root: &[u8; 32],
+    leader_address: &Address,
+) -> Result<(), Error> {
+    let signer_address = batch
+        .signature
+        .get_signer_address(platform.into(), merkle_root)?;
+
+    if signer_address != batch.batch_creator_address || &signer_address != leader_address {
+        return Err(Error::InvalidSignature);
+    }
+
+    Ok(())
+}
+
+/// Checks that the order commitment was issued for the transaction it is
+/// stored with. Signed commitments must also be signed by a member of the
+/// cluster. Bundle commitments cover several positions and are not accepted
+/// from a peer.
+fn is_valid_order_commitment(
+    platform: Platform,
+    rollup_id: &RollupId,
+    tx_orderer_address_list: &[Address],
+    order_commitment: &OrderCommitment,
+    batch_number: u64,
+    transaction_order: u64,
+    transaction_hash: &RawTransactionHash,
+) -> bool {
+    let transaction_hash: &str = transaction_hash.as_ref();
+
+    match order_commitment {
+        OrderCommitment::Single(SingleOrderCommitment::TransactionHash(
+            transaction_hash_order_commitment,
+        )) => transaction_hash_order_commitment.as_ref() == transaction_hash,
+        OrderCommitment::Single(SingleOrderCommitment::Sign(sign_order_commitment)) => {
+            let data = &sign_order_commitment.data;
+
+            data.rollup_id == *rollup_id
+                && data.batch_number == batch_number
+                && data.transaction_order == transaction_order
+                && data.transaction_hash == transaction_hash
+                && tx_orderer_address_list
+                    .contains(&sign_order_commitment.get_signer_address(platform.into()))
+        }
+        OrderCommitment::Bundle(_) => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use radius_sdk::signature::{ChainType, PrivateKeySigner};
+
+    use super::*;
+
+    const OTHER_SIGNING_KEY: &str =
+        "0x59c6995e998f97a5a0044966f0945389dc9e86dae88c7a8412f4603b6b78690d";
+
+    fn signer(signing_key: &str) -> PrivateKeySigner {
+        PrivateKeySigner::from_str(ChainType::Ethereum, signing_key).unwrap()
+    }
+
+    fn sealed_batch(batch_creator: &PrivateKeySigner, signer: &PrivateKeySigner) -> Batch {
+        let merkle_root = [7_u8; 32];
+
+        Batch::new(
+            0,
+            vec![None],
+            vec![RawTransaction::default()],
+            BatchCommitment::from(merkle_root),
+            batch_creator.address().clone(),
+            signer.sign_message(&merkle_root).unwrap(),
+        )
+    }
+
+    fn sign_order_commitment(
+        signer: &PrivateKeySigner,
+        batch_number: u64,
+        transaction_order: u64,
+        transaction_hash: &RawTransactionHash,
+    ) -> OrderCommitment {
+        let data = OrderCommitmentData {
+            rollup_id: "rollup".to_owned(),
+            batch_number,
+            transaction_order,
+            transaction_hash: transaction_hash.clone().as_string(),
+            pre_merkle_path: Vec::new(),
+        };
+        let signature = signer.sign_message(&data).unwrap();
+
+        OrderCommitment::Single(SingleOrderCommitment::Sign(SignOrderCommitment {
+            data,
+            signature,
+        }))
+    }
+
+    #[test]
+    fn batch_signed_by_the_leader_is_accepted() {
+        let leader = signer(DEFAULT_SIGNING_KEY);
+        let batch = sealed_batch(&leader, &leader);
+
+        assert!(check_batch_signer(Platform::Ethereum, &batch, &[7; 32], leader.address()).is_ok());
+    }
+
+    #[test]
+    fn forged_batch_is_rejected() {
+        let leader = signer(DEFAULT_SIGNING_KEY);
+        let forger = signer(OTHER_SIGNING_KEY);
+
+        // Signed by another member that claims to be the leader.
+        let batch = sealed_batch(&leader, &forger);
+        assert!(
+            check_batch_signer(Platform::Ethereum, &batch, &[7; 32], leader.address()).is_err()
+        );
+
+        // Signed by a member that was not the leader of the batch.
+        let batch = sealed_batch(&forger, &forger);
+        assert!(
+            check_batch_signer(Platform::Ethereum, &batch, &[7; 32], leader.address()).is_err()
+        );
+    }
+
+    #[test]
+    fn forged_order_commitment_is_rejected() {
+        let member = signer(DEFAULT_SIGNING_KEY);
+        let tx_orderer_address_list = vec![member.address().clone()];
+        let rollup_id = "rollup".to_owned();
+        let transaction_hash = RawTransactionHash::new([1_u8; 32]);
+        let other_transaction_hash = RawTransactionHash::new([2_u8; 32]);
+
+        let is_valid = |order_commitment: &OrderCommitment| {
+            is_valid_order_commitment(
+                Platform::Ethereum,
+                &rollup_id,
+                &tx_orderer_address_list,
+                order_commitment,
+                3,
+                5,
+                &transaction_hash,
+            )
+        };
+
+        assert!(is_valid(&sign_order_commitment(
+            &member,
+            3,
+            5,
+            &transaction_hash
+        )));
+        assert!(is_valid(&OrderCommitment::Single(
+            SingleOrderCommitment::TransactionHash(TransactionHashOrderCommitment::new(
+                transaction_hash.clone().as_string()
+            ))
+        )));
+
+        // Issued for another transaction or position.
+        assert!(!is_valid(&sign_order_commitment(
+            &member,
+            3,
+            5,
+            &other_transaction_hash
+        )));
+        assert!(!is_valid(&sign_order_commitment(
+            &member,
+            3,
+            6,
+            &transaction_hash
+        )));
+        assert!(!is_valid(&sign_order_commitment(
+            &member,
+            4,
+            5,
+            &transaction_hash
+        )));
+        assert!(!is_valid(&OrderCommitment::Single(
+            SingleOrderCommitment::TransactionHash(TransactionHashOrderCommitment::new(
+                other_transaction_hash.as_string()
+            ))
+        )));
+
+        // Signed by a tx_orderer outside of the cluster.
+        assert!(!is_valid(&sign_order_commitment(
+            &signer(OTHER_SIGNING_KEY),
+            3,
+            5,
+            &transaction_hash
+        )));
+    }
+
+    /// Returns what a peer that holds every position below `held_count` would
+    /// reply, capped like `GetTransactionRange`.
+    fn peer_page(
+        held_count: u64,
+        start_transaction_order: u64,
+        end_transaction_order: u64,
+    ) -> Vec<u64> {
+        let end_transaction_order = end_transaction_order
+            .min(start_transaction_order + MAX_TRANSACTION_RANGE)
+            .min(held_count);
+
+        (start_transaction_order..end_transaction_order).collect()
+    }
+
+    #[tokio::test]
+    async fn batch_larger_than_a_page_is_backfilled_completely() {
+        let mut requested_range_list = Vec::new();
+        let mut backfilled_list = Vec::new();
+
+        backfill_in_pages(0, 600, |start_transaction_order, end_transaction_order| {
+            requested_range_list.push((start_transaction_order, end_transaction_order));
+            let page = peer_page(600, start_transaction_order, end_transaction_order);
+            let last_transaction_order = page.last().copied();
+            backfilled_list.extend(page);
+
+            async move { Ok(last_transaction_order) }
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(backfilled_list, (0..600).collect::<Vec<u64>>());
+        assert_eq!(requested_range_list, vec![(0, 256), (256, 512), (512, 600)]);
+    }
+
+    #[tokio::test]
+    async fn backfill_stops_where_the_peer_runs_out() {
+        let mut backfilled_list = Vec::new();
+
+        backfill_in_pages(
+            10,
+            1000,
+            |start_transaction_order, end_transaction_order| {
+                let page = peer_page(300, start_transaction_order, end_transaction_order);
+                let last_transaction_order = page.last().copied();
+                backfilled_list.extend(page);
+
+                async move { Ok(last_transaction_order) }
+            },
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(backfilled_list, (10..300).collect::<Vec<u64>>());
+    }
+}
@@ -0,0 +1,648 @@
+use std::{sync::Arc, time::Duration};
+
+use futures::{SinkExt, StreamExt};
+use serde::{Deserialize, Serialize};
+use tokio::{
+    io::copy_bidirectional,
+    net::{TcpListener, TcpStream},
+    sync::{
+        broadcast::{self, error::RecvError},
+        mpsc::{self, error::TrySendError},
+        Notify,
+    },
+    task::JoinHandle,
+};
+use tokio_tungstenite::{accept_async, tungstenite::Message};
+
+use crate::{error::Error, state::AppState, types::*};
+
+const SUBSCRIPTION_CHANNEL_CAPACITY: usize = 4096;
+
+/// The number of messages that can be queued for a subscriber before it is
+/// disconnected for not reading them.
+const SUBSCRIBER_QUEUE_CAPACITY: usize = 1024;
+
+/// How far behind the current batch a subscriber can ask to replay from.
+const MAX_REPLAY_BATCH_COUNT: u64 = 256;
+
+/// The size of the request head that is read to tell a WebSocket upgrade from
+/// a JSON-RPC request.
+const REQUEST_HEAD_SIZE: usize = 4096;
+
+const REQUEST_HEAD_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Broadcasts the events that external subscribers can listen to.
+pub struct SubscriptionManager {
+    inner: Arc<SubscriptionManagerInner>,
+}
+
+struct SubscriptionManagerInner {
+    sender: broadcast::Sender<SubscriptionEvent>,
+}
+
+impl Clone for SubscriptionManager {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl Default for SubscriptionManager {
+    fn default() -> Self {
+        let (sender, _) = broadcast::channel(SUBSCRIPTION_CHANNEL_CAPACITY);
+
+        Self {
+            inner: Arc::new(SubscriptionManagerInner { sender }),
+        }
+    }
+}
+
+impl SubscriptionManager {
+    pub fn subscribe(&self) -> broadcast::Receiver<SubscriptionEvent> {
+        self.inner.sender.subscribe()
+    }
+
+    pub fn publish_order_commitment(
+        &self,
+        rollup_id: &RollupId,
+        batch_number: u64,
+        transaction_order: u64,
+        order_commitment: &OrderCommitment,
+    ) {
+        self.publish(SubscriptionEvent::OrderCommitment(OrderCommitmentEvent {
+            rollup_id: rollup_id.to_owned(),
+            batch_number,
+            transaction_order,
+            order_commitment: order_commitment.clone(),
+        }));
+    }
+
+    pub fn publish_batch(&self, rollup_id: &RollupId, batch: &Batch) {
+        self.publish(SubscriptionEvent::Batch(BatchEvent {
+            rollup_id: rollup_id.to_owned(),
+            batch_number: batch.batch_number,
+            batch: batch.clone(),
+        }));
+    }
+
+    pub fn publish_decrypted_transaction(
+        &self,
+        rollup_id: &RollupId,
+        batch_number: u64,
+        transaction_order: u64,
+        raw_transaction: &RawTransaction,
+    ) {
+        self.publish(SubscriptionEvent::DecryptedTransaction(
+            DecryptedTransactionEvent {
+                rollup_id: rollup_id.to_owned(),
+                batch_number,
+                transaction_order,
+                raw_transaction: raw_transaction.clone(),
+            },
+        ));
+    }
+
+    fn publish(&self, event: SubscriptionEvent) {
+        // Sending only fails when there is no subscriber, which is not an error.
+        let _ = self.inner.sender.send(event);
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(tag = "type", content = "data")]
+#[serde(rename_all = "snake_case")]
+pub enum SubscriptionEvent {
+    OrderCommitment(OrderCommitmentEvent),
+    Batch(BatchEvent),
+    DecryptedTransaction(DecryptedTransactionEvent),
+}
+
+impl SubscriptionEvent {
+    pub fn rollup_id(&self) -> &RollupId {
+        match self {
+            Self::OrderCommitment(event) => &event.rollup_id,
+            Self::Batch(event) => &event.rollup_id,
+            Self::DecryptedTransaction(event) => &event.rollup_id,
+        }
+    }
+
+    /// Returns `(batch_number, transaction_order)` of the event. Batch events
+    /// use `0` as the transaction order.
+    pub fn position(&self) -> (u64, u64) {
+        match self {
+            Self::OrderCommitment(event) => (event.batch_number, event.transaction_order),
+            Self::Batch(event) => (event.batch_number, 0),
+            Self::DecryptedTransaction(event) => (event.batch_number, event.transaction_order),
+        }
+    }
+
+    pub fn kind(&self) -> SubscriptionKind {
+        match self {
+            Self::OrderCommitment(_) => SubscriptionKind::OrderCommitments,
+            Self::Batch(_) => SubscriptionKind::Batches,
+            Self::DecryptedTransaction(_) => SubscriptionKind::DecryptedTransactions,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct OrderCommitmentEvent {
+    pub rollup_id: RollupId,
+    pub batch_number: u64,
+    pub transaction_order: u64,
+    pub order_commitment: OrderCommitment,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct BatchEvent {
+    pub rollup_id: RollupId,
+    pub batch_number: u64,
+    pub batch: Batch,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct DecryptedTransactionEvent {
+    pub rollup_id: RollupId,
+    pub batch_number: u64,
+    pub transaction_order: u64,
+    pub raw_transaction: RawTransaction,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SubscriptionKind {
+    OrderCommitments,
+    Batches,
+    DecryptedTransactions,
+}
+
+impl SubscriptionKind {
+    fn from_method(method: &str) -> Option<Self> {
+        match method {
+            "subscribe_order_commitments" => Some(Self::OrderCommitments),
+            "subscribe_batches" => Some(Self::Batches),
+            "subscribe_decrypted_transactions" => Some(Self::DecryptedTransactions),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct SubscriptionRequest {
+    pub id: serde_json::Value,
+    pub method: String,
+    pub params: SubscriptionParams,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct SubscriptionParams {
+    pub rollup_id: RollupId,
+
+    /// Replays the stored events starting from this batch number before
+    /// streaming new ones, so that a subscriber can resume after a reconnect.
+    pub from_batch_number: Option<u64>,
+}
+
+/// Serves the subscriptions and the external RPC server at
+/// `upstream_rpc_address` on the same `listener`. The WebSocket upgrade
+/// requests are handled here and every other connection is passed through to
+/// the RPC server.
+pub async fn run_external_rpc_front(
+    context: AppState,
+    listener: TcpListener,
+    upstream_rpc_address: String,
+) -> Result<(), Error> {
+    loop {
+        let (tcp_stream, socket_addr) = listener.accept().await.map_err(Error::Syscall)?;
+        let context = context.clone();
+        let upstream_rpc_address = upstream_rpc_address.clone();
+
+        tokio::spawn(async move {
+            let request_head =
+                match tokio::time::timeout(REQUEST_HEAD_TIMEOUT, peek_request_head(&tcp_stream))
+                    .await
+                {
+                    Ok(Ok(request_head)) => request_head,
+                    // The connection was closed or did not send a request in time.
+                    _ => return,
+                };
+
+            if is_websocket_upgrade(&request_head) {
+                handle_connection(context, tcp_stream).await;
+
+                tracing::info!("Subscriber disconnected: {}", socket_addr);
+            } else if let Err(error) = pass_through(tcp_stream, &upstream_rpc_address).await {
+                tracing::warn!(
+                    "Failed to pass the connection through to the external RPC server - address: {:?} / error: {:?}",
+                    socket_addr,
+                    error
+                );
+            }
+        });
+    }
+}
+
+/// Reads the request head without consuming it, so that the connection can
+/// still be handed over as it is.
+async fn peek_request_head(tcp_stream: &TcpStream) -> std::io::Result<Vec<u8>> {
+    let mut buffer = vec![0; REQUEST_HEAD_SIZE];
+
+    loop {
+        let length = tcp_stream.peek(&mut buffer).await?;
+        if length == 0 {
+            return Err(std::io::ErrorKind::UnexpectedEof.into());
+        }
+
+        let is_complete = buffer[..length]
+            .windows(4)
+            .any(|window| window == b"\r\n\r\n");
+        if is_complete || length == buffer.len() {
+            buffer.truncate(length);
+            return Ok(buffer);
+        }
+
+        // The rest of the head has not arrived yet.
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+}
+
+fn is_websocket_upgrade(request_head: &[u8]) -> bool {
+    let request_head = String::from_utf8_lossy(request_head);
+    let mut line_list = request_head.lines();
+
+    if !line_list
+        .next()
+        .is_some_and(|request_line| request_line.starts_with("GET "))
+    {
+        return false;
+    }
+
+    line_list
+        .take_while(|line| !line.is_empty())
+        .filter_map(|line| line.split_once(':'))
+        .any(|(name, value)| {
+            name.trim().eq_ignore_ascii_case("upgrade")
+                && value.trim().eq_ignore_ascii_case("websocket")
+        })
+}
+
+async fn pass_through(
+    mut tcp_stream: TcpStream,
+    upstream_rpc_address: &str,
+) -> std::io::Result<()> {
+    let mut upstream_stream = TcpStream::connect(upstream_rpc_address).await?;
+    copy_bidirectional(&mut tcp_stream, &mut upstream_stream).await?;
+
+    Ok(())
+}
+
+fn is_within_replay_window(from_batch_number: u64, current_batch_number: u64) -> bool {
+    from_batch_number.saturating_add(MAX_REPLAY_BATCH_COUNT) >= current_batch_number
+}
+
+/// The messages queued for a subscriber. A subscriber that lets the queue fill
+/// up is disconnected, so that a slow reader cannot hold on to the memory.
+#[derive(Clone)]
+struct SubscriberQueue {
+    sender: mpsc::Sender<Message>,
+    disconnect: Arc<Notify>,
+}
+
+impl SubscriberQueue {
+    fn with_capacity(capacity: usize) -> (Self, mpsc::Receiver<Message>) {
+        let (sender, receiver) = mpsc::channel(capacity);
+
+        (
+            Self {
+                sender,
+                disconnect: Arc::new(Notify::new()),
+            },
+            receiver,
+        )
+    }
+
+    /// Returns `false` when the subscriber is gone or has been disconnected.
+    fn send_json(&self, value: serde_json::Value) -> bool {
+        match self.sender.try_send(Message::Text(value.to_string())) {
+            Ok(()) => true,
+            Err(TrySendError::Full(_)) => {
+                self.disconnect.notify_one();
+                false
+            }
+            Err(TrySendError::Closed(_)) => false,
+        }
+    }
+
+    /// Waits for room in the queue instead of disconnecting the subscriber, so
+    /// that the replay goes at the pace the subscriber reads it.
+    async fn send_json_when_ready(&self, value: serde_json::Value) -> bool {
+        self.sender
+            .send(Message::Text(value.to_string()))
+            .await
+            .is_ok()
+    }
+
+    async fn disconnected(&self) {
+        self.disconnect.notified().await
+    }
+}
+
+async fn handle_connection(context: AppState, tcp_stream: TcpStream) {
+    let ws_stream = match accept_async(tcp_stream).await {
+        Ok(ws_stream) => ws_stream,
+        Err(error) => {
+            tracing::warn!("WebSocket handshake failure: {:?}", error);
+            return;
+        }
+    };
+
+    let (mut write, mut read) = ws_stream.split();
+    let (subscriber_queue, mut message_receiver) =
+        SubscriberQueue::with_capacity(SUBSCRIBER_QUEUE_CAPACITY);
+
+    let write_task = tokio::spawn(async move {
+        while let Some(message) = message_receiver.recv().await {
+            if write.send(message).await.is_err() {
+                break;
+            }
+        }
+    });
+
+    let mut subscription_task_list: Vec<JoinHandle<()>> = Vec::new();
+    let mut next_subscription_id: u64 = 0;
+
+    loop {
+        let message = tokio::select! {
+            message = read.next() => message,
+            _ = subscriber_queue.disconnected() => {
+                tracing::warn!("Disconnecting a subscriber that does not read its messages");
+                break;
+            }
+        };
+
+        let text = match message {
+            Some(Ok(Message::Text(text))) => text,
+            Some(Ok(Message::Close(_))) | None => break,
+            Some(Ok(_)) => continue,
+            Some(Err(error)) => {
+                tracing::warn!("WebSocket error: {}", error);
+                break;
+            }
+        };
+
+        let request: SubscriptionRequest = match serde_json::from_str(&text) {
+            Ok(request) => request,
+            Err(error) => {
+                subscriber_queue.send_json(serde_json::json!({
+                    "jsonrpc": "2.0",
+                    "id": serde_json::Value::Null,
+                    "error": { "code": -32700, "message": error.to_string() },
+                }));
+                continue;
+            }
+        };
+
+        let subscription_kind = match SubscriptionKind::from_method(&request.method) {
+            Some(subscription_kind) => subscription_kind,
+            None => {
+                subscriber_queue.send_json(serde_json::json!({
+                    "jsonrpc": "2.0",
+                    "id": request.id,
+                    "error": { "code": -32601, "message": "Method not found" },
+                }));
+                continue;
+            }
+        };
+
+        if let Some(from_batch_number) = request.params.from_batch_number {
+            let current_batch_number = RollupMetadata::get(&request.params.rollup_id)
+                .map(|rollup_metadata| rollup_metadata.batch_number)
+                .unwrap_or_default();
+
+            if !is_within_replay_window(from_batch_number, current_batch_number) {
+                subscriber_queue.send_json(serde_json::json!({
+                    "jsonrpc": "2.0",
+                    "id": request.id,
+                    "error": {
+                        "code": -32602,
+                        "message": format!(
+                            "from_batch_number must be within {} batches of the current batch {}",
+                            MAX_REPLAY_BATCH_COUNT, current_batch_number
+                        ),
+                    },
+                }));
+                continue;
+            }
+        }
+
+        let subscription_id = next_subscription_id;
+        next_subscription_id += 1;
+
+        subscriber_queue.send_json(serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": request.id,
+            "result": subscription_id,
+        }));
+
+        subscription_task_list.push(tokio::spawn(forward_events(
+            context.clone(),
+            subscription_kind,
+            subscription_id,
+            request.params,
+            subscriber_queue.clone(),
+        )));
+    }
+
+    for subscription_task in subscription_task_list {
+        subscription_task.abort();
+    }
+    write_task.abort();
+}
+
+async fn forward_events(
+    context: AppState,
+    subscription_kind: SubscriptionKind,
+    subscription_id: u64,
+    params: SubscriptionParams,
+    subscriber_queue: SubscriberQueue,
+) {
+    // Subscribe before replaying so that no event is missed in between.
+    let mut event_receiver = context.subscription_manager().subscribe();
+
+    // The replay goes one batch at a time so that only a single batch is held
+    // in memory.
+    let mut last_replayed_position = None;
+    if let Some(from_batch_number) = params.from_batch_number {
+        let mut batch_number = from_batch_number;
+
+        loop {
+            let (event_list, is_batch_sealed) =
+                replay_batch(subscription_kind, &params.rollup_id, batch_number);
+
+            for event in event_list {
+                last_replayed_position = Some(event.position());
+
+                if !subscriber_queue
+                    .send_json_when_ready(notification(subscription_id, &event))
+                    .await
+                {
+                    return;
+                }
+            }
+
+            // Only move on to the next batch when the current one is sealed.
+            if !is_batch_sealed {
+                break;
+            }
+
+            batch_number += 1;
+        }
+    }
+
+    loop {
+        match event_receiver.recv().await {
+            Ok(event) => {
+                if event.kind() != subscription_kind
+                    || event.rollup_id() != &params.rollup_id
+                    || last_replayed_position.is_some_and(|position| event.position() <= position)
+                {
+                    continue;
+                }
+
+                if !subscriber_queue.send_json(notification(subscription_id, &event)) {
+                    return;
+                }
+            }
+            Err(RecvError::Lagged(skipped_count)) => {
+                tracing::warn!(
+                    "Subscriber lagged behind - subscription_id: {:?} / skipped: {:?}",
+                    subscription_id,
+                    skipped_count
+                );
+
+                // The subscriber has to resume with `from_batch_number`.
+                subscriber_queue.send_json(serde_json::json!({
+                    "jsonrpc": "2.0",
+                    "method": "subscription_lagged",
+                    "params": { "subscription": subscription_id },
+                }));
+                return;
+            }
+            Err(RecvError::Closed) => return,
+        }
+    }
+}
+
+/// Returns the stored events of the batch and whether the batch is sealed.
+fn replay_batch(
+    subscription_kind: SubscriptionKind,
+    rollup_id: &RollupId,
+    batch_number: u64,
+) -> (Vec<SubscriptionEvent>, bool) {
+    let mut event_list = Vec::new();
+
+    let batch = Batch::get(rollup_id, batch_number).ok();
+    let is_batch_sealed = batch.is_some();
+
+    match subscription_kind {
+        SubscriptionKind::Batches => {
+            if let Some(batch) = batch {
+                event_list.push(SubscriptionEvent::Batch(BatchEvent {
+                    rollup_id: rollup_id.to_owned(),
+                    batch_number,
+                    batch,
+                }));
+            }
+        }
+        SubscriptionKind::OrderCommitments => {
+            let mut transaction_order = 0;
+            while let Ok(order_commitment) =
+                OrderCommitment::get(rollup_id, batch_number, transaction_order)
+            {
+                event_list.push(SubscriptionEvent::OrderCommitment(OrderCommitmentEvent {
+                    rollup_id: rollup_id.to_owned(),
+                    batch_number,
+                    transaction_order,
+                    order_commitment,
+                }));
+                transaction_order += 1;
+            }
+        }
+        SubscriptionKind::DecryptedTransactions => {
+            let mut transaction_order = 0;
+            loop {
+                let is_encrypted =
+                    EncryptedTransactionModel::get(rollup_id, batch_number, transaction_order)
+                        .is_ok();
+                let raw_transaction =
+                    RawTransactionModel::get(rollup_id, batch_number, transaction_order).ok();
+
+                if !is_encrypted && raw_transaction.is_none() {
+                    break;
+                }
+
+                if let (true, Some((raw_transaction, _))) = (is_encrypted, raw_transaction) {
+                    event_list.push(SubscriptionEvent::DecryptedTransaction(
+                        DecryptedTransactionEvent {
+                            rollup_id: rollup_id.to_owned(),
+                            batch_number,
+                            transaction_order,
+                            raw_transaction,
+                        },
+                    ));
+                }
+                transaction_order += 1;
+            }
+        }
+    }
+
+    (event_list, is_batch_sealed)
+}
+
+fn notification(subscription_id: u64, event: &SubscriptionEvent) -> serde_json::Value {
+    serde_json::json!({
+        "jsonrpc": "2.0",
+        "method": "subscription",
+        "params": { "subscription": subscription_id, "result": event },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn websocket_upgrade_is_told_from_a_json_rpc_request() {
+        let upgrade_request = b"GET / HTTP/1.1\r\nHost: localhost:3000\r\nConnection: Upgrade\r\nUpgrade: websocket\r\nSec-WebSocket-Version: 13\r\n\r\n";
+        let rpc_request = b"POST / HTTP/1.1\r\nHost: localhost:3000\r\nContent-Type: application/json\r\n\r\n{\"jsonrpc\":\"2.0\"}";
+        let upgrade_in_body = b"POST / HTTP/1.1\r\nHost: localhost:3000\r\n\r\nUpgrade: websocket";
+
+        assert!(is_websocket_upgrade(upgrade_request));
+        assert!(!is_websocket_upgrade(rpc_request));
+        assert!(!is_websocket_upgrade(upgrade_in_body));
+    }
+
+    #[test]
+    fn replay_is_limited_to_the_recent_batches() {
+        assert!(is_within_replay_window(0, 0));
+        assert!(is_within_replay_window(10, MAX_REPLAY_BATCH_COUNT + 10));
+        assert!(!is_within_replay_window(9, MAX_REPLAY_BATCH_COUNT + 10));
+        assert!(is_within_replay_window(u64::MAX, 0));
+    }
+
+    #[tokio::test]
+    async fn subscriber_is_disconnected_when_its_queue_is_full() {
+        let (subscriber_queue, mut message_receiver) = SubscriberQueue::with_capacity(1);
+
+        assert!(subscriber_queue.send_json(serde_json::json!(0)));
+        assert!(!subscriber_queue.send_json(serde_json::json!(1)));
+
+        tokio::time::timeout(Duration::from_secs(1), subscriber_queue.disconnected())
+            .await
+            .expect("the subscriber should be disconnected");
+        assert_eq!(
+            message_receiver.recv().await,
+            Some(Message::Text("0".to_string()))
+        );
+    }
+}
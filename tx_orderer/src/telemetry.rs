@@ -0,0 +1,177 @@
+//! OpenTelemetry tracing across secure-rpc, the tx_orderers of a cluster, the
+//! decryptor and the builder sinks.
+//!
+//! The JSON-RPC client of the SDK does not expose the HTTP headers, so the W3C
+//! trace context travels inside the request as a [`TraceContext`] field of the
+//! parameter. A receiver that does not know the field ignores it, and a sender
+//! that does not set it starts a new trace. The spans carry the transaction
+//! hash, so one transaction can be followed end to end from the span it
+//! entered the system with.
+
+use std::{collections::HashMap, fs::OpenOptions, io};
+
+use opentelemetry::{
+    global,
+    trace::{TraceError, TracerProvider as _},
+    KeyValue,
+};
+use opentelemetry_otlp::WithExportConfig;
+use opentelemetry_sdk::{
+    propagation::TraceContextPropagator,
+    runtime,
+    trace::{self, Tracer, TracerProvider},
+    Resource,
+};
+use serde::{Deserialize, Serialize};
+use tracing_opentelemetry::OpenTelemetrySpanExt;
+
+/// The `trace_file_path` that writes the spans to stdout.
+pub const STDOUT_TRACE_FILE_PATH: &str = "-";
+
+#[derive(Debug)]
+pub enum TelemetryError {
+    Otlp(TraceError),
+    OpenFile(io::Error),
+}
+
+impl std::fmt::Display for TelemetryError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+impl std::error::Error for TelemetryError {}
+
+/// The W3C `traceparent` and `tracestate` of the span that sent a request.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct TraceContext(HashMap<String, String>);
+
+impl TraceContext {
+    /// Returns the trace context of the current span, if it is traced.
+    pub fn current() -> Option<Self> {
+        Self::from_span(&tracing::Span::current())
+    }
+
+    pub fn from_span(span: &tracing::Span) -> Option<Self> {
+        let context = span.context();
+
+        let mut trace_context = HashMap::new();
+        global::get_text_map_propagator(|propagator| {
+            propagator.inject_context(&context, &mut trace_context)
+        });
+
+        (!trace_context.is_empty()).then_some(Self(trace_context))
+    }
+
+    /// Makes the span a child of the span that sent the trace context.
+    pub fn attach(&self, span: &tracing::Span) {
+        let context = global::get_text_map_propagator(|propagator| propagator.extract(&self.0));
+        span.set_parent(context);
+    }
+}
+
+/// Installs the tracer of the node. The spans are exported to `otlp_endpoint`
+/// if set, written to `trace_file_path` otherwise, and only propagated if
+/// neither is set, so that the trace of a transaction is not cut at this node.
+pub fn init_tracer(
+    service_name: &'static str,
+    otlp_endpoint: Option<&str>,
+    trace_file_path: Option<&str>,
+) -> Result<Tracer, TelemetryError> {
+    global::set_text_map_propagator(TraceContextPropagator::new());
+
+    let trace_config = trace::config().with_resource(Resource::new(vec![KeyValue::new(
+        "service.name",
+        service_name,
+    )]));
+
+    if let Some(otlp_endpoint) = otlp_endpoint {
+        return opentelemetry_otlp::new_pipeline()
+            .tracing()
+            .with_exporter(
+                opentelemetry_otlp::new_exporter()
+                    .tonic()
+                    .with_endpoint(otlp_endpoint),
+            )
+            .with_trace_config(trace_config)
+            .install_batch(runtime::Tokio)
+            .map_err(TelemetryError::Otlp);
+    }
+
+    let mut tracer_provider_builder = TracerProvider::builder().with_config(trace_config);
+    match trace_file_path {
+        Some(STDOUT_TRACE_FILE_PATH) => {
+            let exporter = opentelemetry_stdout::SpanExporter::builder()
+                .with_writer(io::stdout())
+                .build();
+            tracer_provider_builder =
+                tracer_provider_builder.with_batch_exporter(exporter, runtime::Tokio);
+        }
+        Some(trace_file_path) => {
+            let trace_file = OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(trace_file_path)
+                .map_err(TelemetryError::OpenFile)?;
+            let exporter = opentelemetry_stdout::SpanExporter::builder()
+                .with_writer(trace_file)
+                .build();
+            tracer_provider_builder =
+                tracer_provider_builder.with_batch_exporter(exporter, runtime::Tokio);
+        }
+        None => {}
+    }
+
+    let tracer_provider = tracer_provider_builder.build();
+    let tracer = tracer_provider.tracer(service_name);
+    global::set_tracer_provider(tracer_provider);
+
+    Ok(tracer)
+}
+
+/// Exports the spans that are still buffered.
+pub fn shutdown_tracer() {
+    global::shutdown_tracer_provider();
+}
+
+#[cfg(test)]
+mod tests {
+    use opentelemetry::trace::{TraceContextExt, TraceId};
+    use tracing_subscriber::layer::SubscriberExt;
+
+    use super::*;
+
+    fn get_trace_id(span: &tracing::Span) -> TraceId {
+        span.context().span().span_context().trace_id()
+    }
+
+    #[test]
+    fn trace_context_continues_the_trace_of_the_sender() {
+        global::set_text_map_propagator(TraceContextPropagator::new());
+        // Spans are recorded but not exported. The tracer only works while its
+        // provider is alive.
+        let tracer_provider = TracerProvider::builder().build();
+        let subscriber = tracing_subscriber::registry()
+            .with(tracing_opentelemetry::layer().with_tracer(tracer_provider.tracer("test")));
+
+        tracing::subscriber::with_default(subscriber, || {
+            let sender_span = tracing::info_span!("sender");
+            let trace_context = TraceContext::from_span(&sender_span).unwrap();
+            assert!(trace_context.0.contains_key("traceparent"));
+
+            // The trace context travels as a field of the request.
+            let trace_context: TraceContext =
+                serde_json::from_value(serde_json::to_value(&trace_context).unwrap()).unwrap();
+
+            let receiver_span = tracing::info_span!("receiver");
+            trace_context.attach(&receiver_span);
+            assert_ne!(get_trace_id(&sender_span), TraceId::INVALID);
+            assert_eq!(get_trace_id(&receiver_span), get_trace_id(&sender_span));
+        });
+    }
+
+    #[test]
+    fn untraced_span_has_no_trace_context() {
+        assert!(TraceContext::from_span(&tracing::Span::none()).is_none());
+    }
+}
@@ -0,0 +1,87 @@
+use std::net::IpAddr;
+
+use super::prelude::*;
+
+/// The number of records the audit log keeps. Appending past it deletes the
+/// oldest record.
+pub const MAX_INTERNAL_AUDIT_LOG_LENGTH: u64 = 10_000;
+
+/// A call to an internal RPC method that changes the state of the node. It is
+/// recorded before the call is forwarded, and completed with the response.
+#[derive(Clone, Debug, Deserialize, Serialize, Model)]
+#[kvstore(key(index: u64))]
+pub struct InternalAuditRecord {
+    pub index: u64,
+    pub caller: String,
+    pub method: String,
+    /// The parameters of the call as JSON.
+    pub params: String,
+    pub outcome: InternalAuditOutcome,
+    pub called_at: u64,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum InternalAuditOutcome {
+    /// No response has been recorded, e.g. the node stopped during the call.
+    Pending,
+    Succeeded,
+    /// The error the method responded with, or the reason the call failed.
+    Failed(String),
+}
+
+impl InternalAuditRecord {
+    /// Appends a pending record to the audit log and returns its index.
+    pub fn append(
+        caller: IpAddr,
+        method: impl AsRef<str>,
+        params: impl AsRef<str>,
+    ) -> Result<u64, KvStoreError> {
+        let mut internal_audit_log_length =
+            InternalAuditLogLength::get_mut_or(InternalAuditLogLength::default)?;
+        let index = internal_audit_log_length.0;
+
+        Self {
+            index,
+            caller: caller.to_string(),
+            method: method.as_ref().to_owned(),
+            params: params.as_ref().to_owned(),
+            outcome: InternalAuditOutcome::Pending,
+            called_at: current_timestamp(),
+        }
+        .put(index)?;
+
+        if let Some(expired_index) = index.checked_sub(MAX_INTERNAL_AUDIT_LOG_LENGTH) {
+            Self::delete(expired_index)?;
+        }
+
+        internal_audit_log_length.0 += 1;
+        internal_audit_log_length.update()?;
+
+        Ok(index)
+    }
+
+    /// Records the response of the call at `index`.
+    pub fn complete(index: u64, outcome: InternalAuditOutcome) -> Result<(), KvStoreError> {
+        let mut internal_audit_record = Self::get_mut(index)?;
+        internal_audit_record.outcome = outcome;
+        internal_audit_record.update()
+    }
+}
+
+/// Number of records appended to the audit log, which is also the index of
+/// the next record.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize, Model)]
+#[kvstore(key())]
+pub struct InternalAuditLogLength(u64);
+
+impl InternalAuditLogLength {
+    pub fn get_length(&self) -> u64 {
+        self.0
+    }
+
+    /// The index of the oldest record the audit log still keeps.
+    pub fn get_first_index(&self) -> u64 {
+        self.0.saturating_sub(MAX_INTERNAL_AUDIT_LOG_LENGTH)
+    }
+}
@@ -0,0 +1,68 @@
+use crate::types::prelude::*;
+
+/// The maximum number of dead letters kept per rollup and sink. The oldest
+/// ones are dropped first.
+const MAX_DEAD_LETTER_COUNT: usize = 1024;
+
+/// The next position a builder sink has to deliver for a rollup.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize, Model)]
+#[kvstore(key(rollup_id: &RollupId, sink_name: &str))]
+pub struct BuilderSinkCursor {
+    pub batch_number: u64,
+    pub transaction_order: u64,
+}
+
+impl BuilderSinkCursor {
+    pub fn new(batch_number: u64, transaction_order: u64) -> Self {
+        Self {
+            batch_number,
+            transaction_order,
+        }
+    }
+}
+
+/// A transaction a builder sink skipped after its delivery kept failing.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct BuilderSinkDeadLetter {
+    pub batch_number: u64,
+    pub transaction_order: u64,
+    pub transaction_hash: String,
+
+    pub attempt_count: u32,
+    pub error: String,
+    pub failed_at: u64,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize, Model)]
+#[kvstore(key(rollup_id: &RollupId, sink_name: &str))]
+pub struct BuilderSinkDeadLetterList(Vec<BuilderSinkDeadLetter>);
+
+impl BuilderSinkDeadLetterList {
+    pub fn add(
+        rollup_id: &RollupId,
+        sink_name: &str,
+        dead_letter: BuilderSinkDeadLetter,
+    ) -> Result<(), KvStoreError> {
+        let mut dead_letter_list = Self::get_mut_or(rollup_id, sink_name, Self::default)?;
+        dead_letter_list.0.push(dead_letter);
+
+        if dead_letter_list.0.len() > MAX_DEAD_LETTER_COUNT {
+            let excess = dead_letter_list.0.len() - MAX_DEAD_LETTER_COUNT;
+            dead_letter_list.0.drain(..excess);
+        }
+
+        dead_letter_list.update()
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn into_inner(self) -> Vec<BuilderSinkDeadLetter> {
+        self.0
+    }
+}
@@ -0,0 +1,101 @@
+use std::collections::{btree_set, BTreeSet};
+
+use crate::types::prelude::*;
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(tag = "type", content = "data")]
+#[serde(rename_all = "snake_case")]
+pub enum ForwardedTransactionPayload {
+    Raw(RawTransaction),
+    Encrypted(EncryptedTransaction),
+}
+
+/// A transaction this follower forwarded to the leader, kept until it shows up
+/// in a sealed batch.
+#[derive(Clone, Debug, Deserialize, Serialize, Model)]
+#[kvstore(key(rollup_id: &RollupId, transaction_hash: &str))]
+pub struct ForwardedTransaction {
+    pub payload: ForwardedTransactionPayload,
+
+    #[serde(serialize_with = "serialize_address")]
+    pub leader_tx_orderer_address: Address,
+
+    pub forwarded_batch_number: u64,
+    pub deadline_batch_number: u64,
+    pub forward_count: u64,
+    pub is_violated: bool,
+
+    pub forwarded_at: u64,
+}
+
+/// Hashes of the forwarded transactions of a rollup that are not yet known to
+/// be in a sealed batch.
+#[derive(Clone, Debug, Default, Deserialize, Serialize, Model)]
+#[kvstore(key(rollup_id: &RollupId))]
+pub struct ForwardedTransactionList(BTreeSet<String>);
+
+impl ForwardedTransactionList {
+    pub fn insert(&mut self, transaction_hash: impl AsRef<str>) {
+        self.0.insert(transaction_hash.as_ref().into());
+    }
+
+    pub fn remove(&mut self, transaction_hash: impl AsRef<str>) {
+        self.0.remove(transaction_hash.as_ref());
+    }
+
+    pub fn iter(&self) -> btree_set::Iter<'_, String> {
+        self.0.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+/// A forwarded transaction the leader did not include before the deadline.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct CensorshipViolation {
+    pub transaction_hash: String,
+
+    #[serde(serialize_with = "serialize_address")]
+    pub leader_tx_orderer_address: Address,
+
+    pub forwarded_batch_number: u64,
+    pub deadline_batch_number: u64,
+    pub forward_count: u64,
+
+    pub detected_at: u64,
+}
+
+/// The maximum number of violations kept per rollup. The oldest ones are
+/// dropped first.
+const MAX_CENSORSHIP_VIOLATION_COUNT: usize = 1024;
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize, Model)]
+#[kvstore(key(rollup_id: &RollupId))]
+pub struct CensorshipViolationList(Vec<CensorshipViolation>);
+
+impl CensorshipViolationList {
+    pub fn add(
+        rollup_id: &RollupId,
+        censorship_violation: CensorshipViolation,
+    ) -> Result<(), KvStoreError> {
+        let mut censorship_violation_list = Self::get_mut_or(rollup_id, Self::default)?;
+        censorship_violation_list.0.push(censorship_violation);
+
+        if censorship_violation_list.0.len() > MAX_CENSORSHIP_VIOLATION_COUNT {
+            let excess = censorship_violation_list.0.len() - MAX_CENSORSHIP_VIOLATION_COUNT;
+            censorship_violation_list.0.drain(..excess);
+        }
+
+        censorship_violation_list.update()
+    }
+
+    pub fn into_inner(self) -> Vec<CensorshipViolation> {
+        self.0
+    }
+}
@@ -45,6 +45,10 @@ impl ClusterIdList {
         self.0.remove(cluster_id.as_ref());
     }
 
+    pub fn contains(&self, cluster_id: impl AsRef<str>) -> bool {
+        self.0.contains(cluster_id.as_ref())
+    }
+
     pub fn iter(&self) -> btree_set::Iter<'_, ClusterId> {
         self.0.iter()
     }
@@ -77,34 +81,40 @@ impl Cluster {
         }
     }
 
-    // pub async fn put_and_update_with_margin(
-    //     cluster: &Cluster,
-    //     platform: Platform,
-    //     liveness_service_provider: LivenessServiceProvider,
-    //     cluster_id: &ClusterId,
-    //     platform_block_height: u64,
-    // ) -> Result<(), KvStoreError> {
-    //     Cluster::put(
-    //         cluster,
-    //         platform,
-    //         liveness_service_provider,
-    //         cluster_id,
-    //         platform_block_height,
-    //     )?;
-
-    //     // Keep [`ClusterInfo`] for `Self::Margin` blocks.
-    //     let block_height_for_remove =
-    // platform_block_height.wrapping_sub(cluster.block_margin * 2);
-
-    //     Cluster::delete(
-    //         platform,
-    //         liveness_service_provider,
-    //         cluster_id,
-    //         block_height_for_remove,
-    //     )?;
-
-    //     Ok(())
-    // }
+    /// Stores the cluster at `platform_block_height` and drops the snapshot
+    /// that fell out of the `block_margin * 2` window.
+    pub fn put_and_update_with_margin(
+        &self,
+        platform: Platform,
+        liveness_service_provider: LivenessServiceProvider,
+        cluster_id: &ClusterId,
+        platform_block_height: u64,
+    ) -> Result<(), KvStoreError> {
+        self.put(
+            platform,
+            liveness_service_provider,
+            cluster_id,
+            platform_block_height,
+        )?;
+
+        // Keep [`Cluster`] for `block_margin * 2` blocks.
+        if let Some(block_height_for_remove) =
+            platform_block_height.checked_sub(self.block_margin * 2)
+        {
+            match Self::delete(
+                platform,
+                liveness_service_provider,
+                cluster_id,
+                block_height_for_remove,
+            ) {
+                Ok(()) => {}
+                Err(error) if error.is_none_type() => {}
+                Err(error) => return Err(error),
+            }
+        }
+
+        Ok(())
+    }
 
     pub fn get_tx_orderer_address_list(&self) -> Vec<Address> {
         self.tx_orderer_rpc_infos
@@ -172,6 +182,26 @@ impl Cluster {
             .map(|(_index, tx_orderer_rpc_info)| tx_orderer_rpc_info.clone())
     }
 
+    /// Returns the tx_orderer that follows `tx_orderer_address` in the cluster
+    /// order, wrapping around to the first one.
+    pub fn get_next_tx_orderer_rpc_info(
+        &self,
+        tx_orderer_address: &Address,
+    ) -> Option<TxOrdererRpcInfo> {
+        let position = self
+            .tx_orderer_rpc_infos
+            .values()
+            .position(|tx_orderer_rpc_info| {
+                tx_orderer_rpc_info.tx_orderer_address == tx_orderer_address
+            })?;
+
+        self.tx_orderer_rpc_infos
+            .values()
+            .cycle()
+            .nth(position + 1)
+            .cloned()
+    }
+
     pub fn register_tx_orderer(&mut self, index: usize, tx_orderer_rpc_info: TxOrdererRpcInfo) {
         self.tx_orderer_rpc_infos.insert(index, tx_orderer_rpc_info);
     }
@@ -11,6 +11,8 @@ const DEFAULT_CLUSTER_RPC_URL: &str = "http://127.0.0.1:5000";
 const DEFAULT_SEEDER_RPC_URL: &str = "http://127.0.0.1:6000";
 const DEFAULT_REWARD_MANAGER_RPC_URL: &str = "http://127.0.0.1:6100";
 const DEFAULT_DISTRIBUTED_KEY_GENERATION_RPC_URL: &str = "http://127.0.0.1:7100";
+pub const DEFAULT_CENSORSHIP_DEADLINE_BATCH_COUNT: u64 = 3;
+pub const DEFAULT_SHUTDOWN_TIMEOUT: u64 = 25;
 
 #[derive(Debug, Deserialize, Parser, Serialize)]
 pub struct ConfigOption {
@@ -49,6 +51,66 @@ pub struct ConfigOption {
     #[doc = "Builder rpc url"]
     #[clap(long = "builder-rpc-rul")]
     pub builder_rpc_url: Option<String>,
+
+    #[doc = "Set the file the ordered transactions are appended to"]
+    #[clap(long = "builder-sink-file-path")]
+    pub builder_sink_file_path: Option<String>,
+
+    #[doc = "Set the websocket url the ordered transactions are pushed to"]
+    #[clap(long = "builder-sink-websocket-url")]
+    pub builder_sink_websocket_url: Option<String>,
+
+    #[doc = "Set the leader failover timeout in seconds (disabled if not set)"]
+    #[clap(long = "leader-failover-timeout")]
+    pub leader_failover_timeout: Option<u64>,
+
+    #[doc = "Set how long the node drains in seconds after SIGTERM before it exits"]
+    #[clap(long = "shutdown-timeout")]
+    pub shutdown_timeout: Option<u64>,
+
+    #[doc = "Set the number of batches a forwarded transaction must be included within"]
+    #[clap(long = "censorship-deadline-batch-count")]
+    pub censorship_deadline_batch_count: Option<u64>,
+
+    #[doc = "Set the number of batches kept per rollup by default (all batches are kept if not set)"]
+    #[clap(long = "retention-batch-count")]
+    pub retention_batch_count: Option<u64>,
+
+    #[doc = "Set the encrypted keystore of the signing key (defaults to `keystore.json` if it exists, or the plaintext `signing_key` file)"]
+    #[clap(long = "keystore-path")]
+    pub keystore_path: Option<String>,
+
+    #[doc = "Set the file the keystore password is read from (falls back to a prompt)"]
+    #[clap(long = "keystore-password-file")]
+    pub keystore_password_file: Option<String>,
+
+    #[doc = "Set the url of a web3signer-style remote signer that signs instead of the local key"]
+    #[clap(long = "remote-signer-url")]
+    pub remote_signer_url: Option<String>,
+
+    #[doc = "Set the public key to sign with (defaults to the only key of the remote signer)"]
+    #[clap(long = "remote-signer-public-key")]
+    pub remote_signer_public_key: Option<String>,
+
+    #[doc = "Set the file the JWT secret of the internal rpc server is read from (defaults to `jwt.hex`)"]
+    #[clap(long = "jwt-secret-path")]
+    pub jwt_secret_path: Option<String>,
+
+    #[doc = "Set the IP addresses allowed to call the internal rpc server (any address if not set)"]
+    #[clap(long = "internal-rpc-allowlist", value_delimiter = ',')]
+    pub internal_rpc_allowlist: Option<Vec<String>>,
+
+    #[doc = "Set the address the Prometheus metrics and the health endpoints are served on, e.g. `127.0.0.1:9100` (disabled if not set)"]
+    #[clap(long = "metrics-url")]
+    pub metrics_url: Option<String>,
+
+    #[doc = "Set the OTLP endpoint spans are exported to, e.g. `http://127.0.0.1:4317`"]
+    #[clap(long = "otlp-endpoint")]
+    pub otlp_endpoint: Option<String>,
+
+    #[doc = "Set the file spans are written to when no OTLP endpoint is set, `-` for stdout (not exported if not set)"]
+    #[clap(long = "trace-file-path")]
+    pub trace_file_path: Option<String>,
 }
 
 impl Default for ConfigOption {
@@ -69,6 +131,30 @@ impl Default for ConfigOption {
             is_using_zkp: Some(false),
 
             builder_rpc_url: None,
+            builder_sink_file_path: None,
+            builder_sink_websocket_url: None,
+
+            leader_failover_timeout: None,
+
+            shutdown_timeout: Some(DEFAULT_SHUTDOWN_TIMEOUT),
+
+            censorship_deadline_batch_count: Some(DEFAULT_CENSORSHIP_DEADLINE_BATCH_COUNT),
+
+            retention_batch_count: None,
+
+            keystore_path: None,
+            keystore_password_file: None,
+
+            remote_signer_url: None,
+            remote_signer_public_key: None,
+
+            jwt_secret_path: None,
+            internal_rpc_allowlist: None,
+
+            metrics_url: None,
+
+            otlp_endpoint: None,
+            trace_file_path: None,
         }
     }
 }
@@ -109,6 +195,114 @@ impl ConfigOption {
         set_toml_comment(&mut toml_string, "Set builder rpc url");
         set_toml_name_value(&mut toml_string, "builder_rpc_url", &self.builder_rpc_url);
 
+        set_toml_comment(&mut toml_string, "Set builder sink file path");
+        set_toml_name_value(
+            &mut toml_string,
+            "builder_sink_file_path",
+            &self.builder_sink_file_path,
+        );
+
+        set_toml_comment(&mut toml_string, "Set builder sink websocket url");
+        set_toml_name_value(
+            &mut toml_string,
+            "builder_sink_websocket_url",
+            &self.builder_sink_websocket_url,
+        );
+
+        set_toml_comment(&mut toml_string, "Set leader failover timeout in seconds");
+        set_toml_name_value(
+            &mut toml_string,
+            "leader_failover_timeout",
+            &self.leader_failover_timeout,
+        );
+
+        set_toml_comment(&mut toml_string, "Set shutdown timeout in seconds");
+        set_toml_name_value(&mut toml_string, "shutdown_timeout", &self.shutdown_timeout);
+
+        set_toml_comment(
+            &mut toml_string,
+            "Set the number of batches a forwarded transaction must be included within",
+        );
+        set_toml_name_value(
+            &mut toml_string,
+            "censorship_deadline_batch_count",
+            &self.censorship_deadline_batch_count,
+        );
+
+        set_toml_comment(
+            &mut toml_string,
+            "Set the number of batches kept per rollup by default",
+        );
+        set_toml_name_value(
+            &mut toml_string,
+            "retention_batch_count",
+            &self.retention_batch_count,
+        );
+
+        set_toml_comment(
+            &mut toml_string,
+            "Set the encrypted keystore of the signing key",
+        );
+        set_toml_name_value(&mut toml_string, "keystore_path", &self.keystore_path);
+
+        set_toml_comment(&mut toml_string, "Set the keystore password file");
+        set_toml_name_value(
+            &mut toml_string,
+            "keystore_password_file",
+            &self.keystore_password_file,
+        );
+
+        set_toml_comment(&mut toml_string, "Set the url of the remote signer");
+        set_toml_name_value(
+            &mut toml_string,
+            "remote_signer_url",
+            &self.remote_signer_url,
+        );
+
+        set_toml_comment(
+            &mut toml_string,
+            "Set the public key of the remote signer to sign with",
+        );
+        set_toml_name_value(
+            &mut toml_string,
+            "remote_signer_public_key",
+            &self.remote_signer_public_key,
+        );
+
+        set_toml_comment(
+            &mut toml_string,
+            "Set the JWT secret file of the internal rpc server",
+        );
+        set_toml_name_value(&mut toml_string, "jwt_secret_path", &self.jwt_secret_path);
+
+        set_toml_comment(
+            &mut toml_string,
+            "Set the IP addresses allowed to call the internal rpc server",
+        );
+        set_toml_name_value(
+            &mut toml_string,
+            "internal_rpc_allowlist",
+            &self.internal_rpc_allowlist,
+        );
+
+        set_toml_comment(
+            &mut toml_string,
+            "Set the address the Prometheus metrics and the health endpoints are served on",
+        );
+        set_toml_name_value(&mut toml_string, "metrics_url", &self.metrics_url);
+
+        set_toml_comment(
+            &mut toml_string,
+            "Set the OTLP endpoint spans are exported to",
+        );
+        set_toml_name_value(&mut toml_string, "otlp_endpoint", &self.otlp_endpoint);
+
+        set_toml_comment(
+            &mut toml_string,
+            "Set the file spans are written to when no OTLP endpoint is set",
+        );
+        set_toml_name_value(&mut toml_string, "trace_file_path", &self.trace_file_path);
+
         toml_string
     }
 
@@ -151,6 +345,74 @@ impl ConfigOption {
             self.builder_rpc_url.clone_from(&other.builder_rpc_url);
         }
 
+        if other.builder_sink_file_path.is_some() {
+            self.builder_sink_file_path
+                .clone_from(&other.builder_sink_file_path);
+        }
+
+        if other.builder_sink_websocket_url.is_some() {
+            self.builder_sink_websocket_url
+                .clone_from(&other.builder_sink_websocket_url);
+        }
+
+        if other.leader_failover_timeout.is_some() {
+            self.leader_failover_timeout
+                .clone_from(&other.leader_failover_timeout);
+        }
+
+        if other.shutdown_timeout.is_some() {
+            self.shutdown_timeout.clone_from(&other.shutdown_timeout);
+        }
+
+        if other.censorship_deadline_batch_count.is_some() {
+            self.censorship_deadline_batch_count
+                .clone_from(&other.censorship_deadline_batch_count);
+        }
+
+        if other.retention_batch_count.is_some() {
+            self.retention_batch_count
+                .clone_from(&other.retention_batch_count);
+        }
+
+        if other.keystore_path.is_some() {
+            self.keystore_path.clone_from(&other.keystore_path);
+        }
+
+        if other.keystore_password_file.is_some() {
+            self.keystore_password_file
+                .clone_from(&other.keystore_password_file);
+        }
+
+        if other.remote_signer_url.is_some() {
+            self.remote_signer_url.clone_from(&other.remote_signer_url);
+        }
+
+        if other.remote_signer_public_key.is_some() {
+            self.remote_signer_public_key
+                .clone_from(&other.remote_signer_public_key);
+        }
+
+        if other.jwt_secret_path.is_some() {
+            self.jwt_secret_path.clone_from(&other.jwt_secret_path);
+        }
+
+        if other.internal_rpc_allowlist.is_some() {
+            self.internal_rpc_allowlist
+                .clone_from(&other.internal_rpc_allowlist);
+        }
+
+        if other.metrics_url.is_some() {
+            self.metrics_url.clone_from(&other.metrics_url);
+        }
+
+        if other.otlp_endpoint.is_some() {
+            self.otlp_endpoint.clone_from(&other.otlp_endpoint);
+        }
+
+        if other.trace_file_path.is_some() {
+            self.trace_file_path.clone_from(&other.trace_file_path);
+        }
+
         self
     }
 }
@@ -6,6 +6,7 @@ use std::{
 use clap::Parser;
 use serde::{Deserialize, Serialize};
 
+use crate::access_control::{JwtSecret, JWT_SECRET_FILE_NAME};
 use crate::util::clear_dir;
 use crate::types::{
     config::ConfigError, ConfigOption, CONFIG_FILE_NAME, DEFAULT_SIGNING_KEY, SIGNING_KEY_PATH,
@@ -62,7 +63,18 @@ impl ConfigPath {
         fs::write(signing_key_path, DEFAULT_SIGNING_KEY)
             .map_err(ConfigError::CreatePrivateKeyFile)?;
 
+        // Generate the secret the callers of the internal RPC server authenticate
+        // with.
+        let jwt_secret_path = self.as_ref().join(JWT_SECRET_FILE_NAME);
+        JwtSecret::random()
+            .write(&jwt_secret_path)
+            .map_err(ConfigError::CreateJwtSecretFile)?;
+
         tracing::info!("Created a sign key {:?}", DEFAULT_SIGNING_KEY);
+        tracing::info!("Created a JWT secret at {:?}", jwt_secret_path);
+        tracing::warn!(
+            "The sign key is a well-known development key and is refused on mainnet. Run `tx_orderer key generate` to replace it"
+        );
         tracing::info!("Created a new config directory at {:?}", self.as_ref());
         Ok(())
     }
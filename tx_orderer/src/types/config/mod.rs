@@ -1,15 +1,28 @@
 mod config_option;
 mod config_path;
 
-use std::{fs, path::PathBuf};
+use std::{
+    fs,
+    net::IpAddr,
+    path::{Path, PathBuf},
+};
 
 pub use config_option::*;
 pub use config_path::*;
 use serde::{Deserialize, Serialize};
 
+use super::{Platform, RetentionPolicy};
+use crate::{
+    access_control::JWT_SECRET_FILE_NAME,
+    keystore::{
+        decrypt_keystore, is_default_signing_key, read_password, KeystoreError, KEYSTORE_FILE_NAME,
+    },
+};
+
 pub const DEFAULT_DATA_PATH: &str = ".radius";
 pub const DATABASE_DIR_NAME: &str = "database";
 pub const LOG_DIR_NAME: &str = "logs";
+pub const ARCHIVE_DIR_NAME: &str = "archive";
 
 pub const CONFIG_FILE_NAME: &str = "Config.toml";
 pub const SIGNING_KEY_PATH: &str = "signing_key";
@@ -29,11 +42,31 @@ pub struct Config {
 
     pub distributed_key_generation_rpc_url: String,
 
-    pub signing_key: String,
+    pub signing_key: Option<String>,
+    pub keystore_path: Option<PathBuf>,
+
+    pub remote_signer_url: Option<String>,
+    pub remote_signer_public_key: Option<String>,
+
+    pub jwt_secret_path: PathBuf,
+    pub internal_rpc_allowlist: Vec<IpAddr>,
+
+    pub metrics_url: Option<String>,
+    pub otlp_endpoint: Option<String>,
+    pub trace_file_path: Option<String>,
 
     pub is_using_zkp: bool,
 
     pub builder_rpc_url: Option<String>,
+    pub builder_sink_file_path: Option<String>,
+    pub builder_sink_websocket_url: Option<String>,
+
+    pub leader_failover_timeout: Option<u64>,
+    pub shutdown_timeout: u64,
+
+    pub censorship_deadline_batch_count: u64,
+
+    pub retention_batch_count: Option<u64>,
 }
 
 /// Provides a default implementation for the `Config` struct.
@@ -54,8 +87,32 @@ pub struct Config {
 /// - `distributed_key_generation_rpc_url`: RPC address for distributed key
 ///   generation service.
 /// - `signing_key`: A placeholder signing key for development.
+/// - `keystore_path`: Encrypted keystore the signing key was decrypted from.
+///   The key is read from the plaintext `signing_key` file if not set.
+/// - `remote_signer_url`, `remote_signer_public_key`: Remote signer the node
+///   signs its messages and L1 transactions with. The signing key is not loaded
+///   if it is set. None is set by default.
+/// - `jwt_secret_path`: File the JWT secret the callers of the internal RPC
+///   server authenticate with is read from.
+/// - `internal_rpc_allowlist`: IP addresses allowed to call the internal RPC
+///   server. Any address is allowed if empty.
+/// - `metrics_url`: Address the Prometheus metrics, liveness and readiness are
+///   served on. They are not served if not set.
+/// - `otlp_endpoint`: OTLP endpoint the spans are exported to.
+/// - `trace_file_path`: File the spans are written to when `otlp_endpoint` is
+///   not set, `-` for stdout. Spans are not exported if neither is set.
 /// - `is_using_zkp`: Boolean flag indicating whether Zero-Knowledge Proofs
 ///   (ZKP) are enabled.
+/// - `builder_rpc_url`, `builder_sink_file_path`, `builder_sink_websocket_url`:
+///   Sinks the ordered transactions are delivered to. None is set by default.
+/// - `leader_failover_timeout`: Seconds without a leader heartbeat before the
+///   next tx_orderer takes over. Failover is disabled if not set.
+/// - `shutdown_timeout`: Seconds the node drains for after SIGTERM before it
+///   exits.
+/// - `censorship_deadline_batch_count`: Number of sealed batches within which a
+///   transaction forwarded to the leader must be included.
+/// - `retention_batch_count`: Number of batches kept for a rollup without its
+///   own retention policy. All batches are kept if not set.
 ///
 /// Note: For production use, ensure these values are set explicitly in the
 /// configuration file or environment variables to meet security and functional
@@ -70,9 +127,23 @@ impl Default for Config {
             seeder_rpc_url: "http://127.0.0.1:6000".to_string(),
             reward_manager_rpc_url: "http://127.0.0.1:6100".to_string(),
             distributed_key_generation_rpc_url: "http://127.0.0.1:7100".to_string(),
-            signing_key: DEFAULT_SIGNING_KEY.to_string(),
+            signing_key: Some(DEFAULT_SIGNING_KEY.to_string()),
+            keystore_path: None,
+            remote_signer_url: None,
+            remote_signer_public_key: None,
+            jwt_secret_path: PathBuf::from("./data").join(JWT_SECRET_FILE_NAME),
+            internal_rpc_allowlist: Vec::new(),
+            metrics_url: None,
+            otlp_endpoint: None,
+            trace_file_path: None,
             is_using_zkp: true,
             builder_rpc_url: None,
+            builder_sink_file_path: None,
+            builder_sink_websocket_url: None,
+            leader_failover_timeout: None,
+            shutdown_timeout: DEFAULT_SHUTDOWN_TIMEOUT,
+            censorship_deadline_batch_count: DEFAULT_CENSORSHIP_DEADLINE_BATCH_COUNT,
+            retention_batch_count: None,
         }
     }
 }
@@ -99,9 +170,44 @@ impl Config {
         // Merge configs from CLI input
         let merged_config_option = config_file.merge(config_option);
 
-        // Read signing key
-        let signing_key_path = config_path.join(SIGNING_KEY_PATH);
-        let signing_key = fs::read_to_string(signing_key_path).unwrap();
+        // Read signing key, unless a remote signer holds it
+        let is_using_remote_signer = merged_config_option.remote_signer_url.is_some();
+        let keystore_path = match &merged_config_option.keystore_path {
+            _ if is_using_remote_signer => None,
+            Some(keystore_path) => Some(config_path.join(keystore_path)),
+            None => Some(config_path.join(KEYSTORE_FILE_NAME)).filter(|path| path.exists()),
+        };
+        let signing_key = match &keystore_path {
+            Some(keystore_path) => {
+                let password_file = merged_config_option
+                    .keystore_password_file
+                    .as_ref()
+                    .map(|password_file| config_path.join(password_file));
+                let password = read_password(password_file.as_deref(), false)
+                    .map_err(ConfigError::Keystore)?;
+
+                Some(decrypt_keystore(keystore_path, &password).map_err(ConfigError::Keystore)?)
+            }
+            None if is_using_remote_signer => None,
+            None => Some(load_plaintext_signing_key(&config_path)?),
+        };
+
+        let jwt_secret_path = config_path.join(
+            merged_config_option
+                .jwt_secret_path
+                .as_deref()
+                .unwrap_or(JWT_SECRET_FILE_NAME),
+        );
+        let internal_rpc_allowlist = merged_config_option
+            .internal_rpc_allowlist
+            .iter()
+            .flatten()
+            .map(|address| {
+                address
+                    .parse()
+                    .map_err(|_| ConfigError::InvalidInternalRpcAllowlist(address.clone()))
+            })
+            .collect::<Result<Vec<IpAddr>, ConfigError>>()?;
 
         Ok(Config {
             path: config_path,
@@ -114,9 +220,34 @@ impl Config {
                 .distributed_key_generation_rpc_url
                 .unwrap(),
             signing_key,
+            keystore_path,
+
+            remote_signer_url: merged_config_option.remote_signer_url,
+            remote_signer_public_key: merged_config_option.remote_signer_public_key,
+
+            jwt_secret_path,
+            internal_rpc_allowlist,
+
+            metrics_url: merged_config_option.metrics_url,
+            otlp_endpoint: merged_config_option.otlp_endpoint,
+            trace_file_path: merged_config_option.trace_file_path,
+
             is_using_zkp: merged_config_option.is_using_zkp.unwrap(),
 
             builder_rpc_url: merged_config_option.builder_rpc_url,
+            builder_sink_file_path: merged_config_option.builder_sink_file_path,
+            builder_sink_websocket_url: merged_config_option.builder_sink_websocket_url,
+
+            leader_failover_timeout: merged_config_option.leader_failover_timeout,
+            shutdown_timeout: merged_config_option
+                .shutdown_timeout
+                .unwrap_or(DEFAULT_SHUTDOWN_TIMEOUT),
+
+            censorship_deadline_batch_count: merged_config_option
+                .censorship_deadline_batch_count
+                .unwrap_or(DEFAULT_CENSORSHIP_DEADLINE_BATCH_COUNT),
+
+            retention_batch_count: merged_config_option.retention_batch_count,
         })
     }
 
@@ -124,10 +255,43 @@ impl Config {
         self.path.join(DATABASE_DIR_NAME)
     }
 
+    pub fn default_retention_policy(&self) -> RetentionPolicy {
+        match self.retention_batch_count {
+            Some(batch_count) => RetentionPolicy::KeepBatchCount { batch_count },
+            None => RetentionPolicy::KeepAll,
+        }
+    }
+
+    /// Refuses the well-known development key on mainnet platforms.
+    pub fn check_signing_key(&self, platform: Platform) -> Result<(), ConfigError> {
+        if platform.is_mainnet()
+            && self
+                .signing_key
+                .as_deref()
+                .is_some_and(is_default_signing_key)
+        {
+            return Err(ConfigError::DefaultSigningKeyOnMainnet(platform));
+        }
+
+        Ok(())
+    }
+
+    /// Returns the `host:port` the internal RPC gateway listens on.
+    pub fn internal_rpc_address(&self) -> &str {
+        match self.internal_rpc_url.split_once("://") {
+            Some((_, address)) => address,
+            None => &self.internal_rpc_url,
+        }
+    }
+
     pub fn log_path(&self) -> PathBuf {
         self.path.join(LOG_DIR_NAME)
     }
 
+    pub fn archive_path(&self) -> PathBuf {
+        self.path.join(ARCHIVE_DIR_NAME)
+    }
+
     pub fn external_port(&self) -> Result<String, ConfigError> {
         Ok(self
             .external_rpc_url
@@ -147,6 +311,13 @@ impl Config {
     }
 }
 
+fn load_plaintext_signing_key(config_path: &Path) -> Result<String, ConfigError> {
+    let signing_key_path = config_path.join(SIGNING_KEY_PATH);
+    let signing_key = fs::read_to_string(signing_key_path).map_err(ConfigError::LoadSigningKey)?;
+
+    Ok(signing_key.trim().to_owned())
+}
+
 #[derive(Debug)]
 pub enum ConfigError {
     Load(std::io::Error),
@@ -155,8 +326,13 @@ pub enum ConfigError {
     CreateConfigDirectory(std::io::Error),
     CreateConfigFile(std::io::Error),
     CreatePrivateKeyFile(std::io::Error),
+    CreateJwtSecretFile(std::io::Error),
     InvalidExternalPort,
     InvalidClusterPort,
+    InvalidInternalRpcAllowlist(String),
+    LoadSigningKey(std::io::Error),
+    Keystore(KeystoreError),
+    DefaultSigningKeyOnMainnet(Platform),
 }
 
 impl std::fmt::Display for ConfigError {
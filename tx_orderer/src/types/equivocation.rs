@@ -0,0 +1,319 @@
+use radius_sdk::signature::ChainType;
+
+use crate::types::prelude::*;
+
+/// The maximum number of pieces of evidence kept per rollup. The first ones
+/// are kept, as one piece is enough to prove the misbehavior of a
+/// tx_orderer.
+const MAX_EQUIVOCATION_EVIDENCE_COUNT: usize = 1024;
+
+/// A batch creation as announced through `SyncBatchCreation`, kept so that a
+/// conflicting announcement for the same batch can be proven.
+#[derive(Clone, Debug, Deserialize, Serialize, Model)]
+#[kvstore(key(rollup_id: &RollupId, batch_number: u64))]
+pub struct SignedBatchCreation {
+    pub rollup_id: RollupId,
+    pub batch_number: u64,
+    pub batch_commitment: [u8; 32],
+    pub batch_creator_signature: Signature,
+
+    #[serde(serialize_with = "serialize_address")]
+    pub leader_tx_orderer_address: Address,
+    pub leader_tx_orderer_signature: Signature,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(tag = "type", content = "data")]
+#[serde(rename_all = "snake_case")]
+pub enum ConflictingStatements {
+    OrderCommitment {
+        first: SignOrderCommitment,
+        second: SignOrderCommitment,
+    },
+    BatchCreation {
+        first: SignedBatchCreation,
+        second: SignedBatchCreation,
+    },
+}
+
+/// Two statements signed by the same tx_orderer that cannot both be true.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct EquivocationEvidence {
+    #[serde(serialize_with = "serialize_address")]
+    pub tx_orderer_address: Address,
+
+    pub batch_number: u64,
+    pub transaction_order: Option<u64>,
+
+    pub conflicting_statements: ConflictingStatements,
+    pub detected_at: u64,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize, Model)]
+#[kvstore(key(rollup_id: &RollupId))]
+pub struct EquivocationEvidenceList(Vec<EquivocationEvidence>);
+
+impl EquivocationEvidenceList {
+    /// Records the evidence unless the same tx_orderer was already caught at
+    /// the same position, or the list is full.
+    pub fn add(
+        rollup_id: &RollupId,
+        equivocation_evidence: EquivocationEvidence,
+    ) -> Result<(), KvStoreError> {
+        let mut equivocation_evidence_list = Self::get_mut_or(rollup_id, Self::default)?;
+
+        if equivocation_evidence_list.insert(equivocation_evidence) {
+            equivocation_evidence_list.update()?;
+        }
+
+        Ok(())
+    }
+
+    /// Returns `false` if the evidence is known or the list is full.
+    fn insert(&mut self, equivocation_evidence: EquivocationEvidence) -> bool {
+        let is_known = self.0.iter().any(|stored_equivocation_evidence| {
+            stored_equivocation_evidence.batch_number == equivocation_evidence.batch_number
+                && stored_equivocation_evidence.transaction_order
+                    == equivocation_evidence.transaction_order
+                && stored_equivocation_evidence.tx_orderer_address
+                    == equivocation_evidence.tx_orderer_address
+        });
+        if is_known {
+            return false;
+        }
+
+        if self.0.len() >= MAX_EQUIVOCATION_EVIDENCE_COUNT {
+            tracing::warn!(
+                "Equivocation evidence list is full - tx_orderer_address: {:?} / batch_number: {:?} / transaction_order: {:?}",
+                equivocation_evidence.tx_orderer_address,
+                equivocation_evidence.batch_number,
+                equivocation_evidence.transaction_order
+            );
+            return false;
+        }
+
+        self.0.push(equivocation_evidence);
+
+        true
+    }
+
+    pub fn into_inner(self) -> Vec<EquivocationEvidence> {
+        self.0
+    }
+}
+
+impl EquivocationEvidence {
+    /// Compares a signed order commitment with the one stored for the same
+    /// position and records the pair if both were signed by the same
+    /// tx_orderer for different data.
+    pub fn check_order_commitment(
+        chain_type: ChainType,
+        sign_order_commitment: &SignOrderCommitment,
+    ) -> Result<Option<Self>, KvStoreError> {
+        let data = &sign_order_commitment.data;
+
+        let stored_sign_order_commitment = match OrderCommitment::get(
+            &data.rollup_id,
+            data.batch_number,
+            data.transaction_order,
+        ) {
+            Ok(OrderCommitment::Single(SingleOrderCommitment::Sign(
+                stored_sign_order_commitment,
+            ))) => stored_sign_order_commitment,
+            Ok(_) => return Ok(None),
+            Err(error) if error.is_none_type() => return Ok(None),
+            Err(error) => return Err(error),
+        };
+
+        if !is_conflicting_order_commitment(
+            chain_type,
+            &stored_sign_order_commitment,
+            sign_order_commitment,
+        ) {
+            return Ok(None);
+        }
+
+        let tx_orderer_address = sign_order_commitment.get_signer_address(chain_type);
+
+        let equivocation_evidence = Self {
+            tx_orderer_address,
+            batch_number: data.batch_number,
+            transaction_order: Some(data.transaction_order),
+            conflicting_statements: ConflictingStatements::OrderCommitment {
+                first: stored_sign_order_commitment,
+                second: sign_order_commitment.clone(),
+            },
+            detected_at: current_timestamp(),
+        };
+        EquivocationEvidenceList::add(&data.rollup_id, equivocation_evidence.clone())?;
+
+        Ok(Some(equivocation_evidence))
+    }
+
+    /// Stores the first batch creation seen for a batch and records any later
+    /// one from the same leader with a different batch commitment.
+    pub fn check_batch_creation(
+        signed_batch_creation: &SignedBatchCreation,
+    ) -> Result<Option<Self>, KvStoreError> {
+        let rollup_id = &signed_batch_creation.rollup_id;
+        let batch_number = signed_batch_creation.batch_number;
+
+        let stored_signed_batch_creation = match SignedBatchCreation::get(rollup_id, batch_number) {
+            Ok(stored_signed_batch_creation) => stored_signed_batch_creation,
+            Err(error) if error.is_none_type() => {
+                signed_batch_creation.put(rollup_id, batch_number)?;
+                return Ok(None);
+            }
+            Err(error) => return Err(error),
+        };
+
+        if !is_conflicting_batch_creation(&stored_signed_batch_creation, signed_batch_creation) {
+            return Ok(None);
+        }
+
+        let equivocation_evidence = Self {
+            tx_orderer_address: signed_batch_creation.leader_tx_orderer_address.clone(),
+            batch_number,
+            transaction_order: None,
+            conflicting_statements: ConflictingStatements::BatchCreation {
+                first: stored_signed_batch_creation,
+                second: signed_batch_creation.clone(),
+            },
+            detected_at: current_timestamp(),
+        };
+        EquivocationEvidenceList::add(rollup_id, equivocation_evidence.clone())?;
+
+        Ok(Some(equivocation_evidence))
+    }
+}
+
+/// Both order commitments are signed by the same tx_orderer for different
+/// data at the same position.
+fn is_conflicting_order_commitment(
+    chain_type: ChainType,
+    first: &SignOrderCommitment,
+    second: &SignOrderCommitment,
+) -> bool {
+    first.data != second.data
+        && first.get_signer_address(chain_type) == second.get_signer_address(chain_type)
+}
+
+/// Both batch creations are announced by the same leader with different batch
+/// commitments.
+fn is_conflicting_batch_creation(
+    first: &SignedBatchCreation,
+    second: &SignedBatchCreation,
+) -> bool {
+    first.batch_commitment != second.batch_commitment
+        && first.leader_tx_orderer_address == second.leader_tx_orderer_address
+}
+
+#[cfg(test)]
+mod tests {
+    use radius_sdk::signature::PrivateKeySigner;
+
+    use super::*;
+
+    fn signer(signing_key: &str) -> PrivateKeySigner {
+        PrivateKeySigner::from_str(ChainType::Ethereum, signing_key).unwrap()
+    }
+
+    fn sign(signer: &PrivateKeySigner, transaction_hash: &str) -> SignOrderCommitment {
+        let data = OrderCommitmentData {
+            transaction_hash: transaction_hash.to_owned(),
+            ..OrderCommitmentData::default()
+        };
+
+        SignOrderCommitment {
+            signature: signer.sign_message(&data).unwrap(),
+            data,
+        }
+    }
+
+    fn signed_batch_creation(
+        signer: &PrivateKeySigner,
+        batch_commitment: [u8; 32],
+    ) -> SignedBatchCreation {
+        SignedBatchCreation {
+            rollup_id: RollupId::from("rollup_id"),
+            batch_number: 0,
+            batch_commitment,
+            batch_creator_signature: signer.sign_message(&batch_commitment).unwrap(),
+            leader_tx_orderer_address: signer.address().clone(),
+            leader_tx_orderer_signature: signer.sign_message(&batch_commitment).unwrap(),
+        }
+    }
+
+    #[test]
+    fn order_commitments_conflict_only_if_the_same_signer_signed_different_data() {
+        let leader = signer(DEFAULT_SIGNING_KEY);
+        let other = signer("0x59c6995e998f97a5a0044966f0945389dc9e86dae88c7a8412f4603b6b78690d");
+
+        let first = sign(&leader, "0x01");
+        assert!(is_conflicting_order_commitment(
+            ChainType::Ethereum,
+            &first,
+            &sign(&leader, "0x02")
+        ));
+        assert!(!is_conflicting_order_commitment(
+            ChainType::Ethereum,
+            &first,
+            &sign(&leader, "0x01")
+        ));
+        assert!(!is_conflicting_order_commitment(
+            ChainType::Ethereum,
+            &first,
+            &sign(&other, "0x02")
+        ));
+    }
+
+    #[test]
+    fn batch_creations_conflict_only_if_the_same_leader_announced_different_commitments() {
+        let leader = signer(DEFAULT_SIGNING_KEY);
+        let other = signer("0x59c6995e998f97a5a0044966f0945389dc9e86dae88c7a8412f4603b6b78690d");
+
+        let first = signed_batch_creation(&leader, [1; 32]);
+        assert!(is_conflicting_batch_creation(
+            &first,
+            &signed_batch_creation(&leader, [2; 32])
+        ));
+        assert!(!is_conflicting_batch_creation(
+            &first,
+            &signed_batch_creation(&leader, [1; 32])
+        ));
+        assert!(!is_conflicting_batch_creation(
+            &first,
+            &signed_batch_creation(&other, [2; 32])
+        ));
+    }
+
+    #[test]
+    fn evidence_is_kept_once_per_position_and_tx_orderer() {
+        let leader = signer(DEFAULT_SIGNING_KEY);
+        let equivocation_evidence = |batch_number: u64| EquivocationEvidence {
+            tx_orderer_address: leader.address().clone(),
+            batch_number,
+            transaction_order: None,
+            conflicting_statements: ConflictingStatements::BatchCreation {
+                first: signed_batch_creation(&leader, [1; 32]),
+                second: signed_batch_creation(&leader, [2; 32]),
+            },
+            detected_at: 0,
+        };
+
+        let mut equivocation_evidence_list = EquivocationEvidenceList::default();
+        assert!(equivocation_evidence_list.insert(equivocation_evidence(0)));
+        assert!(!equivocation_evidence_list.insert(equivocation_evidence(0)));
+
+        for batch_number in 1..MAX_EQUIVOCATION_EVIDENCE_COUNT as u64 {
+            assert!(equivocation_evidence_list.insert(equivocation_evidence(batch_number)));
+        }
+        assert!(!equivocation_evidence_list.insert(equivocation_evidence(
+            MAX_EQUIVOCATION_EVIDENCE_COUNT as u64
+        )));
+        assert_eq!(
+            equivocation_evidence_list.into_inner().len(),
+            MAX_EQUIVOCATION_EVIDENCE_COUNT
+        );
+    }
+}
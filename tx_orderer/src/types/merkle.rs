@@ -3,6 +3,8 @@ use std::sync::Arc;
 use sha3::{Digest, Keccak256};
 use tokio::sync::Mutex;
 
+use super::RawTransaction;
+
 #[derive(Clone, Debug, Default)]
 pub struct MerkleTree {
     pub nodes: Arc<Mutex<Vec<Vec<[u8; 32]>>>>,
@@ -15,6 +17,23 @@ impl MerkleTree {
         }
     }
 
+    /// Builds the finalized tree of a batch, whose leaves are the hashes of
+    /// its raw transactions in order. This is the tree the batch commitment
+    /// is the root of.
+    pub async fn from_raw_transaction_list<'a>(
+        raw_transaction_list: impl IntoIterator<Item = &'a RawTransaction>,
+    ) -> Self {
+        let merkle_tree = Self::new();
+        for raw_transaction in raw_transaction_list {
+            merkle_tree
+                .add_data(raw_transaction.raw_transaction_hash().as_ref())
+                .await;
+        }
+        merkle_tree.finalize_tree().await;
+
+        merkle_tree
+    }
+
     pub async fn add_data(&self, data: &str) -> (u64, Vec<[u8; 32]>) {
         let mut nodes = self.nodes.lock().await;
         Self::update_tree(&mut nodes);
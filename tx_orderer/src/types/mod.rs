@@ -1,24 +1,34 @@
+mod audit;
 mod batch;
+mod builder_sink;
+mod censorship;
 mod cluster;
 mod config;
+mod equivocation;
 mod liveness;
 mod merkle;
 mod mev_searcher_info;
 mod order_commitment;
 mod platform;
+mod retention;
 mod rollup;
 mod transaction;
 mod validation;
 mod version;
 
+pub use audit::*;
 pub use batch::*;
+pub use builder_sink::*;
+pub use censorship::*;
 pub use cluster::*;
 pub use config::*;
+pub use equivocation::*;
 pub use liveness::*;
 pub use merkle::*;
 pub use mev_searcher_info::*;
 pub use order_commitment::*;
 pub use platform::*;
+pub use retention::*;
 use radius_sdk::signature::Address;
 pub use rollup::*;
 use serde::{ser::SerializeSeq, Deserialize, Deserializer};
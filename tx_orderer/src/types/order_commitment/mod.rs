@@ -1,12 +1,14 @@
 mod bundle_order_commitment;
 mod order_commitment_type;
 mod single_order_commitment;
+mod verification;
 
 pub use bundle_order_commitment::*;
 pub use order_commitment_type::*;
 use radius_sdk::kvstore::Model;
 use serde::{Deserialize, Serialize};
 pub use single_order_commitment::*;
+pub use verification::*;
 
 use crate::types::RollupId;
 
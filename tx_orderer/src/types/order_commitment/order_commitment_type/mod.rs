@@ -1,8 +1,10 @@
+mod quorum_order_commitment;
 mod sign_order_commitment;
 mod transaction_hash_order_commitment;
 
 use std::str::FromStr;
 
+pub use quorum_order_commitment::*;
 use serde::{Deserialize, Serialize};
 pub use sign_order_commitment::*;
 pub use transaction_hash_order_commitment::*;
@@ -14,6 +16,7 @@ use crate::error::Error;
 pub enum OrderCommitmentType {
     TransactionHash,
     Sign,
+    Quorum,
 }
 
 impl FromStr for OrderCommitmentType {
@@ -23,6 +26,7 @@ impl FromStr for OrderCommitmentType {
         match s.to_lowercase().as_str() {
             "transaction_hash" => Ok(Self::TransactionHash),
             "sign" => Ok(Self::Sign),
+            "quorum" => Ok(Self::Quorum),
             _ => Err(Error::UnsupportedOrderCommitmentType),
         }
     }
@@ -0,0 +1,189 @@
+use std::collections::BTreeSet;
+
+use radius_sdk::signature::ChainType;
+
+use super::SignOrderCommitment;
+use crate::types::prelude::*;
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct OrderCommitmentSignature {
+    #[serde(serialize_with = "serialize_address")]
+    pub tx_orderer_address: Address,
+    pub signature: Signature,
+}
+
+/// An order commitment attested by a quorum of the cluster. The leader
+/// signature is the first entry of `signature_list` and the followers add
+/// theirs as they receive the transaction.
+#[derive(Clone, Debug, Deserialize, Serialize, Model)]
+#[kvstore(key(rollup_id: &RollupId, batch_number: u64, transaction_order: u64))]
+pub struct QuorumOrderCommitment {
+    pub data: OrderCommitmentData,
+
+    /// Block height of the cluster the signers belong to.
+    pub platform_block_height: u64,
+    pub threshold: usize,
+
+    pub signature_list: Vec<OrderCommitmentSignature>,
+}
+
+impl QuorumOrderCommitment {
+    pub fn new(
+        sign_order_commitment: &SignOrderCommitment,
+        chain_type: ChainType,
+        platform_block_height: u64,
+        cluster_size: usize,
+    ) -> Self {
+        Self {
+            data: sign_order_commitment.data.clone(),
+            platform_block_height,
+            threshold: Self::quorum_threshold(cluster_size),
+            signature_list: vec![OrderCommitmentSignature {
+                tx_orderer_address: sign_order_commitment.get_signer_address(chain_type),
+                signature: sign_order_commitment.signature.clone(),
+            }],
+        }
+    }
+
+    /// More than two thirds of the cluster.
+    pub fn quorum_threshold(cluster_size: usize) -> usize {
+        cluster_size * 2 / 3 + 1
+    }
+
+    /// Adds the signature of a tx_orderer. Returns `false` if the tx_orderer
+    /// has already signed.
+    pub fn add_signature(&mut self, tx_orderer_address: Address, signature: Signature) -> bool {
+        if self
+            .signature_list
+            .iter()
+            .any(|order_commitment_signature| {
+                order_commitment_signature.tx_orderer_address == tx_orderer_address
+            })
+        {
+            return false;
+        }
+
+        self.signature_list.push(OrderCommitmentSignature {
+            tx_orderer_address,
+            signature,
+        });
+
+        true
+    }
+
+    pub fn is_certified(&self) -> bool {
+        self.signature_list.len() >= self.threshold
+    }
+
+    /// Checks that at least `threshold` distinct members of the cluster signed
+    /// the order commitment data.
+    pub fn verify(&self, chain_type: ChainType, tx_orderer_address_list: &[Address]) -> bool {
+        let signer_address_list: BTreeSet<String> = self
+            .signature_list
+            .iter()
+            .filter_map(|order_commitment_signature| {
+                let signer_address = order_commitment_signature
+                    .signature
+                    .get_signer_address(chain_type, &self.data)
+                    .ok()?;
+
+                (signer_address == order_commitment_signature.tx_orderer_address
+                    && tx_orderer_address_list.contains(&signer_address))
+                .then(|| signer_address.as_hex_string())
+            })
+            .collect();
+
+        signer_address_list.len() >= self.threshold
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use radius_sdk::signature::PrivateKeySigner;
+
+    use super::*;
+
+    const SIGNING_KEY_LIST: [&str; 4] = [
+        "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80",
+        "0x59c6995e998f97a5a0044966f0945389dc9e86dae88c7a8412f4603b6b78690d",
+        "0x5de4111afa1a4b94908f83103eb1f1706367c2e68ca870fc3fb9a804cdab365a",
+        "0x7c852118294e51e653712a81e05800f419141751be58f605c371e15141b007a6",
+    ];
+
+    fn signer_list() -> Vec<PrivateKeySigner> {
+        SIGNING_KEY_LIST
+            .iter()
+            .map(|signing_key| {
+                PrivateKeySigner::from_str(ChainType::Ethereum, signing_key).unwrap()
+            })
+            .collect()
+    }
+
+    fn sign(signer: &PrivateKeySigner, data: &OrderCommitmentData) -> SignOrderCommitment {
+        SignOrderCommitment {
+            data: data.clone(),
+            signature: signer.sign_message(data).unwrap(),
+        }
+    }
+
+    #[test]
+    fn verify_requires_a_quorum_of_cluster_members() {
+        let signer_list = signer_list();
+        // The last signer is not a member of the cluster.
+        let tx_orderer_address_list: Vec<Address> = signer_list[..3]
+            .iter()
+            .map(|signer| signer.address().clone())
+            .collect();
+        let data = OrderCommitmentData::default();
+
+        let mut quorum_order_commitment = QuorumOrderCommitment::new(
+            &sign(&signer_list[0], &data),
+            ChainType::Ethereum,
+            0,
+            tx_orderer_address_list.len(),
+        );
+        assert_eq!(quorum_order_commitment.threshold, 3);
+
+        let outsider = sign(&signer_list[3], &data);
+        quorum_order_commitment.add_signature(signer_list[3].address().clone(), outsider.signature);
+        let member = sign(&signer_list[1], &data);
+        quorum_order_commitment.add_signature(signer_list[1].address().clone(), member.signature);
+        assert!(quorum_order_commitment.is_certified());
+        assert!(!quorum_order_commitment.verify(ChainType::Ethereum, &tx_orderer_address_list));
+
+        let member = sign(&signer_list[2], &data);
+        quorum_order_commitment.add_signature(signer_list[2].address().clone(), member.signature);
+        assert!(quorum_order_commitment.verify(ChainType::Ethereum, &tx_orderer_address_list));
+    }
+
+    #[test]
+    fn verify_rejects_signatures_over_other_data() {
+        let signer_list = signer_list();
+        let tx_orderer_address_list: Vec<Address> = signer_list[..3]
+            .iter()
+            .map(|signer| signer.address().clone())
+            .collect();
+        let data = OrderCommitmentData::default();
+        let other_data = OrderCommitmentData {
+            transaction_order: 1,
+            ..OrderCommitmentData::default()
+        };
+
+        let mut quorum_order_commitment = QuorumOrderCommitment::new(
+            &sign(&signer_list[0], &data),
+            ChainType::Ethereum,
+            0,
+            tx_orderer_address_list.len(),
+        );
+        for signer in &signer_list[1..3] {
+            quorum_order_commitment.add_signature(
+                signer.address().clone(),
+                sign(signer, &other_data).signature,
+            );
+        }
+
+        assert!(!quorum_order_commitment.verify(ChainType::Ethereum, &tx_orderer_address_list));
+    }
+}
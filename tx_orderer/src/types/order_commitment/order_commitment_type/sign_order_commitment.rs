@@ -9,7 +9,7 @@ pub struct SignOrderCommitment {
     pub signature: Signature,
 }
 
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
 pub struct OrderCommitmentData {
     pub rollup_id: RollupId,
     pub batch_number: u64,
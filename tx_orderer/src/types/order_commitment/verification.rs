@@ -0,0 +1,277 @@
+use crate::{error::Error, types::prelude::*};
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum LeaderCheck {
+    /// The signer was the leader when the transaction was ordered.
+    Leader,
+    /// Another tx_orderer was the leader when the transaction was ordered.
+    NotLeader,
+    /// No leader hand-over is known for the position.
+    Unknown,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum MerklePathCheck {
+    /// The sealed batch contains the transaction at the committed position.
+    Valid,
+    /// The sealed batch contradicts the order commitment.
+    Invalid,
+    /// The batch has not been sealed yet.
+    Pending,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct OrderCommitmentVerdict {
+    #[serde(serialize_with = "serialize_address")]
+    pub signer_address: Address,
+    pub leader_tx_orderer_address: Option<String>,
+    pub platform_block_height: Option<u64>,
+
+    pub leader_check: LeaderCheck,
+    pub merkle_path_check: MerklePathCheck,
+
+    /// Whether the commitment was signed by the leader and honored by the
+    /// sealed batch.
+    pub is_honored: bool,
+    /// Whether a quorum of the cluster co-signed the committed data. `None` if
+    /// this node holds no quorum order commitment for the position.
+    pub is_quorum_certified: Option<bool>,
+}
+
+/// Verifies a signed order commitment against the cluster and the sealed
+/// batch stored on this node.
+///
+/// The signer must be the leader that took over the rollup at or before the
+/// committed position, and a cluster member at the block height of that
+/// hand-over. Once the batch is sealed, `pre_merkle_path` together with the
+/// transaction hash must lead to the batch commitment. If the rollup uses
+/// quorum order commitments, the co-signatures collected for the position are
+/// verified against the cluster as well.
+pub async fn verify_order_commitment(
+    sign_order_commitment: &SignOrderCommitment,
+) -> Result<OrderCommitmentVerdict, Error> {
+    let data = &sign_order_commitment.data;
+    let rollup = Rollup::get(&data.rollup_id).map_err(|error| {
+        tracing::error!(
+            "Failed to get rollup - rollup_id: {:?} / error: {:?}",
+            data.rollup_id,
+            error
+        );
+        Error::RollupNotFound
+    })?;
+
+    let signer_address = sign_order_commitment
+        .signature
+        .get_signer_address(rollup.platform.into(), data)
+        .map_err(Error::Signature)?;
+
+    let leader_state = LeaderHistory::get_or(&data.rollup_id, LeaderHistory::default)?
+        .get_leader_state(data.batch_number, data.transaction_order)
+        .cloned();
+
+    let leader_check = match &leader_state {
+        Some(leader_state) => {
+            let cluster = Cluster::get(
+                rollup.platform,
+                rollup.liveness_service_provider,
+                &rollup.cluster_id,
+                leader_state.platform_block_height,
+            )?;
+
+            if leader_state.leader_tx_orderer_address == signer_address
+                && cluster
+                    .get_tx_orderer_address_list()
+                    .contains(&signer_address)
+            {
+                LeaderCheck::Leader
+            } else {
+                LeaderCheck::NotLeader
+            }
+        }
+        None => LeaderCheck::Unknown,
+    };
+
+    let is_quorum_certified = match QuorumOrderCommitment::get(
+        &data.rollup_id,
+        data.batch_number,
+        data.transaction_order,
+    ) {
+        Ok(quorum_order_commitment) => {
+            let cluster = Cluster::get(
+                rollup.platform,
+                rollup.liveness_service_provider,
+                &rollup.cluster_id,
+                quorum_order_commitment.platform_block_height,
+            )?;
+
+            Some(
+                quorum_order_commitment.data == *data
+                    && quorum_order_commitment.verify(
+                        rollup.platform.into(),
+                        &cluster.get_tx_orderer_address_list(),
+                    ),
+            )
+        }
+        Err(error) if error.is_none_type() => None,
+        Err(error) => return Err(error.into()),
+    };
+
+    let merkle_path_check = match Batch::get(&data.rollup_id, data.batch_number) {
+        Ok(batch) => check_merkle_path(data, &batch).await,
+        Err(error) if error.is_none_type() => MerklePathCheck::Pending,
+        Err(error) => return Err(error.into()),
+    };
+
+    Ok(OrderCommitmentVerdict {
+        signer_address,
+        leader_tx_orderer_address: leader_state
+            .as_ref()
+            .map(|leader_state| leader_state.leader_tx_orderer_address.as_hex_string()),
+        platform_block_height: leader_state.map(|leader_state| leader_state.platform_block_height),
+        is_honored: leader_check == LeaderCheck::Leader
+            && merkle_path_check == MerklePathCheck::Valid,
+        leader_check,
+        merkle_path_check,
+        is_quorum_certified,
+    })
+}
+
+async fn check_merkle_path(data: &OrderCommitmentData, batch: &Batch) -> MerklePathCheck {
+    let index = data.transaction_order as usize;
+
+    let Some(raw_transaction) = batch.raw_transaction_list.get(index) else {
+        return MerklePathCheck::Invalid;
+    };
+    if raw_transaction.raw_transaction_hash().as_string() != data.transaction_hash {
+        return MerklePathCheck::Invalid;
+    }
+
+    let merkle_tree = MerkleTree::from_raw_transaction_list(&batch.raw_transaction_list).await;
+    let merkle_root = merkle_tree.get_merkle_root().await;
+    if BatchCommitment::from(merkle_root) != batch.batch_commitment {
+        return MerklePathCheck::Invalid;
+    }
+
+    let post_merkle_path = merkle_tree.get_post_merkle_path(index).await;
+    let is_valid = merkle_tree
+        .verify_proof(
+            data.pre_merkle_path.clone(),
+            post_merkle_path,
+            index,
+            &data.transaction_hash,
+            merkle_root,
+        )
+        .await;
+
+    if is_valid {
+        MerklePathCheck::Valid
+    } else {
+        MerklePathCheck::Invalid
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use radius_sdk::signature::{ChainType, PrivateKeySigner};
+
+    use super::*;
+
+    const TRANSACTION_INDEX: u64 = 1;
+
+    fn raw_transaction_list() -> Vec<RawTransaction> {
+        ["0x0201", "0x0202", "0x0203"]
+            .into_iter()
+            .map(|raw_transaction| {
+                RawTransaction::from(EthRawTransaction::from(raw_transaction.to_owned()))
+            })
+            .collect()
+    }
+
+    /// Returns the batch and the order commitment data of the leader for the
+    /// transaction at [`TRANSACTION_INDEX`].
+    async fn sealed_batch() -> (Batch, OrderCommitmentData) {
+        let raw_transaction_list = raw_transaction_list();
+
+        let merkle_tree = MerkleTree::new();
+        let mut pre_merkle_path_list = Vec::new();
+        for raw_transaction in &raw_transaction_list {
+            let (_, pre_merkle_path) = merkle_tree
+                .add_data(raw_transaction.raw_transaction_hash().as_ref())
+                .await;
+            pre_merkle_path_list.push(pre_merkle_path);
+        }
+        merkle_tree.finalize_tree().await;
+        let merkle_root = merkle_tree.get_merkle_root().await;
+
+        let data = OrderCommitmentData {
+            rollup_id: "rollup".to_owned(),
+            batch_number: 0,
+            transaction_order: TRANSACTION_INDEX,
+            transaction_hash: raw_transaction_list[TRANSACTION_INDEX as usize]
+                .raw_transaction_hash()
+                .as_string(),
+            pre_merkle_path: pre_merkle_path_list.swap_remove(TRANSACTION_INDEX as usize),
+        };
+
+        let leader = PrivateKeySigner::from_str(ChainType::Ethereum, DEFAULT_SIGNING_KEY).unwrap();
+        let batch = Batch::new(
+            0,
+            vec![None; raw_transaction_list.len()],
+            raw_transaction_list,
+            BatchCommitment::from(merkle_root),
+            leader.address().clone(),
+            leader.sign_message(&merkle_root).unwrap(),
+        );
+
+        (batch, data)
+    }
+
+    #[tokio::test]
+    async fn sealed_batch_honors_the_committed_position() {
+        let (batch, data) = sealed_batch().await;
+
+        assert_eq!(
+            check_merkle_path(&data, &batch).await,
+            MerklePathCheck::Valid
+        );
+    }
+
+    #[tokio::test]
+    async fn sealed_batch_contradicts_another_position() {
+        let (batch, data) = sealed_batch().await;
+
+        // Another transaction was sealed at the committed position.
+        let moved_data = OrderCommitmentData {
+            transaction_order: TRANSACTION_INDEX + 1,
+            ..data.clone()
+        };
+        assert_eq!(
+            check_merkle_path(&moved_data, &batch).await,
+            MerklePathCheck::Invalid
+        );
+
+        // The batch is shorter than the committed position.
+        let missing_data = OrderCommitmentData {
+            transaction_order: batch.raw_transaction_list.len() as u64,
+            ..data.clone()
+        };
+        assert_eq!(
+            check_merkle_path(&missing_data, &batch).await,
+            MerklePathCheck::Invalid
+        );
+
+        // The batch commitment does not match the sealed transactions.
+        let tampered_batch = Batch {
+            batch_commitment: BatchCommitment::from([7_u8; 32]),
+            ..batch
+        };
+        assert_eq!(
+            check_merkle_path(&data, &tampered_batch).await,
+            MerklePathCheck::Invalid
+        );
+    }
+}
@@ -22,6 +22,18 @@ impl From<Platform> for ChainType {
     }
 }
 
+impl Platform {
+    /// Whether the platform holds real value, so that the development signing
+    /// key must not be used on it.
+    pub fn is_mainnet(&self) -> bool {
+        match self {
+            Self::Ethereum => true,
+            Self::Holesky => false,
+            Self::Local => false,
+        }
+    }
+}
+
 impl FromStr for Platform {
     type Err = Error;
 
@@ -0,0 +1,60 @@
+use crate::types::prelude::*;
+
+/// How long the sealed batches of a rollup are kept. The batches that are not
+/// delivered to every builder sink yet are kept regardless of the policy.
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, Model, PartialEq, Eq)]
+#[kvstore(key(rollup_id: &RollupId))]
+#[serde(tag = "type", content = "data")]
+#[serde(rename_all = "snake_case")]
+pub enum RetentionPolicy {
+    KeepAll,
+    /// Keeps the latest `batch_count` batches.
+    KeepBatchCount {
+        batch_count: u64,
+    },
+    /// Keeps a batch until the task of its batch commitment has been responded
+    /// on the validation service manager.
+    KeepUntilFinalized,
+}
+
+impl RetentionPolicy {
+    /// Returns the policy of the rollup, falling back to the one in the config.
+    pub fn get_or_default(rollup_id: &RollupId, config: &Config) -> Result<Self, KvStoreError> {
+        match Self::get(rollup_id) {
+            Ok(retention_policy) => Ok(retention_policy),
+            Err(error) if error.is_none_type() => Ok(config.default_retention_policy()),
+            Err(error) => Err(error),
+        }
+    }
+}
+
+/// The first batch of a rollup that has not been pruned yet.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize, Model)]
+#[kvstore(key(rollup_id: &RollupId))]
+pub struct RetentionCursor {
+    pub batch_number: u64,
+}
+
+/// Number of entries and their encoded size for a model.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct ModelUsage {
+    pub model: String,
+    pub count: u64,
+    pub encoded_size: u64,
+}
+
+impl ModelUsage {
+    pub fn new(model: impl AsRef<str>) -> Self {
+        Self {
+            model: model.as_ref().to_owned(),
+            ..Default::default()
+        }
+    }
+
+    pub fn add<V: Serialize>(&mut self, value: &V) {
+        self.count += 1;
+        self.encoded_size += serde_json::to_vec(value)
+            .map(|encoded| encoded.len() as u64)
+            .unwrap_or_default();
+    }
+}
@@ -0,0 +1,207 @@
+use crate::types::prelude::*;
+
+/// The maximum number of hand-overs kept per rollup when no batch is pruned.
+/// The oldest ones are dropped first.
+const MAX_LEADER_HISTORY_LENGTH: usize = 1024;
+
+/// The latest leader hand-over of a rollup, as received through
+/// `SyncLeaderTxOrderer`. Used as the starting point for a failover.
+#[derive(Clone, Debug, Deserialize, Serialize, Model)]
+#[kvstore(key(rollup_id: &RollupId))]
+pub struct LeaderState {
+    pub platform_block_height: u64,
+
+    #[serde(serialize_with = "serialize_address")]
+    pub leader_tx_orderer_address: Address,
+
+    pub batch_number: u64,
+    pub transaction_order: u64,
+}
+
+impl LeaderState {
+    pub fn new(
+        platform_block_height: u64,
+        leader_tx_orderer_address: Address,
+        batch_number: u64,
+        transaction_order: u64,
+    ) -> Self {
+        Self {
+            platform_block_height,
+            leader_tx_orderer_address,
+            batch_number,
+            transaction_order,
+        }
+    }
+
+    /// Stores the hand-over as the latest one and appends it to the
+    /// [`LeaderHistory`] of the rollup.
+    pub fn record(&self, rollup_id: &RollupId) -> Result<(), KvStoreError> {
+        self.put(rollup_id)?;
+
+        let mut leader_history = LeaderHistory::get_mut_or(rollup_id, LeaderHistory::default)?;
+        leader_history.add(self.clone());
+        leader_history.update()
+    }
+}
+
+/// Every leader hand-over of a rollup, ordered by the position from which the
+/// leader started ordering transactions.
+#[derive(Clone, Debug, Default, Deserialize, Serialize, Model)]
+#[kvstore(key(rollup_id: &RollupId))]
+pub struct LeaderHistory(Vec<LeaderState>);
+
+impl LeaderHistory {
+    pub fn add(&mut self, leader_state: LeaderState) {
+        let position = (leader_state.batch_number, leader_state.transaction_order);
+
+        // A repeated hand-over for the same position replaces the older one.
+        self.0.retain(|stored_leader_state| {
+            (
+                stored_leader_state.batch_number,
+                stored_leader_state.transaction_order,
+            ) != position
+        });
+
+        let index = self.0.partition_point(|stored_leader_state| {
+            (
+                stored_leader_state.batch_number,
+                stored_leader_state.transaction_order,
+            ) < position
+        });
+        self.0.insert(index, leader_state);
+
+        if self.0.len() > MAX_LEADER_HISTORY_LENGTH {
+            let excess = self.0.len() - MAX_LEADER_HISTORY_LENGTH;
+            self.0.drain(..excess);
+        }
+    }
+
+    /// Drops the hand-overs that only cover batches before `batch_number`. The
+    /// latest hand-over before it is kept, as it covers the start of the
+    /// batch.
+    pub fn prune(&mut self, batch_number: u64) {
+        let index = self
+            .0
+            .partition_point(|leader_state| leader_state.batch_number < batch_number);
+
+        self.0.drain(..index.saturating_sub(1));
+    }
+
+    /// Returns the hand-over of the leader that ordered the transaction at the
+    /// given position.
+    pub fn get_leader_state(
+        &self,
+        batch_number: u64,
+        transaction_order: u64,
+    ) -> Option<&LeaderState> {
+        self.0.iter().rev().find(|leader_state| {
+            (leader_state.batch_number, leader_state.transaction_order)
+                <= (batch_number, transaction_order)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use radius_sdk::signature::{ChainType, PrivateKeySigner};
+
+    use super::*;
+
+    fn leader_state(signing_key: &str, batch_number: u64, transaction_order: u64) -> LeaderState {
+        let leader = PrivateKeySigner::from_str(ChainType::Ethereum, signing_key).unwrap();
+
+        LeaderState::new(0, leader.address().clone(), batch_number, transaction_order)
+    }
+
+    fn get_leader_address(
+        leader_history: &LeaderHistory,
+        batch_number: u64,
+        transaction_order: u64,
+    ) -> Option<Address> {
+        leader_history
+            .get_leader_state(batch_number, transaction_order)
+            .map(|leader_state| leader_state.leader_tx_orderer_address.clone())
+    }
+
+    #[test]
+    fn position_is_ordered_by_the_leader_that_took_over_before_it() {
+        let first_leader = leader_state(DEFAULT_SIGNING_KEY, 0, 0);
+        let second_leader = leader_state(
+            "0x59c6995e998f97a5a0044966f0945389dc9e86dae88c7a8412f4603b6b78690d",
+            2,
+            5,
+        );
+
+        // Hand-overs may arrive out of order.
+        let mut leader_history = LeaderHistory::default();
+        leader_history.add(second_leader.clone());
+        leader_history.add(first_leader.clone());
+
+        assert_eq!(
+            get_leader_address(&leader_history, 2, 4),
+            Some(first_leader.leader_tx_orderer_address.clone())
+        );
+        assert_eq!(
+            get_leader_address(&leader_history, 2, 5),
+            Some(second_leader.leader_tx_orderer_address.clone())
+        );
+        assert_eq!(
+            get_leader_address(&leader_history, 3, 0),
+            Some(second_leader.leader_tx_orderer_address)
+        );
+
+        // A repeated hand-over replaces the older one.
+        leader_history.add(leader_state(
+            "0x5de4111afa1a4b94908f83103eb1f1706367c2e68ca870fc3fb9a804cdab365a",
+            0,
+            0,
+        ));
+        assert_eq!(leader_history.0.len(), 2);
+        assert_ne!(
+            get_leader_address(&leader_history, 1, 0),
+            Some(first_leader.leader_tx_orderer_address)
+        );
+    }
+
+    #[test]
+    fn position_before_the_first_hand_over_is_unknown() {
+        let mut leader_history = LeaderHistory::default();
+        leader_history.add(leader_state(DEFAULT_SIGNING_KEY, 1, 3));
+
+        assert!(leader_history.get_leader_state(1, 2).is_none());
+        assert!(leader_history.get_leader_state(1, 3).is_some());
+    }
+
+    #[test]
+    fn pruning_keeps_the_hand_over_that_covers_the_start_of_the_batch() {
+        let mut leader_history = LeaderHistory::default();
+        for batch_number in [0, 2, 4, 6] {
+            leader_history.add(leader_state(DEFAULT_SIGNING_KEY, batch_number, 1));
+        }
+
+        leader_history.prune(5);
+        assert_eq!(
+            leader_history
+                .0
+                .iter()
+                .map(|leader_state| leader_state.batch_number)
+                .collect::<Vec<_>>(),
+            vec![4, 6]
+        );
+        assert!(leader_history.get_leader_state(5, 0).is_some());
+    }
+
+    #[test]
+    fn oldest_hand_overs_are_dropped_past_the_maximum_length() {
+        let mut leader_history = LeaderHistory::default();
+        for batch_number in 0..=MAX_LEADER_HISTORY_LENGTH as u64 {
+            leader_history.add(leader_state(DEFAULT_SIGNING_KEY, batch_number, 0));
+        }
+
+        assert_eq!(leader_history.0.len(), MAX_LEADER_HISTORY_LENGTH);
+        assert!(leader_history.get_leader_state(0, 0).is_none());
+        assert!(leader_history.get_leader_state(1, 0).is_some());
+    }
+}
@@ -1,8 +1,10 @@
+mod leader_state;
 mod rollup_metadata;
 mod rollup_type;
 
 use std::collections::{btree_set, BTreeSet};
 
+pub use leader_state::*;
 pub use rollup_metadata::*;
 pub use rollup_type::*;
 
@@ -94,6 +96,10 @@ impl RollupIdList {
         self.0.remove(cluster_id.as_ref());
     }
 
+    pub fn contains(&self, rollup_id: impl AsRef<str>) -> bool {
+        self.0.contains(rollup_id.as_ref())
+    }
+
     pub fn iter(&self) -> btree_set::Iter<'_, RollupId> {
         self.0.iter()
     }
@@ -3,9 +3,8 @@ use std::collections::{BTreeSet, HashMap};
 use radius_sdk::kvstore::Model;
 use serde::{Deserialize, Serialize};
 
-use crate::{error::Error, types::ClusterId};
-
 use super::RollupId;
+use crate::{error::Error, types::ClusterId};
 
 #[derive(Clone, Debug, Deserialize, Serialize, Model)]
 #[kvstore(key(rollup_id: &RollupId))]
@@ -96,3 +95,37 @@ impl RollupMetadata {
         false
     }
 }
+
+/// The furthest position `GetTransactionsSince` has served the transactions
+/// up to. The executor cannot acknowledge past it.
+#[derive(Clone, Debug, Default, Deserialize, Serialize, Model)]
+#[kvstore(key(rollup_id: &RollupId))]
+pub struct ServedTransactionCursor {
+    pub batch_number: u64,
+    pub transaction_order: u64,
+}
+
+impl ServedTransactionCursor {
+    /// Moves the cursor forward to `(batch_number, transaction_order)`. An
+    /// older position leaves it untouched.
+    pub fn advance(
+        rollup_id: &RollupId,
+        batch_number: u64,
+        transaction_order: u64,
+    ) -> Result<(), Error> {
+        let mut served_transaction_cursor = Self::get_mut_or(rollup_id, Self::default)?;
+
+        if (batch_number, transaction_order)
+            > (
+                served_transaction_cursor.batch_number,
+                served_transaction_cursor.transaction_order,
+            )
+        {
+            served_transaction_cursor.batch_number = batch_number;
+            served_transaction_cursor.transaction_order = transaction_order;
+            served_transaction_cursor.update()?;
+        }
+
+        Ok(())
+    }
+}
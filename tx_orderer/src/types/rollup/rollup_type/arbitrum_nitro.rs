@@ -0,0 +1,43 @@
+use radius_sdk::signature::ChainType;
+
+use super::{ExecutorRequest, RollupStack};
+
+/// Arbitrum internal transaction types, from deposits (0x64) to the internal
+/// transactions of ArbOS (0x6A). They are created by the chain itself.
+pub const ARBITRUM_INTERNAL_TRANSACTION_TYPE_RANGE: std::ops::RangeInclusive<u8> = 0x64..=0x6A;
+
+pub struct ArbitrumNitroRollupStack;
+
+impl RollupStack for ArbitrumNitroRollupStack {
+    fn chain_type(&self) -> ChainType {
+        ChainType::Ethereum
+    }
+
+    /// Arbitrum internal transactions and blob transactions (0x03) are not
+    /// accepted from users.
+    fn is_allowed_transaction_type(&self, transaction_type: Option<u8>) -> bool {
+        match transaction_type {
+            Some(transaction_type)
+                if ARBITRUM_INTERNAL_TRANSACTION_TYPE_RANGE.contains(&transaction_type) =>
+            {
+                false
+            }
+            None | Some(0x01) | Some(0x02) => true,
+            Some(_) => false,
+        }
+    }
+
+    /// The sequencer feed is built from the order of arrival, so only the raw
+    /// transaction is sent.
+    fn executor_request(
+        &self,
+        raw_transaction: &str,
+        _batch_number: u64,
+        _transaction_order: u64,
+    ) -> ExecutorRequest {
+        ExecutorRequest {
+            method: "eth_sendRawTransaction",
+            params: serde_json::json!([raw_transaction]),
+        }
+    }
+}
@@ -0,0 +1,126 @@
+mod arbitrum_nitro;
+mod op_stack;
+mod polygon_cdk;
+
+use std::str::FromStr;
+
+pub use arbitrum_nitro::*;
+pub use op_stack::*;
+pub use polygon_cdk::*;
+use radius_sdk::signature::ChainType;
+use serde::{Deserialize, Serialize};
+
+use crate::{error::Error, types::RawTransaction};
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum RollupType {
+    PolygonCdk,
+    OpStack,
+    ArbitrumNitro,
+}
+
+impl RollupType {
+    /// Returns the stack specific behavior of the rollup type.
+    pub fn stack(&self) -> &'static dyn RollupStack {
+        match self {
+            Self::PolygonCdk => &PolygonCdkRollupStack,
+            Self::OpStack => &OpStackRollupStack,
+            Self::ArbitrumNitro => &ArbitrumNitroRollupStack,
+        }
+    }
+
+    /// Rejects raw transactions users are not allowed to submit to the rollup.
+    pub fn validate_raw_transaction(&self, raw_transaction: &RawTransaction) -> Result<(), Error> {
+        match raw_transaction {
+            RawTransaction::Eth(eth_raw_transaction) => {
+                let transaction_type = eth_raw_transaction.transaction_type()?;
+                if !self.stack().is_allowed_transaction_type(transaction_type) {
+                    return Err(Error::UnsupportedTransactionType);
+                }
+
+                // Only the legacy, access list and dynamic fee transactions can be
+                // decoded here. The other allowed types are left to the executor.
+                if matches!(transaction_type, None | Some(0x01) | Some(0x02)) {
+                    eth_raw_transaction.rollup_transaction()?;
+                }
+
+                Ok(())
+            }
+            RawTransaction::EthBundle(_) => Ok(()),
+        }
+    }
+}
+
+impl From<RollupType> for ChainType {
+    fn from(value: RollupType) -> Self {
+        value.stack().chain_type()
+    }
+}
+
+impl FromStr for RollupType {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "polygon_cdk" | "PolygonCdk" => Ok(Self::PolygonCdk),
+            "op_stack" | "OpStack" => Ok(Self::OpStack),
+            "arbitrum_nitro" | "ArbitrumNitro" => Ok(Self::ArbitrumNitro),
+            _ => Err(Error::UnsupportedRollupType),
+        }
+    }
+}
+
+/// The request that hands an ordered transaction over to the executor of the
+/// rollup.
+pub struct ExecutorRequest {
+    pub method: &'static str,
+    pub params: serde_json::Value,
+}
+
+/// What differs between rollup stacks. Adding a stack only requires a new
+/// [`RollupType`] variant and an implementation of this trait.
+pub trait RollupStack: Send + Sync {
+    fn chain_type(&self) -> ChainType;
+
+    /// Returns whether users may submit transactions of the given EIP-2718
+    /// type. `None` is a legacy transaction.
+    fn is_allowed_transaction_type(&self, transaction_type: Option<u8>) -> bool;
+
+    fn executor_request(
+        &self,
+        raw_transaction: &str,
+        batch_number: u64,
+        transaction_order: u64,
+    ) -> ExecutorRequest;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::EthRawTransaction;
+
+    fn raw_transaction(transaction_type: u8) -> RawTransaction {
+        RawTransaction::Eth(EthRawTransaction(format!("0x{:02x}f8", transaction_type)))
+    }
+
+    #[test]
+    fn test_polygon_cdk_accepts_blob_and_set_code_transactions() {
+        assert!(RollupType::PolygonCdk
+            .validate_raw_transaction(&raw_transaction(0x03))
+            .is_ok());
+        assert!(RollupType::PolygonCdk
+            .validate_raw_transaction(&raw_transaction(0x04))
+            .is_ok());
+    }
+
+    #[test]
+    fn test_op_stack_rejects_deposit_and_blob_transactions() {
+        for transaction_type in [0x03, 0x7e] {
+            assert!(matches!(
+                RollupType::OpStack.validate_raw_transaction(&raw_transaction(transaction_type)),
+                Err(Error::UnsupportedTransactionType)
+            ));
+        }
+    }
+}
@@ -0,0 +1,38 @@
+use radius_sdk::signature::ChainType;
+
+use super::{ExecutorRequest, RollupStack};
+
+/// Deposit transactions are derived from L1 by the rollup node.
+pub const OP_DEPOSIT_TRANSACTION_TYPE: u8 = 0x7E;
+
+pub struct OpStackRollupStack;
+
+impl RollupStack for OpStackRollupStack {
+    fn chain_type(&self) -> ChainType {
+        ChainType::Ethereum
+    }
+
+    /// Deposit transactions (0x7E) and blob transactions (0x03) are not
+    /// accepted from users.
+    fn is_allowed_transaction_type(&self, transaction_type: Option<u8>) -> bool {
+        match transaction_type {
+            Some(OP_DEPOSIT_TRANSACTION_TYPE) | Some(0x03) => false,
+            None | Some(0x01) | Some(0x02) => true,
+            Some(_) => false,
+        }
+    }
+
+    /// The execution engine includes transactions in the order it receives
+    /// them, so only the raw transaction is sent.
+    fn executor_request(
+        &self,
+        raw_transaction: &str,
+        _batch_number: u64,
+        _transaction_order: u64,
+    ) -> ExecutorRequest {
+        ExecutorRequest {
+            method: "eth_sendRawTransaction",
+            params: serde_json::json!([raw_transaction]),
+        }
+    }
+}
@@ -0,0 +1,31 @@
+use radius_sdk::signature::ChainType;
+
+use super::{ExecutorRequest, RollupStack};
+
+pub struct PolygonCdkRollupStack;
+
+impl RollupStack for PolygonCdkRollupStack {
+    fn chain_type(&self) -> ChainType {
+        ChainType::Ethereum
+    }
+
+    /// Legacy, access list (0x01), dynamic fee (0x02), blob (0x03) and set
+    /// code (0x04) transactions. The executor rejects the types it does not
+    /// support itself.
+    fn is_allowed_transaction_type(&self, transaction_type: Option<u8>) -> bool {
+        matches!(transaction_type, None | Some(0x01..=0x04))
+    }
+
+    /// The executor takes the position of the transaction along with it.
+    fn executor_request(
+        &self,
+        raw_transaction: &str,
+        batch_number: u64,
+        transaction_order: u64,
+    ) -> ExecutorRequest {
+        ExecutorRequest {
+            method: "eth_sendRawTransaction",
+            params: serde_json::json!([raw_transaction, batch_number, transaction_order]),
+        }
+    }
+}
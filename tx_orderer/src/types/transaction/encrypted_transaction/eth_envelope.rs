@@ -0,0 +1,876 @@
+use ethers_core::{
+    types::{self as eth_types, transaction::eip2930::AccessList, Address, Bytes, H256, U256},
+    utils::{
+        keccak256,
+        rlp::{Decodable, DecoderError, Encodable, Rlp, RlpStream},
+    },
+};
+use serde::{de::Error as _, Deserializer, Serializer};
+
+use crate::{error::Error, types::prelude::*};
+
+/// EIP-2718 transaction types.
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum EthTransactionType {
+    Legacy,
+    /// EIP-2930
+    AccessList,
+    /// EIP-1559
+    DynamicFee,
+    /// EIP-4844
+    Blob,
+    /// EIP-7702
+    SetCode,
+}
+
+impl EthTransactionType {
+    pub fn type_byte(&self) -> Option<u8> {
+        match self {
+            Self::Legacy => None,
+            Self::AccessList => Some(0x01),
+            Self::DynamicFee => Some(0x02),
+            Self::Blob => Some(0x03),
+            Self::SetCode => Some(0x04),
+        }
+    }
+
+    fn from_type_byte(type_byte: u8) -> Result<Self, Error> {
+        match type_byte {
+            0x01 => Ok(Self::AccessList),
+            0x02 => Ok(Self::DynamicFee),
+            0x03 => Ok(Self::Blob),
+            0x04 => Ok(Self::SetCode),
+            _ => Err(Error::UnsupportedTransactionType),
+        }
+    }
+
+    /// Number of fields of the signed transaction, including the signature.
+    fn field_count(&self) -> usize {
+        match self {
+            Self::Legacy => 9,
+            Self::AccessList => 11,
+            Self::DynamicFee => 12,
+            Self::Blob => 14,
+            Self::SetCode => 13,
+        }
+    }
+}
+
+/// An entry of the authorization list of a set code transaction.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+pub struct EthAuthorization {
+    pub chain_id: U256,
+    pub address: Address,
+    pub nonce: u64,
+    pub y_parity: u8,
+    pub r: U256,
+    pub s: U256,
+}
+
+impl Encodable for EthAuthorization {
+    fn rlp_append(&self, stream: &mut RlpStream) {
+        stream.begin_list(6);
+        stream.append(&self.chain_id);
+        stream.append(&self.address);
+        stream.append(&self.nonce);
+        stream.append(&self.y_parity);
+        stream.append(&self.r);
+        stream.append(&self.s);
+    }
+}
+
+impl Decodable for EthAuthorization {
+    fn decode(rlp: &Rlp) -> Result<Self, DecoderError> {
+        if rlp.item_count()? != 6 {
+            return Err(DecoderError::RlpIncorrectListLen);
+        }
+
+        Ok(Self {
+            chain_id: rlp.val_at(0)?,
+            address: rlp.val_at(1)?,
+            nonce: rlp.val_at(2)?,
+            y_parity: rlp.val_at(3)?,
+            r: rlp.val_at(4)?,
+            s: rlp.val_at(5)?,
+        })
+    }
+}
+
+/// The blobs, commitments and proofs sent along with a blob transaction in its
+/// network form. They are not part of the transaction hash.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+pub struct EthBlobSidecar {
+    pub blobs: Vec<Bytes>,
+    pub commitments: Vec<Bytes>,
+    pub proofs: Vec<Bytes>,
+}
+
+/// The open data as it was built from a decoded `ethers` transaction, which
+/// secure RPC providers built against earlier releases still send.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct LegacyEthOpenData {
+    pub raw_tx_hash: RawTransactionHash,
+    pub from: Address,
+    pub nonce: U256,
+    pub gas_price: Option<U256>,
+    pub gas_limit: U256,
+    pub signature: eth_types::Signature,
+    pub block_hash: Option<H256>,
+    pub block_number: Option<eth_types::U64>,
+    pub transaction_index: Option<eth_types::U64>,
+    pub transaction_type: Option<eth_types::U64>,
+    pub access_list: Option<AccessList>,
+    pub max_priority_fee_per_gas: Option<U256>,
+    pub max_fee_per_gas: Option<U256>,
+    pub chain_id: Option<U256>,
+    pub other: eth_types::OtherFields,
+}
+
+impl TryFrom<LegacyEthOpenData> for EthOpenData {
+    type Error = Error;
+
+    fn try_from(value: LegacyEthOpenData) -> Result<Self, Self::Error> {
+        let transaction_type = match value.transaction_type.map(|t| t.as_u64()) {
+            None | Some(0) => EthTransactionType::Legacy,
+            Some(0x01) => EthTransactionType::AccessList,
+            Some(0x02) => EthTransactionType::DynamicFee,
+            Some(_) => return Err(Error::UnsupportedTransactionType),
+        };
+
+        let (chain_id, gas_price, max_priority_fee_per_gas, max_fee_per_gas, access_list) =
+            match transaction_type {
+                EthTransactionType::Legacy => (
+                    legacy_chain_id(value.signature.v).map(U256::from),
+                    value.gas_price,
+                    None,
+                    None,
+                    None,
+                ),
+                EthTransactionType::AccessList => (
+                    value.chain_id,
+                    value.gas_price,
+                    None,
+                    None,
+                    Some(value.access_list.unwrap_or_default()),
+                ),
+                _ => (
+                    value.chain_id,
+                    None,
+                    value.max_priority_fee_per_gas,
+                    value.max_fee_per_gas,
+                    Some(value.access_list.unwrap_or_default()),
+                ),
+            };
+
+        Ok(Self {
+            raw_tx_hash: value.raw_tx_hash,
+            from: value.from,
+            transaction_type,
+            chain_id,
+            nonce: value.nonce,
+            gas_limit: value.gas_limit,
+            gas_price,
+            max_priority_fee_per_gas,
+            max_fee_per_gas,
+            access_list,
+            max_fee_per_blob_gas: None,
+            blob_versioned_hashes: None,
+            blob_sidecar: None,
+            authorization_list: None,
+            signature: value.signature,
+        })
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum EthOpenDataFormat {
+    Typed(#[serde(deserialize_with = "EthOpenData::deserialize")] EthOpenData),
+    Legacy(LegacyEthOpenData),
+}
+
+impl Serialize for EthOpenData {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        EthOpenData::serialize(self, serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for EthOpenData {
+    /// The legacy shape is only told apart in self-describing formats, which
+    /// is what the RPC uses. The database always holds the typed shape.
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if !deserializer.is_human_readable() {
+            return EthOpenData::deserialize(deserializer);
+        }
+
+        match EthOpenDataFormat::deserialize(deserializer)? {
+            EthOpenDataFormat::Typed(open_data) => Ok(open_data),
+            EthOpenDataFormat::Legacy(legacy_open_data) => {
+                EthOpenData::try_from(legacy_open_data).map_err(D::Error::custom)
+            }
+        }
+    }
+}
+
+impl EthOpenData {
+    /// Splits a signed transaction into the data that stays in the clear and
+    /// the data to encrypt. Transactions that are not canonically encoded are
+    /// rejected, as they would not re-encode to the same bytes.
+    pub fn split_raw_transaction(raw_transaction: &str) -> Result<(Self, EthPlainData), Error> {
+        let transaction_bytes = decode_hex(raw_transaction)?;
+
+        let (transaction_type, payload) = match transaction_bytes.first() {
+            Some(&first_byte) if first_byte >= 0xc0 => {
+                (EthTransactionType::Legacy, transaction_bytes.as_slice())
+            }
+            Some(&first_byte) if first_byte <= 0x7f => (
+                EthTransactionType::from_type_byte(first_byte)?,
+                &transaction_bytes[1..],
+            ),
+            _ => return Err(Error::InvalidTransaction),
+        };
+
+        let (mut open_data, plain_data) =
+            Self::decode_payload(transaction_type, payload).map_err(|error| {
+                tracing::error!("Failed to decode transaction: {:?}", error);
+                Error::InvalidTransaction
+            })?;
+
+        let (encoded_transaction_bytes, transaction_hash) = open_data.encode(&plain_data)?;
+        if encoded_transaction_bytes != transaction_bytes {
+            return Err(Error::InvalidTransaction);
+        }
+        open_data.raw_tx_hash = RawTransactionHash::new(transaction_hash);
+
+        let signature_hash = open_data.signature_hash(&plain_data)?;
+        open_data.from = open_data
+            .signature
+            .recover(signature_hash)
+            .map_err(|_| Error::InvalidSignature)?;
+
+        Ok((open_data, plain_data))
+    }
+
+    /// Rebuilds the signed transaction, and checks that it hashes to
+    /// `raw_tx_hash` and is signed by `from`.
+    pub fn to_raw_transaction(
+        &self,
+        plain_data: &EthPlainData,
+    ) -> Result<EthRawTransaction, Error> {
+        let (transaction_bytes, transaction_hash) = self.encode(plain_data)?;
+
+        let raw_tx_hash: &str = self.raw_tx_hash.as_ref();
+        if !raw_tx_hash.eq_ignore_ascii_case(&const_hex::encode_prefixed(transaction_hash)) {
+            return Err(Error::InvalidTransaction);
+        }
+
+        let signer = self
+            .signature
+            .recover(self.signature_hash(plain_data)?)
+            .map_err(|_| Error::InvalidSignature)?;
+        if signer != self.from {
+            return Err(Error::InvalidSignature);
+        }
+
+        Ok(EthRawTransaction::from(const_hex::encode_prefixed(
+            transaction_bytes,
+        )))
+    }
+
+    fn decode_payload(
+        transaction_type: EthTransactionType,
+        payload: &[u8],
+    ) -> Result<(Self, EthPlainData), DecoderError> {
+        let rlp = Rlp::new(payload);
+        let payload_info = rlp.payload_info()?;
+        if payload_info.header_len + payload_info.value_len != payload.len() {
+            return Err(DecoderError::RlpInconsistentLengthAndData);
+        }
+
+        // A blob transaction in its network form wraps the transaction fields
+        // in a list followed by the sidecar.
+        let (fields, blob_sidecar) =
+            if transaction_type == EthTransactionType::Blob && rlp.at(0)?.is_list() {
+                if rlp.item_count()? != 4 {
+                    return Err(DecoderError::RlpIncorrectListLen);
+                }
+
+                let blob_sidecar = EthBlobSidecar {
+                    blobs: decode_bytes_list(&rlp, 1)?,
+                    commitments: decode_bytes_list(&rlp, 2)?,
+                    proofs: decode_bytes_list(&rlp, 3)?,
+                };
+                (rlp.at(0)?, Some(blob_sidecar))
+            } else {
+                (rlp, None)
+            };
+
+        if fields.item_count()? != transaction_type.field_count() {
+            return Err(DecoderError::RlpIncorrectListLen);
+        }
+
+        let mut reader = FieldReader::new(fields);
+        let is_legacy = transaction_type == EthTransactionType::Legacy;
+        let has_dynamic_fee = matches!(
+            transaction_type,
+            EthTransactionType::DynamicFee | EthTransactionType::Blob | EthTransactionType::SetCode
+        );
+
+        let chain_id = (!is_legacy).then(|| reader.next()).transpose()?;
+        let nonce = reader.next()?;
+        let gas_price = (!has_dynamic_fee).then(|| reader.next()).transpose()?;
+        let max_priority_fee_per_gas = has_dynamic_fee.then(|| reader.next()).transpose()?;
+        let max_fee_per_gas = has_dynamic_fee.then(|| reader.next()).transpose()?;
+        let gas_limit = reader.next()?;
+        let to = reader.next_to()?;
+        let value = reader.next()?;
+        let input: Vec<u8> = reader.next()?;
+        let access_list = (!is_legacy).then(|| reader.next()).transpose()?;
+
+        let is_blob = transaction_type == EthTransactionType::Blob;
+        let max_fee_per_blob_gas = is_blob.then(|| reader.next()).transpose()?;
+        let blob_versioned_hashes = is_blob.then(|| reader.next_list()).transpose()?;
+
+        let is_set_code = transaction_type == EthTransactionType::SetCode;
+        let authorization_list = is_set_code.then(|| reader.next_list()).transpose()?;
+
+        // Blob and set code transactions cannot create a contract.
+        if (is_blob || is_set_code) && to.is_none() {
+            return Err(DecoderError::Custom("missing destination"));
+        }
+
+        let v: u64 = reader.next()?;
+        if !is_legacy && v > 1 {
+            return Err(DecoderError::Custom("invalid y parity"));
+        }
+
+        let open_data = Self {
+            raw_tx_hash: RawTransactionHash::default(),
+            from: Address::zero(),
+            transaction_type,
+            chain_id: chain_id.or_else(|| legacy_chain_id(v).map(U256::from)),
+            nonce,
+            gas_limit,
+            gas_price,
+            max_priority_fee_per_gas,
+            max_fee_per_gas,
+            access_list,
+            max_fee_per_blob_gas,
+            blob_versioned_hashes,
+            blob_sidecar,
+            authorization_list,
+            signature: eth_types::Signature {
+                r: reader.next()?,
+                s: reader.next()?,
+                v,
+            },
+        };
+        let plain_data = EthPlainData {
+            to,
+            value,
+            input: Bytes::from(input),
+        };
+
+        Ok((open_data, plain_data))
+    }
+
+    /// Returns the signed transaction and its hash. The hash of a blob
+    /// transaction in its network form does not cover the sidecar.
+    fn encode(&self, plain_data: &EthPlainData) -> Result<(Vec<u8>, [u8; 32]), Error> {
+        let mut stream = RlpStream::new_list(self.transaction_type.field_count());
+        self.append_unsigned_fields(&mut stream, plain_data)?;
+        stream.append(&self.signature.v);
+        stream.append(&self.signature.r);
+        stream.append(&self.signature.s);
+        let fields = stream.out().to_vec();
+
+        let transaction_hash = keccak256(with_type_byte(self.transaction_type, &fields));
+
+        let payload = match &self.blob_sidecar {
+            Some(blob_sidecar) if self.transaction_type == EthTransactionType::Blob => {
+                let mut stream = RlpStream::new_list(4);
+                stream.append_raw(&fields, 1);
+                append_bytes_list(&mut stream, &blob_sidecar.blobs);
+                append_bytes_list(&mut stream, &blob_sidecar.commitments);
+                append_bytes_list(&mut stream, &blob_sidecar.proofs);
+                stream.out().to_vec()
+            }
+            _ => fields,
+        };
+
+        Ok((
+            with_type_byte(self.transaction_type, &payload),
+            transaction_hash,
+        ))
+    }
+
+    fn signature_hash(&self, plain_data: &EthPlainData) -> Result<H256, Error> {
+        let unsigned_field_count = self.transaction_type.field_count() - 3;
+
+        let eip155_chain_id = match self.transaction_type {
+            EthTransactionType::Legacy => legacy_chain_id(self.signature.v),
+            _ => None,
+        };
+
+        let mut stream = match eip155_chain_id {
+            Some(_) => RlpStream::new_list(unsigned_field_count + 3),
+            None => RlpStream::new_list(unsigned_field_count),
+        };
+        self.append_unsigned_fields(&mut stream, plain_data)?;
+        if let Some(chain_id) = eip155_chain_id {
+            stream.append(&chain_id);
+            stream.append(&0_u8);
+            stream.append(&0_u8);
+        }
+
+        Ok(H256::from(keccak256(with_type_byte(
+            self.transaction_type,
+            &stream.out(),
+        ))))
+    }
+
+    fn append_unsigned_fields(
+        &self,
+        stream: &mut RlpStream,
+        plain_data: &EthPlainData,
+    ) -> Result<(), Error> {
+        let transaction_type = self.transaction_type;
+
+        if transaction_type != EthTransactionType::Legacy {
+            stream.append(required(&self.chain_id)?);
+        }
+        stream.append(&self.nonce);
+        match transaction_type {
+            EthTransactionType::Legacy | EthTransactionType::AccessList => {
+                stream.append(required(&self.gas_price)?);
+            }
+            _ => {
+                stream.append(required(&self.max_priority_fee_per_gas)?);
+                stream.append(required(&self.max_fee_per_gas)?);
+            }
+        }
+        stream.append(&self.gas_limit);
+        match &plain_data.to {
+            Some(to) => stream.append(to),
+            None => stream.append_empty_data(),
+        };
+        stream.append(&plain_data.value);
+        stream.append(&plain_data.input.to_vec());
+        if transaction_type != EthTransactionType::Legacy {
+            stream.append(required(&self.access_list)?);
+        }
+        if transaction_type == EthTransactionType::Blob {
+            stream.append(required(&self.max_fee_per_blob_gas)?);
+            stream.append_list::<H256, H256>(required(&self.blob_versioned_hashes)?);
+        }
+        if transaction_type == EthTransactionType::SetCode {
+            stream.append_list::<EthAuthorization, EthAuthorization>(required(
+                &self.authorization_list,
+            )?);
+        }
+
+        Ok(())
+    }
+}
+
+/// Hash of a signed transaction in any supported envelope.
+pub fn eth_transaction_hash(raw_transaction: &str) -> Result<RawTransactionHash, Error> {
+    let transaction_bytes = decode_hex(raw_transaction)?;
+
+    // The network form of a blob transaction starts with the list of the
+    // transaction fields.
+    if transaction_bytes.first() == EthTransactionType::Blob.type_byte().as_ref() {
+        if let Ok(fields) = Rlp::new(&transaction_bytes[1..]).at(0) {
+            if fields.is_list() {
+                return Ok(RawTransactionHash::new(keccak256(with_type_byte(
+                    EthTransactionType::Blob,
+                    fields.as_raw(),
+                ))));
+            }
+        }
+    }
+
+    Ok(RawTransactionHash::new(keccak256(&transaction_bytes)))
+}
+
+/// Reads the fields of a transaction in order.
+struct FieldReader<'a> {
+    rlp: Rlp<'a>,
+    index: usize,
+}
+
+impl<'a> FieldReader<'a> {
+    fn new(rlp: Rlp<'a>) -> Self {
+        Self { rlp, index: 0 }
+    }
+
+    fn next<T: Decodable>(&mut self) -> Result<T, DecoderError> {
+        self.index += 1;
+        self.rlp.val_at(self.index - 1)
+    }
+
+    fn next_list<T: Decodable>(&mut self) -> Result<Vec<T>, DecoderError> {
+        self.index += 1;
+        self.rlp.list_at(self.index - 1)
+    }
+
+    /// The destination is empty for a contract creation.
+    fn next_to(&mut self) -> Result<Option<Address>, DecoderError> {
+        let to: Vec<u8> = self.next()?;
+
+        match to.len() {
+            0 => Ok(None),
+            20 => Ok(Some(Address::from_slice(&to))),
+            _ => Err(DecoderError::RlpInvalidLength),
+        }
+    }
+}
+
+/// Chain id of an EIP-155 legacy transaction.
+fn legacy_chain_id(v: u64) -> Option<u64> {
+    (v >= 35).then(|| (v - 35) / 2)
+}
+
+fn required<T>(value: &Option<T>) -> Result<&T, Error> {
+    value.as_ref().ok_or(Error::InvalidTransaction)
+}
+
+fn with_type_byte(transaction_type: EthTransactionType, payload: &[u8]) -> Vec<u8> {
+    let mut transaction_bytes = Vec::with_capacity(payload.len() + 1);
+    transaction_bytes.extend(transaction_type.type_byte());
+    transaction_bytes.extend_from_slice(payload);
+    transaction_bytes
+}
+
+fn decode_hex(raw_transaction: &str) -> Result<Vec<u8>, Error> {
+    const_hex::decode(raw_transaction.trim_start_matches("0x"))
+        .map_err(|_| Error::InvalidTransaction)
+}
+
+fn decode_bytes_list(rlp: &Rlp, index: usize) -> Result<Vec<Bytes>, DecoderError> {
+    Ok(rlp
+        .list_at::<Vec<u8>>(index)?
+        .into_iter()
+        .map(Bytes::from)
+        .collect())
+}
+
+fn append_bytes_list(stream: &mut RlpStream, bytes_list: &[Bytes]) {
+    stream.begin_list(bytes_list.len());
+    for bytes in bytes_list {
+        stream.append(&bytes.to_vec());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ethers_core::{
+        k256::ecdsa::SigningKey, types::transaction::eip2930::AccessListItem,
+        utils::secret_key_to_address,
+    };
+
+    use super::*;
+
+    const CHAIN_ID: u64 = 901;
+
+    fn to_address() -> Address {
+        Address::repeat_byte(0x35)
+    }
+
+    fn access_list() -> AccessList {
+        AccessList(vec![AccessListItem {
+            address: Address::repeat_byte(0x11),
+            storage_keys: vec![H256::repeat_byte(0x22), H256::repeat_byte(0x33)],
+        }])
+    }
+
+    /// Signs the transaction built by `append_fields` and returns it as a raw
+    /// transaction along with the signer address.
+    fn sign_transaction(
+        transaction_type: EthTransactionType,
+        append_fields: impl Fn(&mut RlpStream),
+    ) -> (String, Address) {
+        let signing_key = SigningKey::from_slice(&[0x42; 32]).unwrap();
+        let unsigned_field_count = transaction_type.field_count() - 3;
+        let is_legacy = transaction_type == EthTransactionType::Legacy;
+
+        let mut stream = if is_legacy {
+            RlpStream::new_list(unsigned_field_count + 3)
+        } else {
+            RlpStream::new_list(unsigned_field_count)
+        };
+        append_fields(&mut stream);
+        if is_legacy {
+            stream.append(&CHAIN_ID);
+            stream.append(&0_u8);
+            stream.append(&0_u8);
+        }
+        let signature_hash = keccak256(with_type_byte(transaction_type, &stream.out()));
+
+        let (signature, recovery_id) = signing_key
+            .sign_prehash_recoverable(&signature_hash)
+            .unwrap();
+        let signature_bytes = signature.to_bytes();
+        let v = if is_legacy {
+            recovery_id.to_byte() as u64 + 35 + CHAIN_ID * 2
+        } else {
+            recovery_id.to_byte() as u64
+        };
+
+        let mut stream = RlpStream::new_list(transaction_type.field_count());
+        append_fields(&mut stream);
+        stream.append(&v);
+        stream.append(&U256::from_big_endian(&signature_bytes[..32]));
+        stream.append(&U256::from_big_endian(&signature_bytes[32..]));
+
+        (
+            const_hex::encode_prefixed(with_type_byte(transaction_type, &stream.out())),
+            secret_key_to_address(&signing_key),
+        )
+    }
+
+    fn append_dynamic_fee_fields(stream: &mut RlpStream) {
+        stream.append(&CHAIN_ID);
+        stream.append(&7_u64);
+        stream.append(&1_000_000_000_u64);
+        stream.append(&30_000_000_000_u64);
+        stream.append(&100_000_u64);
+        stream.append(&to_address());
+        stream.append(&U256::exp10(18));
+        stream.append(&vec![0xde, 0xad, 0xbe, 0xef]);
+        stream.append(&access_list());
+    }
+
+    fn append_blob_fields(stream: &mut RlpStream) {
+        append_dynamic_fee_fields(stream);
+        stream.append(&1_000_u64);
+        stream.append_list::<H256, H256>(&[H256::repeat_byte(0x01), H256::repeat_byte(0x02)]);
+    }
+
+    fn assert_round_trip(raw_transaction: &str, signer_address: Address) {
+        let (open_data, plain_data) = EthOpenData::split_raw_transaction(raw_transaction).unwrap();
+
+        assert_eq!(open_data.from, signer_address);
+        assert_eq!(
+            open_data.raw_tx_hash,
+            eth_transaction_hash(raw_transaction).unwrap()
+        );
+
+        let rebuilt_raw_transaction = open_data.to_raw_transaction(&plain_data).unwrap();
+        assert_eq!(rebuilt_raw_transaction.0, raw_transaction);
+
+        // The open data and the plain data are sent over the wire separately.
+        let open_data: EthOpenData =
+            serde_json::from_str(&serde_json::to_string(&open_data).unwrap()).unwrap();
+        let plain_data: EthPlainData =
+            serde_json::from_str(&serde_json::to_string(&plain_data).unwrap()).unwrap();
+        let rebuilt_raw_transaction = open_data.to_raw_transaction(&plain_data).unwrap();
+        assert_eq!(rebuilt_raw_transaction.0, raw_transaction);
+    }
+
+    #[test]
+    fn test_legacy_round_trip() {
+        let (raw_transaction, signer_address) =
+            sign_transaction(EthTransactionType::Legacy, |stream| {
+                stream.append(&9_u64);
+                stream.append(&20_000_000_000_u64);
+                stream.append(&21_000_u64);
+                stream.append(&to_address());
+                stream.append(&U256::exp10(18));
+                stream.append(&Vec::<u8>::new());
+            });
+
+        assert_round_trip(&raw_transaction, signer_address);
+    }
+
+    #[test]
+    fn test_legacy_contract_creation_round_trip() {
+        let (raw_transaction, signer_address) =
+            sign_transaction(EthTransactionType::Legacy, |stream| {
+                stream.append(&0_u64);
+                stream.append(&20_000_000_000_u64);
+                stream.append(&1_000_000_u64);
+                stream.append_empty_data();
+                stream.append(&0_u64);
+                stream.append(&vec![0x60, 0x80, 0x60, 0x40, 0x52]);
+            });
+
+        assert_round_trip(&raw_transaction, signer_address);
+    }
+
+    #[test]
+    fn test_access_list_round_trip() {
+        let (raw_transaction, signer_address) =
+            sign_transaction(EthTransactionType::AccessList, |stream| {
+                stream.append(&CHAIN_ID);
+                stream.append(&3_u64);
+                stream.append(&20_000_000_000_u64);
+                stream.append(&50_000_u64);
+                stream.append(&to_address());
+                stream.append(&0_u64);
+                stream.append(&vec![0x01; 68]);
+                stream.append(&access_list());
+            });
+
+        assert_round_trip(&raw_transaction, signer_address);
+    }
+
+    #[test]
+    fn test_dynamic_fee_round_trip() {
+        let (raw_transaction, signer_address) =
+            sign_transaction(EthTransactionType::DynamicFee, append_dynamic_fee_fields);
+
+        assert_round_trip(&raw_transaction, signer_address);
+    }
+
+    #[test]
+    fn test_blob_round_trip() {
+        let (raw_transaction, signer_address) =
+            sign_transaction(EthTransactionType::Blob, append_blob_fields);
+
+        assert_round_trip(&raw_transaction, signer_address);
+    }
+
+    #[test]
+    fn test_blob_network_form_round_trip() {
+        let (raw_transaction, signer_address) =
+            sign_transaction(EthTransactionType::Blob, append_blob_fields);
+        let transaction_bytes = decode_hex(&raw_transaction).unwrap();
+
+        let mut stream = RlpStream::new_list(4);
+        stream.append_raw(&transaction_bytes[1..], 1);
+        for item in [vec![0xaa; 64], vec![0xbb; 48], vec![0xcc; 48]] {
+            stream.begin_list(2);
+            stream.append(&item);
+            stream.append(&item);
+        }
+        let network_raw_transaction =
+            const_hex::encode_prefixed(with_type_byte(EthTransactionType::Blob, &stream.out()));
+
+        assert_round_trip(&network_raw_transaction, signer_address);
+
+        // The sidecar is not part of the transaction hash.
+        assert_eq!(
+            eth_transaction_hash(&network_raw_transaction).unwrap(),
+            eth_transaction_hash(&raw_transaction).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_set_code_round_trip() {
+        let authorization = EthAuthorization {
+            chain_id: U256::from(CHAIN_ID),
+            address: Address::repeat_byte(0x77),
+            nonce: 8,
+            y_parity: 1,
+            r: U256::from(0x1234_u64),
+            s: U256::from(0x5678_u64),
+        };
+
+        let (raw_transaction, signer_address) =
+            sign_transaction(EthTransactionType::SetCode, |stream| {
+                append_dynamic_fee_fields(stream);
+                stream.append_list::<EthAuthorization, EthAuthorization>(&[authorization.clone()]);
+            });
+
+        assert_round_trip(&raw_transaction, signer_address);
+    }
+
+    /// Builds the open data and the data to encrypt the way secure RPC
+    /// providers built against earlier releases do, from the decoded `ethers`
+    /// transaction.
+    fn legacy_encrypt_transaction(raw_transaction: &str) -> (String, String) {
+        let transaction = decode_rlp_transaction(raw_transaction).unwrap();
+
+        let to_encrypt_data = serde_json::json!({
+            "to": transaction.to,
+            "value": transaction.value,
+            "data": transaction.input,
+        });
+        let legacy_open_data = LegacyEthOpenData {
+            raw_tx_hash: const_hex::encode_prefixed(transaction.hash.as_bytes()).into(),
+            from: transaction.from,
+            nonce: transaction.nonce,
+            gas_price: transaction.gas_price,
+            gas_limit: transaction.gas,
+            signature: eth_types::Signature {
+                r: transaction.r,
+                s: transaction.s,
+                v: transaction.v.as_u64(),
+            },
+            block_hash: transaction.block_hash,
+            block_number: transaction.block_number,
+            transaction_index: transaction.transaction_index,
+            transaction_type: transaction.transaction_type,
+            access_list: transaction.access_list,
+            max_priority_fee_per_gas: transaction.max_priority_fee_per_gas,
+            max_fee_per_gas: transaction.max_fee_per_gas,
+            chain_id: transaction.chain_id,
+            other: transaction.other,
+        };
+
+        (
+            serde_json::to_string(&legacy_open_data).unwrap(),
+            to_encrypt_data.to_string(),
+        )
+    }
+
+    fn assert_legacy_round_trip(raw_transaction: &str, signer_address: Address) {
+        let (legacy_open_data, to_encrypt_data) = legacy_encrypt_transaction(raw_transaction);
+
+        let open_data: EthOpenData = serde_json::from_str(&legacy_open_data).unwrap();
+        let plain_data: EthPlainData = serde_json::from_str(&to_encrypt_data).unwrap();
+        let (expected_open_data, expected_plain_data) =
+            EthOpenData::split_raw_transaction(raw_transaction).unwrap();
+        assert_eq!(open_data.from, signer_address);
+        assert_eq!(open_data, expected_open_data);
+        assert_eq!(plain_data, expected_plain_data);
+
+        let rebuilt_raw_transaction = open_data.to_raw_transaction(&plain_data).unwrap();
+        assert_eq!(rebuilt_raw_transaction.0, raw_transaction);
+    }
+
+    #[test]
+    fn test_legacy_shape_legacy_transaction_round_trip() {
+        let (raw_transaction, signer_address) =
+            sign_transaction(EthTransactionType::Legacy, |stream| {
+                stream.append(&9_u64);
+                stream.append(&20_000_000_000_u64);
+                stream.append(&21_000_u64);
+                stream.append(&to_address());
+                stream.append(&U256::exp10(18));
+                stream.append(&Vec::<u8>::new());
+            });
+
+        assert_legacy_round_trip(&raw_transaction, signer_address);
+    }
+
+    #[test]
+    fn test_legacy_shape_dynamic_fee_round_trip() {
+        let (raw_transaction, signer_address) =
+            sign_transaction(EthTransactionType::DynamicFee, append_dynamic_fee_fields);
+
+        assert_legacy_round_trip(&raw_transaction, signer_address);
+    }
+
+    #[test]
+    fn test_tampered_plain_data_is_rejected() {
+        let (raw_transaction, _) =
+            sign_transaction(EthTransactionType::DynamicFee, append_dynamic_fee_fields);
+        let (open_data, mut plain_data) =
+            EthOpenData::split_raw_transaction(&raw_transaction).unwrap();
+
+        plain_data.value += U256::one();
+
+        assert!(open_data.to_raw_transaction(&plain_data).is_err());
+    }
+
+    #[test]
+    fn test_non_canonical_encoding_is_rejected() {
+        let (raw_transaction, _) =
+            sign_transaction(EthTransactionType::DynamicFee, append_dynamic_fee_fields);
+
+        assert!(EthOpenData::split_raw_transaction(&format!("{}00", raw_transaction)).is_err());
+    }
+}
@@ -24,13 +24,13 @@ impl EthTransactionData {
     }
 
     pub fn convert_to_rollup_transaction(&self) -> Result<RollupTransaction, Error> {
-        if self.plain_data.is_none() {
-            return Err(Error::PlainDataDoesNotExist);
-        }
+        let plain_data = self
+            .plain_data
+            .as_ref()
+            .ok_or(Error::PlainDataDoesNotExist)?;
 
         Ok(RollupTransaction::Eth(
-            self.open_data
-                .convert_to_rollup_transaction(self.plain_data.as_ref().unwrap()),
+            self.open_data.to_raw_transaction(plain_data)?,
         ))
     }
 
@@ -43,90 +43,52 @@ impl EthTransactionData {
     }
 }
 
+/// The part of a signed transaction that stays in the clear. Together with
+/// [`EthPlainData`] it re-encodes to the original signed transaction byte for
+/// byte (see [`EthOpenData::split_raw_transaction`] and
+/// [`EthOpenData::to_raw_transaction`]). The open data of the legacy shape is
+/// still accepted from JSON (see [`LegacyEthOpenData`]).
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(remote = "Self")]
 pub struct EthOpenData {
     pub raw_tx_hash: RawTransactionHash,
     pub from: eth_types::Address,
+    pub transaction_type: EthTransactionType,
+
+    /// `None` for a legacy transaction, whose chain id is part of `v`.
+    pub chain_id: Option<eth_types::U256>,
     pub nonce: eth_types::U256,
-    pub gas_price: Option<eth_types::U256>,
     pub gas_limit: eth_types::U256,
-    pub signature: eth_types::Signature, // (v, r, s)
 
-    // Additional fields
-    pub block_hash: Option<eth_types::H256>,
-    pub block_number: Option<eth_types::U64>,
-    pub transaction_index: Option<eth_types::U64>,
-    pub transaction_type: Option<eth_types::U64>,
-    pub access_list: Option<eth_types::transaction::eip2930::AccessList>,
+    /// Legacy and access list transactions.
+    pub gas_price: Option<eth_types::U256>,
+
+    /// Dynamic fee, blob and set code transactions.
     pub max_priority_fee_per_gas: Option<eth_types::U256>,
     pub max_fee_per_gas: Option<eth_types::U256>,
-    pub chain_id: Option<eth_types::U256>,
-    pub other: eth_types::OtherFields,
-}
 
-impl From<eth_types::Transaction> for EthOpenData {
-    fn from(transaction: eth_types::Transaction) -> Self {
-        Self {
-            raw_tx_hash: const_hex::encode_prefixed(transaction.hash.as_bytes()).into(),
-            from: transaction.from,
-            nonce: transaction.nonce,
-            gas_price: transaction.gas_price,
-            gas_limit: transaction.gas,
-            signature: eth_types::Signature {
-                r: transaction.r,
-                s: transaction.s,
-                v: transaction.v.as_u64(),
-            },
-            block_hash: transaction.block_hash,
-            block_number: transaction.block_number,
-            transaction_index: transaction.transaction_index,
-            transaction_type: transaction.transaction_type,
-            access_list: transaction.access_list,
-            max_priority_fee_per_gas: transaction.max_priority_fee_per_gas,
-            max_fee_per_gas: transaction.max_fee_per_gas,
-            chain_id: transaction.chain_id,
-            other: transaction.other,
-        }
-    }
+    /// Every transaction type but legacy.
+    pub access_list: Option<eth_types::transaction::eip2930::AccessList>,
+
+    /// Blob transactions.
+    pub max_fee_per_blob_gas: Option<eth_types::U256>,
+    pub blob_versioned_hashes: Option<Vec<eth_types::H256>>,
+    pub blob_sidecar: Option<EthBlobSidecar>,
+
+    /// Set code transactions.
+    pub authorization_list: Option<Vec<EthAuthorization>>,
+
+    /// `v` is the y parity for every transaction type but legacy.
+    pub signature: eth_types::Signature,
 }
 
 impl EthOpenData {
-    pub fn convert_to_rollup_transaction(
-        &self,
-        plain_data: &EthPlainData,
-    ) -> eth_types::Transaction {
-        eth_types::Transaction {
-            hash: eth_types::H256::from_slice(
-                const_hex::decode(&self.raw_tx_hash).unwrap().as_slice(),
-            ),
-            nonce: self.nonce,
-            block_hash: self.block_hash,
-            block_number: self.block_number,
-            transaction_index: self.transaction_index,
-            from: self.from,
-            gas_price: self.gas_price,
-            gas: self.gas_limit,
-            to: plain_data.to,
-            value: plain_data.value,
-            input: plain_data.input.clone(),
-            v: self.signature.v.into(),
-            r: self.signature.r,
-            s: self.signature.s,
-            transaction_type: self.transaction_type,
-            access_list: self.access_list.clone(),
-            max_priority_fee_per_gas: self.max_priority_fee_per_gas,
-            max_fee_per_gas: self.max_fee_per_gas,
-            chain_id: self.chain_id,
-            other: self.other.clone(),
-        }
-    }
-
     pub fn raw_tx_hash(&self) -> &RawTransactionHash {
         &self.raw_tx_hash
     }
 }
 
-#[derive(Serialize, Deserialize, Clone, Debug)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
 pub struct EthPlainData {
     pub to: Option<eth_types::Address>,
     pub value: eth_types::U256,
@@ -134,12 +96,6 @@ pub struct EthPlainData {
     pub input: eth_types::Bytes,
 }
 
-pub fn to_raw_tx(transaction: eth_types::Transaction) -> String {
-    let rlp_bytes = transaction.rlp();
-
-    const_hex::encode_prefixed(rlp_bytes)
-}
-
 pub fn eth_bytes_to_hex(bytes: eth_types::Bytes) -> String {
     const_hex::encode_prefixed(bytes)
 }
@@ -157,13 +113,8 @@ pub fn decode_rlp_transaction(rlp_hex: &str) -> Result<eth_types::Transaction, D
     eth_types::Transaction::decode(&rlp)
 }
 
-pub fn to_encrypt_data_string(eth_transaction: &eth_types::Transaction) -> String {
-    let payload = serde_json::json!({
-        "to": eth_transaction.to,
-        "value": eth_transaction.value,
-        "data": eth_transaction.input,
-    });
-    serde_json::to_string(&payload).unwrap()
+pub fn to_encrypt_data_string(plain_data: &EthPlainData) -> String {
+    serde_json::to_string(plain_data).unwrap()
 }
 
 pub fn string_to_eth_plain_data(string: &str) -> Result<EthPlainData, Box<dyn std::error::Error>> {
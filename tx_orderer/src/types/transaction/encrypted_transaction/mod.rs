@@ -1,12 +1,12 @@
-use ethers_core::types as eth_types;
-
 use crate::{error::Error, types::prelude::*};
 
 mod eth_bundle_transaction;
+mod eth_envelope;
 mod eth_transaction;
 mod model;
 
 pub use eth_bundle_transaction::*;
+pub use eth_envelope::*;
 pub use eth_transaction::*;
 pub use model::*;
 
@@ -207,21 +207,14 @@ impl From<EthBundlePlainData> for PlainData {
 #[derive(Clone, Debug, Deserialize, Serialize)]
 #[serde(rename_all = "snake_case")]
 pub enum RollupTransaction {
-    Eth(eth_types::Transaction),
+    Eth(EthRawTransaction),
     EthBundle,
 }
 
 impl RollupTransaction {
     pub fn to_raw_transaction(&self) -> Result<RawTransaction, Error> {
         match self {
-            Self::Eth(transaction) => {
-                let raw_transaction_string = serde_json::to_string(transaction)
-                    .map_err(Error::SerializeEthRawTransaction)?;
-
-                Ok(RawTransaction::Eth(EthRawTransaction::from(
-                    raw_transaction_string,
-                )))
-            }
+            Self::Eth(eth_raw_transaction) => Ok(RawTransaction::Eth(eth_raw_transaction.clone())),
             // Todo: implement EthBundle
             Self::EthBundle => Ok(RawTransaction::EthBundle(EthRawBundleTransaction::from(
                 String::new(),
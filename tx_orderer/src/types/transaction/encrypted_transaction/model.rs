@@ -55,4 +55,23 @@ impl EncryptedTransactionModel {
 
         kvstore()?.get_mut(key)
     }
+
+    pub fn delete(
+        rollup_id: &RollupId,
+        batch_number: u64,
+        transaction_order: u64,
+    ) -> Result<(), KvStoreError> {
+        let key = &(Self::ID, rollup_id, batch_number, transaction_order);
+
+        kvstore()?.delete(key)
+    }
+
+    pub fn delete_with_transaction_hash(
+        rollup_id: &RollupId,
+        transaction_hash: &str,
+    ) -> Result<(), KvStoreError> {
+        let key = &(Self::ID, rollup_id, transaction_hash);
+
+        kvstore()?.delete(key)
+    }
 }
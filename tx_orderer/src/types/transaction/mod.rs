@@ -1,5 +1,7 @@
 mod encrypted_transaction;
 mod raw_transaction;
+mod transaction_status;
 
 pub use encrypted_transaction::*;
 pub use raw_transaction::*;
+pub use transaction_status::*;
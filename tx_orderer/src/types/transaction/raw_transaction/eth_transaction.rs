@@ -19,14 +19,23 @@ impl From<String> for EthRawTransaction {
 
 impl EthRawTransaction {
     pub fn raw_transaction_hash(&self) -> RawTransactionHash {
-        let decoded_transaction = decode_rlp_transaction(&self.0).unwrap();
-
-        let transaction_hash = const_hex::encode_prefixed(decoded_transaction.hash);
-
-        RawTransactionHash::from(transaction_hash)
+        eth_transaction_hash(&self.0).unwrap()
     }
 
     pub fn rollup_transaction(&self) -> Result<eth_types::Transaction, Error> {
         decode_rlp_transaction(&self.0).map_err(|_| Error::InvalidTransaction)
     }
+
+    /// Returns the EIP-2718 transaction type, or `None` for a legacy
+    /// transaction.
+    pub fn transaction_type(&self) -> Result<Option<u8>, Error> {
+        let transaction_bytes = const_hex::decode(self.0.trim_start_matches("0x"))
+            .map_err(|_| Error::InvalidTransaction)?;
+
+        match transaction_bytes.first() {
+            Some(&first_byte) if first_byte >= 0xc0 => Ok(None),
+            Some(&first_byte) if first_byte <= 0x7f => Ok(Some(first_byte)),
+            _ => Err(Error::InvalidTransaction),
+        }
+    }
 }
@@ -49,6 +49,18 @@ impl RawTransactionHash {
         Self(const_hex::encode_prefixed(value))
     }
 
+    /// Writes a hash given by a caller the way the stored hashes are written:
+    /// lowercase with the `0x` prefix.
+    pub fn normalize(value: &str) -> Self {
+        let value = value.trim();
+        let value = value
+            .strip_prefix("0x")
+            .or_else(|| value.strip_prefix("0X"))
+            .unwrap_or(value);
+
+        Self(format!("0x{}", value.to_ascii_lowercase()))
+    }
+
     pub fn as_string(self) -> String {
         self.0
     }
@@ -99,3 +111,23 @@ impl RawTransaction {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalized_hash_matches_the_stored_hash() {
+        let transaction_hash = RawTransactionHash::new([0xab_u8; 32]);
+        let digits = "AB".repeat(32);
+
+        for value in [
+            digits.clone(),
+            format!("0x{}", digits),
+            format!("0X{}", digits),
+            format!(" 0x{} ", digits.to_ascii_lowercase()),
+        ] {
+            assert_eq!(RawTransactionHash::normalize(&value), transaction_hash);
+        }
+    }
+}
@@ -49,4 +49,23 @@ impl RawTransactionModel {
 
         kvstore()?.get(key)
     }
+
+    pub fn delete(
+        rollup_id: &RollupId,
+        batch_number: u64,
+        transaction_order: u64,
+    ) -> Result<(), KvStoreError> {
+        let key = &(Self::ID, rollup_id, batch_number, transaction_order);
+
+        kvstore()?.delete(key)
+    }
+
+    pub fn delete_with_transaction_hash(
+        rollup_id: &RollupId,
+        transaction_hash: &str,
+    ) -> Result<(), KvStoreError> {
+        let key = &(Self::ID, rollup_id, transaction_hash);
+
+        kvstore()?.delete(key)
+    }
 }
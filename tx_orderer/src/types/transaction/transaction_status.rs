@@ -0,0 +1,70 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::types::prelude::*;
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum TransactionStatus {
+    Ordered,
+    DecryptionPending,
+    Decrypted,
+    Invalid,
+    InSealedBatch,
+    BatchCommitmentSubmitted,
+    TaskResponded,
+}
+
+/// Lifecycle record of a transaction, indexed by its transaction hash so that
+/// the `(batch_number, transaction_order)` of an ordered transaction can be
+/// found without the order commitment.
+#[derive(Clone, Debug, Deserialize, Serialize, Model)]
+#[kvstore(key(rollup_id: &RollupId, transaction_hash: &str))]
+pub struct TransactionStatusInfo {
+    pub batch_number: u64,
+    pub transaction_order: u64,
+
+    pub is_encrypted: bool,
+    pub is_invalid: bool,
+
+    pub ordered_at: u64,
+    pub decrypted_at: Option<u64>,
+}
+
+impl TransactionStatusInfo {
+    pub fn new(batch_number: u64, transaction_order: u64, is_encrypted: bool) -> Self {
+        Self {
+            batch_number,
+            transaction_order,
+            is_encrypted,
+            is_invalid: false,
+            ordered_at: current_timestamp(),
+            decrypted_at: None,
+        }
+    }
+
+    pub fn set_decrypted(
+        rollup_id: &RollupId,
+        transaction_hash: &RawTransactionHash,
+    ) -> Result<(), KvStoreError> {
+        let mut transaction_status_info = Self::get_mut(rollup_id, transaction_hash.as_ref())?;
+        transaction_status_info.decrypted_at = Some(current_timestamp());
+        transaction_status_info.update()
+    }
+
+    pub fn set_invalid(
+        rollup_id: &RollupId,
+        transaction_hash: &RawTransactionHash,
+    ) -> Result<(), KvStoreError> {
+        let mut transaction_status_info = Self::get_mut(rollup_id, transaction_hash.as_ref())?;
+        transaction_status_info.is_invalid = true;
+        transaction_status_info.update()
+    }
+}
+
+/// Returns the current unix timestamp in milliseconds.
+pub fn current_timestamp() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("Time went backwards")
+        .as_millis() as u64
+}
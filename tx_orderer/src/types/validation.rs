@@ -130,3 +130,32 @@ impl SymbioticValidationInfo {
         }
     }
 }
+
+/// Result of submitting a batch commitment to the validation service manager.
+#[derive(Clone, Debug, Default, Deserialize, Serialize, Model)]
+#[kvstore(key(rollup_id: &RollupId, batch_number: u64))]
+pub struct BatchCommitmentSubmission {
+    pub transaction_hash: Option<String>,
+    pub submitted_at: Option<u64>,
+
+    pub task_responded_at: Option<u64>,
+}
+
+impl BatchCommitmentSubmission {
+    pub fn set_submitted(
+        rollup_id: &RollupId,
+        batch_number: u64,
+        transaction_hash: String,
+    ) -> Result<(), KvStoreError> {
+        let mut submission = Self::get_mut_or(rollup_id, batch_number, Self::default)?;
+        submission.transaction_hash = Some(transaction_hash);
+        submission.submitted_at = Some(current_timestamp());
+        submission.update()
+    }
+
+    pub fn set_task_responded(rollup_id: &RollupId, batch_number: u64) -> Result<(), KvStoreError> {
+        let mut submission = Self::get_mut_or(rollup_id, batch_number, Self::default)?;
+        submission.task_responded_at = Some(current_timestamp());
+        submission.update()
+    }
+}
@@ -1,7 +1,7 @@
 use super::prelude::*;
 
 pub const CURRENT_CODE_VERSION: &str = "v0.1.0";
-pub const REQURIED_DATABASE_VERSION: &str = "v0.0.2";
+pub const REQURIED_DATABASE_VERSION: &str = "v0.0.3";
 
 #[derive(Clone, Debug, Deserialize, Serialize, Model)]
 #[kvstore(key())]
@@ -1,10 +1,11 @@
-use std::{fs, io, path::Path, time::Duration};
+use std::{fs, io, path::Path, sync::Arc, time::Duration};
 
 use radius_sdk::json_rpc::client::{Id, RpcClient, RpcClientError};
 use reqwest::Client;
 
 use crate::{
     error::{self, Error},
+    health::LIVENESS_PATH,
     logger::Logger,
     rpc::{
         external::{
@@ -13,11 +14,20 @@ use crate::{
         },
         prelude::*,
     },
+    telemetry::init_tracer,
     types::{Cluster, Config, RawTransaction},
 };
 
-pub async fn health_check(tx_orderer_external_rpc_url: impl AsRef<str>) -> Result<(), Error> {
-    let health_check_url = format!("{}/health", tx_orderer_external_rpc_url.as_ref());
+/// Checks the liveness of a tx_orderer at `tx_orderer_metrics_url`, where it
+/// serves `/health` next to its metrics. The URL may omit the scheme, like the
+/// `metrics_url` of the configuration.
+pub async fn health_check(tx_orderer_metrics_url: impl AsRef<str>) -> Result<(), Error> {
+    let tx_orderer_metrics_url = tx_orderer_metrics_url.as_ref().trim_end_matches('/');
+    let health_check_url = if tx_orderer_metrics_url.contains("://") {
+        format!("{}{}", tx_orderer_metrics_url, LIVENESS_PATH)
+    } else {
+        format!("http://{}{}", tx_orderer_metrics_url, LIVENESS_PATH)
+    };
 
     let client = Client::builder()
         .timeout(Duration::from_secs(3))
@@ -28,15 +38,22 @@ pub async fn health_check(tx_orderer_external_rpc_url: impl AsRef<str>) -> Resul
         .get(health_check_url)
         .send()
         .await
+        .and_then(|response| response.error_for_status())
         .map_err(Error::HealthCheck)?;
 
     Ok(())
 }
 
 pub fn initialize_logger(config: &Config) -> Result<(), Error> {
+    let tracer = init_tracer(
+        "tx_orderer",
+        config.otlp_endpoint.as_deref(),
+        config.trace_file_path.as_deref(),
+    )?;
+
     Logger::new(config.log_path())
         .map_err(error::Error::Logger)?
-        .init();
+        .init(tracer);
     tracing::info!("Logger initialized.");
     Ok(())
 }
@@ -153,6 +170,46 @@ pub async fn fetch_encrypted_transaction(
         })
 }
 
+/// Sends the parameter to every url without waiting for the responses, like
+/// `fire_and_forget_multicast`, and counts the urls that did not accept it.
+pub fn multicast<P>(
+    context: &AppState,
+    rpc_url_list: Vec<String>,
+    method: &'static str,
+    parameter: P,
+) where
+    P: Serialize + Send + Sync + 'static,
+{
+    let parameter = Arc::new(parameter);
+
+    for rpc_url in rpc_url_list {
+        let context = context.clone();
+        let parameter = parameter.clone();
+
+        context.shutdown_manager().clone().spawn(async move {
+            let result = context
+                .rpc_client()
+                .request::<P, serde_json::Value>(
+                    rpc_url.clone(),
+                    method,
+                    parameter.as_ref(),
+                    Id::Null,
+                )
+                .await;
+
+            if let Err(error) = result {
+                tracing::warn!(
+                    "Multicast failed - method: {:?} / rpc_url: {:?} / error: {:?}",
+                    method,
+                    rpc_url,
+                    error
+                );
+                context.metrics().record_sync_multicast_failure(method);
+            }
+        });
+    }
+}
+
 pub fn clear_dir<P: AsRef<Path>>(path: P) -> Result<(), io::Error> {
     if path.as_ref().exists() {
         for entry in fs::read_dir(&path)? {